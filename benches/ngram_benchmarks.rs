@@ -27,7 +27,7 @@ fn bench_extraction(c: &mut Criterion) {
 
 fn bench_update(c: &mut Criterion) {
     let keystrokes = make_keystrokes(500);
-    let bigram_events = extract_ngram_events(&keystrokes, 800.0);
+    let bigram_events = extract_ngram_events(&keystrokes, 800.0).bigrams;
 
     c.bench_function("bigram_stats update (400 events)", |b| {
         b.iter(|| {
@@ -96,7 +96,7 @@ fn bench_history_replay(c: &mut Criterion) {
             let mut key_stats = KeyStatsStore::default();
 
             for (drill_idx, keystrokes) in drills.iter().enumerate() {
-                let bigram_events = extract_ngram_events(keystrokes, 800.0);
+                let bigram_events = extract_ngram_events(keystrokes, 800.0).bigrams;
 
                 for kt in keystrokes {
                     if kt.correct {
@@ -5,6 +5,7 @@ use std::thread;
 use std::time::{Duration, Instant};
 
 use rand::Rng;
+use rand::RngCore;
 use rand::SeedableRng;
 use rand::rngs::SmallRng;
 
@@ -12,13 +13,19 @@ use crate::i18n::t;
 
 use crate::config::Config;
 use crate::engine::FocusSelection;
+use crate::engine::custom_branch::custom_branch_definitions;
 use crate::engine::filter::CharFilter;
 use crate::engine::key_stats::KeyStatsStore;
 use crate::engine::ngram_stats::{
-    self, BigramStatsStore, extract_ngram_events, select_focus,
+    self, BigramStatsStore, TrigramStatsStore, extract_ngram_events, select_focus,
 };
+use crate::engine::review::{MAX_DUE_PER_DRILL, ReviewItem, WORD_REVIEW_REPEATS};
 use crate::engine::scoring;
-use crate::engine::skill_tree::{BranchId, BranchStatus, DrillScope, SkillTree, SkillTreeProgress};
+use crate::engine::skill_tree::{
+    BranchDefinition, BranchId, BranchStatus, DrillScope, SkillTree, SkillTreeProgress,
+    SkillTreeUpdate,
+};
+use crate::engine::word_stats::{WordStatsStore, extract_word_events, normalize_word};
 use crate::generator::TextGenerator;
 use crate::generator::capitalize;
 use crate::generator::code_patterns;
@@ -27,30 +34,49 @@ use crate::generator::code_syntax::{
     download_code_repo_to_cache_with_progress, is_language_cached, language_by_key,
     languages_with_content,
 };
+use crate::generator::custom_symbols;
 use crate::generator::dictionary::Dictionary;
+use crate::generator::key_set::{self, KeySet, is_key_set_key};
+use crate::generator::number_formats;
 use crate::generator::numbers;
+use crate::generator::numpad;
 use crate::generator::passage::{
     GUTENBERG_BOOKS, PassageGenerator, book_by_key, download_book_to_cache_with_progress,
     is_book_cached, passage_options, uncached_books,
 };
 use crate::generator::phonetic::PhoneticGenerator;
+use crate::generator::placement;
+use crate::generator::problem_words;
 use crate::generator::punctuate;
+use crate::generator::technique::{self, Technique};
 use crate::generator::transition_table::TransitionTable;
-use crate::keyboard::display::BACKSPACE;
+use crate::generator::vocabulary::Vocabulary;
+use crate::keyboard::display::{BACKSPACE, ENTER, numpad_sentinel};
 use crate::keyboard::model::KeyboardModel;
+use crate::keyboard::numpad::numpad_chars;
 use crate::l10n::language_pack::{
     CapabilityState, DEFAULT_LATIN_PRIMARY_SEQUENCE, LanguageLayoutValidationError,
     default_keyboard_layout_for_language, find_language_pack, normalized_primary_letter_sequence,
     ranked_adaptive_readiness, validate_language_layout_pair,
 };
+use crate::l10n::text_rules::EN_TEXT_RULES;
+use crate::race::{RaceFinish, RacePhase, RaceSession};
+use crate::report::{self, ReportData};
 
 use crate::session::drill::DrillState;
 use crate::session::input::{self, KeystrokeEvent};
 use crate::session::result::{DrillResult, KeyTime};
+use crate::shortcut::session::{ChordOutcome, SHORTCUT_PROMPTS_PER_ROUND, ShortcutSession};
+use crate::shortcut::stats::ShortcutStatsStore;
+use crate::shortcut::{Chord, ShortcutPack};
+use crate::store::importers::{self, ExternalSource, ImportSummary};
 use crate::store::json_store::JsonStore;
+use crate::store::merge::{self, HistoryMerge};
 use crate::store::schema::{
     DrillHistoryData, EXPORT_VERSION, ExportData, KeyStatsData, ProfileData, SCHEMA_VERSION,
+    ShortcutStatsData,
 };
+use crate::store::tabular::{self, TableFormat, TableSources};
 use crate::ui::components::menu::Menu;
 use crate::ui::line_input::{LineInput, PathField};
 use crate::ui::theme::Theme;
@@ -73,6 +99,11 @@ pub enum AppScreen {
     CodeDownloadProgress,
     Keyboard,
     UiLanguageSelect,
+    VocabularyEditor,
+    RaceLobby,
+    RaceResult,
+    ShortcutDrill,
+    TechniqueSelect,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -80,16 +111,26 @@ pub enum DrillMode {
     Adaptive,
     Code,
     Passage,
+    Race,
+    DataEntry,
+    Numpad,
+    ProblemWords,
+    Placement,
+    Technique,
+    KeySet,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum SettingItem {
     TargetWpm,
+    ForgettingCurve,
     Theme,
     WordCount,
     UiLanguage,
     DictionaryLanguage,
+    Vocabulary,
     KeyboardLayout,
+    ShortcutPack,
     CodeLanguage,
     CodeDownloads,
     CodeDownloadDir,
@@ -101,18 +142,24 @@ pub enum SettingItem {
     DownloadPassagesNow,
     ExportPath,
     ExportData,
+    TableFormat,
+    ExportTables,
     ImportPath,
     ImportData,
+    MergeImport,
 }
 
 impl SettingItem {
-    pub const ALL: [Self; 19] = [
+    pub const ALL: [Self; 25] = [
         Self::TargetWpm,
+        Self::ForgettingCurve,
         Self::Theme,
         Self::WordCount,
         Self::UiLanguage,
         Self::DictionaryLanguage,
+        Self::Vocabulary,
         Self::KeyboardLayout,
+        Self::ShortcutPack,
         Self::CodeLanguage,
         Self::CodeDownloads,
         Self::CodeDownloadDir,
@@ -124,8 +171,11 @@ impl SettingItem {
         Self::DownloadPassagesNow,
         Self::ExportPath,
         Self::ExportData,
+        Self::TableFormat,
+        Self::ExportTables,
         Self::ImportPath,
         Self::ImportData,
+        Self::MergeImport,
     ];
 
     pub fn from_index(index: usize) -> Self {
@@ -153,7 +203,12 @@ impl SettingItem {
     pub fn is_action_button(self) -> bool {
         matches!(
             self,
-            Self::DownloadCodeNow | Self::DownloadPassagesNow | Self::ExportData | Self::ImportData
+            Self::DownloadCodeNow
+                | Self::DownloadPassagesNow
+                | Self::ExportData
+                | Self::ExportTables
+                | Self::ImportData
+                | Self::MergeImport
         )
     }
 
@@ -213,6 +268,8 @@ fn mastery_messages() -> Vec<String> {
 }
 
 const POST_DRILL_INPUT_LOCK_MS: u64 = 800;
+/// Distinct words practised in one problem-words drill.
+const PROBLEM_WORDS_PER_DRILL: usize = 8;
 
 struct DownloadJob {
     downloaded_bytes: Arc<AtomicU64>,
@@ -234,6 +291,22 @@ pub struct StatusMessage {
     pub text: String,
 }
 
+/// What an export would overwrite, shown in the file-exists dialog.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportConflict {
+    Data,
+    Tables,
+}
+
+/// An export file staged for merge import, waiting for the user to confirm the summary.
+#[derive(Clone, Debug)]
+pub struct PendingMerge {
+    pub profile: ProfileData,
+    pub history: HistoryMerge,
+    /// Branches in the active language whose status or level the import would raise.
+    pub branches_advanced: usize,
+}
+
 /// Given a file path, find the next available path by appending/incrementing
 /// a `-N` numeric suffix before the extension. Strips any existing trailing
 /// `-N` suffix to normalize before scanning.
@@ -293,11 +366,18 @@ impl DrillMode {
             DrillMode::Adaptive => "adaptive",
             DrillMode::Code => "code",
             DrillMode::Passage => "passage",
+            DrillMode::Race => "race",
+            DrillMode::DataEntry => "data_entry",
+            DrillMode::Numpad => "numpad",
+            DrillMode::ProblemWords => "problem_words",
+            DrillMode::Technique => "technique",
+            DrillMode::KeySet => "key_set",
+            DrillMode::Placement => "placement",
         }
     }
 
     pub fn is_ranked(self) -> bool {
-        matches!(self, DrillMode::Adaptive)
+        matches!(self, DrillMode::Adaptive | DrillMode::Placement)
     }
 }
 
@@ -308,6 +388,8 @@ pub struct App {
     pub drill: Option<DrillState>,
     pub drill_events: Vec<KeystrokeEvent>,
     pub last_result: Option<DrillResult>,
+    /// Keys that slipped out of mastery with the last ranked drill.
+    pub last_regressed_keys: Vec<char>,
     pub drill_history: Vec<DrillResult>,
     pub menu: Menu<'static>,
     pub theme: &'static Theme,
@@ -315,6 +397,8 @@ pub struct App {
     pub key_stats: KeyStatsStore,
     pub ranked_key_stats: KeyStatsStore,
     pub skill_tree: SkillTree,
+    /// Definitions of `config.custom_branches`, added to every skill tree.
+    custom_branches: Vec<&'static BranchDefinition>,
     pub profile: ProfileData,
     pub store: Option<JsonStore>,
     pub should_quit: bool,
@@ -339,6 +423,9 @@ pub struct App {
     pub code_language_selected: usize,
     pub code_language_scroll: usize,
     pub passage_book_selected: usize,
+    /// Technique of the current or last technique drill.
+    pub technique: Technique,
+    pub technique_selected: usize,
     pub passage_intro_selected: usize,
     pub passage_intro_downloads_enabled: bool,
     pub passage_intro_download_dir: String,
@@ -370,27 +457,58 @@ pub struct App {
     pub code_download_action: CodeDownloadCompleteAction,
     pub shift_held: bool,
     pub caps_lock: bool,
+    /// The last key press came from the numeric keypad (kitty protocol only).
+    pub keypad_input: bool,
     pub keyboard_model: KeyboardModel,
     pub milestone_queue: VecDeque<KeyMilestonePopup>,
     pub show_adaptive_intro: bool,
     pub settings_confirm_import: bool,
-    pub settings_export_conflict: bool,
+    pub settings_merge_pending: Option<PendingMerge>,
+    pub settings_export_conflict: Option<ExportConflict>,
     pub settings_status_message: Option<StatusMessage>,
     pub settings_export_path: String,
+    pub settings_table_format: TableFormat,
     pub settings_import_path: String,
     pub keyboard_explorer_selected: Option<char>,
     pub explorer_accuracy_cache_overall: Option<(char, usize, usize)>,
     pub explorer_accuracy_cache_ranked: Option<(char, usize, usize)>,
+    /// Keys picked in the keyboard explorer for a key-set drill.
+    pub explorer_key_set: Vec<char>,
+    /// Preset `explorer_key_set` was loaded from or saved as, until it changes.
+    pub explorer_key_set_name: Option<String>,
+    pub key_set_name_input: Option<LineInput>,
+    /// Keys of the current or last key-set drill.
+    pub key_set: Vec<char>,
     pub bigram_stats: BigramStatsStore,
     pub ranked_bigram_stats: BigramStatsStore,
+    pub trigram_stats: TrigramStatsStore,
+    pub ranked_trigram_stats: TrigramStatsStore,
+    pub word_stats: WordStatsStore,
     pub user_median_transition_ms: f64,
     pub transition_buffer: Vec<f64>,
     pub current_focus: Option<FocusSelection>,
+    /// Spaced-repetition items interleaved into the current adaptive drill.
+    pub current_review: Vec<ReviewItem>,
     pub post_drill_input_lock_until: Option<Instant>,
+    pub race: Option<RaceSession>,
+    pub shortcut_session: Option<ShortcutSession>,
+    /// Why the configured shortcut pack could not be loaded, shown in place of a prompt.
+    pub shortcut_load_error: Option<String>,
+    pub shortcut_stats: ShortcutStatsStore,
     adaptive_word_history: VecDeque<HashSet<String>>,
     rng: SmallRng,
     transition_table: TransitionTable,
     dictionary: Dictionary,
+    /// The user's word list and blocklist for the dictionary language.
+    pub vocabulary: Vocabulary,
+    /// Where `vocabulary` is saved; `None` keeps edits in memory only.
+    vocabulary_dir: Option<std::path::PathBuf>,
+    /// 0 = custom words, 1 = blocklist.
+    pub vocabulary_tab: usize,
+    pub vocabulary_selected: usize,
+    pub vocabulary_input: Option<LineInput>,
+    /// Dictionary nouns for languages that capitalize them; empty otherwise.
+    dictionary_nouns: HashSet<String>,
     passage_download_job: Option<DownloadJob>,
     code_download_job: Option<DownloadJob>,
 }
@@ -409,7 +527,7 @@ impl App {
         let filtered_sequence = if let Some(model) = KeyboardModel::from_key(layout_key) {
             let filtered: String = normalized_primary_letter_sequence(sequence)
                 .into_iter()
-                .filter(|&ch| model.physical_key_for(ch).is_some())
+                .filter(|&ch| model.can_type(ch))
                 .collect();
             if filtered.is_empty() {
                 sequence.to_string()
@@ -423,6 +541,13 @@ impl App {
         SkillTree::new_with_primary_sequence(progress, &filtered_sequence)
     }
 
+    /// Skill tree for a language on the configured layout, with custom branches.
+    fn skill_tree_for(&self, progress: SkillTreeProgress, language_key: &str) -> SkillTree {
+        Self::build_skill_tree_for_language(progress, language_key, &self.config.keyboard_layout)
+            .with_custom_branches(self.custom_branches.clone())
+            .with_mastery(self.config.mastery.clone())
+    }
+
     pub fn new() -> Self {
         let mut config = Config::load().unwrap_or_default();
 
@@ -434,6 +559,10 @@ impl App {
         let menu = Menu::new(theme);
 
         let store = JsonStore::new().ok();
+        let shortcut_stats = store
+            .as_ref()
+            .map(|s| s.load_shortcut_stats().stats)
+            .unwrap_or_default();
 
         let (key_stats, ranked_key_stats, skill_tree, profile, drill_history) =
             if let Some(ref s) = store {
@@ -491,12 +620,25 @@ impl App {
                 )
             };
 
+        let custom_branches = custom_branch_definitions(&config.custom_branches);
+        let skill_tree = skill_tree
+            .with_custom_branches(custom_branches.clone())
+            .with_mastery(config.mastery.clone());
         let mut key_stats_with_target = key_stats;
         key_stats_with_target.target_cpm = config.target_cpm();
+        key_stats_with_target.forgetting = config.forgetting_curve;
         let mut ranked_key_stats_with_target = ranked_key_stats;
         ranked_key_stats_with_target.target_cpm = config.target_cpm();
-
-        let dictionary = Dictionary::load_for_language(&config.dictionary_language);
+        ranked_key_stats_with_target.forgetting = config.forgetting_curve;
+
+        let vocabulary_dir = Vocabulary::dir_for(&config.dictionary_language);
+        let vocabulary = vocabulary_dir
+            .as_deref()
+            .map(Vocabulary::load_from)
+            .unwrap_or_default();
+        let dictionary =
+            Dictionary::load_for_language(&config.dictionary_language).with_vocabulary(&vocabulary);
+        let dictionary_nouns = dictionary_nouns(&dictionary, &config.dictionary_language);
         let transition_table = TransitionTable::build_from_words(dictionary.words_list());
         let keyboard_model = KeyboardModel::from_key(&config.keyboard_layout)
             .expect("config validation must ensure a known keyboard layout");
@@ -514,6 +656,7 @@ impl App {
             drill: None,
             drill_events: Vec::new(),
             last_result: None,
+            last_regressed_keys: Vec::new(),
             drill_history,
             menu,
             theme,
@@ -521,6 +664,7 @@ impl App {
             key_stats: key_stats_with_target,
             ranked_key_stats: ranked_key_stats_with_target,
             skill_tree,
+            custom_branches,
             profile,
             store,
             should_quit: false,
@@ -545,6 +689,8 @@ impl App {
             code_language_selected: 0,
             code_language_scroll: 0,
             passage_book_selected: 0,
+            technique: Technique::all()[0],
+            technique_selected: 0,
             passage_intro_selected: 0,
             passage_intro_downloads_enabled: intro_downloads_enabled,
             passage_intro_download_dir: intro_download_dir,
@@ -576,27 +722,48 @@ impl App {
             code_download_action: CodeDownloadCompleteAction::StartCodeDrill,
             shift_held: false,
             caps_lock: false,
+            keypad_input: false,
             keyboard_model,
             milestone_queue: VecDeque::new(),
             show_adaptive_intro: false,
             settings_confirm_import: false,
-            settings_export_conflict: false,
+            settings_merge_pending: None,
+            settings_export_conflict: None,
             settings_status_message: None,
             settings_export_path: default_export_path(),
+            settings_table_format: TableFormat::Csv,
             settings_import_path: default_export_path(),
             keyboard_explorer_selected: None,
             explorer_accuracy_cache_overall: None,
             explorer_accuracy_cache_ranked: None,
+            explorer_key_set: Vec::new(),
+            explorer_key_set_name: None,
+            key_set_name_input: None,
+            key_set: Vec::new(),
             bigram_stats: BigramStatsStore::default(),
             ranked_bigram_stats: BigramStatsStore::default(),
+            trigram_stats: TrigramStatsStore::default(),
+            ranked_trigram_stats: TrigramStatsStore::default(),
+            word_stats: WordStatsStore::default(),
             user_median_transition_ms: 0.0,
             transition_buffer: Vec::new(),
             current_focus: None,
+            current_review: Vec::new(),
             post_drill_input_lock_until: None,
+            race: None,
+            shortcut_session: None,
+            shortcut_load_error: None,
+            shortcut_stats,
             adaptive_word_history: VecDeque::new(),
             rng: SmallRng::from_entropy(),
             transition_table,
             dictionary,
+            vocabulary,
+            vocabulary_dir,
+            vocabulary_tab: 0,
+            vocabulary_selected: 0,
+            vocabulary_input: None,
+            dictionary_nouns,
             passage_download_job: None,
             code_download_job: None,
         };
@@ -625,7 +792,8 @@ impl App {
     /// Clear all import/export modal and edit states.
     pub fn clear_settings_modals(&mut self) {
         self.settings_confirm_import = false;
-        self.settings_export_conflict = false;
+        self.settings_merge_pending = None;
+        self.settings_export_conflict = None;
         self.settings_editing_path = None;
     }
 
@@ -667,7 +835,7 @@ impl App {
 
         // Check for existing file
         if path.exists() {
-            self.settings_export_conflict = true;
+            self.settings_export_conflict = Some(ExportConflict::Data);
             return;
         }
 
@@ -683,6 +851,24 @@ impl App {
         self.write_export_to_path();
     }
 
+    /// File-exists dialog: write over the existing file(s).
+    pub fn export_conflict_overwrite(&mut self) {
+        match self.settings_export_conflict.take() {
+            Some(ExportConflict::Data) => self.export_data_overwrite(),
+            Some(ExportConflict::Tables) => self.export_tables_overwrite(),
+            None => {}
+        }
+    }
+
+    /// File-exists dialog: write under the next free `-N` name instead.
+    pub fn export_conflict_rename(&mut self) {
+        match self.settings_export_conflict.take() {
+            Some(ExportConflict::Data) => self.export_data_rename(),
+            Some(ExportConflict::Tables) => self.export_tables_rename(),
+            None => {}
+        }
+    }
+
     fn write_export_to_path(&mut self) {
         // Check parent directory exists
         let path = std::path::Path::new(&self.settings_export_path);
@@ -745,7 +931,125 @@ impl App {
         }
     }
 
-    pub fn import_data(&mut self) {
+    /// Write the flat drill, key and bigram tables into `dir`.
+    pub fn write_tables(
+        &self,
+        dir: &std::path::Path,
+        prefix: &str,
+        format: TableFormat,
+    ) -> anyhow::Result<Vec<std::path::PathBuf>> {
+        let sources = TableSources {
+            history: &self.drill_history,
+            key_stats: &self.key_stats,
+            ranked_key_stats: &self.ranked_key_stats,
+            bigram_stats: &self.bigram_stats,
+            ranked_bigram_stats: &self.ranked_bigram_stats,
+        };
+        tabular::write_tables(&sources, dir, prefix, format)
+    }
+
+    /// Settings action: tables land next to the export path, named after its stem
+    /// (`keydr-export-2026-01-01-drills.csv`, ...). Existing tables are not
+    /// replaced without confirmation.
+    pub fn export_tables(&mut self) {
+        let Some((dir, stem)) = self.table_export_location() else {
+            return;
+        };
+        let prefix = format!("{stem}-");
+        let format = self.settings_table_format;
+        if tabular::table_paths(&dir, &prefix, format)
+            .iter()
+            .any(|path| path.exists())
+        {
+            self.settings_export_conflict = Some(ExportConflict::Tables);
+            return;
+        }
+        self.write_tables_with_status(&dir, &prefix);
+    }
+
+    pub fn export_tables_overwrite(&mut self) {
+        if let Some((dir, stem)) = self.table_export_location() {
+            self.write_tables_with_status(&dir, &format!("{stem}-"));
+        }
+    }
+
+    /// Write the tables as `{stem}-N-drills.csv`, ... with the first `N` whose
+    /// tables are all free.
+    pub fn export_tables_rename(&mut self) {
+        let Some((dir, stem)) = self.table_export_location() else {
+            return;
+        };
+        let format = self.settings_table_format;
+        let prefix = (1u32..)
+            .map(|n| format!("{stem}-{n}-"))
+            .find(|prefix| {
+                tabular::table_paths(&dir, prefix, format)
+                    .iter()
+                    .all(|path| !path.exists())
+            })
+            .expect("some numbered prefix is free");
+        self.write_tables_with_status(&dir, &prefix);
+    }
+
+    /// Directory and file stem of the export path, or `None` with an error
+    /// status when the directory does not exist.
+    fn table_export_location(&mut self) -> Option<(std::path::PathBuf, String)> {
+        let path = std::path::Path::new(&self.settings_export_path);
+        let dir = path
+            .parent()
+            .filter(|p| !p.as_os_str().is_empty())
+            .unwrap_or(std::path::Path::new("."));
+        if !dir.exists() {
+            self.settings_status_message = Some(StatusMessage {
+                kind: StatusKind::Error,
+                text: t!("status.dir_not_exist", path = dir.display().to_string()).to_string(),
+            });
+            return None;
+        }
+        let stem = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("keydr-export");
+        Some((dir.to_path_buf(), stem.to_string()))
+    }
+
+    fn write_tables_with_status(&mut self, dir: &std::path::Path, prefix: &str) {
+        self.settings_status_message = Some(
+            match self.write_tables(dir, prefix, self.settings_table_format) {
+                Ok(written) => StatusMessage {
+                    kind: StatusKind::Success,
+                    text: t!(
+                        "status.exported_tables",
+                        count = written.len(),
+                        path = dir.display().to_string()
+                    )
+                    .to_string(),
+                },
+                Err(e) => StatusMessage {
+                    kind: StatusKind::Error,
+                    text: t!("status.export_failed", error = e.to_string()).to_string(),
+                },
+            },
+        );
+    }
+
+    /// Standalone HTML report of the current profile (`keydr report --html`).
+    pub fn render_html_report(&self) -> String {
+        report::render_html(&ReportData {
+            history: &self.drill_history,
+            key_stats: &self.key_stats,
+            ranked_key_stats: &self.ranked_key_stats,
+            ranked_bigram_stats: &self.ranked_bigram_stats,
+            skill_tree: &self.skill_tree,
+            keyboard_model: &self.keyboard_model,
+            target_wpm: self.config.target_wpm,
+            generated_at: chrono::Utc::now(),
+        })
+    }
+
+    /// Read and version-check the file at the import path, reporting problems
+    /// through the settings status message.
+    fn read_import_file(&mut self) -> Option<ExportData> {
         let path = std::path::Path::new(&self.settings_import_path);
 
         // Read and parse
@@ -756,18 +1060,18 @@ impl App {
                     kind: StatusKind::Error,
                     text: t!("status.could_not_read", error = e.to_string()).to_string(),
                 });
-                return;
+                return None;
             }
         };
 
-        let mut export: ExportData = match serde_json::from_str(&content) {
+        let export: ExportData = match serde_json::from_str(&content) {
             Ok(d) => d,
             Err(e) => {
                 self.settings_status_message = Some(StatusMessage {
                     kind: StatusKind::Error,
                     text: t!("status.invalid_export", error = e.to_string()).to_string(),
                 });
-                return;
+                return None;
             }
         };
 
@@ -777,8 +1081,15 @@ impl App {
                 kind: StatusKind::Error,
                 text: t!("status.unsupported_version", got = export.keydr_export_version, expected = EXPORT_VERSION).to_string(),
             });
-            return;
+            return None;
         }
+        Some(export)
+    }
+
+    pub fn import_data(&mut self) {
+        let Some(mut export) = self.read_import_file() else {
+            return;
+        };
 
         // Normalize imported payload schema versions before writing to disk.
         export.profile.schema_version = SCHEMA_VERSION;
@@ -813,6 +1124,7 @@ impl App {
         let valid_keys: Vec<&str> = code_language_options().iter().map(|(k, _)| *k).collect();
         self.config.validate(&valid_keys);
         let _ = self.config.save();
+        self.custom_branches = custom_branch_definitions(&self.config.custom_branches);
 
         // Reload in-memory state from imported data
         self.profile = export.profile;
@@ -824,14 +1136,15 @@ impl App {
             .set_skill_tree_for_language(&self.config.dictionary_language, scoped_progress);
         self.key_stats = export.key_stats.stats;
         self.key_stats.target_cpm = self.config.target_cpm();
+        self.key_stats.forgetting = self.config.forgetting_curve;
         self.ranked_key_stats = export.ranked_key_stats.stats;
         self.ranked_key_stats.target_cpm = self.config.target_cpm();
+        self.ranked_key_stats.forgetting = self.config.forgetting_curve;
         self.drill_history = export.drill_history.drills;
-        self.skill_tree = Self::build_skill_tree_for_language(
+        self.skill_tree = self.skill_tree_for(
             self.profile
                 .skill_tree_for_language(&self.config.dictionary_language),
             &self.config.dictionary_language,
-            &self.config.keyboard_layout,
         );
         self.keyboard_model = KeyboardModel::from_key(&self.config.keyboard_layout)
             .expect("config validation must ensure a known keyboard layout");
@@ -863,17 +1176,200 @@ impl App {
         }
     }
 
+    /// Settings action: stage the file at the import path for a merge import and
+    /// open the confirmation dialog with a summary of what it would add.
+    pub fn prepare_merge_import(&mut self) {
+        let Some(export) = self.read_import_file() else {
+            return;
+        };
+        let language = &self.config.dictionary_language;
+        let branches_advanced = count_branches_ahead(
+            &self.profile.skill_tree_for_language(language),
+            &export.profile.skill_tree_for_language(language),
+        );
+        self.settings_merge_pending = Some(PendingMerge {
+            history: merge::union_histories(&self.drill_history, &export.drill_history.drills),
+            profile: export.profile,
+            branches_advanced,
+        });
+    }
+
+    /// Commit a staged merge: keep local settings, union the drill histories,
+    /// merge skill tree progress non-regressively in both directions, take in the
+    /// other side's review schedules and recompute every derived stat from the
+    /// merged history.
+    pub fn commit_merge_import(&mut self) {
+        let Some(pending) = self.settings_merge_pending.take() else {
+            return;
+        };
+        if self.store.is_none() {
+            self.settings_status_message = Some(StatusMessage {
+                kind: StatusKind::Error,
+                text: t!("status.no_data_store").to_string(),
+            });
+            return;
+        }
+
+        let previous_profile = self.profile.clone();
+        let previous_history = std::mem::replace(&mut self.drill_history, pending.history.drills);
+
+        // Merge progress for every language either side has seen; the active
+        // language goes last so the legacy mirror ends up pointing at it.
+        let active = self.config.dictionary_language.clone();
+        let mut languages: Vec<String> = self
+            .profile
+            .skill_tree_by_language
+            .keys()
+            .chain(pending.profile.skill_tree_by_language.keys())
+            .filter(|lang| **lang != active)
+            .cloned()
+            .collect();
+        languages.sort();
+        languages.dedup();
+        languages.push(active);
+        for language in &languages {
+            let mut tree =
+                self.skill_tree_for(self.profile.skill_tree_for_language(language), language);
+            merge_skill_tree_progress_non_regressive(
+                &mut tree,
+                &pending.profile.skill_tree_for_language(language),
+            );
+            self.profile
+                .set_skill_tree_for_language(language, tree.progress);
+        }
+        for (language, schedule) in &pending.profile.review_schedule_by_language {
+            self.profile.review_schedule_mut(language).merge(schedule);
+        }
+        self.rebuild_from_history();
+
+        let merged = ExportData {
+            keydr_export_version: EXPORT_VERSION,
+            exported_at: chrono::Utc::now(),
+            config: self.config.clone(),
+            profile: self.profile.clone(),
+            key_stats: KeyStatsData {
+                schema_version: SCHEMA_VERSION,
+                stats: self.key_stats.clone(),
+            },
+            ranked_key_stats: KeyStatsData {
+                schema_version: SCHEMA_VERSION,
+                stats: self.ranked_key_stats.clone(),
+            },
+            drill_history: DrillHistoryData {
+                schema_version: SCHEMA_VERSION,
+                drills: self.drill_history.clone(),
+            },
+        };
+        let committed = self
+            .store
+            .as_ref()
+            .map_or(Ok(()), |store| store.import_all(&merged));
+        if let Err(e) = committed {
+            self.profile = previous_profile;
+            self.drill_history = previous_history;
+            self.rebuild_from_history();
+            self.settings_status_message = Some(StatusMessage {
+                kind: StatusKind::Error,
+                text: t!("status.import_failed", error = e.to_string()).to_string(),
+            });
+            return;
+        }
+
+        self.settings_status_message = Some(StatusMessage {
+            kind: StatusKind::Success,
+            text: t!("status.merged_success", count = pending.history.added).to_string(),
+        });
+    }
+
+    /// Merge another trainer's history into the store (`keydr import-history`).
+    /// With `dry_run` nothing is written and the summary previews what would be added.
+    pub fn import_external_history(
+        &mut self,
+        source: ExternalSource,
+        path: &std::path::Path,
+        dry_run: bool,
+    ) -> anyhow::Result<ImportSummary> {
+        let content = std::fs::read_to_string(path)?;
+        let import = source.parse(&content)?;
+        let Some(ref store) = self.store else {
+            anyhow::bail!("{}", t!("status.no_data_store"));
+        };
+
+        let mut export = store.export_all(&self.config);
+        let summary = importers::merge_into(&mut export, &import);
+        if dry_run || summary.added == 0 {
+            return Ok(summary);
+        }
+
+        export.profile.schema_version = SCHEMA_VERSION;
+        export.key_stats.schema_version = SCHEMA_VERSION;
+        export.ranked_key_stats.schema_version = SCHEMA_VERSION;
+        export.drill_history.schema_version = SCHEMA_VERSION;
+        store.import_all(&export)?;
+
+        self.key_stats = export.key_stats.stats;
+        self.key_stats.target_cpm = self.config.target_cpm();
+        self.key_stats.forgetting = self.config.forgetting_curve;
+        self.drill_history = export.drill_history.drills;
+        self.rebuild_ngram_stats();
+        Ok(summary)
+    }
+
     pub fn start_drill(&mut self) {
         self.clear_post_drill_input_lock();
         let (text, source_info) = self.generate_text();
         let mut drill = DrillState::new(&text);
-        drill.auto_indent_after_newline = self.drill_mode != DrillMode::Adaptive;
+        drill.auto_indent_after_newline =
+            !matches!(self.drill_mode, DrillMode::Adaptive | DrillMode::Placement);
         self.drill = Some(drill);
         self.drill_source_info = source_info;
         self.drill_events.clear();
         self.screen = AppScreen::Drill;
     }
 
+    /// Enroll this drill's focus targets and the worst problem word in the
+    /// review schedule and pick the due items that can be interleaved into the
+    /// generated text.
+    fn schedule_review(
+        &mut self,
+        unlocked: &[char],
+        focused_char: Option<char>,
+        focused_bigram: Option<[char; 2]>,
+    ) -> Vec<ReviewItem> {
+        let now = chrono::Utc::now();
+        let problem_word = self.word_stats.problem_words(1).pop().map(|p| p.word);
+        let schedule = self
+            .profile
+            .review_schedule_mut(&self.config.dictionary_language);
+        if let Some(ch) = focused_char {
+            schedule.enroll(ReviewItem::Key(ch), now);
+        }
+        if let Some(pair) = focused_bigram {
+            schedule.enroll(ReviewItem::Bigram(pair), now);
+        }
+        if let Some(word) = problem_word {
+            schedule.enroll(ReviewItem::Word(word), now);
+        }
+        // Only letters can steer word generation; other due keys are still
+        // graded whenever they show up in a drill.
+        let items = schedule.due_items(now, MAX_DUE_PER_DRILL, |item| match item {
+            ReviewItem::Key(ch) => {
+                Some(*ch) != focused_char
+                    && unlocked.contains(ch)
+                    && lowercase_generation_focus(Some(*ch)).is_some()
+            }
+            ReviewItem::Bigram(pair) => {
+                Some(*pair) != focused_bigram
+                    && pair
+                        .iter()
+                        .all(|ch| ch.is_lowercase() && unlocked.contains(ch))
+            }
+            ReviewItem::Word(word) => word.chars().all(|ch| unlocked.contains(&ch)),
+        });
+        self.current_review = items.clone();
+        items
+    }
+
     fn generate_text(&mut self) -> (String, Option<String>) {
         let word_count = self.config.word_count;
         let mode = self.drill_mode;
@@ -883,16 +1379,19 @@ impl App {
                 let scope = self.drill_scope;
                 let all_keys = self.skill_tree.unlocked_keys(scope);
 
-                // Select focus targets: char and bigram independently
+                // Select focus targets: char, bigram and trigram independently
                 let selection = select_focus(
                     &self.skill_tree,
                     scope,
                     &self.ranked_key_stats,
                     &self.ranked_bigram_stats,
+                    &self.ranked_trigram_stats,
                 );
                 self.current_focus = Some(selection.clone());
                 let focused_char = selection.char_focus;
                 let focused_bigram = selection.bigram_focus.map(|(k, _, _)| k.0);
+                let focused_trigram = selection.trigram_focus.map(|(k, _, _)| k.0);
+                let review_items = self.schedule_review(&all_keys, focused_char, focused_bigram);
 
                 // Generate base lowercase text using only lowercase keys from scope
                 let lowercase_keys: Vec<char> = all_keys
@@ -913,9 +1412,37 @@ impl App {
                     .flatten()
                     .cloned()
                     .collect();
-                let mut generator = PhoneticGenerator::new(table, dict, rng, cross_drill_history);
+                let mut generator = PhoneticGenerator::new(table, dict, rng, cross_drill_history)
+                    .with_focused_trigram(focused_trigram);
+                // Due review items each get a share of the drill, interleaved with the main focus
+                let review_words = (word_count / 5).max(1);
+                let review_share = |item: &ReviewItem| match item {
+                    ReviewItem::Word(_) => WORD_REVIEW_REPEATS,
+                    _ => review_words,
+                };
+                let main_words = word_count
+                    .saturating_sub(review_items.iter().map(review_share).sum())
+                    .max(1);
                 let mut text =
-                    generator.generate(&filter, lowercase_focused, focused_bigram, word_count);
+                    generator.generate(&filter, lowercase_focused, focused_bigram, main_words);
+                let review_segments: Vec<String> = review_items
+                    .iter()
+                    .map(|item| match item {
+                        ReviewItem::Key(ch) => generator.generate(
+                            &filter,
+                            lowercase_generation_focus(Some(*ch)),
+                            None,
+                            review_words,
+                        ),
+                        ReviewItem::Bigram(pair) => {
+                            generator.generate(&filter, None, Some(*pair), review_words)
+                        }
+                        ReviewItem::Word(word) => [word.as_str(); WORD_REVIEW_REPEATS].join(" "),
+                    })
+                    .collect();
+                if !review_segments.is_empty() {
+                    text = interleave_words(&text, &review_segments);
+                }
 
                 // Track words for cross-drill history (before capitalization/punctuation)
                 let drill_words: HashSet<String> =
@@ -931,26 +1458,44 @@ impl App {
                     .copied()
                     .filter(|ch| ch.is_uppercase())
                     .collect();
+                let rules = find_language_pack(&self.config.dictionary_language)
+                    .map(|pack| pack.text_rules)
+                    .unwrap_or(&EN_TEXT_RULES);
                 if !cap_keys.is_empty() {
                     let mut rng = SmallRng::from_rng(&mut self.rng).unwrap();
-                    text =
-                        capitalize::apply_capitalization(&text, &cap_keys, focused_char, &mut rng);
+                    text = capitalize::apply_capitalization(
+                        &text,
+                        &cap_keys,
+                        focused_char,
+                        rules,
+                        &self.dictionary_nouns,
+                        &mut rng,
+                    );
                 }
 
-                // Apply punctuation if punctuation keys are in scope
+                // Apply punctuation if punctuation keys are in scope. The language's
+                // own quote and inverted marks are passed along once unlocked.
                 let punct_keys: Vec<char> = all_keys
                     .iter()
                     .copied()
-                    .filter(|ch| {
+                    .filter(|&ch| {
                         matches!(
                             ch,
                             '.' | ',' | '\'' | ';' | ':' | '"' | '-' | '?' | '!' | '(' | ')'
-                        )
+                        ) || (rules.inverted_marks && matches!(ch, '¿' | '¡'))
+                            || ch == rules.open_quote
+                            || ch == rules.close_quote
                     })
                     .collect();
                 if !punct_keys.is_empty() {
                     let mut rng = SmallRng::from_rng(&mut self.rng).unwrap();
-                    text = punctuate::apply_punctuation(&text, &punct_keys, focused_char, &mut rng);
+                    text = punctuate::apply_punctuation(
+                        &text,
+                        &punct_keys,
+                        focused_char,
+                        rules,
+                        &mut rng,
+                    );
                 }
 
                 // Apply numbers if digit keys are in scope
@@ -962,10 +1507,15 @@ impl App {
                     .filter(|ch| ch.is_ascii_digit())
                     .collect();
                 if !digit_keys.is_empty() {
-                    let has_dot = all_keys.contains(&'.');
                     let mut rng = SmallRng::from_rng(&mut self.rng).unwrap();
-                    text =
-                        numbers::apply_numbers(&text, &digit_keys, has_dot, focused_char, &mut rng);
+                    text = numbers::apply_numbers(
+                        &text,
+                        &digit_keys,
+                        &all_keys,
+                        focused_char,
+                        rules,
+                        &mut rng,
+                    );
                 }
 
                 // Apply code symbols only if this drill is for the CodeSymbols branch,
@@ -1019,6 +1569,39 @@ impl App {
                     }
                 }
 
+                // Apply keys of the custom branches this drill covers
+                let mut custom_keys: Vec<char> = Vec::new();
+                for def in self.skill_tree.custom_branches() {
+                    let active = match scope {
+                        DrillScope::Branch(id) => id == def.id,
+                        DrillScope::Global => matches!(
+                            self.skill_tree.branch_status(def.id),
+                            BranchStatus::InProgress | BranchStatus::Complete
+                        ),
+                    };
+                    if !active {
+                        continue;
+                    }
+                    for ch in self.skill_tree.branch_keys(def.id) {
+                        // Letters and digits already come from the passes above.
+                        if !ch.is_alphanumeric()
+                            && all_keys.contains(&ch)
+                            && !custom_keys.contains(&ch)
+                        {
+                            custom_keys.push(ch);
+                        }
+                    }
+                }
+                if !custom_keys.is_empty() {
+                    let mut rng = SmallRng::from_rng(&mut self.rng).unwrap();
+                    text = custom_symbols::apply_custom_symbols(
+                        &text,
+                        &custom_keys,
+                        focused_char,
+                        &mut rng,
+                    );
+                }
+
                 // Apply whitespace line breaks if newline is in scope
                 let has_newline = all_keys.contains(&'\n');
                 let has_tab = all_keys.contains(&'\t');
@@ -1033,6 +1616,7 @@ impl App {
                     !punct_keys.is_empty(),
                     !digit_keys.is_empty(),
                     code_active && !symbol_keys.is_empty(),
+                    !custom_keys.is_empty(),
                     has_newline || has_tab,
                 ]
                 .into_iter()
@@ -1090,16 +1674,199 @@ impl App {
                 let text = generator.generate(&filter, None, None, word_count);
                 (text, Some(generator.last_source().to_string()))
             }
+            DrillMode::Race => {
+                // Race text comes from the host so every racer types the same thing.
+                let text = self
+                    .race
+                    .as_ref()
+                    .and_then(|race| race.text.clone())
+                    .unwrap_or_default();
+                (text, None)
+            }
+            DrillMode::DataEntry => {
+                // Numbers can be practised before the Numbers branch unlocks.
+                let mut keys = self.skill_tree.unlocked_keys(DrillScope::Global);
+                if !keys.iter().any(|ch| ch.is_ascii_digit()) {
+                    keys.extend('0'..='9');
+                }
+                let rules = find_language_pack(&self.config.dictionary_language)
+                    .map(|pack| pack.text_rules)
+                    .unwrap_or(&EN_TEXT_RULES);
+                let mut rng = SmallRng::from_rng(&mut self.rng).unwrap();
+                // A formatted token is roughly two words long.
+                let text = number_formats::generate_data_entry(
+                    &keys,
+                    None,
+                    rules,
+                    word_count.div_ceil(2),
+                    &mut rng,
+                );
+                (text, None)
+            }
+            DrillMode::Numpad => {
+                let focused = self.weakest_numpad_key();
+                let mut rng = SmallRng::from_rng(&mut self.rng).unwrap();
+                let text =
+                    numpad::generate_numpad(focused, (word_count / 2).clamp(4, 20), &mut rng);
+                (text, None)
+            }
+            DrillMode::Placement => {
+                let mut rng = SmallRng::from_rng(&mut self.rng).unwrap();
+                let text =
+                    placement::generate_placement(&self.skill_tree, &self.dictionary, &mut rng);
+                (text, None)
+            }
+            DrillMode::Technique => {
+                let mut rng = SmallRng::from_rng(&mut self.rng).unwrap();
+                let text = technique::generate_technique_drill(
+                    self.technique,
+                    &self.keyboard_model,
+                    &self.dictionary,
+                    &self.transition_table,
+                    word_count,
+                    &mut rng,
+                );
+                (text, None)
+            }
+            DrillMode::KeySet => {
+                let background: Vec<char> = self.skill_tree.unlocked_keys(DrillScope::Global);
+                let focused = self.key_set.iter().copied().min_by(|a, b| {
+                    let confidence = |ch| self.ranked_key_stats.get_confidence(ch);
+                    confidence(*a).total_cmp(&confidence(*b))
+                });
+                let mut rng = SmallRng::from_rng(&mut self.rng).unwrap();
+                let mut text = key_set::generate_key_set_words(
+                    &self.key_set,
+                    &background,
+                    focused,
+                    &self.dictionary,
+                    &self.transition_table,
+                    word_count,
+                    &mut rng,
+                );
+                let capitals: Vec<char> = self
+                    .key_set
+                    .iter()
+                    .copied()
+                    .filter(|ch| ch.is_uppercase())
+                    .collect();
+                if !capitals.is_empty() {
+                    let rules = find_language_pack(&self.config.dictionary_language)
+                        .map(|pack| pack.text_rules)
+                        .unwrap_or(&EN_TEXT_RULES);
+                    text = capitalize::apply_capitalization(
+                        &text,
+                        &capitals,
+                        focused,
+                        rules,
+                        &self.dictionary_nouns,
+                        &mut rng,
+                    );
+                }
+                let symbols: Vec<char> = self
+                    .key_set
+                    .iter()
+                    .copied()
+                    .filter(|ch| !ch.is_alphabetic())
+                    .collect();
+                text = custom_symbols::apply_custom_symbols(&text, &symbols, focused, &mut rng);
+                (text, None)
+            }
+            DrillMode::ProblemWords => {
+                let words = self.problem_drill_words();
+                let mut rng = SmallRng::from_rng(&mut self.rng).unwrap();
+                let text = problem_words::generate_problem_word_drill(
+                    &words,
+                    Some(&self.dictionary),
+                    word_count,
+                    &mut rng,
+                );
+                (text, None)
+            }
+        }
+    }
+
+    /// Words for a problem-words drill: confirmed problem words first, then the
+    /// weakest words seen so far, then common dictionary words for new profiles.
+    fn problem_drill_words(&self) -> Vec<String> {
+        let mut words: Vec<String> = self
+            .word_stats
+            .problem_words(PROBLEM_WORDS_PER_DRILL)
+            .into_iter()
+            .map(|p| p.word)
+            .collect();
+        if words.len() < PROBLEM_WORDS_PER_DRILL {
+            for p in self.word_stats.ranked_words(false) {
+                if words.len() >= PROBLEM_WORDS_PER_DRILL {
+                    break;
+                }
+                if !words.contains(&p.word) {
+                    words.push(p.word);
+                }
+            }
+        }
+        if words.is_empty() {
+            words = self
+                .dictionary
+                .words_list()
+                .iter()
+                .filter_map(|w| normalize_word(w))
+                .take(PROBLEM_WORDS_PER_DRILL)
+                .collect();
         }
+        words
+    }
+
+    /// Numpad key with the lowest confidence among those already practised.
+    fn weakest_numpad_key(&self) -> Option<char> {
+        numpad_chars()
+            .filter(|&ch| ch != ENTER)
+            .filter_map(|ch| {
+                let sentinel = numpad_sentinel(ch)?;
+                self.key_stats.get_stat(sentinel)?;
+                Some((ch, self.key_stats.get_confidence(sentinel)))
+            })
+            .filter(|&(_, confidence)| confidence < 1.0)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(ch, _)| ch)
+    }
+
+    /// Generate the shared race text for `seed`.
+    ///
+    /// Uses the full primary letter set of the active language rather than the
+    /// host's unlocked keys, so racers at different skill-tree stages get a fair text.
+    fn generate_race_text(&self, seed: u64) -> String {
+        let sequence = find_language_pack(&self.config.dictionary_language)
+            .map(|pack| pack.primary_letter_sequence)
+            .unwrap_or(DEFAULT_LATIN_PRIMARY_SEQUENCE);
+        let filter = CharFilter::new(normalized_primary_letter_sequence(sequence));
+        let mut generator = PhoneticGenerator::new(
+            self.transition_table.clone(),
+            self.dictionary.clone(),
+            SmallRng::seed_from_u64(seed),
+            HashSet::new(),
+        );
+        generator.generate(&filter, None, None, self.config.word_count)
     }
 
     pub fn type_char(&mut self, ch: char) {
         if let Some(ref mut drill) = self.drill {
-            let event = input::process_char(drill, ch);
-            let had_event = event.is_some();
-            if let Some(event) = event {
+            let events = input::process_input(drill, ch, self.keyboard_model.dead_keys);
+            let had_event = !events.is_empty();
+            for mut event in events {
+                // Keypad strokes keep their own stats, apart from the number row.
+                if (self.keypad_input || self.drill_mode == DrillMode::Numpad)
+                    && let Some(sentinel) = numpad_sentinel(event.expected)
+                {
+                    event.expected = sentinel;
+                }
                 self.drill_events.push(event);
             }
+            if self.drill_mode == DrillMode::Race
+                && let Some(ref mut race) = self.race
+            {
+                race.report_progress(drill.cursor);
+            }
 
             if drill.is_complete() {
                 let synthetic_reached_end = drill
@@ -1117,7 +1884,8 @@ impl App {
 
     pub fn backspace(&mut self) {
         if let Some(ref mut drill) = self.drill {
-            if drill.cursor == 0 {
+            // Backspace after a dead key only cancels the pending accent.
+            if input::cancel_pending(drill) || drill.cursor == 0 {
                 return;
             }
             self.drill_events.push(KeystrokeEvent {
@@ -1127,6 +1895,11 @@ impl App {
                 correct: true,
             });
             input::process_backspace(drill);
+            if self.drill_mode == DrillMode::Race
+                && let Some(ref mut race) = self.race
+            {
+                race.report_progress(drill.cursor);
+            }
         }
     }
 
@@ -1147,160 +1920,77 @@ impl App {
             } else {
                 None
             };
+            let at = result.timestamp;
             for kt in &result.per_key_times {
                 if kt.correct {
                     self.key_stats.update_key(kt.key, kt.time_ms);
                 } else {
                     self.key_stats.update_key_error(kt.key);
                 }
+                self.key_stats.mark_practiced(kt.key, at);
             }
 
             // Extract and update n-gram stats for all drill modes
             let drill_index = self.drill_history.len() as u32;
             let hesitation_thresh =
                 ngram_stats::hesitation_threshold(self.user_median_transition_ms);
-            let bigram_events =
-                extract_ngram_events(&result.per_key_times, hesitation_thresh);
-            // Collect unique bigram keys for per-drill streak updates
-            let mut seen_bigrams: std::collections::HashSet<ngram_stats::BigramKey> =
-                std::collections::HashSet::new();
-            for ev in &bigram_events {
-                seen_bigrams.insert(ev.key.clone());
-                self.bigram_stats.update(
-                    ev.key.clone(),
-                    ev.total_time_ms,
-                    ev.correct,
-                    ev.has_hesitation,
-                    drill_index,
-                );
-            }
-            // Update streaks once per drill per unique bigram (not per event)
-            for key in &seen_bigrams {
-                self.bigram_stats
-                    .update_error_anomaly_streak(key, &self.key_stats);
-                self.bigram_stats
-                    .update_speed_anomaly_streak(key, &self.key_stats);
-            }
+            let ngram_events = extract_ngram_events(&result.per_key_times, hesitation_thresh);
+            let word_events = extract_word_events(&result.per_key_times, hesitation_thresh);
+            self.bigram_stats
+                .record_drill(&ngram_events.bigrams, &self.key_stats, drill_index, at);
+            self.trigram_stats.record_drill(
+                &ngram_events.trigrams,
+                &self.key_stats,
+                drill_index,
+                at,
+            );
+            self.word_stats.record_drill(&word_events, drill_index, at);
 
             if ranked {
-                let mut seen_ranked_bigrams: std::collections::HashSet<ngram_stats::BigramKey> =
-                    std::collections::HashSet::new();
                 for kt in &result.per_key_times {
                     if kt.correct {
                         self.ranked_key_stats.update_key(kt.key, kt.time_ms);
                     } else {
                         self.ranked_key_stats.update_key_error(kt.key);
                     }
+                    self.ranked_key_stats.mark_practiced(kt.key, at);
                 }
-                for ev in &bigram_events {
-                    seen_ranked_bigrams.insert(ev.key.clone());
-                    self.ranked_bigram_stats.update(
-                        ev.key.clone(),
-                        ev.total_time_ms,
-                        ev.correct,
-                        ev.has_hesitation,
-                        drill_index,
+                self.ranked_bigram_stats.record_drill(
+                    &ngram_events.bigrams,
+                    &self.ranked_key_stats,
+                    drill_index,
+                    at,
+                );
+                self.ranked_trigram_stats.record_drill(
+                    &ngram_events.trigrams,
+                    &self.ranked_key_stats,
+                    drill_index,
+                    at,
+                );
+                self.profile
+                    .review_schedule_mut(&self.config.dictionary_language)
+                    .record_drill(
+                        &result.per_key_times,
+                        &ngram_events.bigrams,
+                        &word_events,
+                        self.ranked_key_stats.target_cpm,
+                        at,
                     );
+                if self.drill_mode == DrillMode::Placement {
+                    // A placement test can unlock most of the tree at once; the skill
+                    // tree screen shows the result instead of a stack of popups.
+                    self.skill_tree.apply_placement(&self.ranked_key_stats);
+                } else {
+                    let update = self
+                        .skill_tree
+                        .update(&self.ranked_key_stats, before_stats.as_ref());
+                    self.queue_milestones(update);
                 }
-                for key in &seen_ranked_bigrams {
-                    self.ranked_bigram_stats
-                        .update_error_anomaly_streak(key, &self.ranked_key_stats);
-                    self.ranked_bigram_stats
-                        .update_speed_anomaly_streak(key, &self.ranked_key_stats);
-                }
-                let update = self
+                self.last_regressed_keys = self
                     .skill_tree
-                    .update(&self.ranked_key_stats, before_stats.as_ref());
-
-                // Queue milestone overlays for newly unlocked keys
-                if !update.newly_unlocked.is_empty() {
-                    let finger_info: Vec<(char, String)> = update
-                        .newly_unlocked
-                        .iter()
-                        .map(|&ch| {
-                            let desc = self.keyboard_model.finger_for_char(ch).localized_description();
-                            (ch, desc)
-                        })
-                        .collect();
-                    let msgs = unlock_messages();
-                    let msg = msgs[self.rng.gen_range(0..msgs.len())].clone();
-                    self.milestone_queue.push_back(KeyMilestonePopup {
-                        kind: MilestoneKind::Unlock,
-                        keys: update.newly_unlocked,
-                        finger_info,
-                        message: msg,
-                        branch_ids: vec![],
-                    });
-                }
-
-                // Queue milestone overlays for newly mastered keys
-                if !update.newly_mastered.is_empty() {
-                    let finger_info: Vec<(char, String)> = update
-                        .newly_mastered
-                        .iter()
-                        .map(|&ch| {
-                            let desc = self.keyboard_model.finger_for_char(ch).localized_description();
-                            (ch, desc)
-                        })
-                        .collect();
-                    let msgs = mastery_messages();
-                    let msg = msgs[self.rng.gen_range(0..msgs.len())].clone();
-                    self.milestone_queue.push_back(KeyMilestonePopup {
-                        kind: MilestoneKind::Mastery,
-                        keys: update.newly_mastered,
-                        finger_info,
-                        message: msg,
-                        branch_ids: vec![],
-                    });
-                }
-
-                // Queue milestone popups for branch/global milestones
-                if !update.branches_newly_available.is_empty() {
-                    self.milestone_queue.push_back(KeyMilestonePopup {
-                        kind: MilestoneKind::BranchesAvailable,
-                        keys: vec![],
-                        finger_info: vec![],
-                        message: String::new(),
-                        branch_ids: update.branches_newly_available,
-                    });
-                }
-
-                // Branch complete (excluding Lowercase, since BranchesAvailable covers it)
-                let completed_non_lowercase: Vec<BranchId> = update
-                    .branches_newly_completed
-                    .iter()
-                    .filter(|&&id| id != BranchId::Lowercase)
-                    .copied()
-                    .collect();
-                if !completed_non_lowercase.is_empty() {
-                    self.milestone_queue.push_back(KeyMilestonePopup {
-                        kind: MilestoneKind::BranchComplete,
-                        keys: vec![],
-                        finger_info: vec![],
-                        message: String::new(),
-                        branch_ids: completed_non_lowercase,
-                    });
-                }
-
-                if update.all_keys_unlocked {
-                    self.milestone_queue.push_back(KeyMilestonePopup {
-                        kind: MilestoneKind::AllKeysUnlocked,
-                        keys: vec![],
-                        finger_info: vec![],
-                        message: String::new(),
-                        branch_ids: vec![],
-                    });
-                }
-
-                if update.all_keys_mastered {
-                    self.milestone_queue.push_back(KeyMilestonePopup {
-                        kind: MilestoneKind::AllKeysMastered,
-                        keys: vec![],
-                        finger_info: vec![],
-                        message: String::new(),
-                        branch_ids: vec![],
-                    });
-                }
+                    .record_key_mastery(&self.ranked_key_stats, &result.per_key_times);
+            } else {
+                self.last_regressed_keys.clear();
             }
 
             let complexity = self.skill_tree.complexity();
@@ -1334,19 +2024,34 @@ impl App {
             self.update_transition_buffer(&result.per_key_times);
 
             self.drill_history.push(result.clone());
-            if self.drill_history.len() > 500 {
+            if self.drill_history.len() > merge::MAX_HISTORY_DRILLS {
                 self.drill_history.remove(0);
             }
 
+            if self.drill_mode == DrillMode::Race
+                && let Some(ref mut race) = self.race
+            {
+                let elapsed_secs = race.elapsed_secs();
+                race.report_finish(RaceFinish {
+                    elapsed_secs,
+                    wpm: result.wpm,
+                    accuracy: result.accuracy,
+                });
+            }
+
             self.last_result = Some(result);
             if !self.milestone_queue.is_empty() || self.drill_mode != DrillMode::Adaptive {
                 self.arm_post_drill_input_lock();
             }
 
             // Adaptive mode auto-continues unless milestone popups must be shown first.
-            if self.drill_mode == DrillMode::Adaptive && self.milestone_queue.is_empty() {
+            if self.drill_mode == DrillMode::Race {
+                self.screen = AppScreen::RaceResult;
+            } else if self.drill_mode == DrillMode::Adaptive && self.milestone_queue.is_empty() {
                 self.start_drill();
                 self.arm_post_drill_input_lock();
+            } else if self.drill_mode == DrillMode::Placement {
+                self.go_to_skill_tree();
             } else {
                 self.screen = AppScreen::DrillResult;
             }
@@ -1355,7 +2060,107 @@ impl App {
         }
     }
 
+    /// Queue milestone overlays for a ranked drill's skill tree update.
+    fn queue_milestones(&mut self, update: SkillTreeUpdate) {
+        // Queue milestone overlays for newly unlocked keys
+        if !update.newly_unlocked.is_empty() {
+            let finger_info: Vec<(char, String)> = update
+                .newly_unlocked
+                .iter()
+                .map(|&ch| {
+                    let desc = self
+                        .keyboard_model
+                        .finger_for_char(ch)
+                        .localized_description();
+                    (ch, desc)
+                })
+                .collect();
+            let msgs = unlock_messages();
+            let msg = msgs[self.rng.gen_range(0..msgs.len())].clone();
+            self.milestone_queue.push_back(KeyMilestonePopup {
+                kind: MilestoneKind::Unlock,
+                keys: update.newly_unlocked,
+                finger_info,
+                message: msg,
+                branch_ids: vec![],
+            });
+        }
+
+        // Queue milestone overlays for newly mastered keys
+        if !update.newly_mastered.is_empty() {
+            let finger_info: Vec<(char, String)> = update
+                .newly_mastered
+                .iter()
+                .map(|&ch| {
+                    let desc = self
+                        .keyboard_model
+                        .finger_for_char(ch)
+                        .localized_description();
+                    (ch, desc)
+                })
+                .collect();
+            let msgs = mastery_messages();
+            let msg = msgs[self.rng.gen_range(0..msgs.len())].clone();
+            self.milestone_queue.push_back(KeyMilestonePopup {
+                kind: MilestoneKind::Mastery,
+                keys: update.newly_mastered,
+                finger_info,
+                message: msg,
+                branch_ids: vec![],
+            });
+        }
+
+        // Queue milestone popups for branch/global milestones
+        if !update.branches_newly_available.is_empty() {
+            self.milestone_queue.push_back(KeyMilestonePopup {
+                kind: MilestoneKind::BranchesAvailable,
+                keys: vec![],
+                finger_info: vec![],
+                message: String::new(),
+                branch_ids: update.branches_newly_available,
+            });
+        }
+
+        // Branch complete (excluding Lowercase, since BranchesAvailable covers it)
+        let completed_non_lowercase: Vec<BranchId> = update
+            .branches_newly_completed
+            .iter()
+            .filter(|&&id| id != BranchId::Lowercase)
+            .copied()
+            .collect();
+        if !completed_non_lowercase.is_empty() {
+            self.milestone_queue.push_back(KeyMilestonePopup {
+                kind: MilestoneKind::BranchComplete,
+                keys: vec![],
+                finger_info: vec![],
+                message: String::new(),
+                branch_ids: completed_non_lowercase,
+            });
+        }
+
+        if update.all_keys_unlocked {
+            self.milestone_queue.push_back(KeyMilestonePopup {
+                kind: MilestoneKind::AllKeysUnlocked,
+                keys: vec![],
+                finger_info: vec![],
+                message: String::new(),
+                branch_ids: vec![],
+            });
+        }
+
+        if update.all_keys_mastered {
+            self.milestone_queue.push_back(KeyMilestonePopup {
+                kind: MilestoneKind::AllKeysMastered,
+                keys: vec![],
+                finger_info: vec![],
+                message: String::new(),
+                branch_ids: vec![],
+            });
+        }
+    }
+
     pub fn finish_partial_drill(&mut self) {
+        self.last_regressed_keys.clear();
         if let Some(ref drill) = self.drill {
             let result = DrillResult::from_drill(
                 drill,
@@ -1366,50 +2171,51 @@ impl App {
             );
 
             // Update timing stats for all completed keystrokes
+            let at = result.timestamp;
             for kt in &result.per_key_times {
                 if kt.correct {
                     self.key_stats.update_key(kt.key, kt.time_ms);
                 } else {
                     self.key_stats.update_key_error(kt.key);
                 }
+                self.key_stats.mark_practiced(kt.key, at);
             }
 
             // Extract and update n-gram stats
             let drill_index = self.drill_history.len() as u32;
             let hesitation_thresh =
                 ngram_stats::hesitation_threshold(self.user_median_transition_ms);
-            let bigram_events =
-                extract_ngram_events(&result.per_key_times, hesitation_thresh);
-            let mut seen_bigrams: std::collections::HashSet<ngram_stats::BigramKey> =
-                std::collections::HashSet::new();
-            for ev in &bigram_events {
-                seen_bigrams.insert(ev.key.clone());
-                self.bigram_stats.update(
-                    ev.key.clone(),
-                    ev.total_time_ms,
-                    ev.correct,
-                    ev.has_hesitation,
-                    drill_index,
-                );
-            }
-            for key in &seen_bigrams {
-                self.bigram_stats
-                    .update_error_anomaly_streak(key, &self.key_stats);
-                self.bigram_stats
-                    .update_speed_anomaly_streak(key, &self.key_stats);
-            }
+            let ngram_events = extract_ngram_events(&result.per_key_times, hesitation_thresh);
+            self.bigram_stats
+                .record_drill(&ngram_events.bigrams, &self.key_stats, drill_index, at);
+            self.trigram_stats.record_drill(
+                &ngram_events.trigrams,
+                &self.key_stats,
+                drill_index,
+                at,
+            );
+            self.word_stats.record_drill(
+                &extract_word_events(&result.per_key_times, hesitation_thresh),
+                drill_index,
+                at,
+            );
 
             // Update transition buffer for hesitation baseline
             self.update_transition_buffer(&result.per_key_times);
 
             self.drill_history.push(result.clone());
-            if self.drill_history.len() > 500 {
+            if self.drill_history.len() > merge::MAX_HISTORY_DRILLS {
                 self.drill_history.remove(0);
             }
 
             self.last_result = Some(result);
             self.arm_post_drill_input_lock();
-            self.screen = AppScreen::DrillResult;
+            // Abandoning a race keeps the connection so the standings stay visible.
+            self.screen = if self.drill_mode == DrillMode::Race {
+                AppScreen::RaceResult
+            } else {
+                AppScreen::DrillResult
+            };
             self.save_data();
         }
     }
@@ -1452,7 +2258,7 @@ impl App {
         self.user_median_transition_ms = ngram_stats::compute_median(&mut buf);
     }
 
-    /// Rebuild all n-gram stats and char-level error/total counts from drill history.
+    /// Rebuild all n-gram and word stats and char-level error/total counts from drill history.
     /// This is the sole source of truth for error_count/total_count on KeyStat
     /// and all n-gram stores. Timing EMA on KeyStat is NOT touched here
     /// (it is either loaded from disk or rebuilt by `rebuild_from_history`).
@@ -1460,6 +2266,9 @@ impl App {
         // Reset n-gram stores
         self.bigram_stats = BigramStatsStore::default();
         self.ranked_bigram_stats = BigramStatsStore::default();
+        self.trigram_stats = TrigramStatsStore::default();
+        self.ranked_trigram_stats = TrigramStatsStore::default();
+        self.word_stats = WordStatsStore::default();
         self.transition_buffer.clear();
         self.user_median_transition_ms = 0.0;
 
@@ -1481,10 +2290,15 @@ impl App {
         for (drill_index, result) in history.iter().enumerate() {
             let hesitation_thresh =
                 ngram_stats::hesitation_threshold(self.user_median_transition_ms);
-            let bigram_events =
-                extract_ngram_events(&result.per_key_times, hesitation_thresh);
+            let sequence: &[KeyTime] = if result.has_keystroke_order() {
+                &result.per_key_times
+            } else {
+                &[]
+            };
+            let ngram_events = extract_ngram_events(sequence, hesitation_thresh);
+            let at = result.timestamp;
 
-            // Rebuild char-level error/total counts and EMA from history
+            // Rebuild char-level error/total counts, EMA and practice times from history
             for kt in &result.per_key_times {
                 if kt.correct {
                     let stat = self.key_stats.stats.entry(kt.key).or_default();
@@ -1498,34 +2312,25 @@ impl App {
                 } else {
                     self.key_stats.update_key_error(kt.key);
                 }
+                self.key_stats.mark_practiced(kt.key, at);
             }
 
-            // Collect unique bigram keys seen this drill for per-drill streak updates
-            let mut seen_bigrams: std::collections::HashSet<ngram_stats::BigramKey> =
-                std::collections::HashSet::new();
-
-            for ev in &bigram_events {
-                seen_bigrams.insert(ev.key.clone());
-                self.bigram_stats.update(
-                    ev.key.clone(),
-                    ev.total_time_ms,
-                    ev.correct,
-                    ev.has_hesitation,
-                    drill_index as u32,
-                );
-            }
-            // Update streaks once per drill per unique bigram (not per event)
-            for key in &seen_bigrams {
-                self.bigram_stats
-                    .update_error_anomaly_streak(key, &self.key_stats);
-                self.bigram_stats
-                    .update_speed_anomaly_streak(key, &self.key_stats);
-            }
+            let drill_index = drill_index as u32;
+            self.bigram_stats
+                .record_drill(&ngram_events.bigrams, &self.key_stats, drill_index, at);
+            self.trigram_stats.record_drill(
+                &ngram_events.trigrams,
+                &self.key_stats,
+                drill_index,
+                at,
+            );
+            self.word_stats.record_drill(
+                &extract_word_events(sequence, hesitation_thresh),
+                drill_index,
+                at,
+            );
 
             if result.ranked {
-                let mut seen_ranked_bigrams: std::collections::HashSet<ngram_stats::BigramKey> =
-                    std::collections::HashSet::new();
-
                 for kt in &result.per_key_times {
                     if kt.correct {
                         let stat = self.ranked_key_stats.stats.entry(kt.key).or_default();
@@ -1538,23 +2343,20 @@ impl App {
                     } else {
                         self.ranked_key_stats.update_key_error(kt.key);
                     }
+                    self.ranked_key_stats.mark_practiced(kt.key, at);
                 }
-                for ev in &bigram_events {
-                    seen_ranked_bigrams.insert(ev.key.clone());
-                    self.ranked_bigram_stats.update(
-                        ev.key.clone(),
-                        ev.total_time_ms,
-                        ev.correct,
-                        ev.has_hesitation,
-                        drill_index as u32,
-                    );
-                }
-                for key in &seen_ranked_bigrams {
-                    self.ranked_bigram_stats
-                        .update_error_anomaly_streak(key, &self.ranked_key_stats);
-                    self.ranked_bigram_stats
-                        .update_speed_anomaly_streak(key, &self.ranked_key_stats);
-                }
+                self.ranked_bigram_stats.record_drill(
+                    &ngram_events.bigrams,
+                    &self.ranked_key_stats,
+                    drill_index,
+                    at,
+                );
+                self.ranked_trigram_stats.record_drill(
+                    &ngram_events.trigrams,
+                    &self.ranked_key_stats,
+                    drill_index,
+                    at,
+                );
             }
 
             // Update transition buffer
@@ -1579,7 +2381,8 @@ impl App {
         if let Some(ref drill) = self.drill {
             let text: String = drill.target.iter().collect();
             let mut retry = DrillState::new(&text);
-            retry.auto_indent_after_newline = self.drill_mode != DrillMode::Adaptive;
+            retry.auto_indent_after_newline =
+                !matches!(self.drill_mode, DrillMode::Adaptive | DrillMode::Placement);
             self.drill = Some(retry);
             self.drill_events.clear();
             self.last_result = None;
@@ -1609,6 +2412,15 @@ impl App {
                 }
                 self.start_passage_drill();
             }
+            DrillMode::Race => self.leave_race(),
+            DrillMode::DataEntry => self.start_data_entry_drill(),
+            DrillMode::Numpad => self.start_numpad_drill(),
+            DrillMode::ProblemWords => self.start_problem_words_drill(),
+            DrillMode::Technique => self.start_technique_drill(self.technique),
+            DrillMode::KeySet => self.start_key_set_drill(self.key_set.clone()),
+            DrillMode::Placement => {
+                self.start_global_adaptive_drill();
+            }
         }
     }
 
@@ -1658,9 +2470,13 @@ impl App {
         // Reset all derived state
         self.key_stats = KeyStatsStore::default();
         self.key_stats.target_cpm = self.config.target_cpm();
+        self.key_stats.forgetting = self.config.forgetting_curve;
         self.ranked_key_stats = KeyStatsStore::default();
         self.ranked_key_stats.target_cpm = self.config.target_cpm();
-        self.skill_tree = SkillTree::default();
+        self.ranked_key_stats.forgetting = self.config.forgetting_curve;
+        self.skill_tree = SkillTree::default()
+            .with_custom_branches(self.custom_branches.clone())
+            .with_mastery(self.config.mastery.clone());
         self.profile.total_score = 0.0;
         self.profile.total_drills = 0;
         self.profile.streak_days = 0;
@@ -1683,6 +2499,8 @@ impl App {
                     }
                 }
                 self.skill_tree.update(&self.ranked_key_stats, None);
+                self.skill_tree
+                    .record_key_mastery(&self.ranked_key_stats, &result.per_key_times);
             }
 
             // Partial sessions are visible in history but do not affect profile/streak activity.
@@ -1770,9 +2588,67 @@ impl App {
         self.keyboard_explorer_selected = None;
         self.explorer_accuracy_cache_overall = None;
         self.explorer_accuracy_cache_ranked = None;
+        self.key_set_name_input = None;
         self.screen = AppScreen::Keyboard;
     }
 
+    /// Add `ch` to the explorer's key set, or take it out if it is there.
+    pub fn toggle_explorer_key(&mut self, ch: char) {
+        if !is_key_set_key(ch) {
+            return;
+        }
+        if let Some(pos) = self.explorer_key_set.iter().position(|&k| k == ch) {
+            self.explorer_key_set.remove(pos);
+        } else {
+            self.explorer_key_set.push(ch);
+        }
+        self.explorer_key_set_name = None;
+    }
+
+    /// Save the explorer's key set under `name`, replacing a preset of the
+    /// same name. The caller saves the config.
+    pub fn save_key_set_preset(&mut self, name: &str) -> bool {
+        let name = name.trim();
+        if name.is_empty() || self.explorer_key_set.is_empty() {
+            return false;
+        }
+        let preset = KeySet {
+            name: name.to_string(),
+            keys: self.explorer_key_set.iter().collect(),
+        };
+        match self.config.key_sets.iter_mut().find(|set| set.name == name) {
+            Some(existing) => *existing = preset,
+            None => self.config.key_sets.push(preset),
+        }
+        self.explorer_key_set_name = Some(name.to_string());
+        true
+    }
+
+    /// Replace the explorer's key set with the preset after the current one.
+    pub fn load_next_key_set_preset(&mut self) {
+        let presets = &self.config.key_sets;
+        if presets.is_empty() {
+            return;
+        }
+        let next = self
+            .explorer_key_set_name
+            .as_ref()
+            .and_then(|name| presets.iter().position(|set| &set.name == name))
+            .map_or(0, |idx| (idx + 1) % presets.len());
+        self.explorer_key_set = presets[next].keys.chars().collect();
+        self.explorer_key_set_name = Some(presets[next].name.clone());
+    }
+
+    pub fn start_key_set_drill(&mut self, keys: Vec<char>) {
+        if keys.is_empty() {
+            return;
+        }
+        self.key_set = keys;
+        self.drill_mode = DrillMode::KeySet;
+        self.drill_scope = DrillScope::Global;
+        self.start_drill();
+    }
+
     pub fn key_accuracy(&mut self, ch: char, ranked_only: bool) -> (usize, usize) {
         let cache = if ranked_only {
             self.explorer_accuracy_cache_ranked
@@ -1818,17 +2694,113 @@ impl App {
         self.screen = AppScreen::CodeLanguageSelect;
     }
 
-    /// Rebuild language-derived generation assets after dictionary language changes.
-    ///
-    /// Contract:
-    /// - rebuild `dictionary` and `transition_table`
-    /// - clear adaptive cross-drill word history (language-specific cache)
-    /// - do not mutate current in-progress drill text
-    /// - do not swap the active skill-tree scope (caller handles language scope changes)
-    pub fn rebuild_language_assets(&mut self) {
-        self.dictionary = Dictionary::load_for_language(&self.config.dictionary_language);
-        self.transition_table = TransitionTable::build_from_words(self.dictionary.words_list());
-        self.adaptive_word_history.clear();
+    /// Rebuild language-derived generation assets after dictionary language changes.
+    ///
+    /// Contract:
+    /// - rebuild `dictionary`, `dictionary_nouns` and `transition_table`
+    /// - clear adaptive cross-drill word history (language-specific cache)
+    /// - do not mutate current in-progress drill text
+    /// - do not swap the active skill-tree scope (caller handles language scope changes)
+    pub fn rebuild_language_assets(&mut self) {
+        if self.vocabulary_dir.is_some() {
+            self.vocabulary_dir = Vocabulary::dir_for(&self.config.dictionary_language);
+            self.vocabulary = self
+                .vocabulary_dir
+                .as_deref()
+                .map(Vocabulary::load_from)
+                .unwrap_or_default();
+        } else {
+            self.vocabulary = Vocabulary::default();
+        }
+        self.rebuild_dictionary();
+    }
+
+    fn rebuild_dictionary(&mut self) {
+        self.dictionary = Dictionary::load_for_language(&self.config.dictionary_language)
+            .with_vocabulary(&self.vocabulary);
+        self.dictionary_nouns =
+            dictionary_nouns(&self.dictionary, &self.config.dictionary_language);
+        self.transition_table = TransitionTable::build_from_words(self.dictionary.words_list());
+        self.adaptive_word_history.clear();
+    }
+
+    /// Whether adaptive drills draw on the user's word list or blocklist.
+    pub fn custom_vocabulary_active(&self) -> bool {
+        self.dictionary.has_custom_vocabulary()
+    }
+
+    pub fn go_to_vocabulary_editor(&mut self) {
+        self.vocabulary_tab = 0;
+        self.vocabulary_selected = 0;
+        self.vocabulary_input = None;
+        self.settings_status_message = None;
+        self.screen = AppScreen::VocabularyEditor;
+    }
+
+    /// Number of entries on the active editor tab.
+    pub fn vocabulary_len(&self) -> usize {
+        if self.vocabulary_tab == 0 {
+            self.vocabulary.words.len()
+        } else {
+            self.vocabulary.blocked.len()
+        }
+    }
+
+    pub fn switch_vocabulary_tab(&mut self) {
+        self.vocabulary_tab = 1 - self.vocabulary_tab;
+        self.vocabulary_selected = 0;
+    }
+
+    /// Add a `word [weight]` line to the word list, or a word to the blocklist.
+    pub fn submit_vocabulary_entry(&mut self, line: &str) {
+        let added = if self.vocabulary_tab == 0 {
+            self.vocabulary.add_word_line(line)
+        } else {
+            self.vocabulary.block(line)
+        };
+        if added {
+            self.vocabulary_selected = self.vocabulary_len() - 1;
+            self.commit_vocabulary();
+        }
+    }
+
+    pub fn remove_selected_vocabulary_entry(&mut self) {
+        let index = self.vocabulary_selected;
+        if index >= self.vocabulary_len() {
+            return;
+        }
+        if self.vocabulary_tab == 0 {
+            self.vocabulary.words.remove(index);
+        } else {
+            self.vocabulary.blocked.remove(index);
+        }
+        self.vocabulary_selected = index.min(self.vocabulary_len().saturating_sub(1));
+        self.commit_vocabulary();
+    }
+
+    pub fn adjust_selected_vocabulary_weight(&mut self, delta: i32) {
+        if self.vocabulary_tab != 0 {
+            return;
+        }
+        let Some(entry) = self.vocabulary.words.get(self.vocabulary_selected) else {
+            return;
+        };
+        let weight = entry.weight.saturating_add_signed(delta);
+        self.vocabulary.set_weight(self.vocabulary_selected, weight);
+        self.commit_vocabulary();
+    }
+
+    /// Save the vocabulary files and rebuild the dictionary from them.
+    fn commit_vocabulary(&mut self) {
+        if let Some(dir) = &self.vocabulary_dir
+            && let Err(err) = self.vocabulary.save_to(dir)
+        {
+            self.settings_status_message = Some(StatusMessage {
+                kind: StatusKind::Error,
+                text: t!("status.vocabulary_save_failed", error = err.to_string()).to_string(),
+            });
+        }
+        self.rebuild_dictionary();
     }
 
     fn switch_dictionary_language(&mut self, next_language_key: &str) {
@@ -1837,11 +2809,10 @@ impl App {
             .set_skill_tree_for_language(&previous_language_key, self.skill_tree.progress.clone());
         self.config.dictionary_language = next_language_key.to_string();
         self.rebuild_language_assets();
-        self.skill_tree = Self::build_skill_tree_for_language(
+        self.skill_tree = self.skill_tree_for(
             self.profile
                 .skill_tree_for_language(&self.config.dictionary_language),
             &self.config.dictionary_language,
-            &self.config.keyboard_layout,
         );
     }
 
@@ -1878,6 +2849,164 @@ impl App {
         true
     }
 
+    pub fn start_data_entry_drill(&mut self) {
+        self.drill_mode = DrillMode::DataEntry;
+        self.drill_scope = DrillScope::Global;
+        self.start_drill();
+    }
+
+    pub fn start_numpad_drill(&mut self) {
+        self.drill_mode = DrillMode::Numpad;
+        self.drill_scope = DrillScope::Global;
+        self.start_drill();
+    }
+
+    /// Start the placement test: one long sample over every branch whose results
+    /// seed ranked key stats and unlock whatever is already at confidence.
+    pub fn start_placement_test(&mut self) -> bool {
+        if !self.ensure_ranked_adaptive_readiness() {
+            return false;
+        }
+        self.drill_mode = DrillMode::Placement;
+        self.drill_scope = DrillScope::Global;
+        self.start_drill();
+        true
+    }
+
+    pub fn start_problem_words_drill(&mut self) {
+        self.drill_mode = DrillMode::ProblemWords;
+        self.drill_scope = DrillScope::Global;
+        self.start_drill();
+    }
+
+    pub fn go_to_technique_select(&mut self) {
+        self.technique_selected = Technique::all()
+            .iter()
+            .position(|t| *t == self.technique)
+            .unwrap_or(0);
+        self.screen = AppScreen::TechniqueSelect;
+    }
+
+    pub fn start_technique_drill(&mut self, technique: Technique) {
+        self.technique = technique;
+        self.drill_mode = DrillMode::Technique;
+        self.drill_scope = DrillScope::Global;
+        self.start_drill();
+    }
+
+    /// Start a round of shortcut prompts from the configured pack.
+    pub fn start_shortcut_drill(&mut self) {
+        self.clear_post_drill_input_lock();
+        self.drill = None;
+        self.drill_events.clear();
+        self.screen = AppScreen::ShortcutDrill;
+        match ShortcutPack::load(&self.config.shortcut_pack) {
+            Ok(pack) => {
+                self.shortcut_load_error = None;
+                self.shortcut_session = Some(ShortcutSession::new(
+                    pack,
+                    &self.shortcut_stats,
+                    SHORTCUT_PROMPTS_PER_ROUND,
+                    &mut self.rng,
+                ));
+            }
+            Err(err) => {
+                self.shortcut_load_error = Some(err.to_string());
+                self.shortcut_session = None;
+            }
+        }
+    }
+
+    /// Feed a pressed chord to the shortcut round, recording completed prompts.
+    pub fn shortcut_press(&mut self, chord: Chord) {
+        let Some(ref mut session) = self.shortcut_session else {
+            return;
+        };
+        if session.press(chord, Instant::now()) != ChordOutcome::Completed {
+            return;
+        }
+        if let Some((shortcut, attempt)) = session.last_attempt() {
+            self.shortcut_stats
+                .record(&session.pack.key, shortcut, attempt.clean, attempt.time_ms);
+        }
+        if session.is_finished() {
+            self.save_shortcut_stats();
+        }
+    }
+
+    pub fn leave_shortcut_drill(&mut self) {
+        self.save_shortcut_stats();
+        self.shortcut_session = None;
+        self.shortcut_load_error = None;
+        self.go_to_menu();
+    }
+
+    fn save_shortcut_stats(&self) {
+        if let Some(ref store) = self.store {
+            let _ = store.save_shortcut_stats(&ShortcutStatsData {
+                schema_version: SCHEMA_VERSION,
+                stats: self.shortcut_stats.clone(),
+            });
+        }
+    }
+
+    pub fn host_race(&mut self, port: u16, name: &str) -> anyhow::Result<()> {
+        let session = RaceSession::host(&format!("0.0.0.0:{port}"), name)?;
+        self.enter_race_lobby(session);
+        Ok(())
+    }
+
+    pub fn join_race(&mut self, address: &str, name: &str) -> anyhow::Result<()> {
+        let session = RaceSession::join(address, name)?;
+        self.enter_race_lobby(session);
+        Ok(())
+    }
+
+    fn enter_race_lobby(&mut self, session: RaceSession) {
+        self.clear_post_drill_input_lock();
+        self.race = Some(session);
+        self.drill_mode = DrillMode::Race;
+        self.drill = None;
+        self.drill_events.clear();
+        self.drill_source_info = None;
+        self.current_focus = None;
+        self.current_review.clear();
+        self.show_adaptive_intro = false;
+        self.screen = AppScreen::RaceLobby;
+    }
+
+    /// Host only: generate the race text and start the countdown.
+    pub fn start_race(&mut self) {
+        if !self.race.as_ref().is_some_and(|race| race.is_host()) {
+            return;
+        }
+        let seed = self.rng.next_u64();
+        let text = self.generate_race_text(seed);
+        if let Some(ref mut race) = self.race {
+            race.start(seed, &text);
+        }
+    }
+
+    /// Poll the race connection and open the drill once the countdown ends.
+    pub fn process_race_tick(&mut self) {
+        let Some(ref mut race) = self.race else {
+            return;
+        };
+        race.poll();
+        if self.screen == AppScreen::RaceLobby
+            && matches!(race.phase(), RacePhase::Racing | RacePhase::Finished)
+        {
+            self.start_drill();
+        }
+    }
+
+    /// Disconnect from the race (dropping a hosted race closes it for everyone).
+    pub fn leave_race(&mut self) {
+        self.race = None;
+        self.drill_mode = DrillMode::Adaptive;
+        self.go_to_menu();
+    }
+
     pub fn go_to_settings(&mut self) {
         self.settings_selected = SettingItem::TargetWpm.index();
         self.settings_editing_path = None;
@@ -1885,7 +3014,7 @@ impl App {
     }
 
     pub fn go_to_ui_language_select(&mut self) {
-        self.ui_language_selected = crate::i18n::SUPPORTED_UI_LOCALES
+        self.ui_language_selected = crate::i18n::ui_locales()
             .iter()
             .position(|&k| k == self.config.ui_language)
             .unwrap_or(0);
@@ -2442,6 +3571,19 @@ impl App {
         });
     }
 
+    fn cycle_shortcut_pack(&mut self, step: isize) {
+        let packs = ShortcutPack::available();
+        if packs.is_empty() {
+            return;
+        }
+        let idx = packs
+            .iter()
+            .position(|p| *p == self.config.shortcut_pack)
+            .unwrap_or(0) as isize;
+        let next = (idx + step).rem_euclid(packs.len() as isize) as usize;
+        self.config.shortcut_pack = packs[next].clone();
+    }
+
     pub fn settings_cycle_forward(&mut self) {
         match SettingItem::from_index(self.settings_selected) {
             SettingItem::TargetWpm => {
@@ -2449,6 +3591,11 @@ impl App {
                 self.key_stats.target_cpm = self.config.target_cpm();
                 self.ranked_key_stats.target_cpm = self.config.target_cpm();
             }
+            SettingItem::ForgettingCurve => {
+                self.config.forgetting_curve = self.config.forgetting_curve.next();
+                self.key_stats.forgetting = self.config.forgetting_curve;
+                self.ranked_key_stats.forgetting = self.config.forgetting_curve;
+            }
             SettingItem::Theme => {
                 let themes = Theme::available_themes();
                 if let Some(idx) = themes.iter().position(|t| *t == self.config.theme) {
@@ -2467,7 +3614,7 @@ impl App {
                 self.config.word_count = (self.config.word_count + 5).min(100);
             }
             SettingItem::UiLanguage => {
-                let locales = crate::i18n::SUPPORTED_UI_LOCALES;
+                let locales = crate::i18n::ui_locales();
                 let idx = locales.iter().position(|&l| l == self.config.ui_language).unwrap_or(0);
                 let next = (idx + 1) % locales.len();
                 self.config.ui_language = locales[next].to_string();
@@ -2479,6 +3626,9 @@ impl App {
             SettingItem::KeyboardLayout => {
                 self.apply_keyboard_layout_by_offset(1);
             }
+            SettingItem::ShortcutPack => {
+                self.cycle_shortcut_pack(1);
+            }
             SettingItem::CodeLanguage => {
                 let options = code_language_options();
                 let keys: Vec<&str> = options.iter().map(|(k, _)| *k).collect();
@@ -2516,12 +3666,18 @@ impl App {
                         n => n + 25,
                     };
             }
+            SettingItem::TableFormat => {
+                self.settings_table_format = self.settings_table_format.next();
+            }
             SettingItem::DownloadCodeNow
             | SettingItem::DownloadPassagesNow
             | SettingItem::ExportPath
             | SettingItem::ExportData
+            | SettingItem::ExportTables
             | SettingItem::ImportPath
-            | SettingItem::ImportData => {}
+            | SettingItem::ImportData
+            | SettingItem::MergeImport
+            | SettingItem::Vocabulary => {}
         }
     }
 
@@ -2532,6 +3688,11 @@ impl App {
                 self.key_stats.target_cpm = self.config.target_cpm();
                 self.ranked_key_stats.target_cpm = self.config.target_cpm();
             }
+            SettingItem::ForgettingCurve => {
+                self.config.forgetting_curve = self.config.forgetting_curve.prev();
+                self.key_stats.forgetting = self.config.forgetting_curve;
+                self.ranked_key_stats.forgetting = self.config.forgetting_curve;
+            }
             SettingItem::Theme => {
                 let themes = Theme::available_themes();
                 if let Some(idx) = themes.iter().position(|t| *t == self.config.theme) {
@@ -2550,7 +3711,7 @@ impl App {
                 self.config.word_count = self.config.word_count.saturating_sub(5).max(5);
             }
             SettingItem::UiLanguage => {
-                let locales = crate::i18n::SUPPORTED_UI_LOCALES;
+                let locales = crate::i18n::ui_locales();
                 let idx = locales.iter().position(|&l| l == self.config.ui_language).unwrap_or(0);
                 let next = if idx == 0 { locales.len() - 1 } else { idx - 1 };
                 self.config.ui_language = locales[next].to_string();
@@ -2562,6 +3723,9 @@ impl App {
             SettingItem::KeyboardLayout => {
                 self.apply_keyboard_layout_by_offset(-1);
             }
+            SettingItem::ShortcutPack => {
+                self.cycle_shortcut_pack(-1);
+            }
             SettingItem::CodeLanguage => {
                 let options = code_language_options();
                 let keys: Vec<&str> = options.iter().map(|(k, _)| *k).collect();
@@ -2599,12 +3763,18 @@ impl App {
                         n => n.saturating_sub(25).max(1),
                     };
             }
+            SettingItem::TableFormat => {
+                self.settings_table_format = self.settings_table_format.next();
+            }
             SettingItem::DownloadCodeNow
             | SettingItem::DownloadPassagesNow
             | SettingItem::ExportPath
             | SettingItem::ExportData
+            | SettingItem::ExportTables
             | SettingItem::ImportPath
-            | SettingItem::ImportData => {}
+            | SettingItem::ImportData
+            | SettingItem::MergeImport
+            | SettingItem::Vocabulary => {}
         }
     }
 }
@@ -2618,15 +3788,30 @@ fn branch_status_rank(status: &BranchStatus) -> u8 {
     }
 }
 
+/// Number of branches, built-in or custom, where `incoming` is further along than `local`.
+fn count_branches_ahead(local: &SkillTreeProgress, incoming: &SkillTreeProgress) -> usize {
+    incoming
+        .branches
+        .iter()
+        .filter(|(key, theirs)| match local.branches.get(key.as_str()) {
+            Some(ours) => {
+                branch_status_rank(&theirs.status) > branch_status_rank(&ours.status)
+                    || theirs.current_level > ours.current_level
+            }
+            None => branch_status_rank(&theirs.status) > 0 || theirs.current_level > 0,
+        })
+        .count()
+}
+
 fn merge_skill_tree_progress_non_regressive(
     skill_tree: &mut SkillTree,
     previous: &crate::engine::skill_tree::SkillTreeProgress,
 ) {
-    for id in crate::engine::skill_tree::BranchId::all() {
+    for id in skill_tree.branch_ids() {
         let Some(prev) = previous.branches.get(id.to_key()) else {
             continue;
         };
-        let curr = skill_tree.branch_progress_mut(*id);
+        let curr = skill_tree.branch_progress_mut(id);
         if branch_status_rank(&curr.status) < branch_status_rank(&prev.status) {
             curr.status = prev.status.clone();
         }
@@ -2664,6 +3849,32 @@ fn insert_line_breaks(text: &str) -> String {
     result
 }
 
+fn dictionary_nouns(dictionary: &Dictionary, language_key: &str) -> HashSet<String> {
+    match find_language_pack(language_key) {
+        Some(pack) if pack.text_rules.capitalizes_nouns => dictionary.capitalized_forms(),
+        _ => HashSet::new(),
+    }
+}
+
+/// Spread the words of each review segment evenly through the main text.
+fn interleave_words(main: &str, segments: &[String]) -> String {
+    let mut streams: Vec<std::str::SplitWhitespace> = std::iter::once(main)
+        .chain(segments.iter().map(String::as_str))
+        .map(str::split_whitespace)
+        .collect();
+    let mut words = Vec::new();
+    loop {
+        let before = words.len();
+        for stream in &mut streams {
+            words.extend(stream.next());
+        }
+        if words.len() == before {
+            break;
+        }
+    }
+    words.join(" ")
+}
+
 fn lowercase_generation_focus(focused: Option<char>) -> Option<char> {
     focused.and_then(|ch| {
         if ch.is_lowercase() {
@@ -2967,6 +4178,7 @@ impl App {
         let theme: &'static Theme = Box::leak(Box::new(Theme::default()));
         let menu = Menu::new(theme);
         let dictionary = Dictionary::load_for_language(&config.dictionary_language);
+        let dictionary_nouns = dictionary_nouns(&dictionary, &config.dictionary_language);
         let transition_table = TransitionTable::build_from_words(dictionary.words_list());
         let keyboard_model = KeyboardModel::from_key(&config.keyboard_layout)
             .expect("config validation must ensure a known keyboard layout");
@@ -2978,6 +4190,7 @@ impl App {
             drill: None,
             drill_events: Vec::new(),
             last_result: None,
+            last_regressed_keys: Vec::new(),
             drill_history: Vec::new(),
             menu,
             theme,
@@ -2985,6 +4198,7 @@ impl App {
             key_stats: KeyStatsStore::default(),
             ranked_key_stats: KeyStatsStore::default(),
             skill_tree: SkillTree::default(),
+            custom_branches: Vec::new(),
             profile: ProfileData::default(),
             store: None,
             should_quit: false,
@@ -3009,6 +4223,8 @@ impl App {
             code_language_selected: 0,
             code_language_scroll: 0,
             passage_book_selected: 0,
+            technique: Technique::all()[0],
+            technique_selected: 0,
             passage_intro_selected: 0,
             passage_intro_downloads_enabled: false,
             passage_intro_download_dir: String::new(),
@@ -3040,27 +4256,48 @@ impl App {
             code_download_action: CodeDownloadCompleteAction::StartCodeDrill,
             shift_held: false,
             caps_lock: false,
+            keypad_input: false,
             keyboard_model,
             milestone_queue: VecDeque::new(),
             show_adaptive_intro: false,
             settings_confirm_import: false,
-            settings_export_conflict: false,
+            settings_merge_pending: None,
+            settings_export_conflict: None,
             settings_status_message: None,
             settings_export_path: default_export_path(),
+            settings_table_format: TableFormat::Csv,
             settings_import_path: default_export_path(),
             keyboard_explorer_selected: None,
             explorer_accuracy_cache_overall: None,
             explorer_accuracy_cache_ranked: None,
+            explorer_key_set: Vec::new(),
+            explorer_key_set_name: None,
+            key_set_name_input: None,
+            key_set: Vec::new(),
             bigram_stats: BigramStatsStore::default(),
             ranked_bigram_stats: BigramStatsStore::default(),
+            trigram_stats: TrigramStatsStore::default(),
+            ranked_trigram_stats: TrigramStatsStore::default(),
+            word_stats: WordStatsStore::default(),
             user_median_transition_ms: 0.0,
             transition_buffer: Vec::new(),
             current_focus: None,
+            current_review: Vec::new(),
             post_drill_input_lock_until: None,
+            race: None,
+            shortcut_session: None,
+            shortcut_load_error: None,
+            shortcut_stats: ShortcutStatsStore::default(),
             adaptive_word_history: VecDeque::new(),
             rng: SmallRng::from_entropy(),
             transition_table,
             dictionary,
+            vocabulary: Vocabulary::default(),
+            vocabulary_dir: None,
+            vocabulary_tab: 0,
+            vocabulary_selected: 0,
+            vocabulary_input: None,
+            dictionary_nouns,
             passage_download_job: None,
             code_download_job: None,
         };
@@ -3372,7 +4609,7 @@ mod tests {
                 );
                 for &ch in tree.primary_letters() {
                     assert!(
-                        model.physical_key_for(ch).is_some(),
+                        model.can_type(ch),
                         "primary letter '{}' should be typeable for {} + {}",
                         ch,
                         pack.language_key,
@@ -3437,6 +4674,206 @@ mod tests {
         );
     }
 
+    #[test]
+    fn data_entry_drill_uses_digits_before_numbers_unlock() {
+        let mut app = App::new_test();
+        app.start_data_entry_drill();
+        assert_eq!(app.drill_mode, DrillMode::DataEntry);
+        let text = &app.drill.as_ref().unwrap().target;
+        assert!(text.iter().any(|ch| ch.is_ascii_digit()), "{text:?}");
+        assert!(!DrillMode::DataEntry.is_ranked());
+    }
+
+    #[test]
+    fn numpad_drill_records_keypad_stats_apart_from_number_row() {
+        let mut app = App::new_test();
+        app.start_numpad_drill();
+        assert_eq!(app.drill_mode, DrillMode::Numpad);
+        let target = app.drill.as_ref().unwrap().target.clone();
+        assert!(target.iter().all(|&ch| numpad_chars().any(|key| key == ch)));
+
+        for &ch in &target {
+            app.type_char(ch);
+        }
+        let typed = target[1];
+        let sentinel = numpad_sentinel(typed).unwrap();
+        assert!(app.key_stats.get_stat(sentinel).is_some());
+        assert!(
+            app.key_stats.get_stat(typed).is_none(),
+            "number row stats must stay untouched"
+        );
+    }
+
+    #[test]
+    fn problem_words_drill_targets_words_rebuilt_from_history() {
+        let mut app = App::new_test();
+        app.drill_history = (0..4)
+            .map(|i| {
+                let mut drill = merge_test_drill(1_000 + i, 't');
+                drill.per_key_times = "the and for rhythm"
+                    .chars()
+                    .map(|key| KeyTime {
+                        key,
+                        time_ms: if "rhythm".contains(key) { 600.0 } else { 100.0 },
+                        correct: true,
+                    })
+                    .collect();
+                drill
+            })
+            .collect();
+        app.rebuild_from_history();
+        assert_eq!(app.word_stats.stats["rhythm"].sample_count, 4);
+        let problems = app.word_stats.problem_words(5);
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].word, "rhythm");
+
+        app.start_problem_words_drill();
+        assert_eq!(app.drill_mode, DrillMode::ProblemWords);
+        assert!(!DrillMode::ProblemWords.is_ranked());
+        let text: String = app.drill.as_ref().unwrap().target.iter().collect();
+        assert!(text.split(' ').any(|w| w == "rhythm"), "{text}");
+    }
+
+    #[test]
+    fn keybr_imports_seed_keys_but_no_ngrams_or_words() {
+        let json = r#"[{"timeStamp":"2024-03-01T10:00:00.000Z","length":120,"time":30000,
+            "errors":1,"histogram":[
+                {"codePoint":97,"hitCount":6,"missCount":1,"timeToType":200},
+                {"codePoint":32,"hitCount":3,"missCount":0,"timeToType":150},
+                {"codePoint":116,"hitCount":5,"missCount":0,"timeToType":180}]}]"#;
+        let mut app = App::new_test();
+        app.drill_history = ExternalSource::Keybr.parse(json).unwrap().drills;
+        app.rebuild_ngram_stats();
+
+        assert_eq!(app.key_stats.stats[&'a'].total_count, 7);
+        assert!(app.bigram_stats.stats.is_empty());
+        assert!(app.trigram_stats.stats.is_empty());
+        assert!(app.word_stats.stats.is_empty());
+    }
+
+    #[test]
+    fn technique_drill_stays_on_the_chosen_hand() {
+        use crate::keyboard::finger::Hand;
+
+        let mut app = App::new_test();
+        app.go_to_technique_select();
+        assert_eq!(app.screen, AppScreen::TechniqueSelect);
+
+        let technique = Technique::Hand(Hand::Left);
+        app.start_technique_drill(technique);
+        assert_eq!(app.drill_mode, DrillMode::Technique);
+        assert!(!DrillMode::Technique.is_ranked());
+        let letters = technique.letters(&app.keyboard_model);
+        let text: String = app.drill.as_ref().unwrap().target.iter().collect();
+        assert!(
+            text.chars().all(|ch| ch == ' ' || letters.contains(&ch)),
+            "{text}"
+        );
+    }
+
+    #[test]
+    fn key_set_drill_uses_the_selection_on_the_unlocked_background() {
+        let mut app = App::new_test();
+        let unlocked = app.skill_tree.unlocked_keys(DrillScope::Global);
+        app.start_key_set_drill(vec!['z', 'Q', '#']);
+        assert_eq!(app.drill_mode, DrillMode::KeySet);
+        assert!(!DrillMode::KeySet.is_ranked());
+        let text: String = app.drill.as_ref().unwrap().target.iter().collect();
+        assert!(text.contains('z') && text.contains('#'), "{text}");
+        assert!(
+            text.chars()
+                .all(|ch| ch == ' ' || "zQq#".contains(ch) || unlocked.contains(&ch)),
+            "{text}"
+        );
+    }
+
+    #[test]
+    fn key_set_presets_save_and_cycle() {
+        let mut app = App::new_test();
+        assert!(!app.save_key_set_preset("Empty"));
+        for ch in ['q', 'a', 'z', ' '] {
+            app.toggle_explorer_key(ch);
+        }
+        assert!(app.save_key_set_preset(" Left pinky "));
+        app.explorer_key_set = vec!['p', ';'];
+        assert!(app.save_key_set_preset("Right pinky"));
+        assert_eq!(app.config.key_sets[0].keys, "qaz");
+        assert_eq!(app.config.key_sets[1].name, "Right pinky");
+
+        app.load_next_key_set_preset();
+        assert_eq!(app.explorer_key_set, vec!['q', 'a', 'z']);
+        assert_eq!(app.explorer_key_set_name.as_deref(), Some("Left pinky"));
+        app.toggle_explorer_key('a');
+        assert_eq!(app.explorer_key_set_name, None);
+        app.load_next_key_set_preset();
+        app.load_next_key_set_preset();
+        assert_eq!(app.explorer_key_set, vec!['p', ';']);
+    }
+
+    #[test]
+    fn custom_branch_drill_practises_its_keys() {
+        use crate::engine::custom_branch::{CustomBranch, CustomLevel};
+        use crate::engine::skill_tree::BranchProgress;
+
+        let mut app = App::new_test();
+        app.config.custom_branches = vec![CustomBranch {
+            key: "shell".to_string(),
+            name: "Shell".to_string(),
+            levels: vec![CustomLevel {
+                name: "Pipes".to_string(),
+                keys: "|>".to_string(),
+            }],
+        }];
+        app.custom_branches = custom_branch_definitions(&app.config.custom_branches);
+        let mut progress = SkillTreeProgress::default();
+        progress.branches.insert(
+            BranchId::Lowercase.to_key().to_string(),
+            BranchProgress {
+                status: BranchStatus::Complete,
+                current_level: 20,
+            },
+        );
+        progress.branches.insert(
+            "shell".to_string(),
+            BranchProgress {
+                status: BranchStatus::Available,
+                current_level: 0,
+            },
+        );
+        app.skill_tree = app.skill_tree_for(progress, "en");
+
+        let shell = BranchId::Custom("shell");
+        app.start_branch_drill(shell);
+        assert_eq!(app.drill_scope, DrillScope::Branch(shell));
+        assert_eq!(
+            *app.skill_tree.branch_status(shell),
+            BranchStatus::InProgress
+        );
+        let text: String = app.drill.as_ref().unwrap().target.iter().collect();
+        assert!(text.contains('|') || text.contains('>'), "{text}");
+    }
+
+    #[test]
+    fn shortcut_drill_records_stats_per_shortcut() {
+        let mut app = App::new_test();
+        app.start_shortcut_drill();
+        assert_eq!(app.screen, AppScreen::ShortcutDrill);
+        let session = app.shortcut_session.as_ref().expect("bundled vim pack");
+        let pack = session.pack.key.clone();
+        let shortcut = session.current().unwrap().clone();
+
+        for &chord in &shortcut.chords {
+            app.shortcut_press(chord);
+        }
+        let stat = app.shortcut_stats.get(&pack, &shortcut).unwrap();
+        assert_eq!((stat.attempts, stat.clean), (1, 1));
+        assert_eq!(app.shortcut_session.as_ref().unwrap().position, 1);
+
+        app.leave_shortcut_drill();
+        assert_eq!(app.screen, AppScreen::Menu);
+        assert!(app.shortcut_session.is_none());
+    }
+
     /// Helper: make the current drill look "completed" so finish_drill() processes it.
     fn complete_current_drill(app: &mut App) {
         if let Some(ref mut drill) = app.drill {
@@ -3691,6 +5128,25 @@ mod tests {
         }
     }
 
+    #[test]
+    fn placement_test_unlocks_confident_keys_without_popups() {
+        let mut app = App::new_test();
+        let unlocked_before = app.skill_tree.total_unlocked_count();
+        assert!(app.start_placement_test());
+        assert_eq!(app.drill_mode, DrillMode::Placement);
+        assert!(DrillMode::Placement.is_ranked());
+        let text: String = app.drill.as_ref().unwrap().target.iter().collect();
+        assert!(text.contains('\t') && text.contains('Q'), "{text}");
+
+        setup_drill_with_events(&mut app, &text);
+        app.milestone_queue.clear();
+        app.finish_drill();
+
+        assert!(app.skill_tree.total_unlocked_count() > unlocked_before);
+        assert!(app.milestone_queue.is_empty());
+        assert_eq!(app.screen, AppScreen::SkillTree);
+    }
+
     #[test]
     fn finish_drill_lowercase_complete_queue_order() {
         let mut app = App::new_test();
@@ -3962,4 +5418,227 @@ mod tests {
             "Should have AllKeysMastered popup, got: {kinds:?}"
         );
     }
+
+    #[test]
+    fn race_text_is_deterministic_for_a_seed() {
+        let app = App::new_test();
+        let text = app.generate_race_text(42);
+        assert!(!text.is_empty());
+        assert_eq!(text, app.generate_race_text(42));
+        assert_ne!(text, app.generate_race_text(43));
+    }
+
+    #[test]
+    fn vocabulary_editor_saves_and_reshapes_dictionary() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut app = App::new_test();
+        app.vocabulary_dir = Some(dir.path().to_path_buf());
+        assert!(!app.custom_vocabulary_active());
+        let bundled = app.dictionary.words_list()[0].clone();
+
+        app.go_to_vocabulary_editor();
+        app.submit_vocabulary_entry("kubectl 2");
+        app.adjust_selected_vocabulary_weight(1);
+        app.switch_vocabulary_tab();
+        app.submit_vocabulary_entry(&bundled);
+
+        assert!(app.custom_vocabulary_active());
+        assert_eq!(app.dictionary.weight("kubectl"), 3);
+        assert!(!app.dictionary.words_list().contains(&bundled));
+        assert_eq!(
+            crate::generator::vocabulary::Vocabulary::load_from(dir.path()),
+            app.vocabulary
+        );
+
+        app.remove_selected_vocabulary_entry();
+        assert!(app.vocabulary.blocked.is_empty());
+        assert!(app.dictionary.words_list().contains(&bundled));
+    }
+
+    fn merge_test_drill(secs: i64, key: char) -> DrillResult {
+        DrillResult {
+            wpm: 40.0,
+            cpm: 200.0,
+            accuracy: 100.0,
+            correct: 1,
+            incorrect: 0,
+            total_chars: 1,
+            elapsed_secs: 0.3,
+            timestamp: chrono::DateTime::from_timestamp(secs, 0).unwrap(),
+            per_key_times: vec![KeyTime {
+                key,
+                time_ms: 300.0,
+                correct: true,
+            }],
+            drill_mode: "adaptive".to_string(),
+            ranked: false,
+            partial: false,
+            completion_percent: 100.0,
+        }
+    }
+
+    #[test]
+    fn adaptive_drill_enrolls_focus_and_interleaves_due_reviews() {
+        let mut app = App::new_test();
+        let lang = app.config.dictionary_language.clone();
+        let past = chrono::Utc::now() - chrono::Duration::days(2);
+        app.profile
+            .review_schedule_mut(&lang)
+            .enroll(ReviewItem::Bigram(['t', 'a']), past);
+        app.start_drill();
+
+        assert_eq!(app.current_review, vec![ReviewItem::Bigram(['t', 'a'])]);
+        let focus = app.current_focus.as_ref().unwrap().char_focus.unwrap();
+        let schedule = app.profile.review_schedule_mut(&lang);
+        assert!(schedule.card(&ReviewItem::Key(focus)).is_some());
+        assert_eq!(schedule.due_items(chrono::Utc::now(), 3, |_| true).len(), 1);
+    }
+
+    #[test]
+    fn adaptive_drill_enrolls_problem_words_and_repeats_due_ones() {
+        let mut app = App::new_test();
+        let lang = app.config.dictionary_language.clone();
+        let unlocked = app.skill_tree.unlocked_keys(app.drill_scope);
+        let due_word: String = unlocked
+            .iter()
+            .filter(|ch| ch.is_lowercase())
+            .take(3)
+            .collect();
+        let past = chrono::Utc::now() - chrono::Duration::days(2);
+        app.profile
+            .review_schedule_mut(&lang)
+            .enroll(ReviewItem::Word(due_word.clone()), past);
+        let slow = crate::engine::word_stats::WordEvent {
+            word: "rhythm".to_string(),
+            time_ms: 5_000.0,
+            char_count: 6,
+            errors: 1,
+            has_hesitation: true,
+        };
+        for i in 0..3 {
+            app.word_stats.update(&slow, i, past);
+        }
+        app.start_drill();
+
+        assert_eq!(app.current_review, vec![ReviewItem::Word(due_word.clone())]);
+        let text: String = app.drill.as_ref().unwrap().target.iter().collect();
+        let repeats = text
+            .split_whitespace()
+            .filter(|w| normalize_word(w).as_deref() == Some(due_word.as_str()))
+            .count();
+        assert!(repeats >= WORD_REVIEW_REPEATS, "{text}");
+        let schedule = app.profile.review_schedule_mut(&lang);
+        assert!(
+            schedule
+                .card(&ReviewItem::Word("rhythm".to_string()))
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn interleave_words_spreads_segments() {
+        let segments = vec!["x y".to_string(), "z".to_string()];
+        assert_eq!(interleave_words("a b c", &segments), "a x z b y c");
+    }
+
+    #[test]
+    fn rebuild_records_last_practice_times() {
+        let mut app = App::new_test();
+        let mut pair = merge_test_drill(2_000, 'a');
+        pair.per_key_times.push(KeyTime {
+            key: 'b',
+            time_ms: 300.0,
+            correct: true,
+        });
+        app.drill_history = vec![
+            merge_test_drill(1_000, 'b'),
+            pair,
+            merge_test_drill(3_000, 'a'),
+        ];
+        app.rebuild_from_history();
+
+        let at = |secs| chrono::DateTime::from_timestamp(secs, 0);
+        let last = |key| app.key_stats.get_stat(key).unwrap().last_practiced;
+        assert_eq!(last('a'), at(3_000));
+        assert_eq!(last('b'), at(2_000));
+        let ab = app
+            .bigram_stats
+            .stats
+            .get(&crate::engine::ngram_stats::BigramKey(['a', 'b']))
+            .unwrap();
+        assert_eq!(ab.last_seen_at, at(2_000));
+    }
+
+    #[test]
+    fn merge_import_unions_history_and_keeps_furthest_progress() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut app = App::new_test();
+        app.store = Some(JsonStore::with_base_dir(dir.path().join("data")).unwrap());
+        app.drill_history = vec![merge_test_drill(1_000, 'a'), merge_test_drill(3_000, 'b')];
+        app.rebuild_from_history();
+
+        // The other machine shares one drill, has two new ones and got further
+        // in the capitals branch.
+        let mut incoming = app.store.as_ref().unwrap().export_all(&app.config);
+        incoming.drill_history.drills = vec![
+            merge_test_drill(1_000, 'a'),
+            merge_test_drill(2_000, 'c'),
+            merge_test_drill(4_000, 'c'),
+        ];
+        let mut progress = incoming
+            .profile
+            .skill_tree_for_language(&app.config.dictionary_language);
+        let capitals = progress
+            .branches
+            .entry(BranchId::Capitals.to_key().to_string())
+            .or_default();
+        capitals.status = BranchStatus::InProgress;
+        capitals.current_level = 2;
+        incoming
+            .profile
+            .set_skill_tree_for_language(&app.config.dictionary_language, progress);
+        let review_word = ReviewItem::Word("rhythm".to_string());
+        incoming
+            .profile
+            .review_schedule_mut(&app.config.dictionary_language)
+            .enroll(review_word.clone(), chrono::Utc::now());
+        let path = dir.path().join("other.json");
+        std::fs::write(&path, serde_json::to_string(&incoming).unwrap()).unwrap();
+        app.settings_import_path = path.to_string_lossy().to_string();
+
+        app.prepare_merge_import();
+        let pending = app.settings_merge_pending.as_ref().unwrap();
+        assert_eq!(pending.history.added, 2);
+        assert_eq!(pending.history.duplicates, 1);
+        assert_eq!(pending.branches_advanced, 1);
+        // Nothing is written before confirmation.
+        assert_eq!(app.drill_history.len(), 2);
+
+        app.commit_merge_import();
+        assert!(app.settings_merge_pending.is_none());
+        assert_eq!(
+            app.settings_status_message.as_ref().unwrap().kind,
+            StatusKind::Success
+        );
+        let stamps: Vec<i64> = app
+            .drill_history
+            .iter()
+            .map(|d| d.timestamp.timestamp())
+            .collect();
+        assert_eq!(stamps, vec![1_000, 2_000, 3_000, 4_000]);
+        assert_eq!(app.key_stats.get_stat('c').unwrap().sample_count, 2);
+        let capitals = app.skill_tree.branch_progress(BranchId::Capitals);
+        assert_eq!(capitals.status, BranchStatus::InProgress);
+        assert!(capitals.current_level >= 2);
+        let lang = app.config.dictionary_language.clone();
+        assert!(
+            app.profile
+                .review_schedule_mut(&lang)
+                .card(&review_word)
+                .is_some()
+        );
+
+        let on_disk = app.store.as_ref().unwrap().load_drill_history();
+        assert_eq!(on_disk.drills.len(), 4);
+    }
 }
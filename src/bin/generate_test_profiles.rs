@@ -57,6 +57,7 @@ fn make_key_stat(rng: &mut SmallRng, confidence: f64, sample_count: usize) -> Ke
         error_count,
         total_count,
         error_rate_ema: error_rate,
+        last_practiced: None,
     }
 }
 
@@ -137,7 +138,10 @@ fn make_skill_tree_progress(branches: Vec<(BranchId, BranchStatus, usize)>) -> S
                 current_level: 0,
             });
     }
-    SkillTreeProgress { branches: map }
+    SkillTreeProgress {
+        branches: map,
+        ..SkillTreeProgress::default()
+    }
 }
 
 /// Fixed exported_at timestamp for deterministic output.
@@ -290,6 +294,7 @@ fn make_profile_data(
         streak_days,
         best_streak,
         last_practice_date,
+        review_schedule_by_language: HashMap::new(),
     }
 }
 
@@ -1,12 +1,17 @@
 use std::fs;
 use std::path::PathBuf;
 
+use crate::engine::custom_branch::{CustomBranch, normalize_custom_branches};
+use crate::engine::forgetting::ForgettingCurve;
+use crate::engine::mastery::MasteryTargets;
+use crate::generator::key_set::{KeySet, normalize_key_sets};
 use crate::i18n;
 use crate::keyboard::model::KeyboardModel;
 use crate::l10n::language_pack::{
     LanguageLayoutValidationError, dictionary_languages_for_layout, supported_dictionary_languages,
     validate_language_layout_pair,
 };
+use crate::shortcut::{DEFAULT_SHORTCUT_PACK, ShortcutPack};
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 
@@ -46,6 +51,19 @@ pub struct Config {
     pub ui_language: String,
     #[serde(default = "default_adaptive_intro_done")]
     pub adaptive_intro_done: bool,
+    #[serde(default = "default_shortcut_pack")]
+    pub shortcut_pack: String,
+    #[serde(default)]
+    pub forgetting_curve: ForgettingCurve,
+    /// Extra skill tree branches, after the built-in ones.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub custom_branches: Vec<CustomBranch>,
+    /// Per-branch speed targets and an accuracy requirement for mastery.
+    #[serde(default)]
+    pub mastery: MasteryTargets,
+    /// Key sets saved from the keyboard explorer.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub key_sets: Vec<KeySet>,
 }
 
 fn default_target_wpm() -> u32 {
@@ -109,6 +127,9 @@ fn default_code_onboarding_done() -> bool {
 fn default_ui_language() -> String {
     "en".to_string()
 }
+fn default_shortcut_pack() -> String {
+    DEFAULT_SHORTCUT_PACK.to_string()
+}
 
 impl Default for Config {
     fn default() -> Self {
@@ -130,6 +151,11 @@ impl Default for Config {
             code_onboarding_done: default_code_onboarding_done(),
             ui_language: default_ui_language(),
             adaptive_intro_done: default_adaptive_intro_done(),
+            shortcut_pack: default_shortcut_pack(),
+            forgetting_curve: ForgettingCurve::default(),
+            custom_branches: Vec::new(),
+            mastery: MasteryTargets::default(),
+            key_sets: Vec::new(),
         }
     }
 }
@@ -177,6 +203,10 @@ impl Config {
         self.normalize_dictionary_language();
         self.normalize_language_layout_pair();
         self.normalize_ui_language();
+        self.normalize_shortcut_pack();
+        self.custom_branches = normalize_custom_branches(&self.custom_branches);
+        self.mastery.normalize();
+        self.key_sets = normalize_key_sets(&self.key_sets);
     }
 
     /// Validate `code_language` against known options, resetting to default if invalid.
@@ -191,6 +221,13 @@ impl Config {
         }
     }
 
+    /// Reset `shortcut_pack` when the pack file has been removed.
+    fn normalize_shortcut_pack(&mut self) {
+        if !ShortcutPack::available().contains(&self.shortcut_pack) {
+            self.shortcut_pack = default_shortcut_pack();
+        }
+    }
+
     /// Validate `dictionary_language` against supported keys.
     fn normalize_dictionary_language(&mut self) {
         if !supported_dictionary_languages().contains(&self.dictionary_language.as_str()) {
@@ -231,7 +268,7 @@ impl Config {
 
     /// Validate `ui_language` against supported UI locales.
     fn normalize_ui_language(&mut self) {
-        if !i18n::SUPPORTED_UI_LOCALES.contains(&self.ui_language.as_str()) {
+        if !i18n::ui_locales().contains(&self.ui_language.as_str()) {
             self.ui_language = default_ui_language();
         }
     }
@@ -253,6 +290,7 @@ mod tests {
         assert_eq!(config.code_snippets_per_repo, 200);
         assert_eq!(config.code_onboarding_done, false);
         assert_eq!(config.dictionary_language, "en");
+        assert_eq!(config.forgetting_curve, ForgettingCurve::Normal);
         assert!(!config.code_download_dir.is_empty());
         assert!(config.code_download_dir.contains("code"));
     }
@@ -297,6 +335,84 @@ code_language = "go"
         assert_eq!(config.dictionary_language, deserialized.dictionary_language);
     }
 
+    #[test]
+    fn test_custom_branches_roundtrip_as_tables() {
+        let toml_str = r#"
+target_wpm = 40
+
+[[custom_branches]]
+key = "shell"
+name = "Shell"
+levels = [
+    { name = "Pipes and redirects", keys = "|>" },
+    { name = "Variables", keys = "$~" },
+]
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.custom_branches.len(), 1);
+        assert_eq!(config.custom_branches[0].levels[1].keys, "$~");
+
+        let serialized = toml::to_string_pretty(&config).unwrap();
+        let deserialized: Config = toml::from_str(&serialized).unwrap();
+        assert_eq!(deserialized.custom_branches, config.custom_branches);
+        assert!(
+            !toml::to_string_pretty(&Config::default())
+                .unwrap()
+                .contains("custom_branches")
+        );
+    }
+
+    #[test]
+    fn test_key_sets_roundtrip_and_validate() {
+        let toml_str = r#"
+[[key_sets]]
+name = "Left pinky"
+keys = "qaz1"
+
+[[key_sets]]
+name = "  "
+keys = "p"
+"#;
+        let mut config: Config = toml::from_str(toml_str).unwrap();
+        config.validate(&["rust"]);
+        assert_eq!(config.key_sets.len(), 1);
+        assert_eq!(config.key_sets[0].keys, "qaz1");
+
+        let serialized = toml::to_string_pretty(&config).unwrap();
+        let deserialized: Config = toml::from_str(&serialized).unwrap();
+        assert_eq!(deserialized.key_sets, config.key_sets);
+        assert!(
+            !toml::to_string_pretty(&Config::default())
+                .unwrap()
+                .contains("key_sets")
+        );
+    }
+
+    #[test]
+    fn test_mastery_targets_roundtrip_and_validate() {
+        let toml_str = r#"
+[mastery]
+max_error_rate = 0.08
+
+[mastery.branch_speed]
+numbers = 70
+code_symbols = 500
+"#;
+        let mut config: Config = toml::from_str(toml_str).unwrap();
+        config.validate(&["rust"]);
+        assert_eq!(config.mastery.max_error_rate, Some(0.08));
+        assert_eq!(config.mastery.branch_speed["numbers"], 70);
+        assert_eq!(config.mastery.branch_speed["code_symbols"], 200);
+
+        let serialized = toml::to_string_pretty(&config).unwrap();
+        let deserialized: Config = toml::from_str(&serialized).unwrap();
+        assert_eq!(deserialized.mastery, config.mastery);
+        assert_eq!(
+            toml::from_str::<Config>("").unwrap().mastery,
+            MasteryTargets::default()
+        );
+    }
+
     #[test]
     fn test_normalize_code_language_valid_key_unchanged() {
         let mut config = Config::default();
@@ -0,0 +1,177 @@
+//! User-defined skill tree branches, read from the `[[custom_branches]]`
+//! tables of `config.toml`:
+//!
+//! ```toml
+//! [[custom_branches]]
+//! key = "markdown"
+//! name = "Markdown"
+//! levels = [
+//!     { name = "Headings and emphasis", keys = "#*_" },
+//!     { name = "Links", keys = "[]()" },
+//! ]
+//! ```
+//!
+//! Progress is saved under `key`, so a branch can be renamed or extended
+//! without losing it.
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::engine::skill_tree::{BranchDefinition, BranchId, LevelDefinition};
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustomBranch {
+    pub key: String,
+    #[serde(default)]
+    pub name: String,
+    pub levels: Vec<CustomLevel>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustomLevel {
+    #[serde(default)]
+    pub name: String,
+    /// Keys unlocked together at this level, written as one string.
+    pub keys: String,
+}
+
+/// Drop branches that can't be used: keys that are empty, malformed, taken by
+/// a built-in branch or repeated, and branches left without any level. Within
+/// a branch, whitespace and repeated keys are removed and empty levels dropped.
+pub fn normalize_custom_branches(branches: &[CustomBranch]) -> Vec<CustomBranch> {
+    let mut seen_keys: HashSet<&str> = HashSet::new();
+    let mut normalized = Vec::new();
+    for branch in branches {
+        let key = branch.key.trim();
+        let valid_key = !key.is_empty()
+            && key
+                .chars()
+                .all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '-');
+        if !valid_key || BranchId::from_key(key).is_some() || !seen_keys.insert(key) {
+            continue;
+        }
+
+        let mut branch_keys: HashSet<char> = HashSet::new();
+        let levels: Vec<CustomLevel> = branch
+            .levels
+            .iter()
+            .map(|level| CustomLevel {
+                name: level.name.trim().to_string(),
+                keys: level
+                    .keys
+                    .chars()
+                    .filter(|ch| !ch.is_whitespace() && !ch.is_control())
+                    .filter(|&ch| branch_keys.insert(ch))
+                    .collect(),
+            })
+            .filter(|level| !level.keys.is_empty())
+            .collect();
+        if levels.is_empty() {
+            continue;
+        }
+        normalized.push(CustomBranch {
+            key: key.to_string(),
+            name: branch.name.trim().to_string(),
+            levels,
+        });
+    }
+    normalized
+}
+
+/// Static definitions for normalized custom branches. Like the built-in
+/// branches they live for the whole process, so each call leaks a little
+/// memory; call it when the configuration is loaded, not per drill.
+pub fn custom_branch_definitions(branches: &[CustomBranch]) -> Vec<&'static BranchDefinition> {
+    branches
+        .iter()
+        .map(|branch| {
+            let key = leak_str(branch.key.clone());
+            let levels: Vec<LevelDefinition> = branch
+                .levels
+                .iter()
+                .enumerate()
+                .map(|(i, level)| LevelDefinition {
+                    name_key: if level.name.is_empty() {
+                        leak_str(format!("{} {}", branch_name(branch), i + 1))
+                    } else {
+                        leak_str(level.name.clone())
+                    },
+                    keys: Box::leak(level.keys.chars().collect::<Vec<_>>().into_boxed_slice()),
+                })
+                .collect();
+            let definition: &'static BranchDefinition = Box::leak(Box::new(BranchDefinition {
+                id: BranchId::Custom(key),
+                name_key: leak_str(branch_name(branch).to_string()),
+                levels: Box::leak(levels.into_boxed_slice()),
+            }));
+            definition
+        })
+        .collect()
+}
+
+fn branch_name(branch: &CustomBranch) -> &str {
+    if branch.name.is_empty() {
+        &branch.key
+    } else {
+        &branch.name
+    }
+}
+
+fn leak_str(value: String) -> &'static str {
+    Box::leak(value.into_boxed_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn branch(key: &str, levels: &[&str]) -> CustomBranch {
+        CustomBranch {
+            key: key.to_string(),
+            name: String::new(),
+            levels: levels
+                .iter()
+                .map(|keys| CustomLevel {
+                    name: String::new(),
+                    keys: keys.to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn normalization_drops_unusable_branches_and_keys() {
+        let branches = vec![
+            branch("markdown", &["# *_", "[]()*", "  "]),
+            branch("markdown", &["~"]),
+            branch("numbers", &["√"]),
+            branch("my shell", &["|"]),
+            branch("", &["|"]),
+            branch("empty", &[" \t"]),
+        ];
+        let normalized = normalize_custom_branches(&branches);
+        assert_eq!(normalized.len(), 1);
+        let keys: Vec<&str> = normalized[0]
+            .levels
+            .iter()
+            .map(|level| level.keys.as_str())
+            .collect();
+        assert_eq!(keys, vec!["#*_", "[]()"]);
+    }
+
+    #[test]
+    fn definitions_keep_level_order_and_literal_names() {
+        let mut math = branch("math", &["^√", "≤≥"]);
+        math.name = "Math".to_string();
+        math.levels[0].name = "Powers and roots".to_string();
+        let definitions = custom_branch_definitions(&[math]);
+        let math = definitions[0];
+        assert_eq!(math.id, BranchId::Custom("math"));
+        assert_eq!(math.id.to_key(), "math");
+        assert_eq!(math.display_name(), "Math");
+        assert_eq!(math.levels[0].display_name(), "Powers and roots");
+        assert_eq!(math.levels[1].display_name(), "Math 2");
+        assert_eq!(math.levels[1].keys, &['≤', '≥']);
+    }
+}
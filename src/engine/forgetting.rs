@@ -0,0 +1,93 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+/// How quickly practiced keys and n-grams are assumed to fade from memory.
+///
+/// Retention follows an exponential curve: after one half-life without
+/// practice, half of the measured confidence is considered retained.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ForgettingCurve {
+    Off,
+    Slow,
+    #[default]
+    Normal,
+    Fast,
+}
+
+impl ForgettingCurve {
+    pub const ALL: [Self; 4] = [Self::Off, Self::Slow, Self::Normal, Self::Fast];
+
+    pub fn half_life_days(self) -> Option<f64> {
+        match self {
+            Self::Off => None,
+            Self::Slow => Some(60.0),
+            Self::Normal => Some(21.0),
+            Self::Fast => Some(7.0),
+        }
+    }
+
+    /// Fraction of skill retained since `last_practiced`, in `(0.0, 1.0]`.
+    /// Items with no recorded practice time are treated as fresh.
+    pub fn retention(self, last_practiced: Option<DateTime<Utc>>, now: DateTime<Utc>) -> f64 {
+        let (Some(half_life), Some(last)) = (self.half_life_days(), last_practiced) else {
+            return 1.0;
+        };
+        let elapsed_days = (now - last).num_seconds().max(0) as f64 / SECONDS_PER_DAY;
+        0.5f64.powf(elapsed_days / half_life)
+    }
+
+    pub fn next(self) -> Self {
+        let idx = Self::ALL.iter().position(|c| *c == self).unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+
+    pub fn prev(self) -> Self {
+        let idx = Self::ALL.iter().position(|c| *c == self).unwrap_or(0);
+        Self::ALL[(idx + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+
+    pub fn localized_name(self) -> String {
+        use crate::i18n::t;
+        match self {
+            Self::Off => t!("settings.forgetting_off").to_string(),
+            Self::Slow => t!("settings.forgetting_slow").to_string(),
+            Self::Normal => t!("settings.forgetting_normal").to_string(),
+            Self::Fast => t!("settings.forgetting_fast").to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn retention_halves_after_one_half_life() {
+        let now = Utc::now();
+        let last = now - Duration::days(21);
+        let r = ForgettingCurve::Normal.retention(Some(last), now);
+        assert!((r - 0.5).abs() < 1e-9, "got {r}");
+    }
+
+    #[test]
+    fn retention_is_full_when_off_or_unknown() {
+        let now = Utc::now();
+        let last = now - Duration::days(365);
+        assert_eq!(ForgettingCurve::Off.retention(Some(last), now), 1.0);
+        assert_eq!(ForgettingCurve::Fast.retention(None, now), 1.0);
+    }
+
+    #[test]
+    fn faster_curves_forget_more() {
+        let now = Utc::now();
+        let last = Some(now - Duration::days(10));
+        let slow = ForgettingCurve::Slow.retention(last, now);
+        let normal = ForgettingCurve::Normal.retention(last, now);
+        let fast = ForgettingCurve::Fast.retention(last, now);
+        assert!(slow > normal && normal > fast);
+    }
+}
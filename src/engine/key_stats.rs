@@ -1,6 +1,9 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::engine::forgetting::ForgettingCurve;
+
 const EMA_ALPHA: f64 = 0.1;
 const DEFAULT_TARGET_CPM: f64 = 175.0;
 
@@ -17,6 +20,8 @@ pub struct KeyStat {
     pub total_count: usize,
     #[serde(default = "default_error_rate_ema")]
     pub error_rate_ema: f64,
+    #[serde(default)]
+    pub last_practiced: Option<DateTime<Utc>>,
 }
 
 fn default_error_rate_ema() -> f64 {
@@ -34,6 +39,7 @@ impl Default for KeyStat {
             error_count: 0,
             total_count: 0,
             error_rate_ema: 0.5,
+            last_practiced: None,
         }
     }
 }
@@ -42,6 +48,9 @@ impl Default for KeyStat {
 pub struct KeyStatsStore {
     pub stats: HashMap<char, KeyStat>,
     pub target_cpm: f64,
+    /// Mirrors the configured curve; not persisted with the stats.
+    #[serde(skip)]
+    pub forgetting: ForgettingCurve,
 }
 
 impl Default for KeyStatsStore {
@@ -49,6 +58,7 @@ impl Default for KeyStatsStore {
         Self {
             stats: HashMap::new(),
             target_cpm: DEFAULT_TARGET_CPM,
+            forgetting: ForgettingCurve::default(),
         }
     }
 }
@@ -87,6 +97,27 @@ impl KeyStatsStore {
         self.stats.get(&key).map(|s| s.confidence).unwrap_or(0.0)
     }
 
+    /// Record that `key` was typed in a drill finished at `at`.
+    pub fn mark_practiced(&mut self, key: char, at: DateTime<Utc>) {
+        if let Some(stat) = self.stats.get_mut(&key)
+            && stat.last_practiced.is_none_or(|last| last < at)
+        {
+            stat.last_practiced = Some(at);
+        }
+    }
+
+    /// Fraction of the key's measured confidence still retained at `now`.
+    pub fn retention(&self, key: char, now: DateTime<Utc>) -> f64 {
+        let last = self.stats.get(&key).and_then(|s| s.last_practiced);
+        self.forgetting.retention(last, now)
+    }
+
+    /// Confidence decayed by the time since the key was last practiced.
+    /// Unlock and mastery decisions keep using `get_confidence`.
+    pub fn retained_confidence(&self, key: char, now: DateTime<Utc>) -> f64 {
+        self.get_confidence(key) * self.retention(key, now)
+    }
+
     #[allow(dead_code)]
     pub fn get_stat(&self, key: char) -> Option<&KeyStat> {
         self.stats.get(&key)
@@ -202,6 +233,22 @@ mod tests {
         assert!((store.smoothed_error_rate('z') - 0.5).abs() < f64::EPSILON);
     }
 
+    #[test]
+    fn test_retained_confidence_decays_with_time() {
+        let mut store = KeyStatsStore::default();
+        for _ in 0..50 {
+            store.update_key('e', 200.0);
+        }
+        let now = Utc::now();
+        store.mark_practiced('e', now - chrono::Duration::days(42));
+        let conf = store.get_confidence('e');
+        let retained = store.retained_confidence('e', now);
+        assert!((retained - conf * 0.25).abs() < 1e-9);
+
+        store.forgetting = ForgettingCurve::Off;
+        assert_eq!(store.retained_confidence('e', now), conf);
+    }
+
     #[test]
     fn test_ema_error_rate_serde_default() {
         // Verify backward compat: deserializing old data without error_rate_ema gets 0.5
@@ -0,0 +1,115 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::engine::key_stats::KeyStatsStore;
+
+const MIN_SPEED_PERCENT: u32 = 25;
+const MAX_SPEED_PERCENT: u32 = 200;
+
+/// What it takes for a key to count as mastered, read from the `[mastery]`
+/// table of `config.toml`:
+///
+/// ```toml
+/// [mastery]
+/// max_error_rate = 0.1
+///
+/// [mastery.branch_speed]
+/// numbers = 80
+/// code_symbols = 60
+/// ```
+///
+/// Speeds are percentages of `target_wpm` for the keys of a branch, so digits
+/// and symbols can ask for less than home-row letters. The defaults keep one
+/// speed for every key and ignore accuracy.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct MasteryTargets {
+    /// Highest smoothed error rate (0.0 to 1.0) a mastered key may have.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_error_rate: Option<f64>,
+    /// Target speed per branch key, in percent of `target_wpm`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub branch_speed: BTreeMap<String, u32>,
+}
+
+impl MasteryTargets {
+    /// Clamp speeds and the error rate to usable ranges.
+    pub fn normalize(&mut self) {
+        for percent in self.branch_speed.values_mut() {
+            *percent = (*percent).clamp(MIN_SPEED_PERCENT, MAX_SPEED_PERCENT);
+        }
+        self.max_error_rate = self.max_error_rate.map(|rate| rate.clamp(0.0, 1.0));
+    }
+
+    /// Target speed of a branch as a fraction of `target_wpm`.
+    pub fn speed_factor(&self, branch_key: &str) -> f64 {
+        self.branch_speed
+            .get(branch_key)
+            .map_or(1.0, |&percent| percent as f64 / 100.0)
+    }
+
+    /// Confidence of `key` against the branch's own speed target; 1.0 means
+    /// the target is met.
+    pub fn progress(&self, branch_key: &str, key: char, stats: &KeyStatsStore) -> f64 {
+        stats.get_confidence(key) / self.speed_factor(branch_key)
+    }
+
+    /// Whether `key` meets the branch's speed target and, when one is set,
+    /// the accuracy requirement.
+    pub fn is_mastered(&self, branch_key: &str, key: char, stats: &KeyStatsStore) -> bool {
+        self.progress(branch_key, key, stats) >= 1.0
+            && self
+                .max_error_rate
+                .is_none_or(|max| stats.smoothed_error_rate(key) <= max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats_at(time_ms: f64, errors: usize) -> KeyStatsStore {
+        let mut stats = KeyStatsStore::default();
+        for _ in 0..50 {
+            stats.update_key('{', time_ms);
+        }
+        for _ in 0..errors {
+            stats.update_key_error('{');
+        }
+        stats
+    }
+
+    #[test]
+    fn branch_speed_lowers_the_bar_for_its_keys() {
+        let mut targets = MasteryTargets::default();
+        // 500ms per key is about 69% of the default 175 CPM target.
+        let stats = stats_at(500.0, 0);
+        assert!(!targets.is_mastered("code_symbols", '{', &stats));
+
+        targets.branch_speed.insert("code_symbols".to_string(), 60);
+        assert!(targets.is_mastered("code_symbols", '{', &stats));
+        assert!(!targets.is_mastered("numbers", '{', &stats));
+        assert!((targets.progress("code_symbols", '{', &stats) - 1.14).abs() < 0.01);
+    }
+
+    #[test]
+    fn accuracy_requirement_uses_smoothed_error_rate() {
+        let targets = MasteryTargets {
+            max_error_rate: Some(0.1),
+            ..MasteryTargets::default()
+        };
+        assert!(targets.is_mastered("numbers", '{', &stats_at(200.0, 0)));
+        assert!(!targets.is_mastered("numbers", '{', &stats_at(200.0, 3)));
+    }
+
+    #[test]
+    fn normalize_clamps_out_of_range_values() {
+        let mut targets = MasteryTargets {
+            max_error_rate: Some(1.5),
+            branch_speed: BTreeMap::from([("numbers".to_string(), 5)]),
+        };
+        targets.normalize();
+        assert_eq!(targets.max_error_rate, Some(1.0));
+        assert_eq!(targets.branch_speed["numbers"], MIN_SPEED_PERCENT);
+    }
+}
@@ -1,8 +1,14 @@
+pub mod custom_branch;
 pub mod filter;
+pub mod forgetting;
 pub mod key_stats;
 pub mod learning_rate;
+pub mod mastery;
 pub mod ngram_stats;
+pub mod regression;
+pub mod review;
 pub mod scoring;
 pub mod skill_tree;
+pub mod word_stats;
 
 pub use ngram_stats::FocusSelection;
@@ -1,5 +1,7 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 use crate::engine::key_stats::KeyStatsStore;
@@ -19,9 +21,33 @@ const MIN_CHAR_SAMPLES_FOR_SPEED: usize = 10;
 // N-gram keys
 // ---------------------------------------------------------------------------
 
+/// A run of consecutive keys tracked as one unit.
+pub trait NgramKey: Clone + Eq + Hash {
+    fn chars(&self) -> &[char];
+
+    fn label(&self) -> String {
+        self.chars().iter().collect()
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct BigramKey(pub [char; 2]);
 
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TrigramKey(pub [char; 3]);
+
+impl NgramKey for BigramKey {
+    fn chars(&self) -> &[char] {
+        &self.0
+    }
+}
+
+impl NgramKey for TrigramKey {
+    fn chars(&self) -> &[char] {
+        &self.0
+    }
+}
+
 // ---------------------------------------------------------------------------
 // NgramStat
 // ---------------------------------------------------------------------------
@@ -41,6 +67,8 @@ pub struct NgramStat {
     pub speed_anomaly_streak: u8,
     #[serde(default)]
     pub last_seen_drill_index: u32,
+    #[serde(default)]
+    pub last_seen_at: Option<DateTime<Utc>>,
 }
 
 fn default_error_rate_ema() -> f64 {
@@ -60,6 +88,7 @@ impl Default for NgramStat {
             error_anomaly_streak: 0,
             speed_anomaly_streak: 0,
             last_seen_drill_index: 0,
+            last_seen_at: None,
         }
     }
 }
@@ -103,18 +132,29 @@ fn update_stat(
 }
 
 // ---------------------------------------------------------------------------
-// BigramStatsStore
+// NgramStatsStore
 // ---------------------------------------------------------------------------
 
-#[derive(Clone, Debug, Default, Serialize, Deserialize)]
-pub struct BigramStatsStore {
-    pub stats: HashMap<BigramKey, NgramStat>,
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NgramStatsStore<K: NgramKey> {
+    pub stats: HashMap<K, NgramStat>,
 }
 
-impl BigramStatsStore {
+pub type BigramStatsStore = NgramStatsStore<BigramKey>;
+pub type TrigramStatsStore = NgramStatsStore<TrigramKey>;
+
+impl<K: NgramKey> Default for NgramStatsStore<K> {
+    fn default() -> Self {
+        Self {
+            stats: HashMap::new(),
+        }
+    }
+}
+
+impl<K: NgramKey> NgramStatsStore<K> {
     pub fn update(
         &mut self,
-        key: BigramKey,
+        key: K,
         time_ms: f64,
         correct: bool,
         hesitation: bool,
@@ -124,48 +164,91 @@ impl BigramStatsStore {
         update_stat(stat, time_ms, correct, hesitation, drill_index);
     }
 
-    pub fn smoothed_error_rate(&self, key: &BigramKey) -> f64 {
+    /// Apply one drill's events, then advance the anomaly streaks once per
+    /// unique n-gram seen (not once per event). `at` is the drill's timestamp.
+    pub fn record_drill(
+        &mut self,
+        events: &[NgramEvent<K>],
+        char_stats: &KeyStatsStore,
+        drill_index: u32,
+        at: DateTime<Utc>,
+    ) {
+        let mut seen: HashSet<K> = HashSet::new();
+        for ev in events {
+            seen.insert(ev.key.clone());
+            self.update(
+                ev.key.clone(),
+                ev.total_time_ms,
+                ev.correct,
+                ev.has_hesitation,
+                drill_index,
+            );
+        }
+        for key in &seen {
+            if let Some(stat) = self.stats.get_mut(key) {
+                stat.last_seen_at = Some(at);
+            }
+            self.update_error_anomaly_streak(key, char_stats);
+            self.update_speed_anomaly_streak(key, char_stats);
+        }
+    }
+
+    pub fn smoothed_error_rate(&self, key: &K) -> f64 {
         match self.stats.get(key) {
             Some(s) => s.error_rate_ema,
             None => 0.5,
         }
     }
 
-    /// Error anomaly ratio: bigram error rate / expected error rate from char independence.
-    /// Values > 1.0 indicate genuine bigram difficulty beyond individual char weakness.
-    pub fn error_anomaly_ratio(&self, key: &BigramKey, char_stats: &KeyStatsStore) -> f64 {
-        let e_a = char_stats.smoothed_error_rate(key.0[0]);
-        let e_b = char_stats.smoothed_error_rate(key.0[1]);
-        let e_ab = self.smoothed_error_rate(key);
-        let expected_ab = 1.0 - (1.0 - e_a) * (1.0 - e_b);
-        e_ab / expected_ab.max(0.01)
+    /// Error rate expected if every key in the n-gram failed independently.
+    fn expected_error_rate(key: &K, char_stats: &KeyStatsStore) -> f64 {
+        let all_correct: f64 = key
+            .chars()
+            .iter()
+            .map(|&ch| 1.0 - char_stats.smoothed_error_rate(ch))
+            .product();
+        1.0 - all_correct
+    }
+
+    /// Error anomaly ratio: n-gram error rate / expected error rate from char independence.
+    /// Values > 1.0 indicate genuine n-gram difficulty beyond individual char weakness.
+    pub fn error_anomaly_ratio(&self, key: &K, char_stats: &KeyStatsStore) -> f64 {
+        let expected = Self::expected_error_rate(key, char_stats);
+        self.smoothed_error_rate(key) / expected.max(0.01)
     }
 
     /// Error anomaly as percentage: (ratio - 1.0) * 100.
-    /// Returns None if bigram has no stats.
+    /// Returns None if the n-gram has no stats.
     #[allow(dead_code)]
-    pub fn error_anomaly_pct(&self, key: &BigramKey, char_stats: &KeyStatsStore) -> Option<f64> {
+    pub fn error_anomaly_pct(&self, key: &K, char_stats: &KeyStatsStore) -> Option<f64> {
         let _stat = self.stats.get(key)?;
         let ratio = self.error_anomaly_ratio(key, char_stats);
         Some((ratio - 1.0) * 100.0)
     }
 
-    /// Speed anomaly: % slower than user types char_b in isolation.
-    /// Compares bigram filtered_time_ms to char_b's filtered_time_ms.
-    /// Returns None if bigram has no stats or char_b has < MIN_CHAR_SAMPLES_FOR_SPEED samples.
-    pub fn speed_anomaly_pct(&self, key: &BigramKey, char_stats: &KeyStatsStore) -> Option<f64> {
+    /// Time the user needs for the keys after the first one when typed in
+    /// isolation. For bigram "ab" this is char_b's filtered_time_ms.
+    /// Returns None if any of those chars has < MIN_CHAR_SAMPLES_FOR_SPEED samples.
+    fn speed_baseline(key: &K, char_stats: &KeyStatsStore) -> Option<f64> {
+        key.chars()[1..].iter().try_fold(0.0, |total, ch| {
+            let stat = char_stats.stats.get(ch)?;
+            (stat.sample_count >= MIN_CHAR_SAMPLES_FOR_SPEED)
+                .then_some(total + stat.filtered_time_ms)
+        })
+    }
+
+    /// Speed anomaly: % slower than the user types the trailing chars in isolation.
+    /// Returns None if the n-gram has no stats or a char baseline is unavailable.
+    pub fn speed_anomaly_pct(&self, key: &K, char_stats: &KeyStatsStore) -> Option<f64> {
         let stat = self.stats.get(key)?;
-        let char_b_stat = char_stats.stats.get(&key.0[1])?;
-        if char_b_stat.sample_count < MIN_CHAR_SAMPLES_FOR_SPEED {
-            return None;
-        }
-        let ratio = stat.filtered_time_ms / char_b_stat.filtered_time_ms;
+        let baseline = Self::speed_baseline(key, char_stats)?;
+        let ratio = stat.filtered_time_ms / baseline;
         Some((ratio - 1.0) * 100.0)
     }
 
-    /// Update error anomaly streak for a bigram given current char stats.
-    /// Call this after updating the bigram stats.
-    pub fn update_error_anomaly_streak(&mut self, key: &BigramKey, char_stats: &KeyStatsStore) {
+    /// Update error anomaly streak for an n-gram given current char stats.
+    /// Call this after updating the n-gram stats.
+    pub fn update_error_anomaly_streak(&mut self, key: &K, char_stats: &KeyStatsStore) {
         let ratio = self.error_anomaly_ratio(key, char_stats);
         if let Some(stat) = self.stats.get_mut(key) {
             if ratio > ERROR_ANOMALY_RATIO_THRESHOLD {
@@ -176,9 +259,9 @@ impl BigramStatsStore {
         }
     }
 
-    /// Update speed anomaly streak for a bigram given current char stats.
+    /// Update speed anomaly streak for an n-gram given current char stats.
     /// If speed_anomaly_pct() returns None (char baseline unavailable), holds previous streak value.
-    pub fn update_speed_anomaly_streak(&mut self, key: &BigramKey, char_stats: &KeyStatsStore) {
+    pub fn update_speed_anomaly_streak(&mut self, key: &K, char_stats: &KeyStatsStore) {
         let stat = match self.stats.get(key) {
             Some(s) => s,
             None => return,
@@ -202,26 +285,24 @@ impl BigramStatsStore {
         }
     }
 
-    /// All bigrams with error anomaly above threshold and sufficient samples.
+    /// All n-grams with error anomaly above threshold and sufficient samples.
     /// Sorted by anomaly_pct desc. Each entry's `confirmed` flag indicates
     /// streak >= ANOMALY_STREAK_REQUIRED && samples >= MIN_SAMPLES_FOR_FOCUS.
-    pub fn error_anomaly_bigrams(
+    pub fn error_anomalies(
         &self,
         char_stats: &KeyStatsStore,
         unlocked: &[char],
-    ) -> Vec<BigramAnomaly> {
+    ) -> Vec<NgramAnomaly<K>> {
         let mut results = Vec::new();
 
         for (key, stat) in &self.stats {
-            if !unlocked.contains(&key.0[0]) || !unlocked.contains(&key.0[1]) {
+            if !key.chars().iter().all(|ch| unlocked.contains(ch)) {
                 continue;
             }
             if stat.sample_count < ANOMALY_MIN_SAMPLES {
                 continue;
             }
-            let e_a = char_stats.smoothed_error_rate(key.0[0]);
-            let e_b = char_stats.smoothed_error_rate(key.0[1]);
-            let expected = 1.0 - (1.0 - e_a) * (1.0 - e_b);
+            let expected = Self::expected_error_rate(key, char_stats);
             let ratio = self.error_anomaly_ratio(key, char_stats);
             if ratio <= ERROR_ANOMALY_RATIO_THRESHOLD {
                 continue;
@@ -229,7 +310,7 @@ impl BigramStatsStore {
             let anomaly_pct = (ratio - 1.0) * 100.0;
             let confirmed = stat.error_anomaly_streak >= ANOMALY_STREAK_REQUIRED
                 && stat.sample_count >= MIN_SAMPLES_FOR_FOCUS;
-            results.push(BigramAnomaly {
+            results.push(NgramAnomaly {
                 key: key.clone(),
                 anomaly_pct,
                 sample_count: stat.sample_count,
@@ -241,49 +322,39 @@ impl BigramStatsStore {
             });
         }
 
-        results.sort_by(|a, b| {
-            b.anomaly_pct
-                .partial_cmp(&a.anomaly_pct)
-                .unwrap_or(std::cmp::Ordering::Equal)
-                .then_with(|| a.key.0.cmp(&b.key.0))
-        });
-
+        sort_anomalies(&mut results);
         results
     }
 
-    /// All bigrams with speed anomaly above threshold and sufficient samples.
+    /// All n-grams with speed anomaly above threshold and sufficient samples.
     /// Sorted by anomaly_pct desc.
-    pub fn speed_anomaly_bigrams(
+    pub fn speed_anomalies(
         &self,
         char_stats: &KeyStatsStore,
         unlocked: &[char],
-    ) -> Vec<BigramAnomaly> {
+    ) -> Vec<NgramAnomaly<K>> {
         let mut results = Vec::new();
 
         for (key, stat) in &self.stats {
-            if !unlocked.contains(&key.0[0]) || !unlocked.contains(&key.0[1]) {
+            if !key.chars().iter().all(|ch| unlocked.contains(ch)) {
                 continue;
             }
             if stat.sample_count < ANOMALY_MIN_SAMPLES {
                 continue;
             }
-            let char_b_speed = char_stats
-                .stats
-                .get(&key.0[1])
-                .map(|s| s.filtered_time_ms)
-                .unwrap_or(0.0);
+            let baseline = Self::speed_baseline(key, char_stats).unwrap_or(0.0);
             match self.speed_anomaly_pct(key, char_stats) {
                 Some(pct) if pct > SPEED_ANOMALY_PCT_THRESHOLD => {
                     let confirmed = stat.speed_anomaly_streak >= ANOMALY_STREAK_REQUIRED
                         && stat.sample_count >= MIN_SAMPLES_FOR_FOCUS;
-                    results.push(BigramAnomaly {
+                    results.push(NgramAnomaly {
                         key: key.clone(),
                         anomaly_pct: pct,
                         sample_count: stat.sample_count,
                         error_count: stat.error_count,
                         error_rate_ema: stat.error_rate_ema,
                         speed_ms: stat.filtered_time_ms,
-                        expected_baseline: char_b_speed,
+                        expected_baseline: baseline,
                         confirmed,
                     });
                 }
@@ -291,34 +362,28 @@ impl BigramStatsStore {
             }
         }
 
-        results.sort_by(|a, b| {
-            b.anomaly_pct
-                .partial_cmp(&a.anomaly_pct)
-                .unwrap_or(std::cmp::Ordering::Equal)
-                .then_with(|| a.key.0.cmp(&b.key.0))
-        });
-
+        sort_anomalies(&mut results);
         results
     }
 
     /// Find the worst confirmed anomaly across both error and speed anomalies.
-    /// Each bigram gets at most one candidacy (whichever anomaly type is higher; error on tie).
+    /// Each n-gram gets at most one candidacy (whichever anomaly type is higher; error on tie).
     pub fn worst_confirmed_anomaly(
         &self,
         char_stats: &KeyStatsStore,
         unlocked: &[char],
-    ) -> Option<(BigramKey, f64, AnomalyType)> {
-        let mut candidates: HashMap<BigramKey, (f64, AnomalyType)> = HashMap::new();
+    ) -> Option<(K, f64, AnomalyType)> {
+        let mut candidates: HashMap<K, (f64, AnomalyType)> = HashMap::new();
 
         // Collect confirmed error anomalies
-        for a in self.error_anomaly_bigrams(char_stats, unlocked) {
+        for a in self.error_anomalies(char_stats, unlocked) {
             if a.confirmed {
                 candidates.insert(a.key, (a.anomaly_pct, AnomalyType::Error));
             }
         }
 
-        // Collect confirmed speed anomalies, dedup per bigram preferring higher pct (error on tie)
-        for a in self.speed_anomaly_bigrams(char_stats, unlocked) {
+        // Collect confirmed speed anomalies, dedup per n-gram preferring higher pct (error on tie)
+        for a in self.speed_anomalies(char_stats, unlocked) {
             if a.confirmed {
                 match candidates.get(&a.key) {
                     Some((existing_pct, _)) if *existing_pct >= a.anomaly_pct => {
@@ -331,40 +396,62 @@ impl BigramStatsStore {
             }
         }
 
+        // Rank by anomaly size, boosted by up to 2x for n-grams not practiced recently
+        let now = Utc::now();
+        let priority = |key: &K, pct: f64| -> f64 {
+            let last = self.stats.get(key).and_then(|s| s.last_seen_at);
+            pct * (2.0 - char_stats.forgetting.retention(last, now))
+        };
         candidates
             .into_iter()
             .max_by(|a, b| {
-                a.1.0
-                    .partial_cmp(&b.1.0)
+                priority(&a.0, a.1.0)
+                    .partial_cmp(&priority(&b.0, b.1.0))
                     .unwrap_or(std::cmp::Ordering::Equal)
             })
             .map(|(key, (pct, typ))| (key, pct, typ))
     }
 }
 
+fn sort_anomalies<K: NgramKey>(results: &mut [NgramAnomaly<K>]) {
+    results.sort_by(|a, b| {
+        b.anomaly_pct
+            .partial_cmp(&a.anomaly_pct)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.key.chars().cmp(b.key.chars()))
+    });
+}
+
 // ---------------------------------------------------------------------------
 // Extraction events & function
 // ---------------------------------------------------------------------------
 
 #[derive(Debug)]
-pub struct BigramEvent {
-    pub key: BigramKey,
+pub struct NgramEvent<K> {
+    pub key: K,
     pub total_time_ms: f64,
     pub correct: bool,
     pub has_hesitation: bool,
 }
 
-/// Extract bigram events from a sequence of per-key times.
+pub type BigramEvent = NgramEvent<BigramKey>;
+pub type TrigramEvent = NgramEvent<TrigramKey>;
+
+#[derive(Debug, Default)]
+pub struct NgramEvents {
+    pub bigrams: Vec<BigramEvent>,
+    pub trigrams: Vec<TrigramEvent>,
+}
+
+/// Extract bigram and trigram events from a sequence of per-key times.
 ///
 /// - BACKSPACE entries are filtered out
-/// - Space characters split windows (no cross-word bigrams)
+/// - Space characters split windows (no cross-word n-grams)
 /// - For bigram "ab": time = window[1].time_ms
-/// - hesitation = transition time > hesitation_threshold
-pub fn extract_ngram_events(
-    per_key_times: &[KeyTime],
-    hesitation_threshold: f64,
-) -> Vec<BigramEvent> {
-    let mut bigrams = Vec::new();
+/// - For trigram "abc": time = window[1].time_ms + window[2].time_ms
+/// - hesitation = any transition time > hesitation_threshold
+pub fn extract_ngram_events(per_key_times: &[KeyTime], hesitation_threshold: f64) -> NgramEvents {
+    let mut events = NgramEvents::default();
 
     // Filter out backspace entries
     let filtered: Vec<&KeyTime> = per_key_times
@@ -386,7 +473,7 @@ pub fn extract_ngram_events(
         let correct = a.correct && b.correct;
         let has_hesitation = b.time_ms > hesitation_threshold;
 
-        bigrams.push(BigramEvent {
+        events.bigrams.push(BigramEvent {
             key: BigramKey([a.key, b.key]),
             total_time_ms: time_ms,
             correct,
@@ -394,7 +481,23 @@ pub fn extract_ngram_events(
         });
     }
 
-    bigrams
+    // Extract trigrams: slide a window of 3
+    for window in filtered.windows(3) {
+        if window.iter().any(|kt| kt.key == ' ') {
+            continue;
+        }
+        let transitions = &window[1..];
+        events.trigrams.push(TrigramEvent {
+            key: TrigramKey([window[0].key, window[1].key, window[2].key]),
+            total_time_ms: transitions.iter().map(|kt| kt.time_ms).sum(),
+            correct: window.iter().all(|kt| kt.correct),
+            has_hesitation: transitions
+                .iter()
+                .any(|kt| kt.time_ms > hesitation_threshold),
+        });
+    }
+
+    events
 }
 
 // ---------------------------------------------------------------------------
@@ -407,8 +510,8 @@ pub enum AnomalyType {
     Speed,
 }
 
-pub struct BigramAnomaly {
-    pub key: BigramKey,
+pub struct NgramAnomaly<K> {
+    pub key: K,
     pub anomaly_pct: f64,
     pub sample_count: usize,
     pub error_count: usize,
@@ -418,31 +521,37 @@ pub struct BigramAnomaly {
     pub confirmed: bool,
 }
 
+pub type BigramAnomaly = NgramAnomaly<BigramKey>;
+
 // ---------------------------------------------------------------------------
 // FocusSelection
 // ---------------------------------------------------------------------------
 
-/// Combined focus selection: carries both char and bigram focus independently.
+/// Combined focus selection: carries char, bigram and trigram focus independently.
 #[derive(Clone, Debug, PartialEq)]
 pub struct FocusSelection {
     pub char_focus: Option<char>,
     pub bigram_focus: Option<(BigramKey, f64, AnomalyType)>,
+    pub trigram_focus: Option<(TrigramKey, f64, AnomalyType)>,
 }
 
-/// Select focus targets: weakest char from skill tree + worst confirmed bigram anomaly.
-/// Both are independent — neither overrides the other.
+/// Select focus targets: weakest char from skill tree + worst confirmed bigram
+/// and trigram anomalies. All are independent — none overrides the others.
 pub fn select_focus(
     skill_tree: &SkillTree,
     scope: DrillScope,
     ranked_key_stats: &KeyStatsStore,
     ranked_bigram_stats: &BigramStatsStore,
+    ranked_trigram_stats: &TrigramStatsStore,
 ) -> FocusSelection {
     let unlocked = skill_tree.unlocked_keys(scope);
     let char_focus = skill_tree.focused_key(scope, ranked_key_stats);
     let bigram_focus = ranked_bigram_stats.worst_confirmed_anomaly(ranked_key_stats, &unlocked);
+    let trigram_focus = ranked_trigram_stats.worst_confirmed_anomaly(ranked_key_stats, &unlocked);
     FocusSelection {
         char_focus,
         bigram_focus,
+        trigram_focus,
     }
 }
 
@@ -496,7 +605,7 @@ mod tests {
             make_keytime('l', 180.0, true),
             make_keytime('o', 160.0, true),
         ];
-        let bigrams = extract_ngram_events(&times, 800.0);
+        let bigrams = extract_ngram_events(&times, 800.0).bigrams;
         assert_eq!(bigrams.len(), 4); // he, el, ll, lo
         assert_eq!(bigrams[0].key, BigramKey(['h', 'e']));
         assert_eq!(bigrams[0].total_time_ms, 200.0);
@@ -511,7 +620,7 @@ mod tests {
             make_keytime(BACKSPACE, 150.0, true),
             make_keytime('b', 180.0, true),
         ];
-        let bigrams = extract_ngram_events(&times, 800.0);
+        let bigrams = extract_ngram_events(&times, 800.0).bigrams;
         // After filtering backspace: a, x, b -> bigrams: ax, xb
         assert_eq!(bigrams.len(), 2);
         assert_eq!(bigrams[0].key, BigramKey(['a', 'x']));
@@ -527,7 +636,7 @@ mod tests {
             make_keytime('c', 180.0, true),
             make_keytime('d', 160.0, true),
         ];
-        let bigrams = extract_ngram_events(&times, 800.0);
+        let bigrams = extract_ngram_events(&times, 800.0).bigrams;
         // ab is valid, b-space skipped, space-c skipped, cd is valid
         assert_eq!(bigrams.len(), 2);
         assert_eq!(bigrams[0].key, BigramKey(['a', 'b']));
@@ -541,7 +650,7 @@ mod tests {
             make_keytime('b', 900.0, true), // > 800 threshold
             make_keytime('c', 200.0, true),
         ];
-        let bigrams = extract_ngram_events(&times, 800.0);
+        let bigrams = extract_ngram_events(&times, 800.0).bigrams;
         assert!(bigrams[0].has_hesitation); // ab: b.time = 900 > 800
         assert!(!bigrams[1].has_hesitation); // bc: c.time = 200 < 800
     }
@@ -553,11 +662,36 @@ mod tests {
             make_keytime('b', 200.0, false), // incorrect
             make_keytime('c', 150.0, true),
         ];
-        let bigrams = extract_ngram_events(&times, 800.0);
+        let bigrams = extract_ngram_events(&times, 800.0).bigrams;
         assert!(!bigrams[0].correct); // ab: a correct, b incorrect -> false
         assert!(!bigrams[1].correct); // bc: b incorrect, c correct -> false
     }
 
+    #[test]
+    fn extract_trigrams_within_words() {
+        let times = vec![
+            make_keytime('t', 100.0, true),
+            make_keytime('h', 200.0, true),
+            make_keytime('e', 900.0, false),
+            make_keytime(' ', 150.0, true),
+            make_keytime('i', 120.0, true),
+            make_keytime('n', 130.0, true),
+            make_keytime(BACKSPACE, 90.0, true),
+            make_keytime('g', 140.0, true),
+        ];
+        let trigrams = extract_ngram_events(&times, 800.0).trigrams;
+        // "the" and "ing"; nothing spans the space, backspace is dropped
+        assert_eq!(trigrams.len(), 2);
+        assert_eq!(trigrams[0].key, TrigramKey(['t', 'h', 'e']));
+        assert_eq!(trigrams[0].total_time_ms, 1100.0);
+        assert!(!trigrams[0].correct);
+        assert!(trigrams[0].has_hesitation);
+        assert_eq!(trigrams[1].key, TrigramKey(['i', 'n', 'g']));
+        assert_eq!(trigrams[1].total_time_ms, 270.0);
+        assert!(trigrams[1].correct);
+        assert!(!trigrams[1].has_hesitation);
+    }
+
     // --- EMA error rate tests ---
 
     #[test]
@@ -764,7 +898,13 @@ mod tests {
         let key_stats = KeyStatsStore::default();
         let bigram_stats = BigramStatsStore::default();
 
-        let selection = select_focus(&skill_tree, DrillScope::Global, &key_stats, &bigram_stats);
+        let selection = select_focus(
+            &skill_tree,
+            DrillScope::Global,
+            &key_stats,
+            &bigram_stats,
+            &TrigramStatsStore::default(),
+        );
 
         // No bigram data → bigram_focus should be None
         assert!(
@@ -797,7 +937,13 @@ mod tests {
         stat.error_rate_ema = 0.80;
         stat.error_anomaly_streak = ANOMALY_STREAK_REQUIRED;
 
-        let selection = select_focus(&skill_tree, DrillScope::Global, &key_stats, &bigram_stats);
+        let selection = select_focus(
+            &skill_tree,
+            DrillScope::Global,
+            &key_stats,
+            &bigram_stats,
+            &TrigramStatsStore::default(),
+        );
 
         // Both should be populated independently
         assert_eq!(
@@ -837,7 +983,13 @@ mod tests {
         stat.error_rate_ema = 0.02;
         stat.error_anomaly_streak = ANOMALY_STREAK_REQUIRED;
 
-        let selection = select_focus(&skill_tree, DrillScope::Global, &key_stats, &bigram_stats);
+        let selection = select_focus(
+            &skill_tree,
+            DrillScope::Global,
+            &key_stats,
+            &bigram_stats,
+            &TrigramStatsStore::default(),
+        );
 
         assert_eq!(
             selection.char_focus,
@@ -874,7 +1026,13 @@ mod tests {
         stat.error_rate_ema = 0.80;
         stat.error_anomaly_streak = ANOMALY_STREAK_REQUIRED - 1; // not enough
 
-        let selection = select_focus(&skill_tree, DrillScope::Global, &key_stats, &bigram_stats);
+        let selection = select_focus(
+            &skill_tree,
+            DrillScope::Global,
+            &key_stats,
+            &bigram_stats,
+            &TrigramStatsStore::default(),
+        );
 
         assert!(
             selection.bigram_focus.is_none(),
@@ -1034,7 +1192,7 @@ mod tests {
     #[test]
     fn perf_budget_update_under_1ms() {
         let keystrokes = make_bench_keystrokes(500);
-        let bigram_events = extract_ngram_events(&keystrokes, 800.0);
+        let bigram_events = extract_ngram_events(&keystrokes, 800.0).bigrams;
         let budget = std::time::Duration::from_millis(1 * DEBUG_MULTIPLIER as u64);
 
         let start = std::time::Instant::now();
@@ -1115,7 +1273,7 @@ mod tests {
         let mut key_stats = KeyStatsStore::default();
 
         for (drill_idx, keystrokes) in drills.iter().enumerate() {
-            let bigram_events = extract_ngram_events(keystrokes, 800.0);
+            let bigram_events = extract_ngram_events(keystrokes, 800.0).bigrams;
 
             for kt in keystrokes {
                 if kt.correct {
@@ -1147,6 +1305,69 @@ mod tests {
         );
     }
 
+    // --- Trigram anomaly tests ---
+
+    #[test]
+    fn trigram_baselines_cover_every_key() {
+        let mut char_stats = KeyStatsStore::default();
+        for ch in ['i', 'n', 'g'] {
+            let s = char_stats.stats.entry(ch).or_default();
+            s.error_rate_ema = 0.1;
+            s.filtered_time_ms = 150.0;
+            s.sample_count = MIN_CHAR_SAMPLES_FOR_SPEED;
+        }
+        let key = TrigramKey(['i', 'n', 'g']);
+        let mut store = TrigramStatsStore::default();
+        store.update(key.clone(), 600.0, false, false, 0);
+
+        // Expected error rate: 1 - 0.9^3 = 0.271, observed EMA is 1.0
+        let ratio = store.error_anomaly_ratio(&key, &char_stats);
+        assert!((ratio - 1.0 / 0.271).abs() < 1e-9, "ratio={ratio}");
+        // Speed baseline is n + g in isolation: 600 / 300 = 100% slower
+        let pct = store.speed_anomaly_pct(&key, &char_stats).unwrap();
+        assert!((pct - 100.0).abs() < 1e-9, "pct={pct}");
+
+        char_stats.stats.get_mut(&'g').unwrap().sample_count = 1;
+        assert!(store.speed_anomaly_pct(&key, &char_stats).is_none());
+    }
+
+    #[test]
+    fn trigram_focus_needs_streak_confirmation() {
+        let mut char_stats = KeyStatsStore::default();
+        for ch in 'a'..='z' {
+            char_stats.stats.entry(ch).or_default().error_rate_ema = 0.03;
+        }
+        let unlocked: Vec<char> = ('a'..='z').collect();
+        let key = TrigramKey(['i', 'o', 'n']);
+        let mut store = TrigramStatsStore::default();
+        let events: Vec<TrigramEvent> = (0..MIN_SAMPLES_FOR_FOCUS)
+            .map(|_| TrigramEvent {
+                key: key.clone(),
+                total_time_ms: 300.0,
+                correct: false,
+                has_hesitation: false,
+            })
+            .collect();
+
+        for drill in 0..ANOMALY_STREAK_REQUIRED {
+            assert!(
+                store
+                    .worst_confirmed_anomaly(&char_stats, &unlocked)
+                    .is_none()
+            );
+            store.record_drill(&events, &char_stats, drill as u32, Utc::now());
+        }
+        assert_eq!(
+            store.stats[&key].error_anomaly_streak,
+            ANOMALY_STREAK_REQUIRED
+        );
+        let (focus, _, anomaly_type) = store
+            .worst_confirmed_anomaly(&char_stats, &unlocked)
+            .unwrap();
+        assert_eq!(focus, key);
+        assert_eq!(anomaly_type, AnomalyType::Error);
+    }
+
     // --- error_anomaly_bigrams tests ---
 
     fn make_bigram_store_with_char_stats() -> (BigramStatsStore, KeyStatsStore) {
@@ -1192,7 +1413,7 @@ mod tests {
         s4.error_rate_ema = 0.02;
         s4.error_anomaly_streak = 3;
 
-        let anomalies = bigram_stats.error_anomaly_bigrams(&char_stats, &unlocked);
+        let anomalies = bigram_stats.error_anomalies(&char_stats, &unlocked);
         let keys: Vec<BigramKey> = anomalies.iter().map(|a| a.key.clone()).collect();
 
         assert!(keys.contains(&k1), "k1 should be in error anomalies");
@@ -1326,7 +1547,7 @@ mod tests {
         s2.sample_count = 25;
         s2.speed_anomaly_streak = 3;
 
-        let anomalies = bigram_stats.speed_anomaly_bigrams(&char_stats, &unlocked);
+        let anomalies = bigram_stats.speed_anomalies(&char_stats, &unlocked);
         let keys: Vec<BigramKey> = anomalies.iter().map(|a| a.key.clone()).collect();
 
         assert!(
@@ -1528,7 +1749,13 @@ mod tests {
         stat.error_rate_ema = 0.80;
         stat.error_anomaly_streak = ANOMALY_STREAK_REQUIRED;
 
-        let selection = select_focus(&skill_tree, DrillScope::Global, &key_stats, &bigram_stats);
+        let selection = select_focus(
+            &skill_tree,
+            DrillScope::Global,
+            &key_stats,
+            &bigram_stats,
+            &TrigramStatsStore::default(),
+        );
 
         assert_eq!(selection.char_focus, Some('n'));
         assert!(selection.bigram_focus.is_some());
@@ -1566,7 +1793,13 @@ mod tests {
         stat.error_rate_ema = 0.80;
         stat.error_anomaly_streak = ANOMALY_STREAK_REQUIRED;
 
-        let selection = select_focus(&skill_tree, DrillScope::Global, &key_stats, &bigram_stats);
+        let selection = select_focus(
+            &skill_tree,
+            DrillScope::Global,
+            &key_stats,
+            &bigram_stats,
+            &TrigramStatsStore::default(),
+        );
 
         assert!(
             selection.char_focus.is_none(),
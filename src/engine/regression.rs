@@ -0,0 +1,117 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Progress toward mastery below which a once-mastered key is slipping.
+pub const REGRESSION_THRESHOLD: f64 = 0.8;
+/// Consecutive ranked drills below the threshold before a key needs review.
+pub const REGRESSION_DRILLS: u32 = 3;
+/// Regressed keys compete for focus as if their progress were this fraction
+/// of what it is, so they come back before keys that were never mastered.
+pub const REGRESSED_FOCUS_WEIGHT: f64 = 0.5;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MasteredKey {
+    /// Consecutive ranked drills spent below `REGRESSION_THRESHOLD`.
+    #[serde(default)]
+    pub drills_below: u32,
+    /// Set after `REGRESSION_DRILLS` such drills, until the key is mastered again.
+    #[serde(default)]
+    pub regressed: bool,
+}
+
+/// Mastery state over time for every key that has been mastered at least once.
+///
+/// Crossing mastery is celebrated as it happens; this catches the opposite,
+/// a key that stays well below mastery for several ranked drills afterwards.
+/// A single slow drill does not count, and a regressed key recovers as soon
+/// as it is mastered again.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RegressionTracker {
+    #[serde(default)]
+    keys: BTreeMap<char, MasteredKey>,
+}
+
+impl RegressionTracker {
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    pub fn is_regressed(&self, key: char) -> bool {
+        self.keys.get(&key).is_some_and(|state| state.regressed)
+    }
+
+    /// Keys that currently need review, in char order.
+    pub fn regressed_keys(&self) -> Vec<char> {
+        self.keys
+            .iter()
+            .filter(|(_, state)| state.regressed)
+            .map(|(&ch, _)| ch)
+            .collect()
+    }
+
+    /// Record one ranked drill from `(key, progress, mastered)` standings of
+    /// the unlocked keys, where progress is 1.0 at the speed target. Returns
+    /// the keys that regressed with this drill.
+    pub fn record_drill(&mut self, standings: &[(char, f64, bool)]) -> Vec<char> {
+        let mut newly_regressed = Vec::new();
+        for &(ch, progress, mastered) in standings {
+            if mastered {
+                self.keys.insert(ch, MasteredKey::default());
+                continue;
+            }
+            let Some(state) = self.keys.get_mut(&ch) else {
+                continue;
+            };
+            if progress >= REGRESSION_THRESHOLD {
+                state.drills_below = 0;
+                continue;
+            }
+            state.drills_below += 1;
+            if state.drills_below >= REGRESSION_DRILLS && !state.regressed {
+                state.regressed = true;
+                newly_regressed.push(ch);
+            }
+        }
+        newly_regressed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sustained_drop_after_mastery_regresses_once() {
+        let mut tracker = RegressionTracker::default();
+        // Never-mastered keys are not tracked at all.
+        assert!(tracker.record_drill(&[('a', 0.2, false)]).is_empty());
+        assert!(tracker.is_empty());
+
+        tracker.record_drill(&[('a', 1.1, true)]);
+        for _ in 1..REGRESSION_DRILLS {
+            assert!(tracker.record_drill(&[('a', 0.6, false)]).is_empty());
+        }
+        assert_eq!(tracker.record_drill(&[('a', 0.6, false)]), vec!['a']);
+        assert!(tracker.record_drill(&[('a', 0.6, false)]).is_empty());
+        assert_eq!(tracker.regressed_keys(), vec!['a']);
+
+        // Getting close again is not enough; mastering the key clears it.
+        tracker.record_drill(&[('a', 0.9, false)]);
+        assert!(tracker.is_regressed('a'));
+        tracker.record_drill(&[('a', 1.0, true)]);
+        assert!(!tracker.is_regressed('a'));
+    }
+
+    #[test]
+    fn a_drill_near_mastery_resets_the_count() {
+        let mut tracker = RegressionTracker::default();
+        tracker.record_drill(&[('s', 1.0, true)]);
+        for _ in 0..REGRESSION_DRILLS * 2 {
+            tracker.record_drill(&[('s', 0.5, false)]);
+            tracker.record_drill(&[('s', 0.85, false)]);
+        }
+        assert!(!tracker.is_regressed('s'));
+        assert_eq!(tracker.keys[&'s'].drills_below, 0);
+    }
+}
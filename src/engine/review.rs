@@ -0,0 +1,438 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::engine::ngram_stats::BigramEvent;
+use crate::engine::word_stats::WordEvent;
+use crate::session::result::KeyTime;
+
+const DEFAULT_EASE: f64 = 2.5;
+const MIN_EASE: f64 = 1.3;
+/// Lowest grade that still counts as a successful recall.
+const PASSING_GRADE: u8 = 3;
+/// Minimum occurrences in a drill before an item's performance is graded.
+const MIN_SAMPLES_FOR_GRADE: usize = 2;
+/// Cap on due items interleaved into a single adaptive drill.
+pub const MAX_DUE_PER_DRILL: usize = 3;
+/// Times a due word is repeated in a drill, enough for it to be graded.
+pub const WORD_REVIEW_REPEATS: usize = 3;
+/// Interval at which an item counts as learned and stops being scheduled.
+const RETIRE_INTERVAL_DAYS: f64 = 180.0;
+/// Most cards a schedule keeps; the ones due furthest out are evicted first.
+const MAX_CARDS: usize = 300;
+
+/// Something the scheduler can bring back for review.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "value", rename_all = "lowercase")]
+pub enum ReviewItem {
+    Key(char),
+    Bigram([char; 2]),
+    Word(String),
+}
+
+impl ReviewItem {
+    pub fn label(&self) -> String {
+        match self {
+            Self::Key(ch) => ch.to_string(),
+            Self::Bigram(pair) => pair.iter().collect(),
+            Self::Word(word) => word.clone(),
+        }
+    }
+}
+
+/// SM-2 scheduling state for one review item.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ReviewCard {
+    pub item: ReviewItem,
+    pub ease: f64,
+    pub interval_days: f64,
+    pub repetitions: u32,
+    pub due: DateTime<Utc>,
+    #[serde(default)]
+    pub last_reviewed: Option<DateTime<Utc>>,
+}
+
+impl ReviewCard {
+    fn new(item: ReviewItem, now: DateTime<Utc>) -> Self {
+        Self {
+            item,
+            ease: DEFAULT_EASE,
+            interval_days: 0.0,
+            repetitions: 0,
+            // The item is being drilled as a focus right now; first review is tomorrow.
+            due: now + Duration::days(1),
+            last_reviewed: None,
+        }
+    }
+
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.due <= now
+    }
+
+    /// Apply an SM-2 review with `grade` in `0..=5`.
+    fn review(&mut self, grade: u8, now: DateTime<Utc>) {
+        let grade = grade.min(5);
+        if grade < PASSING_GRADE {
+            self.repetitions = 0;
+            self.interval_days = 1.0;
+        } else {
+            self.repetitions += 1;
+            self.interval_days = match self.repetitions {
+                1 => 1.0,
+                2 => 6.0,
+                _ => (self.interval_days * self.ease).round(),
+            };
+        }
+        let miss = f64::from(5 - grade);
+        self.ease = (self.ease + 0.1 - miss * (0.08 + miss * 0.02)).max(MIN_EASE);
+        self.due = now + Duration::seconds((self.interval_days * 86_400.0) as i64);
+        self.last_reviewed = Some(now);
+    }
+}
+
+/// Spaced-repetition schedule over previously focused weaknesses.
+///
+/// Items are enrolled when adaptive focus selection picks them and are then
+/// reviewed on an SM-2 schedule: each due item seen in a drill is graded from
+/// that drill's accuracy and speed, which moves its next due date. Items
+/// whose interval reaches [`RETIRE_INTERVAL_DAYS`] are retired.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReviewSchedule {
+    /// Sorted by item, one card per item.
+    #[serde(deserialize_with = "deserialize_cards")]
+    pub cards: Vec<ReviewCard>,
+}
+
+impl ReviewSchedule {
+    fn position(&self, item: &ReviewItem) -> Result<usize, usize> {
+        self.cards.binary_search_by(|c| c.item.cmp(item))
+    }
+
+    #[cfg(test)]
+    pub fn card(&self, item: &ReviewItem) -> Option<&ReviewCard> {
+        self.position(item).ok().map(|i| &self.cards[i])
+    }
+
+    /// Start scheduling `item` if it is not already tracked.
+    pub fn enroll(&mut self, item: ReviewItem, now: DateTime<Utc>) {
+        if let Err(i) = self.position(&item) {
+            self.cards.insert(i, ReviewCard::new(item, now));
+            self.evict_over_cap();
+        }
+    }
+
+    /// Stop scheduling items whose interval reached [`RETIRE_INTERVAL_DAYS`].
+    fn retire_learned(&mut self) {
+        self.cards
+            .retain(|card| card.interval_days < RETIRE_INTERVAL_DAYS);
+    }
+
+    /// Drop the cards due furthest out until at most [`MAX_CARDS`] remain.
+    fn evict_over_cap(&mut self) {
+        while self.cards.len() > MAX_CARDS {
+            let furthest = (0..self.cards.len())
+                .max_by_key(|&i| self.cards[i].due)
+                .expect("schedule over its cap is not empty");
+            self.cards.remove(furthest);
+        }
+    }
+
+    /// Due items accepted by `available`, most overdue first.
+    pub fn due_items(
+        &self,
+        now: DateTime<Utc>,
+        limit: usize,
+        available: impl Fn(&ReviewItem) -> bool,
+    ) -> Vec<ReviewItem> {
+        let mut due: Vec<&ReviewCard> = self
+            .cards
+            .iter()
+            .filter(|c| c.is_due(now) && available(&c.item))
+            .collect();
+        due.sort_by_key(|c| c.due);
+        due.into_iter()
+            .take(limit)
+            .map(|c| c.item.clone())
+            .collect()
+    }
+
+    /// Take in cards from another machine's schedule. For items both sides
+    /// track, the card that was reviewed most recently wins.
+    pub fn merge(&mut self, other: &ReviewSchedule) {
+        for theirs in &other.cards {
+            match self.position(&theirs.item) {
+                Ok(i) => {
+                    if theirs.last_reviewed > self.cards[i].last_reviewed {
+                        self.cards[i] = theirs.clone();
+                    }
+                }
+                Err(i) => self.cards.insert(i, theirs.clone()),
+            }
+        }
+        self.retire_learned();
+        self.evict_over_cap();
+    }
+
+    /// Grade every due item that appeared often enough in a finished drill.
+    /// Items that are not yet due are left alone so incidental practice does
+    /// not inflate their intervals.
+    pub fn record_drill(
+        &mut self,
+        per_key_times: &[KeyTime],
+        bigram_events: &[BigramEvent],
+        word_events: &[WordEvent],
+        target_cpm: f64,
+        now: DateTime<Utc>,
+    ) {
+        let target_time_ms = 60000.0 / target_cpm;
+        for card in self.cards.iter_mut().filter(|c| c.is_due(now)) {
+            let samples: Vec<(bool, f64)> = match &card.item {
+                ReviewItem::Key(ch) => per_key_times
+                    .iter()
+                    .filter(|kt| kt.key == *ch)
+                    .map(|kt| (kt.correct, kt.time_ms))
+                    .collect(),
+                ReviewItem::Bigram(pair) => bigram_events
+                    .iter()
+                    .filter(|ev| ev.key.0 == *pair)
+                    .map(|ev| (ev.correct, ev.total_time_ms))
+                    .collect(),
+                // Words are graded on their time per character.
+                ReviewItem::Word(word) => word_events
+                    .iter()
+                    .filter(|ev| ev.word == *word && ev.char_count > 0)
+                    .map(|ev| (ev.errors == 0, ev.time_ms / ev.char_count as f64))
+                    .collect(),
+            };
+            if let Some(grade) = grade_samples(&samples, target_time_ms) {
+                card.review(grade, now);
+            }
+        }
+        self.retire_learned();
+    }
+}
+
+/// Cards as stored, sorted and deduplicated by item so lookups can binary
+/// search. Older profiles kept them in enrollment order.
+fn deserialize_cards<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<ReviewCard>, D::Error> {
+    let mut cards = Vec::<ReviewCard>::deserialize(deserializer)?;
+    cards.sort_by(|a, b| a.item.cmp(&b.item));
+    cards.dedup_by(|a, b| a.item == b.item);
+    Ok(cards)
+}
+
+/// SM-2 grade from one drill's `(correct, time_ms)` samples of an item:
+/// errors fail the review, and correct recalls are graded by speed.
+fn grade_samples(samples: &[(bool, f64)], target_time_ms: f64) -> Option<u8> {
+    if samples.len() < MIN_SAMPLES_FOR_GRADE {
+        return None;
+    }
+    let errors = samples.iter().filter(|(correct, _)| !correct).count();
+    let error_rate = errors as f64 / samples.len() as f64;
+    let correct_times: Vec<f64> = samples
+        .iter()
+        .filter(|(correct, _)| *correct)
+        .map(|(_, t)| *t)
+        .collect();
+    if error_rate > 0.25 || correct_times.is_empty() {
+        return Some(1);
+    }
+    if error_rate > 0.1 {
+        return Some(2);
+    }
+    let mean_time = correct_times.iter().sum::<f64>() / correct_times.len() as f64;
+    let speed = target_time_ms / mean_time;
+    Some(if speed >= 1.0 {
+        5
+    } else if speed >= 0.8 {
+        4
+    } else {
+        3
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::ngram_stats::BigramKey;
+
+    fn kt(key: char, time_ms: f64, correct: bool) -> KeyTime {
+        KeyTime {
+            key,
+            time_ms,
+            correct,
+        }
+    }
+
+    #[test]
+    fn enrolled_items_become_due_the_next_day() {
+        let now = Utc::now();
+        let mut schedule = ReviewSchedule::default();
+        schedule.enroll(ReviewItem::Key('e'), now);
+        schedule.enroll(ReviewItem::Key('e'), now);
+        assert_eq!(schedule.cards.len(), 1);
+        assert!(schedule.due_items(now, 3, |_| true).is_empty());
+        let tomorrow = now + Duration::days(1);
+        assert_eq!(
+            schedule.due_items(tomorrow, 3, |_| true),
+            vec![ReviewItem::Key('e')]
+        );
+    }
+
+    #[test]
+    fn successful_reviews_grow_the_interval() {
+        let mut now = Utc::now();
+        let mut schedule = ReviewSchedule::default();
+        schedule.enroll(ReviewItem::Key('e'), now);
+        let fast = vec![kt('e', 200.0, true); 4];
+        let mut intervals = Vec::new();
+        for _ in 0..4 {
+            now = schedule.cards[0].due;
+            schedule.record_drill(&fast, &[], &[], 175.0, now);
+            intervals.push(schedule.cards[0].interval_days);
+        }
+        assert_eq!(intervals[..2], [1.0, 6.0]);
+        assert!(intervals[2] > 6.0 && intervals[3] > intervals[2]);
+        assert!(schedule.cards[0].ease > DEFAULT_EASE);
+    }
+
+    #[test]
+    fn failed_review_resets_and_lowers_ease() {
+        let now = Utc::now();
+        let mut schedule = ReviewSchedule::default();
+        schedule.enroll(ReviewItem::Bigram(['t', 'h']), now);
+        let due = schedule.cards[0].due;
+        let events: Vec<BigramEvent> = (0..4)
+            .map(|i| BigramEvent {
+                key: BigramKey(['t', 'h']),
+                total_time_ms: 300.0,
+                correct: i % 2 == 0,
+                has_hesitation: false,
+            })
+            .collect();
+        schedule.record_drill(&[], &events, &[], 175.0, due);
+        let card = &schedule.cards[0];
+        assert_eq!(card.repetitions, 0);
+        assert_eq!(card.interval_days, 1.0);
+        assert!(card.ease < DEFAULT_EASE);
+    }
+
+    #[test]
+    fn items_not_due_are_not_graded() {
+        let now = Utc::now();
+        let mut schedule = ReviewSchedule::default();
+        schedule.enroll(ReviewItem::Key('e'), now);
+        schedule.record_drill(&vec![kt('e', 200.0, true); 4], &[], &[], 175.0, now);
+        assert_eq!(schedule.cards[0].repetitions, 0);
+        assert_eq!(schedule.cards[0].last_reviewed, None);
+    }
+
+    #[test]
+    fn due_items_respect_availability_and_order() {
+        let now = Utc::now();
+        let mut schedule = ReviewSchedule::default();
+        schedule.enroll(ReviewItem::Key('q'), now - Duration::days(3));
+        schedule.enroll(ReviewItem::Key('e'), now - Duration::days(5));
+        schedule.enroll(ReviewItem::Bigram(['t', 'h']), now - Duration::days(4));
+        let later = now + Duration::hours(1);
+        assert_eq!(
+            schedule.due_items(later, 3, |item| *item != ReviewItem::Key('q')),
+            vec![ReviewItem::Key('e'), ReviewItem::Bigram(['t', 'h'])]
+        );
+        assert_eq!(schedule.due_items(later, 1, |_| true).len(), 1);
+    }
+
+    #[test]
+    fn due_words_are_graded_per_character() {
+        let now = Utc::now();
+        let mut schedule = ReviewSchedule::default();
+        schedule.enroll(ReviewItem::Word("rhythm".to_string()), now);
+        let due = schedule.cards[0].due;
+        let word = |time_ms: f64, errors: usize| WordEvent {
+            word: "rhythm".to_string(),
+            time_ms,
+            char_count: 6,
+            errors,
+            has_hesitation: false,
+        };
+        schedule.record_drill(&[], &[], &[word(1_200.0, 0), word(1_100.0, 0)], 175.0, due);
+        let card = &schedule.cards[0];
+        assert_eq!(card.repetitions, 1);
+        assert!(card.ease > DEFAULT_EASE);
+
+        let due = card.due;
+        schedule.record_drill(&[], &[], &[word(1_200.0, 1), word(1_100.0, 2)], 175.0, due);
+        assert_eq!(schedule.cards[0].repetitions, 0);
+    }
+
+    #[test]
+    fn merge_keeps_the_most_recently_reviewed_card() {
+        let now = Utc::now();
+        let mut ours = ReviewSchedule::default();
+        ours.enroll(ReviewItem::Key('e'), now);
+        ours.enroll(ReviewItem::Key('q'), now);
+        ours.cards[1].last_reviewed = Some(now);
+        let mut theirs = ReviewSchedule::default();
+        theirs.enroll(ReviewItem::Key('e'), now);
+        theirs.enroll(ReviewItem::Key('q'), now);
+        theirs.enroll(ReviewItem::Word("rhythm".to_string()), now);
+        theirs.cards[0].repetitions = 2;
+        theirs.cards[0].last_reviewed = Some(now);
+        theirs.cards[1].repetitions = 2;
+        theirs.cards[1].last_reviewed = Some(now - Duration::days(1));
+
+        ours.merge(&theirs);
+        assert_eq!(ours.cards.len(), 3);
+        assert_eq!(ours.cards[0].repetitions, 2);
+        assert_eq!(ours.cards[1].repetitions, 0);
+        assert!(ours.card(&ReviewItem::Word("rhythm".to_string())).is_some());
+    }
+    #[test]
+    fn learned_items_are_retired() {
+        let now = Utc::now();
+        let mut schedule = ReviewSchedule::default();
+        schedule.enroll(ReviewItem::Key('e'), now);
+        schedule.enroll(ReviewItem::Key('q'), now);
+        schedule.cards[0].repetitions = 5;
+        schedule.cards[0].interval_days = 90.0;
+        let due = schedule.cards[0].due;
+        schedule.record_drill(&vec![kt('e', 200.0, true); 4], &[], &[], 175.0, due);
+        assert!(schedule.card(&ReviewItem::Key('e')).is_none());
+        assert!(schedule.card(&ReviewItem::Key('q')).is_some());
+    }
+
+    #[test]
+    fn schedule_is_capped_by_furthest_due() {
+        let now = Utc::now();
+        let mut schedule = ReviewSchedule::default();
+        for i in 0..MAX_CARDS + 20 {
+            let word = format!("word{i:03}");
+            schedule.enroll(ReviewItem::Word(word), now - Duration::hours(i as i64));
+        }
+        assert_eq!(schedule.cards.len(), MAX_CARDS);
+        assert!(
+            schedule
+                .card(&ReviewItem::Word("word000".to_string()))
+                .is_none()
+        );
+        assert!(
+            schedule
+                .card(&ReviewItem::Word("word319".to_string()))
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn unsorted_stored_cards_are_found() {
+        let now = Utc::now();
+        let mut stored = ReviewSchedule::default();
+        for item in [ReviewItem::Key('q'), ReviewItem::Key('e')] {
+            stored.cards.push(ReviewCard::new(item, now));
+        }
+        let json = serde_json::to_string(&stored).unwrap();
+        let loaded: ReviewSchedule = serde_json::from_str(&json).unwrap();
+        assert!(loaded.card(&ReviewItem::Key('q')).is_some());
+        assert!(loaded.card(&ReviewItem::Key('e')).is_some());
+    }
+}
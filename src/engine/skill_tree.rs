@@ -1,12 +1,16 @@
 use std::collections::{HashMap, HashSet};
 
+use chrono::Utc;
 use serde::{Deserialize, Serialize};
 
 use crate::engine::key_stats::KeyStatsStore;
+use crate::engine::mastery::MasteryTargets;
+use crate::engine::regression::{REGRESSED_FOCUS_WEIGHT, RegressionTracker};
 use crate::keyboard::display::{BACKSPACE, SPACE};
 use crate::l10n::language_pack::{
     DEFAULT_LATIN_PRIMARY_SEQUENCE, normalized_primary_letter_sequence,
 };
+use crate::session::result::KeyTime;
 
 /// Events returned by `SkillTree::update` describing what changed.
 pub struct SkillTreeUpdate {
@@ -28,6 +32,8 @@ pub enum BranchId {
     ProsePunctuation,
     Whitespace,
     CodeSymbols,
+    /// A user-defined branch, identified by its key in the config.
+    Custom(&'static str),
 }
 
 impl BranchId {
@@ -39,10 +45,11 @@ impl BranchId {
             BranchId::ProsePunctuation => "prose_punctuation",
             BranchId::Whitespace => "whitespace",
             BranchId::CodeSymbols => "code_symbols",
+            BranchId::Custom(key) => key,
         }
     }
 
-    #[allow(dead_code)]
+    /// The built-in branch with this key.
     pub fn from_key(key: &str) -> Option<Self> {
         match key {
             "lowercase" => Some(BranchId::Lowercase),
@@ -55,6 +62,8 @@ impl BranchId {
         }
     }
 
+    /// The built-in branches; `SkillTree::branch_ids` adds custom ones.
+    #[allow(dead_code)]
     pub fn all() -> &'static [BranchId] {
         &[
             BranchId::Lowercase,
@@ -97,6 +106,8 @@ pub struct BranchDefinition {
 }
 
 impl BranchDefinition {
+    /// Localized name. Custom branch names are not catalog keys, so they are
+    /// shown as written.
     pub fn display_name(&self) -> String {
         crate::i18n::t!(self.name_key).to_string()
     }
@@ -127,6 +138,15 @@ const CAPITALS_LEVELS: &[LevelDefinition] = &[
     },
 ];
 
+/// Sizes of the first two capitals levels, reused when capitals are derived
+/// from a non-Latin primary sequence.
+const DERIVED_CAPITALS_LEVEL_SIZES: [usize; 2] = [8, 10];
+
+/// Letters that never start a word, so sentence capitalization can't drill
+/// their uppercase forms: the Cyrillic hard sign, soft sign and yery, Greek
+/// final sigma and the Greek vowels with dialytika.
+const NON_INITIAL_LETTERS: &[char] = &['ъ', 'ь', 'ы', 'ς', 'ϊ', 'ϋ', 'ΐ', 'ΰ'];
+
 const NUMBERS_LEVELS: &[LevelDefinition] = &[
     LevelDefinition {
         name_key: "skill_tree.level_common_digits",
@@ -256,6 +276,9 @@ impl Default for BranchProgress {
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SkillTreeProgress {
     pub branches: HashMap<String, BranchProgress>,
+    /// Keys that were mastered once, and whether they have slipped since.
+    #[serde(default, skip_serializing_if = "RegressionTracker::is_empty")]
+    pub regressions: RegressionTracker,
 }
 
 impl Default for SkillTreeProgress {
@@ -278,7 +301,10 @@ impl Default for SkillTreeProgress {
         ] {
             branches.insert(id.to_key().to_string(), BranchProgress::default());
         }
-        Self { branches }
+        Self {
+            branches,
+            regressions: RegressionTracker::default(),
+        }
     }
 }
 
@@ -297,6 +323,12 @@ pub struct SkillTree {
     pub progress: SkillTreeProgress,
     pub total_unique_keys: usize,
     primary_letters: Vec<char>,
+    /// Keys per capitals level. Latin sequences keep `CAPITALS_LEVELS`; other
+    /// alphabets get the uppercase forms of their own primary letters.
+    capital_levels: Vec<Vec<char>>,
+    /// User-defined branches, after the built-in ones.
+    custom_branches: Vec<&'static BranchDefinition>,
+    mastery: MasteryTargets,
 }
 
 /// Number of lowercase letters to start with before unlocking one-at-a-time
@@ -310,12 +342,185 @@ impl SkillTree {
 
     pub fn new_with_primary_sequence(progress: SkillTreeProgress, sequence: &str) -> Self {
         let primary_letters = Self::normalize_primary_sequence(sequence);
-        let total_unique_keys = Self::compute_total_unique_keys(&primary_letters);
+        let capital_levels = Self::capital_levels_for(&primary_letters);
+        let total_unique_keys =
+            Self::compute_total_unique_keys(&primary_letters, &capital_levels, &[]);
         Self {
             progress,
             total_unique_keys,
             primary_letters,
+            capital_levels,
+            custom_branches: Vec::new(),
+            mastery: MasteryTargets::default(),
+        }
+    }
+
+    /// Use per-branch speed targets and an accuracy requirement for mastery.
+    pub fn with_mastery(mut self, mastery: MasteryTargets) -> Self {
+        self.mastery = mastery;
+        self
+    }
+
+    pub fn mastery(&self) -> &MasteryTargets {
+        &self.mastery
+    }
+
+    /// Whether `key` meets the mastery targets of branch `id`.
+    pub fn key_mastered(&self, id: BranchId, key: char, stats: &KeyStatsStore) -> bool {
+        self.mastery.is_mastered(id.to_key(), key, stats)
+    }
+
+    /// Confidence of `key` against the speed target of branch `id`.
+    pub fn key_progress(&self, id: BranchId, key: char, stats: &KeyStatsStore) -> f64 {
+        self.mastery.progress(id.to_key(), key, stats)
+    }
+
+    /// Whether `key` was mastered once and has since stayed well below it.
+    pub fn is_regressed(&self, key: char) -> bool {
+        self.progress.regressions.is_regressed(key)
+    }
+
+    pub fn regressed_keys(&self) -> Vec<char> {
+        self.progress.regressions.regressed_keys()
+    }
+
+    /// Record the mastery state of the unlocked keys typed in a ranked drill,
+    /// given the drill's `per_key_times`. Keys the drill did not use keep their
+    /// state. Returns the keys that now need review.
+    pub fn record_key_mastery(
+        &mut self,
+        stats: &KeyStatsStore,
+        per_key_times: &[KeyTime],
+    ) -> Vec<char> {
+        let typed: HashSet<char> = per_key_times.iter().map(|kt| kt.key).collect();
+        let standings: Vec<(char, f64, bool)> = self
+            .unlocked_keys(DrillScope::Global)
+            .into_iter()
+            .filter(|ch| typed.contains(ch))
+            .map(|ch| {
+                let id = self.home_branch(ch);
+                (
+                    ch,
+                    self.key_progress(id, ch, stats),
+                    self.key_mastered(id, ch, stats),
+                )
+            })
+            .collect();
+        self.progress.regressions.record_drill(&standings)
+    }
+
+    /// The branch whose targets apply to `key` outside of a branch drill: the
+    /// first branch that teaches it.
+    pub fn home_branch(&self, key: char) -> BranchId {
+        if self.primary_letters.contains(&key) {
+            return BranchId::Lowercase;
         }
+        self.branches()
+            .map(|def| def.id)
+            .filter(|&id| id != BranchId::Lowercase)
+            .find(|&id| self.branch_keys(id).any(|ch| ch == key))
+            .unwrap_or(BranchId::Lowercase)
+    }
+
+    /// Add user-defined branches after the built-in ones.
+    pub fn with_custom_branches(mut self, custom_branches: Vec<&'static BranchDefinition>) -> Self {
+        self.total_unique_keys = Self::compute_total_unique_keys(
+            &self.primary_letters,
+            &self.capital_levels,
+            &custom_branches,
+        );
+        self.custom_branches = custom_branches;
+        self
+    }
+
+    /// Built-in branches followed by custom ones.
+    pub fn branches(&self) -> impl Iterator<Item = &'static BranchDefinition> + '_ {
+        ALL_BRANCHES
+            .iter()
+            .chain(self.custom_branches.iter().copied())
+    }
+
+    pub fn branch_ids(&self) -> Vec<BranchId> {
+        self.branches().map(|def| def.id).collect()
+    }
+
+    pub fn custom_branches(&self) -> &[&'static BranchDefinition] {
+        &self.custom_branches
+    }
+
+    pub fn branch_definition(&self, id: BranchId) -> &'static BranchDefinition {
+        match id {
+            BranchId::Custom(_) => self
+                .custom_branches
+                .iter()
+                .copied()
+                .find(|def| def.id == id)
+                .expect("custom branch definition not found"),
+            _ => get_branch_definition(id),
+        }
+    }
+
+    /// Like `find_key_branch`, including custom branches.
+    pub fn find_key_branch(
+        &self,
+        ch: char,
+    ) -> Option<(&'static BranchDefinition, &'static LevelDefinition, usize)> {
+        find_key_branch(ch).or_else(|| {
+            self.custom_branches.iter().find_map(|branch| {
+                branch.levels.iter().find_map(|level| {
+                    let pos = level.keys.iter().position(|&k| k == ch)?;
+                    Some((*branch, level, pos + 1))
+                })
+            })
+        })
+    }
+
+    /// Capitals levels for a primary sequence. A sequence without ASCII letters
+    /// belongs to another script, whose capitals are the case pairs of its
+    /// primary letters in frequency order, split like the Latin levels.
+    fn capital_levels_for(primary_letters: &[char]) -> Vec<Vec<char>> {
+        if primary_letters.iter().any(|ch| ch.is_ascii_lowercase()) {
+            return CAPITALS_LEVELS.iter().map(|l| l.keys.to_vec()).collect();
+        }
+        let mut capitals: Vec<char> = Vec::new();
+        for &ch in primary_letters {
+            if NON_INITIAL_LETTERS.contains(&ch) {
+                continue;
+            }
+            let mut upper = ch.to_uppercase();
+            if let (Some(u), None) = (upper.next(), upper.next())
+                && u != ch
+                && !capitals.contains(&u)
+            {
+                capitals.push(u);
+            }
+        }
+
+        let mut levels = Vec::with_capacity(CAPITALS_LEVELS.len());
+        for size in DERIVED_CAPITALS_LEVEL_SIZES {
+            let rest = capitals.split_off(size.min(capitals.len()));
+            levels.push(capitals);
+            capitals = rest;
+        }
+        levels.push(capitals);
+        levels
+    }
+
+    /// Keys of one level of a branch in this tree.
+    pub fn level_keys(&self, id: BranchId, level: usize) -> &[char] {
+        if id == BranchId::Capitals {
+            return self.capital_levels.get(level).map_or(&[], Vec::as_slice);
+        }
+        self.branch_definition(id)
+            .levels
+            .get(level)
+            .map_or(&[], |l| l.keys)
+    }
+
+    /// Every key of a branch across all of its levels.
+    pub fn branch_keys(&self, id: BranchId) -> impl Iterator<Item = char> + '_ {
+        let levels = self.branch_definition(id).levels.len();
+        (0..levels).flat_map(move |i| self.level_keys(id, i).iter().copied())
     }
 
     fn normalize_primary_sequence(sequence: &str) -> Vec<char> {
@@ -327,10 +532,14 @@ impl SkillTree {
         }
     }
 
-    fn compute_total_unique_keys(primary_letters: &[char]) -> usize {
+    fn compute_total_unique_keys(
+        primary_letters: &[char],
+        capital_levels: &[Vec<char>],
+        custom_branches: &[&'static BranchDefinition],
+    ) -> usize {
         let mut all_keys: HashSet<char> = HashSet::new();
-        for branch in ALL_BRANCHES {
-            if branch.id == BranchId::Lowercase {
+        for branch in ALL_BRANCHES.iter().chain(custom_branches.iter().copied()) {
+            if matches!(branch.id, BranchId::Lowercase | BranchId::Capitals) {
                 continue;
             }
             for level in branch.levels {
@@ -339,6 +548,7 @@ impl SkillTree {
                 }
             }
         }
+        all_keys.extend(capital_levels.iter().flatten().copied());
         all_keys.extend(primary_letters.iter().copied());
         all_keys.extend(ALWAYS_UNLOCKED_KEYS.iter().copied());
         all_keys.len()
@@ -390,7 +600,7 @@ impl SkillTree {
 
     fn global_unlocked_keys(&self) -> Vec<char> {
         let mut keys = ALWAYS_UNLOCKED_KEYS.to_vec();
-        for branch_def in ALL_BRANCHES {
+        for branch_def in self.branches() {
             let bp = self.branch_progress(branch_def.id);
             match bp.status {
                 BranchStatus::InProgress => {
@@ -399,9 +609,9 @@ impl SkillTree {
                         keys.extend(self.lowercase_unlocked_keys());
                     } else {
                         // Include current level's keys + all prior levels
-                        for (i, level) in branch_def.levels.iter().enumerate() {
+                        for i in 0..branch_def.levels.len() {
                             if i <= bp.current_level {
-                                keys.extend_from_slice(level.keys);
+                                keys.extend_from_slice(self.level_keys(branch_def.id, i));
                             }
                         }
                     }
@@ -410,9 +620,7 @@ impl SkillTree {
                     if branch_def.id == BranchId::Lowercase {
                         keys.extend(self.primary_letters.iter().copied());
                     } else {
-                        for level in branch_def.levels {
-                            keys.extend_from_slice(level.keys);
-                        }
+                        keys.extend(self.branch_keys(branch_def.id));
                     }
                 }
                 _ => {}
@@ -437,24 +645,20 @@ impl SkillTree {
         }
 
         // Include keys from the target branch
-        let branch_def = get_branch_definition(id);
+        let branch_def = self.branch_definition(id);
         let bp = self.branch_progress(id);
         if id == BranchId::Lowercase {
             keys.extend(self.lowercase_unlocked_keys());
         } else {
             match bp.status {
                 BranchStatus::InProgress => {
-                    for (i, level) in branch_def.levels.iter().enumerate() {
+                    for i in 0..branch_def.levels.len() {
                         if i <= bp.current_level {
-                            keys.extend_from_slice(level.keys);
+                            keys.extend_from_slice(self.level_keys(id, i));
                         }
                     }
                 }
-                BranchStatus::Complete => {
-                    for level in branch_def.levels {
-                        keys.extend_from_slice(level.keys);
-                    }
-                }
+                BranchStatus::Complete => keys.extend(self.branch_keys(id)),
                 _ => {}
             }
         }
@@ -494,7 +698,7 @@ impl SkillTree {
     fn global_focused_key(&self, stats: &KeyStatsStore) -> Option<char> {
         // Collect keys from all InProgress branches (current level only) + complete branches
         let mut focus_candidates = Vec::new();
-        for branch_def in ALL_BRANCHES {
+        for branch_def in self.branches() {
             let bp = self.branch_progress(branch_def.id);
             match bp.status {
                 BranchStatus::InProgress => {
@@ -503,10 +707,10 @@ impl SkillTree {
                     } else if bp.current_level < branch_def.levels.len() {
                         // Only current level keys are focus candidates
                         focus_candidates
-                            .extend_from_slice(branch_def.levels[bp.current_level].keys);
+                            .extend_from_slice(self.level_keys(branch_def.id, bp.current_level));
                         // Plus prior level keys for reinforcement
                         for i in 0..bp.current_level {
-                            focus_candidates.extend_from_slice(branch_def.levels[i].keys);
+                            focus_candidates.extend_from_slice(self.level_keys(branch_def.id, i));
                         }
                     }
                 }
@@ -514,46 +718,59 @@ impl SkillTree {
                     if branch_def.id == BranchId::Lowercase {
                         focus_candidates.extend(self.primary_letters.iter().copied());
                     } else {
-                        for level in branch_def.levels {
-                            focus_candidates.extend_from_slice(level.keys);
-                        }
+                        focus_candidates.extend(self.branch_keys(branch_def.id));
                     }
                 }
                 _ => {}
             }
         }
 
-        Self::weakest_key(&focus_candidates, stats)
+        self.weakest_key(&focus_candidates, None, stats)
     }
 
     fn branch_focused_key(&self, id: BranchId, stats: &KeyStatsStore) -> Option<char> {
-        let branch_def = get_branch_definition(id);
+        let branch_def = self.branch_definition(id);
         let bp = self.branch_progress(id);
 
         if id == BranchId::Lowercase {
-            return Self::weakest_key(&self.lowercase_unlocked_keys(), stats);
+            return self.weakest_key(&self.lowercase_unlocked_keys(), Some(id), stats);
         }
 
         match bp.status {
             BranchStatus::InProgress if bp.current_level < branch_def.levels.len() => {
                 // Focus only within current level's keys
-                let current_keys = branch_def.levels[bp.current_level].keys;
-                Self::weakest_key(&current_keys.to_vec(), stats)
+                self.weakest_key(self.level_keys(id, bp.current_level), Some(id), stats)
             }
             _ => None,
         }
     }
 
-    fn weakest_key(keys: &[char], stats: &KeyStatsStore) -> Option<char> {
+    /// Lowest-confidence key below mastery, with confidence decayed by time since
+    /// last practice so that long-unpracticed keys resurface as focus candidates.
+    /// Keys are measured against the targets of `branch`, or of their home
+    /// branch when `None`. Regressed keys are weighted to come back first.
+    fn weakest_key(
+        &self,
+        keys: &[char],
+        branch: Option<BranchId>,
+        stats: &KeyStatsStore,
+    ) -> Option<char> {
+        let now = Utc::now();
         keys.iter()
-            .filter(|&&ch| stats.get_confidence(ch) < 1.0)
-            .min_by(|&&a, &&b| {
-                stats
-                    .get_confidence(a)
-                    .partial_cmp(&stats.get_confidence(b))
-                    .unwrap_or(std::cmp::Ordering::Equal)
+            .filter_map(|&ch| {
+                let id = branch.unwrap_or_else(|| self.home_branch(ch));
+                let progress =
+                    stats.retained_confidence(ch, now) / self.mastery.speed_factor(id.to_key());
+                let weight = if self.is_regressed(ch) {
+                    REGRESSED_FOCUS_WEIGHT
+                } else {
+                    1.0
+                };
+                (progress < 1.0 || !self.key_mastered(id, ch, stats))
+                    .then_some((ch, progress * weight))
             })
-            .copied()
+            .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|(ch, _)| ch)
     }
 
     /// Update skill tree progress based on current key stats.
@@ -572,7 +789,8 @@ impl SkillTree {
             self.unlocked_keys(DrillScope::Global).into_iter().collect();
 
         // Snapshot branch statuses before any updates
-        let before_branch_statuses: HashMap<BranchId, BranchStatus> = BranchId::all()
+        let branch_ids = self.branch_ids();
+        let before_branch_statuses: HashMap<BranchId, BranchStatus> = branch_ids
             .iter()
             .map(|&id| (id, self.branch_status(id).clone()))
             .collect();
@@ -583,20 +801,18 @@ impl SkillTree {
 
         // Check if lowercase is complete -> unlock other branches
         // Snapshot non-lowercase branch statuses before auto-unlock (canonical order)
-        const NON_LOWERCASE_BRANCHES: &[BranchId] = &[
-            BranchId::Capitals,
-            BranchId::Numbers,
-            BranchId::ProsePunctuation,
-            BranchId::Whitespace,
-            BranchId::CodeSymbols,
-        ];
-        let before_auto_unlock: Vec<(BranchId, BranchStatus)> = NON_LOWERCASE_BRANCHES
+        let non_lowercase_branches: Vec<BranchId> = branch_ids
+            .iter()
+            .copied()
+            .filter(|&id| id != BranchId::Lowercase)
+            .collect();
+        let before_auto_unlock: Vec<(BranchId, BranchStatus)> = non_lowercase_branches
             .iter()
             .map(|&id| (id, self.branch_status(id).clone()))
             .collect();
 
         if *self.branch_status(BranchId::Lowercase) == BranchStatus::Complete {
-            for &id in NON_LOWERCASE_BRANCHES {
+            for &id in &non_lowercase_branches {
                 let bp = self.branch_progress_mut(id);
                 if bp.status == BranchStatus::Locked {
                     bp.status = BranchStatus::Available;
@@ -615,7 +831,8 @@ impl SkillTree {
             .collect();
 
         // Update InProgress branches (non-lowercase)
-        for branch_def in ALL_BRANCHES {
+        let branch_defs: Vec<&'static BranchDefinition> = self.branches().collect();
+        for branch_def in branch_defs {
             if branch_def.id == BranchId::Lowercase {
                 continue;
             }
@@ -627,7 +844,7 @@ impl SkillTree {
         }
 
         // Detect branches that became Complete
-        let branches_newly_completed: Vec<BranchId> = BranchId::all()
+        let branches_newly_completed: Vec<BranchId> = branch_ids
             .iter()
             .filter(|&&id| {
                 before_branch_statuses
@@ -645,10 +862,10 @@ impl SkillTree {
             && before_unlocked_count != self.total_unique_keys;
 
         // Detect all keys mastered
-        let all_complete_now = BranchId::all()
+        let all_complete_now = branch_ids
             .iter()
             .all(|&id| *self.branch_status(id) == BranchStatus::Complete);
-        let all_complete_before = BranchId::all()
+        let all_complete_before = branch_ids
             .iter()
             .all(|id| before_branch_statuses.get(id) == Some(&BranchStatus::Complete));
         let all_keys_mastered = all_complete_now && !all_complete_before;
@@ -662,12 +879,15 @@ impl SkillTree {
             .copied()
             .collect();
 
-        // Detect mastery: keys that were unlocked before, had confidence < 1.0 in before_stats,
-        // but now have confidence >= 1.0 in current stats
+        // Detect mastery: keys that were unlocked before, missed their targets in before_stats,
+        // but meet them in current stats
         let newly_mastered: Vec<char> = if let Some(before) = before_stats {
             before_unlocked
                 .iter()
-                .filter(|&&ch| before.get_confidence(ch) < 1.0 && stats.get_confidence(ch) >= 1.0)
+                .filter(|&&ch| {
+                    let id = self.home_branch(ch);
+                    !self.key_mastered(id, ch, before) && self.key_mastered(id, ch, stats)
+                })
                 .copied()
                 .collect()
         } else {
@@ -684,6 +904,46 @@ impl SkillTree {
         }
     }
 
+    /// Apply placement test results: advance every branch and level whose keys
+    /// are already at confidence, as many `update` calls would over a series of
+    /// drills. Branches that become available are started when their first
+    /// level is already confident. Returns the keys unlocked by the placement.
+    pub fn apply_placement(&mut self, stats: &KeyStatsStore) -> Vec<char> {
+        let mut seen: HashSet<char> = self.unlocked_keys(DrillScope::Global).into_iter().collect();
+        loop {
+            let snapshot = self.progress_snapshot();
+            self.update(stats, None);
+            for id in self.branch_ids() {
+                if *self.branch_status(id) == BranchStatus::Available
+                    && self
+                        .level_keys(id, 0)
+                        .iter()
+                        .all(|&ch| self.key_mastered(id, ch, stats))
+                {
+                    self.start_branch(id);
+                }
+            }
+            if self.progress_snapshot() == snapshot {
+                break;
+            }
+        }
+        // `insert` also drops keys shared between branches after their first occurrence.
+        self.unlocked_keys(DrillScope::Global)
+            .into_iter()
+            .filter(|&ch| seen.insert(ch))
+            .collect()
+    }
+
+    fn progress_snapshot(&self) -> Vec<(BranchStatus, usize)> {
+        self.branch_ids()
+            .into_iter()
+            .map(|id| {
+                let bp = self.branch_progress(id);
+                (bp.status.clone(), bp.current_level)
+            })
+            .collect()
+    }
+
     fn update_lowercase(&mut self, stats: &KeyStatsStore) {
         let bp = self.branch_progress(BranchId::Lowercase).clone();
         if bp.status != BranchStatus::InProgress {
@@ -695,7 +955,9 @@ impl SkillTree {
 
         if current_count >= all_keys.len() {
             // All primary letters unlocked, check if all confident
-            let all_confident = all_keys.iter().all(|&ch| stats.get_confidence(ch) >= 1.0);
+            let all_confident = all_keys
+                .iter()
+                .all(|&ch| self.key_mastered(BranchId::Lowercase, ch, stats));
             if all_confident {
                 let bp_mut = self.branch_progress_mut(BranchId::Lowercase);
                 bp_mut.status = BranchStatus::Complete;
@@ -708,7 +970,7 @@ impl SkillTree {
         let current_keys = &all_keys[..current_count];
         let all_confident = current_keys
             .iter()
-            .all(|&ch| stats.get_confidence(ch) >= 1.0);
+            .all(|&ch| self.key_mastered(BranchId::Lowercase, ch, stats));
 
         if all_confident {
             let bp_mut = self.branch_progress_mut(BranchId::Lowercase);
@@ -726,10 +988,10 @@ impl SkillTree {
         }
 
         // Check if all keys in current level are confident
-        let current_level_keys = branch_def.levels[bp.current_level].keys;
+        let current_level_keys = self.level_keys(branch_def.id, bp.current_level);
         let all_confident = current_level_keys
             .iter()
-            .all(|&ch| stats.get_confidence(ch) >= 1.0);
+            .all(|&ch| self.key_mastered(branch_def.id, ch, stats));
 
         if all_confident {
             let bp_mut = self.branch_progress_mut(branch_def.id);
@@ -744,7 +1006,7 @@ impl SkillTree {
     pub fn total_unlocked_count(&self) -> usize {
         let mut keys: HashSet<char> = HashSet::new();
         keys.extend(ALWAYS_UNLOCKED_KEYS.iter().copied());
-        for branch_def in ALL_BRANCHES {
+        for branch_def in self.branches() {
             let bp = self.branch_progress(branch_def.id);
             match bp.status {
                 BranchStatus::InProgress => {
@@ -753,9 +1015,9 @@ impl SkillTree {
                             keys.insert(key);
                         }
                     } else {
-                        for (i, level) in branch_def.levels.iter().enumerate() {
+                        for i in 0..branch_def.levels.len() {
                             if i <= bp.current_level {
-                                for &key in level.keys {
+                                for &key in self.level_keys(branch_def.id, i) {
                                     keys.insert(key);
                                 }
                             }
@@ -768,11 +1030,7 @@ impl SkillTree {
                             keys.insert(key);
                         }
                     } else {
-                        for level in branch_def.levels {
-                            for &key in level.keys {
-                                keys.insert(key);
-                            }
-                        }
+                        keys.extend(self.branch_keys(branch_def.id));
                     }
                 }
                 _ => {}
@@ -789,33 +1047,30 @@ impl SkillTree {
     /// Get all branch definitions with their current progress (for UI).
     #[allow(dead_code)]
     pub fn all_branches_with_progress(&self) -> Vec<(&'static BranchDefinition, &BranchProgress)> {
-        ALL_BRANCHES
-            .iter()
+        self.branches()
             .map(|def| (def, self.branch_progress(def.id)))
             .collect()
     }
 
     /// Number of unlocked keys in a branch.
     pub fn branch_unlocked_count(&self, id: BranchId) -> usize {
-        let def = get_branch_definition(id);
+        let def = self.branch_definition(id);
         let bp = self.branch_progress(id);
         match bp.status {
             BranchStatus::Complete => {
                 if id == BranchId::Lowercase {
                     self.primary_letters().len()
                 } else {
-                    def.levels.iter().map(|l| l.keys.len()).sum()
+                    self.branch_keys(id).count()
                 }
             }
             BranchStatus::InProgress => {
                 if id == BranchId::Lowercase {
                     self.lowercase_unlocked_count()
                 } else {
-                    def.levels
-                        .iter()
-                        .enumerate()
-                        .filter(|(i, _)| *i <= bp.current_level)
-                        .map(|(_, l)| l.keys.len())
+                    (0..def.levels.len())
+                        .filter(|&i| i <= bp.current_level)
+                        .map(|i| self.level_keys(id, i).len())
                         .sum()
                 }
             }
@@ -823,43 +1078,30 @@ impl SkillTree {
         }
     }
 
-    /// Total keys defined in a branch (across all levels).
-    pub fn branch_total_keys(id: BranchId) -> usize {
-        let def = get_branch_definition(id);
-        def.levels.iter().map(|l| l.keys.len()).sum()
-    }
-
     /// Total keys defined in a branch for this tree configuration.
     pub fn branch_total_keys_for(&self, id: BranchId) -> usize {
         if id == BranchId::Lowercase {
             self.primary_letters().len()
         } else {
-            Self::branch_total_keys(id)
+            self.branch_keys(id).count()
         }
     }
 
     /// Count of unique confident keys across all branches.
     pub fn total_confident_keys(&self, stats: &KeyStatsStore) -> usize {
         let mut keys: HashSet<char> = HashSet::new();
-        for &ch in ALWAYS_UNLOCKED_KEYS {
-            if stats.get_confidence(ch) >= 1.0 {
-                keys.insert(ch);
-            }
-        }
-        for &ch in self.primary_letters() {
-            if stats.get_confidence(ch) >= 1.0 {
+        for &ch in ALWAYS_UNLOCKED_KEYS.iter().chain(self.primary_letters()) {
+            if self.key_mastered(BranchId::Lowercase, ch, stats) {
                 keys.insert(ch);
             }
         }
-        for branch_def in ALL_BRANCHES {
+        for branch_def in self.branches() {
             if branch_def.id == BranchId::Lowercase {
                 continue;
             }
-            for level in branch_def.levels {
-                for &ch in level.keys {
-                    if stats.get_confidence(ch) >= 1.0 {
-                        keys.insert(ch);
-                    }
+            for ch in self.branch_keys(branch_def.id) {
+                if self.key_mastered(branch_def.id, ch, stats) {
+                    keys.insert(ch);
                 }
             }
         }
@@ -871,14 +1113,11 @@ impl SkillTree {
         if id == BranchId::Lowercase {
             self.primary_letters()
                 .iter()
-                .filter(|&&ch| stats.get_confidence(ch) >= 1.0)
+                .filter(|&&ch| self.key_mastered(id, ch, stats))
                 .count()
         } else {
-            let def = get_branch_definition(id);
-            def.levels
-                .iter()
-                .flat_map(|l| l.keys.iter())
-                .filter(|&&ch| stats.get_confidence(ch) >= 1.0)
+            self.branch_keys(id)
+                .filter(|&ch| self.key_mastered(id, ch, stats))
                 .count()
         }
     }
@@ -893,7 +1132,8 @@ impl Default for SkillTree {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::l10n::language_pack::language_packs;
+    use crate::engine::regression::REGRESSION_DRILLS;
+    use crate::l10n::language_pack::{find_language_pack, language_packs};
 
     fn make_stats_confident(stats: &mut KeyStatsStore, keys: &[char]) {
         for &ch in keys {
@@ -1003,6 +1243,54 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_placement_unlocks_confident_branches_and_levels() {
+        let mut tree = SkillTree::default();
+        let mut stats = KeyStatsStore::default();
+        let all_primary = tree.primary_letters().to_vec();
+        make_stats_confident(&mut stats, &all_primary);
+        for level in 0..CAPITALS_LEVELS.len() {
+            let keys = tree.level_keys(BranchId::Capitals, level).to_vec();
+            make_stats_confident(&mut stats, &keys);
+        }
+        make_stats_confident(&mut stats, &['1', '2', '3', '4', '5']);
+
+        let unlocked = tree.apply_placement(&stats);
+
+        assert_eq!(
+            *tree.branch_status(BranchId::Lowercase),
+            BranchStatus::Complete
+        );
+        assert_eq!(
+            *tree.branch_status(BranchId::Capitals),
+            BranchStatus::Complete
+        );
+        // Numbers level 1 is confident, so level 2 is unlocked for practice.
+        let numbers = tree.branch_progress(BranchId::Numbers);
+        assert_eq!(numbers.status, BranchStatus::InProgress);
+        assert_eq!(numbers.current_level, 1);
+        assert_eq!(
+            *tree.branch_status(BranchId::ProsePunctuation),
+            BranchStatus::Available
+        );
+        assert!(unlocked.contains(&'z'));
+        assert!(unlocked.contains(&'Q'));
+        assert!(unlocked.contains(&'9'));
+        assert!(!unlocked.contains(&'.'));
+        assert!(
+            !unlocked.contains(&'e'),
+            "initial keys are not newly unlocked"
+        );
+    }
+
+    #[test]
+    fn test_placement_without_confidence_changes_nothing() {
+        let mut tree = SkillTree::default();
+        let unlocked = tree.apply_placement(&KeyStatsStore::default());
+        assert!(unlocked.is_empty());
+        assert_eq!(tree.branch_progress(BranchId::Lowercase).current_level, 0);
+    }
+
     #[test]
     fn test_start_branch() {
         let mut tree = SkillTree::default();
@@ -1017,6 +1305,180 @@ mod tests {
         assert_eq!(tree.branch_progress(BranchId::Capitals).current_level, 0);
     }
 
+    fn math_tree(progress: SkillTreeProgress) -> SkillTree {
+        use crate::engine::custom_branch::{CustomBranch, CustomLevel, custom_branch_definitions};
+        let math = CustomBranch {
+            key: "math".to_string(),
+            name: "Math".to_string(),
+            levels: vec![
+                CustomLevel {
+                    name: "Powers and roots".to_string(),
+                    keys: "^√".to_string(),
+                },
+                CustomLevel {
+                    name: "Comparisons".to_string(),
+                    keys: "≤≥".to_string(),
+                },
+            ],
+        };
+        SkillTree::new(progress).with_custom_branches(custom_branch_definitions(&[math]))
+    }
+
+    #[test]
+    fn test_custom_branch_progresses_like_built_in_branches() {
+        let mut tree = math_tree(SkillTreeProgress::default());
+        let id = BranchId::Custom("math");
+        // '^' already belongs to the code symbols branch.
+        assert_eq!(tree.total_unique_keys, 101);
+        assert_eq!(*tree.branches().last().unwrap().id.to_key(), *"math");
+        assert_eq!(tree.branch_status(id), &BranchStatus::Locked);
+
+        let mut stats = KeyStatsStore::default();
+        make_stats_confident(&mut stats, tree.primary_letters());
+        let mut available = Vec::new();
+        for _ in 0..30 {
+            available.extend(tree.update(&stats, None).branches_newly_available);
+        }
+        assert_eq!(available.last(), Some(&id));
+
+        tree.start_branch(id);
+        assert_eq!(tree.focused_key(DrillScope::Branch(id), &stats), Some('^'));
+        let keys = tree.unlocked_keys(DrillScope::Branch(id));
+        assert!(keys.contains(&'√') && !keys.contains(&'≤'));
+
+        make_stats_confident(&mut stats, &['^', '√']);
+        tree.update(&stats, None);
+        assert_eq!(tree.branch_progress(id).current_level, 1);
+        assert!(tree.unlocked_keys(DrillScope::Global).contains(&'≤'));
+        assert_eq!(tree.progress.branches["math"].current_level, 1);
+
+        // Progress is stored by key and picked up again by a fresh tree.
+        let reloaded = math_tree(tree.progress.clone());
+        assert_eq!(reloaded.branch_progress(id).current_level, 1);
+        let (branch, level, pos) = reloaded.find_key_branch('≤').unwrap();
+        assert_eq!(
+            (branch.display_name(), level.display_name(), pos),
+            ("Math".to_string(), "Comparisons".to_string(), 1)
+        );
+    }
+
+    #[test]
+    fn test_mastery_targets_gate_branch_levels() {
+        let level_one = ['T', 'I', 'A', 'S', 'W', 'H', 'B', 'M'];
+        let mut stats = KeyStatsStore::default();
+        // About 69% of the default target speed.
+        for &ch in &level_one {
+            for _ in 0..50 {
+                stats.update_key(ch, 500.0);
+            }
+        }
+        let in_progress = |mastery: MasteryTargets| {
+            let mut tree = SkillTree::default().with_mastery(mastery);
+            tree.branch_progress_mut(BranchId::Capitals).status = BranchStatus::InProgress;
+            tree
+        };
+
+        let mut tree = in_progress(MasteryTargets::default());
+        tree.update(&stats, None);
+        assert_eq!(tree.branch_progress(BranchId::Capitals).current_level, 0);
+
+        let slower = MasteryTargets {
+            branch_speed: [("capitals".to_string(), 60)].into(),
+            ..MasteryTargets::default()
+        };
+        let mut tree = in_progress(slower.clone());
+        assert!(tree.key_progress(BranchId::Capitals, 'T', &stats) > 1.0);
+        assert!(tree.key_progress(BranchId::Lowercase, 'T', &stats) < 1.0);
+        tree.update(&stats, None);
+        assert_eq!(tree.branch_progress(BranchId::Capitals).current_level, 1);
+
+        // An accuracy requirement holds the level back until errors settle.
+        stats.update_key_error('T');
+        stats.update_key_error('T');
+        let mut tree = in_progress(MasteryTargets {
+            max_error_rate: Some(0.05),
+            ..slower
+        });
+        tree.update(&stats, None);
+        assert_eq!(tree.branch_progress(BranchId::Capitals).current_level, 0);
+        assert_eq!(
+            tree.focused_key(DrillScope::Branch(BranchId::Capitals), &stats),
+            Some('T')
+        );
+    }
+
+    fn typed(keys: &[char]) -> Vec<KeyTime> {
+        keys.iter()
+            .map(|&key| KeyTime {
+                key,
+                time_ms: 300.0,
+                correct: true,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_regressed_keys_are_flagged_and_focused_first() {
+        let mut tree = SkillTree::default();
+        let keys = tree.lowercase_unlocked_keys();
+        let drill = typed(&keys);
+        let (slipping, never_mastered) = (keys[0], keys[1]);
+        let mut stats = KeyStatsStore::default();
+        make_stats_confident(&mut stats, &keys);
+        for _ in 0..100 {
+            stats.update_key(never_mastered, 686.0);
+        }
+        tree.update(&stats, None);
+        assert!(tree.record_key_mastery(&stats, &drill).is_empty());
+
+        // About 70% of target: below mastery but faster than the never-mastered key.
+        for _ in 0..100 {
+            stats.update_key(slipping, 490.0);
+        }
+        for _ in 1..REGRESSION_DRILLS {
+            assert!(tree.record_key_mastery(&stats, &drill).is_empty());
+        }
+        let focus = |tree: &SkillTree| tree.focused_key(DrillScope::Global, &stats);
+        assert_eq!(focus(&tree), Some(never_mastered));
+
+        assert_eq!(tree.record_key_mastery(&stats, &drill), vec![slipping]);
+        assert_eq!(tree.regressed_keys(), vec![slipping]);
+        assert_eq!(focus(&tree), Some(slipping));
+
+        // The state is saved with the progress.
+        let json = serde_json::to_string(&tree.progress).unwrap();
+        let restored: SkillTreeProgress = serde_json::from_str(&json).unwrap();
+        assert!(restored.regressions.is_regressed(slipping));
+
+        make_stats_confident(&mut stats, &[slipping]);
+        tree.record_key_mastery(&stats, &drill);
+        assert!(!tree.is_regressed(slipping));
+    }
+
+    #[test]
+    fn test_keys_left_out_of_a_drill_do_not_regress() {
+        let mut tree = SkillTree::default();
+        let keys = tree.lowercase_unlocked_keys();
+        let (slipping, other) = (keys[0], keys[1]);
+        let mut stats = KeyStatsStore::default();
+        make_stats_confident(&mut stats, &keys);
+        tree.update(&stats, None);
+        tree.record_key_mastery(&stats, &typed(&keys));
+
+        for _ in 0..100 {
+            stats.update_key(slipping, 490.0);
+        }
+        // Drills that never touch the slow key leave its standing alone.
+        for _ in 0..REGRESSION_DRILLS * 2 {
+            assert!(tree.record_key_mastery(&stats, &typed(&[other])).is_empty());
+        }
+        assert!(!tree.is_regressed(slipping));
+        for _ in 0..REGRESSION_DRILLS {
+            tree.record_key_mastery(&stats, &typed(&[slipping]));
+        }
+        assert!(tree.is_regressed(slipping));
+    }
+
     #[test]
     fn test_branch_level_advancement() {
         let mut tree = SkillTree::default();
@@ -1110,6 +1572,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_focused_key_resurfaces_stale_mastered_key() {
+        let tree = SkillTree::default();
+        let mut stats = KeyStatsStore::default();
+        let initial = ['e', 't', 'a', 'o', 'i', 'n'];
+        make_stats_confident(&mut stats, &initial);
+        let now = Utc::now();
+        for &ch in &initial {
+            stats.mark_practiced(ch, now);
+        }
+        assert_eq!(tree.focused_key(DrillScope::Global, &stats), None);
+
+        stats.stats.get_mut(&'o').unwrap().last_practiced = Some(now - chrono::Duration::days(60));
+        assert_eq!(tree.focused_key(DrillScope::Global, &stats), Some('o'));
+
+        stats.forgetting = crate::engine::forgetting::ForgettingCurve::Off;
+        assert_eq!(tree.focused_key(DrillScope::Global, &stats), None);
+    }
+
     #[test]
     fn test_complexity_scales() {
         let tree = SkillTree::default();
@@ -1176,15 +1657,16 @@ mod tests {
     fn test_selectable_branches_bounds() {
         use crate::ui::components::skill_tree::selectable_branches;
 
-        let branches = selectable_branches();
+        let tree = math_tree(SkillTreeProgress::default());
+        let branches = selectable_branches(&tree);
         assert!(!branches.is_empty());
         assert_eq!(branches[0], BranchId::Lowercase);
+        assert_eq!(branches.last(), Some(&BranchId::Custom("math")));
 
-        let tree = SkillTree::default();
         // Accessing branch_progress for every selectable branch should not panic
         for &branch_id in &branches {
             let _ = tree.branch_progress(branch_id);
-            let _ = SkillTree::branch_total_keys(branch_id);
+            let _ = tree.branch_total_keys_for(branch_id);
             let _ = tree.branch_unlocked_count(branch_id);
         }
 
@@ -1260,6 +1742,58 @@ mod tests {
         }
     }
 
+    #[test]
+    fn cyrillic_capitals_follow_primary_letters() {
+        let sequence = find_language_pack("ru").unwrap().primary_letter_sequence;
+        let mut tree = SkillTree::new_with_primary_sequence(SkillTreeProgress::default(), sequence);
+
+        assert_eq!(
+            tree.level_keys(BranchId::Capitals, 0),
+            &['О', 'Е', 'А', 'И', 'Н', 'Т', 'С', 'Р']
+        );
+        let capitals: Vec<char> = (0..3)
+            .flat_map(|i| tree.level_keys(BranchId::Capitals, i).to_vec())
+            .collect();
+        assert!(capitals.contains(&'Ё'));
+        assert!(!capitals.contains(&'Ь'));
+        assert!(!capitals.iter().any(char::is_ascii));
+        assert_eq!(tree.branch_total_keys_for(BranchId::Capitals), 30);
+
+        tree.branch_progress_mut(BranchId::Capitals).status = BranchStatus::InProgress;
+        let mut stats = KeyStatsStore::default();
+        make_stats_confident(&mut stats, &capitals);
+        for _ in 0..5 {
+            tree.update(&stats, None);
+        }
+        assert_eq!(
+            *tree.branch_status(BranchId::Capitals),
+            BranchStatus::Complete
+        );
+        assert!(tree.unlocked_keys(DrillScope::Global).contains(&'Ё'));
+    }
+
+    #[test]
+    fn greek_capitals_skip_final_sigma_and_keep_tonos() {
+        let sequence = find_language_pack("el").unwrap().primary_letter_sequence;
+        let tree = SkillTree::new_with_primary_sequence(SkillTreeProgress::default(), sequence);
+        let capitals: Vec<char> = (0..3)
+            .flat_map(|i| tree.level_keys(BranchId::Capitals, i).to_vec())
+            .collect();
+        assert_eq!(capitals.iter().filter(|&&ch| ch == 'Σ').count(), 1);
+        assert!(capitals.contains(&'Ά'));
+        assert!(!capitals.contains(&'Ϊ'));
+    }
+
+    #[test]
+    fn latin_sequences_keep_static_capitals() {
+        let sequence = find_language_pack("de").unwrap().primary_letter_sequence;
+        let tree = SkillTree::new_with_primary_sequence(SkillTreeProgress::default(), sequence);
+        assert_eq!(
+            tree.level_keys(BranchId::Capitals, 0),
+            CAPITALS_LEVELS[0].keys
+        );
+    }
+
     #[test]
     fn test_update_returns_newly_unlocked() {
         let mut tree = SkillTree::default();
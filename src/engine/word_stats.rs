@@ -0,0 +1,312 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+
+use crate::engine::ngram_stats::compute_median;
+use crate::keyboard::display::BACKSPACE;
+use crate::session::result::KeyTime;
+
+const EMA_ALPHA: f64 = 0.2;
+const MIN_WORD_CHARS: usize = 3;
+pub(crate) const MIN_SAMPLES_FOR_PROBLEM: usize = 3;
+const PROBLEM_SCORE_THRESHOLD: f64 = 0.25;
+
+/// One typed occurrence of a word within a drill.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WordEvent {
+    pub word: String,
+    pub time_ms: f64,
+    pub char_count: usize,
+    pub errors: usize,
+    pub has_hesitation: bool,
+}
+
+impl WordEvent {
+    pub fn wpm(&self) -> f64 {
+        if self.time_ms <= 0.0 {
+            return 0.0;
+        }
+        (self.char_count as f64 / 5.0) / (self.time_ms / 60000.0)
+    }
+}
+
+/// Lowercase `raw` and strip surrounding punctuation. Returns `None` for
+/// tokens that are too short or are not plain words (numbers, code, etc.).
+pub fn normalize_word(raw: &str) -> Option<String> {
+    let word = raw
+        .trim_matches(|c: char| !c.is_alphanumeric())
+        .to_lowercase();
+    let plain = word
+        .chars()
+        .all(|c| c.is_alphabetic() || c == '\'' || c == '-');
+    (plain && word.chars().count() >= MIN_WORD_CHARS).then_some(word)
+}
+
+/// Reconstruct the words of a drill from its per-key times.
+///
+/// - Correct whitespace keystrokes end a word; the whitespace itself is not timed
+/// - Corrected backspaces remove the previous character but their time still counts
+/// - A word's time is the sum of every keystroke that went into it, including
+///   the transition into its first character
+/// - hesitation = any keystroke in the word slower than `hesitation_threshold`
+pub fn extract_word_events(per_key_times: &[KeyTime], hesitation_threshold: f64) -> Vec<WordEvent> {
+    let mut events = Vec::new();
+    let mut chars: Vec<char> = Vec::new();
+    let mut time_ms = 0.0;
+    let mut errors = 0;
+    let mut has_hesitation = false;
+
+    for kt in per_key_times {
+        if kt.key == BACKSPACE {
+            // Incorrect backspace entries mirror a wrong key that is already counted.
+            if kt.correct {
+                chars.pop();
+                time_ms += kt.time_ms;
+            }
+            continue;
+        }
+        if kt.key.is_whitespace() && kt.correct {
+            let raw: String = chars.drain(..).collect();
+            if let Some(word) = normalize_word(&raw) {
+                events.push(WordEvent {
+                    char_count: word.chars().count(),
+                    word,
+                    time_ms,
+                    errors,
+                    has_hesitation,
+                });
+            }
+            time_ms = 0.0;
+            errors = 0;
+            has_hesitation = false;
+            continue;
+        }
+        chars.push(kt.key);
+        time_ms += kt.time_ms;
+        if !kt.correct {
+            errors += 1;
+        }
+        if kt.time_ms > hesitation_threshold {
+            has_hesitation = true;
+        }
+    }
+
+    // The last word of a drill has no trailing space.
+    let raw: String = chars.into_iter().collect();
+    if let Some(word) = normalize_word(&raw) {
+        events.push(WordEvent {
+            char_count: word.chars().count(),
+            word,
+            time_ms,
+            errors,
+            has_hesitation,
+        });
+    }
+    events
+}
+
+#[derive(Clone, Debug)]
+pub struct WordStat {
+    pub sample_count: usize,
+    /// Occurrences typed with at least one error.
+    pub error_count: usize,
+    pub hesitation_count: usize,
+    pub filtered_wpm: f64,
+    pub best_wpm: f64,
+    pub error_rate_ema: f64,
+    pub last_seen_drill_index: u32,
+    pub last_seen_at: Option<DateTime<Utc>>,
+}
+
+impl Default for WordStat {
+    fn default() -> Self {
+        Self {
+            sample_count: 0,
+            error_count: 0,
+            hesitation_count: 0,
+            filtered_wpm: 0.0,
+            best_wpm: 0.0,
+            error_rate_ema: 0.5,
+            last_seen_drill_index: 0,
+            last_seen_at: None,
+        }
+    }
+}
+
+/// A word that is consistently slow or error-prone relative to the user's norm.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProblemWord {
+    pub word: String,
+    pub score: f64,
+    pub wpm: f64,
+    pub error_rate: f64,
+    pub sample_count: usize,
+}
+
+/// Word-level outcomes keyed by normalized word. Rebuilt from drill history
+/// like the n-gram stores.
+#[derive(Clone, Debug, Default)]
+pub struct WordStatsStore {
+    pub stats: HashMap<String, WordStat>,
+}
+
+impl WordStatsStore {
+    pub fn update(&mut self, event: &WordEvent, drill_index: u32, at: DateTime<Utc>) {
+        let stat = self.stats.entry(event.word.clone()).or_default();
+        let wpm = event.wpm();
+        let error_signal = if event.errors > 0 { 1.0 } else { 0.0 };
+        stat.sample_count += 1;
+        if event.errors > 0 {
+            stat.error_count += 1;
+        }
+        if event.has_hesitation {
+            stat.hesitation_count += 1;
+        }
+        if stat.sample_count == 1 {
+            stat.filtered_wpm = wpm;
+            stat.error_rate_ema = error_signal;
+        } else {
+            stat.filtered_wpm = EMA_ALPHA * wpm + (1.0 - EMA_ALPHA) * stat.filtered_wpm;
+            stat.error_rate_ema =
+                EMA_ALPHA * error_signal + (1.0 - EMA_ALPHA) * stat.error_rate_ema;
+        }
+        stat.best_wpm = stat.best_wpm.max(stat.filtered_wpm);
+        stat.last_seen_drill_index = drill_index;
+        stat.last_seen_at = Some(at);
+    }
+
+    pub fn record_drill(&mut self, events: &[WordEvent], drill_index: u32, at: DateTime<Utc>) {
+        for event in events {
+            self.update(event, drill_index, at);
+        }
+    }
+
+    /// Median smoothed WPM over words with enough samples to judge.
+    pub fn median_wpm(&self) -> f64 {
+        let mut wpms: Vec<f64> = self
+            .stats
+            .values()
+            .filter(|s| s.sample_count >= MIN_SAMPLES_FOR_PROBLEM)
+            .map(|s| s.filtered_wpm)
+            .collect();
+        compute_median(&mut wpms)
+    }
+
+    /// Words ranked by difficulty, worst first.
+    ///
+    /// The score adds the word's error rate, how far it falls below the median
+    /// word speed, and half its hesitation rate. With `confirmed_only`, words
+    /// need enough samples and a score above the problem threshold.
+    pub fn ranked_words(&self, confirmed_only: bool) -> Vec<ProblemWord> {
+        let median = self.median_wpm();
+        let mut words: Vec<ProblemWord> = self
+            .stats
+            .iter()
+            .filter(|(_, s)| !confirmed_only || s.sample_count >= MIN_SAMPLES_FOR_PROBLEM)
+            .map(|(word, s)| {
+                let slowness = if median > 0.0 {
+                    (1.0 - s.filtered_wpm / median).max(0.0)
+                } else {
+                    0.0
+                };
+                let hesitation_rate = s.hesitation_count as f64 / s.sample_count as f64;
+                ProblemWord {
+                    word: word.clone(),
+                    score: s.error_rate_ema + slowness + 0.5 * hesitation_rate,
+                    wpm: s.filtered_wpm,
+                    error_rate: s.error_count as f64 / s.sample_count as f64,
+                    sample_count: s.sample_count,
+                }
+            })
+            .filter(|p| !confirmed_only || p.score >= PROBLEM_SCORE_THRESHOLD)
+            .collect();
+        words.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.word.cmp(&b.word))
+        });
+        words
+    }
+
+    /// Confirmed problem words, worst first.
+    pub fn problem_words(&self, limit: usize) -> Vec<ProblemWord> {
+        let mut words = self.ranked_words(true);
+        words.truncate(limit);
+        words
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kt(key: char, time_ms: f64, correct: bool) -> KeyTime {
+        KeyTime {
+            key,
+            time_ms,
+            correct,
+        }
+    }
+
+    fn typed(text: &str, time_ms: f64) -> Vec<KeyTime> {
+        text.chars().map(|c| kt(c, time_ms, true)).collect()
+    }
+
+    #[test]
+    fn normalize_strips_punctuation_and_rejects_non_words() {
+        assert_eq!(normalize_word("Rhythm,"), Some("rhythm".to_string()));
+        assert_eq!(normalize_word("\"don't\""), Some("don't".to_string()));
+        assert_eq!(normalize_word("an"), None);
+        assert_eq!(normalize_word("1234"), None);
+        assert_eq!(normalize_word("self.foo"), None);
+    }
+
+    #[test]
+    fn extract_splits_on_spaces_and_sums_time() {
+        let events = extract_word_events(&typed("the cat", 100.0), 800.0);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].word, "the");
+        assert_eq!(events[0].time_ms, 300.0);
+        assert_eq!(events[1].word, "cat");
+        assert_eq!(events[1].errors, 0);
+    }
+
+    #[test]
+    fn extract_reconstructs_corrected_words() {
+        // "rhythm" with a wrong 'y' that is backspaced and retyped.
+        let mut times = typed("rh", 100.0);
+        times.push(kt('y', 100.0, false));
+        times.push(kt(BACKSPACE, 150.0, true));
+        times.push(kt('y', 900.0, true));
+        times.extend(typed("thm", 100.0));
+        let events = extract_word_events(&times, 800.0);
+        assert_eq!(events.len(), 1);
+        let ev = &events[0];
+        assert_eq!(ev.word, "rhythm");
+        assert_eq!(ev.char_count, 6);
+        assert_eq!(ev.errors, 1);
+        assert!(ev.has_hesitation);
+        assert_eq!(ev.time_ms, 1650.0);
+    }
+
+    #[test]
+    fn problem_words_rank_slow_and_error_prone_words() {
+        let mut store = WordStatsStore::default();
+        let at = Utc::now();
+        for i in 0..5 {
+            for word in ["the", "and", "for"] {
+                store.record_drill(&extract_word_events(&typed(word, 100.0), 800.0), i, at);
+            }
+            let mut slow = typed("defini", 300.0);
+            slow.push(kt('t', 300.0, false));
+            slow.push(kt(BACKSPACE, 300.0, true));
+            slow.extend(typed("tely", 300.0));
+            store.record_drill(&extract_word_events(&slow, 800.0), i, at);
+        }
+        let problems = store.problem_words(5);
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].word, "definitely");
+        assert_eq!(problems[0].error_rate, 1.0);
+        assert_eq!(store.ranked_words(false).len(), 4);
+    }
+}
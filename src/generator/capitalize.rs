@@ -1,21 +1,30 @@
+use std::collections::HashSet;
+
 use rand::Rng;
 use rand::rngs::SmallRng;
+use rand::seq::SliceRandom;
 
-fn lowercase_eq(a: char, b: char) -> bool {
-    a.to_lowercase().eq(b.to_lowercase())
-}
+use crate::l10n::text_rules::{CaseMapping, TextRules};
 
 /// Post-processing pass that capitalizes words in generated text.
-/// Only capitalizes using letters from `unlocked_capitals`.
+/// Only capitalizes using letters from `unlocked_capitals`. Case mapping follows
+/// the language's `rules`; in languages that capitalize nouns, words found in
+/// `nouns` (lowercased) or carrying a noun suffix are always capitalized and
+/// other words only at sentence starts, even if that leaves the drill with
+/// few capitals. A focused capital is still guaranteed there, by swapping in
+/// nouns that start with it.
 pub fn apply_capitalization(
     text: &str,
     unlocked_capitals: &[char],
     focused: Option<char>,
+    rules: &TextRules,
+    nouns: &HashSet<String>,
     rng: &mut SmallRng,
 ) -> String {
     if unlocked_capitals.is_empty() {
         return text.to_string();
     }
+    let case = rules.case_mapping;
 
     let focused_upper = focused.filter(|ch| ch.is_uppercase());
     let mut words: Vec<String> = text.split_whitespace().map(|w| w.to_string()).collect();
@@ -26,23 +35,35 @@ pub fn apply_capitalization(
     // Prefer capitals at starts of words (sentence starts always when possible).
     let mut at_sentence_start = true;
     for i in 0..words.len() {
-        if let Some(upper) = word_start_upper(&words[i]) {
+        if let Some(upper) = word_start_upper(&words[i], case) {
             if unlocked_capitals.contains(&upper) {
                 let should_cap = if at_sentence_start {
                     true
+                } else if rules.capitalizes_nouns {
+                    is_noun(&words[i], rules, nouns)
                 } else if focused_upper == Some(upper) {
                     rng.gen_bool(0.55)
                 } else {
                     rng.gen_bool(0.22)
                 };
                 if should_cap {
-                    capitalize_word_start(&mut words[i]);
+                    capitalize_word_start(&mut words[i], case);
                 }
             }
         }
         at_sentence_start = ends_sentence(&words[i]);
     }
 
+    // Any further capital would land on a word the language keeps lowercase,
+    // so focused capitals come from nouns instead.
+    if rules.capitalizes_nouns {
+        if let Some(focused_upper) = focused_upper.filter(|ch| unlocked_capitals.contains(ch)) {
+            let min_focused = alpha_word_count(&words).min(4);
+            ensure_min_focused_nouns(&mut words, focused_upper, min_focused, case, nouns, rng);
+        }
+        return words.join(" ");
+    }
+
     // Occasional mid-word capitals are injected as camelCase joins only.
     // This keeps internal capitals realistic for code contexts.
     let mut i = 0;
@@ -51,7 +72,7 @@ pub fn apply_capitalization(
             i += 1;
             continue;
         }
-        let next_upper = match word_start_upper(&words[i + 1]) {
+        let next_upper = match word_start_upper(&words[i + 1], case) {
             Some(upper) if unlocked_capitals.contains(&upper) => upper,
             _ => {
                 i += 1;
@@ -64,7 +85,7 @@ pub fn apply_capitalization(
             0.09
         };
         if rng.gen_bool(prob) {
-            capitalize_word_start(&mut words[i + 1]);
+            capitalize_word_start(&mut words[i + 1], case);
             let next = words.remove(i + 1);
             words[i].push_str(&next);
         } else {
@@ -74,33 +95,42 @@ pub fn apply_capitalization(
 
     // Focused capitals should show up multiple times for focused drills.
     if let Some(focused_upper) = focused_upper.filter(|ch| unlocked_capitals.contains(ch)) {
-        let alpha_words = words
-            .iter()
-            .filter(|w| w.chars().any(|ch| ch.is_alphabetic()))
-            .count();
-        let min_focused = alpha_words.min(4);
-        ensure_min_focused_occurrences(&mut words, focused_upper, min_focused);
+        let min_focused = alpha_word_count(&words).min(4);
+        ensure_min_focused_occurrences(&mut words, focused_upper, min_focused, case);
     }
 
     // Keep a baseline capital density so branch/global drills with capitals
     // unlocked do not feel too sparse.
     let min_total_caps = words.len().clamp(3, 6) / 2; // ~3 for 6+ words
-    ensure_min_total_capitals(&mut words, unlocked_capitals, min_total_caps, rng);
+    ensure_min_total_capitals(&mut words, unlocked_capitals, min_total_caps, case, rng);
 
     words.join(" ")
 }
 
-fn word_start_upper(word: &str) -> Option<char> {
+fn alpha_word_count(words: &[String]) -> usize {
+    words
+        .iter()
+        .filter(|w| w.chars().any(|ch| ch.is_alphabetic()))
+        .count()
+}
+
+fn is_noun(word: &str, rules: &TextRules, nouns: &HashSet<String>) -> bool {
+    let letters: String = word.chars().filter(|ch| ch.is_alphabetic()).collect();
+    let lower = rules.case_mapping.to_lower_word(&letters);
+    nouns.contains(&lower) || rules.has_noun_suffix(&lower)
+}
+
+fn word_start_upper(word: &str, case: CaseMapping) -> Option<char> {
     word.chars()
         .find(|ch| ch.is_alphabetic())
-        .and_then(|ch| ch.to_uppercase().next())
+        .map(|ch| case.to_upper(ch))
 }
 
-fn capitalize_word_start(word: &mut String) -> Option<char> {
+fn capitalize_word_start(word: &mut String, case: CaseMapping) -> Option<char> {
     let mut chars: Vec<char> = word.chars().collect();
     for i in 0..chars.len() {
         if chars[i].is_lowercase() {
-            chars[i] = chars[i].to_uppercase().next().unwrap_or(chars[i]);
+            chars[i] = case.to_upper(chars[i]);
             let upper = chars[i];
             *word = chars.into_iter().collect();
             return Some(upper);
@@ -119,10 +149,10 @@ fn ends_sentence(word: &str) -> bool {
         .is_some_and(|ch| matches!(ch, '.' | '?' | '!'))
 }
 
-fn word_starts_with_lower(word: &str, lower: char) -> bool {
+fn word_starts_with_lower(word: &str, lower: char, case: CaseMapping) -> bool {
     word.chars()
         .find(|ch| ch.is_alphabetic())
-        .is_some_and(|ch| lowercase_eq(ch, lower))
+        .is_some_and(|ch| case.eq_ignore_case(ch, lower))
 }
 
 fn force_word_start_to_upper(word: &mut String, upper: char) -> bool {
@@ -140,8 +170,13 @@ fn force_word_start_to_upper(word: &mut String, upper: char) -> bool {
     false
 }
 
-fn ensure_min_focused_occurrences(words: &mut Vec<String>, focused_upper: char, min_count: usize) {
-    let focused_lower = focused_upper.to_lowercase().next().unwrap_or(focused_upper);
+fn ensure_min_focused_occurrences(
+    words: &mut Vec<String>,
+    focused_upper: char,
+    min_count: usize,
+    case: CaseMapping,
+) {
+    let focused_lower = case.to_lower(focused_upper);
     let mut count = words
         .iter()
         .map(|w| w.chars().filter(|&ch| ch == focused_upper).count())
@@ -156,10 +191,10 @@ fn ensure_min_focused_occurrences(words: &mut Vec<String>, focused_upper: char,
         if count >= min_count {
             break;
         }
-        if !word_starts_with_lower(word, focused_lower) {
+        if !word_starts_with_lower(word, focused_lower, case) {
             continue;
         }
-        if capitalize_word_start(word) == Some(focused_upper) {
+        if capitalize_word_start(word, case) == Some(focused_upper) {
             count += 1;
         }
     }
@@ -178,9 +213,9 @@ fn ensure_min_focused_occurrences(words: &mut Vec<String>, focused_upper: char,
         let next_starts_focused = words[i + 1]
             .chars()
             .find(|ch| ch.is_alphabetic())
-            .is_some_and(|ch| lowercase_eq(ch, focused_lower));
+            .is_some_and(|ch| case.eq_ignore_case(ch, focused_lower));
         if next_starts_focused {
-            capitalize_word_start(&mut words[i + 1]);
+            capitalize_word_start(&mut words[i + 1], case);
             let next = words.remove(i + 1);
             words[i].push_str(&next);
             count += 1;
@@ -200,10 +235,66 @@ fn ensure_min_focused_occurrences(words: &mut Vec<String>, focused_upper: char,
     }
 }
 
+/// Noun-capitalizing languages: replace lowercase words with nouns that start
+/// with the focused capital. Without such nouns, sentence starts are forced to
+/// it instead.
+fn ensure_min_focused_nouns(
+    words: &mut [String],
+    focused_upper: char,
+    min_count: usize,
+    case: CaseMapping,
+    nouns: &HashSet<String>,
+    rng: &mut SmallRng,
+) {
+    let mut count = words
+        .iter()
+        .map(|w| w.chars().filter(|&ch| ch == focused_upper).count())
+        .sum::<usize>();
+    if count >= min_count {
+        return;
+    }
+
+    let focused_lower = case.to_lower(focused_upper);
+    let mut candidates: Vec<&String> = nouns
+        .iter()
+        .filter(|noun| noun.starts_with(focused_lower))
+        .collect();
+    // Sorted so a seeded rng picks the same nouns every time.
+    candidates.sort();
+    let mut slots: Vec<usize> = (0..words.len())
+        .filter(|&i| words[i].chars().next().is_some_and(|ch| ch.is_lowercase()))
+        .collect();
+    slots.shuffle(rng);
+    for i in slots {
+        if count >= min_count || candidates.is_empty() {
+            break;
+        }
+        let mut noun = candidates[rng.gen_range(0..candidates.len())].clone();
+        capitalize_word_start(&mut noun, case);
+        let tail: String = words[i]
+            .chars()
+            .skip_while(|ch| ch.is_alphabetic())
+            .collect();
+        words[i] = noun + &tail;
+        count += 1;
+    }
+
+    for i in 0..words.len() {
+        if count >= min_count {
+            break;
+        }
+        let sentence_start = i == 0 || ends_sentence(&words[i - 1]);
+        if sentence_start && force_word_start_to_upper(&mut words[i], focused_upper) {
+            count += 1;
+        }
+    }
+}
+
 fn ensure_min_total_capitals(
     words: &mut [String],
     unlocked_capitals: &[char],
     min_count: usize,
+    case: CaseMapping,
     rng: &mut SmallRng,
 ) {
     let mut count = words
@@ -219,13 +310,13 @@ fn ensure_min_total_capitals(
         if count >= min_count {
             break;
         }
-        let Some(upper) = word_start_upper(word) else {
+        let Some(upper) = word_start_upper(word, case) else {
             continue;
         };
         if unlocked_capitals.contains(&upper)
-            && word_starts_with_lower(word, upper.to_lowercase().next().unwrap_or(upper))
+            && word_starts_with_lower(word, case.to_lower(upper), case)
         {
-            if capitalize_word_start(word) == Some(upper) {
+            if capitalize_word_start(word, case) == Some(upper) {
                 count += 1;
             }
         }
@@ -246,19 +337,38 @@ fn ensure_min_total_capitals(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::l10n::text_rules::{DE_TEXT_RULES, EL_TEXT_RULES, EN_TEXT_RULES, TR_TEXT_RULES};
     use rand::SeedableRng;
 
+    fn no_nouns() -> HashSet<String> {
+        HashSet::new()
+    }
+
     #[test]
     fn test_no_caps_when_empty() {
         let mut rng = SmallRng::seed_from_u64(42);
-        let result = apply_capitalization("hello world", &[], None, &mut rng);
+        let result = apply_capitalization(
+            "hello world",
+            &[],
+            None,
+            &EN_TEXT_RULES,
+            &no_nouns(),
+            &mut rng,
+        );
         assert_eq!(result, "hello world");
     }
 
     #[test]
     fn test_capitalizes_first_word() {
         let mut rng = SmallRng::seed_from_u64(42);
-        let result = apply_capitalization("hello world", &['H', 'W'], None, &mut rng);
+        let result = apply_capitalization(
+            "hello world",
+            &['H', 'W'],
+            None,
+            &EN_TEXT_RULES,
+            &no_nouns(),
+            &mut rng,
+        );
         assert!(result.starts_with('H'));
     }
 
@@ -266,14 +376,28 @@ mod tests {
     fn test_only_capitalizes_unlocked() {
         let mut rng = SmallRng::seed_from_u64(42);
         // Only 'W' is unlocked, not 'H'
-        let result = apply_capitalization("hello world", &['W'], None, &mut rng);
+        let result = apply_capitalization(
+            "hello world",
+            &['W'],
+            None,
+            &EN_TEXT_RULES,
+            &no_nouns(),
+            &mut rng,
+        );
         assert!(result.starts_with('h')); // 'H' not unlocked
     }
 
     #[test]
     fn test_after_period() {
         let mut rng = SmallRng::seed_from_u64(42);
-        let result = apply_capitalization("one. two", &['O', 'T'], None, &mut rng);
+        let result = apply_capitalization(
+            "one. two",
+            &['O', 'T'],
+            None,
+            &EN_TEXT_RULES,
+            &no_nouns(),
+            &mut rng,
+        );
         assert!(result.starts_with('O'));
         assert!(result.contains("Two") || result.contains("two"));
         // At minimum, first word should be capitalized
@@ -289,11 +413,13 @@ mod tests {
         for seed in 0..200 {
             let mut rng = SmallRng::seed_from_u64(seed);
             let text = "hello world wide web wonder what where who will work";
-            let result = apply_capitalization(text, caps, Some('W'), &mut rng);
+            let result =
+                apply_capitalization(text, caps, Some('W'), &EN_TEXT_RULES, &no_nouns(), &mut rng);
             // Count W capitalizations (skip first word which is always capitalized if 'H' is available)
             focused_count += result.matches('W').count();
             let mut rng2 = SmallRng::seed_from_u64(seed);
-            let result2 = apply_capitalization(text, caps, None, &mut rng2);
+            let result2 =
+                apply_capitalization(text, caps, None, &EN_TEXT_RULES, &no_nouns(), &mut rng2);
             unfocused_count += result2.matches('W').count();
         }
         assert!(
@@ -306,7 +432,14 @@ mod tests {
     fn test_focused_capital_has_minimum_presence_when_available() {
         let mut rng = SmallRng::seed_from_u64(123);
         let text = "we will work with weird words while we wait";
-        let result = apply_capitalization(text, &['W'], Some('W'), &mut rng);
+        let result = apply_capitalization(
+            text,
+            &['W'],
+            Some('W'),
+            &EN_TEXT_RULES,
+            &no_nouns(),
+            &mut rng,
+        );
         let focused_count = result.chars().filter(|&ch| ch == 'W').count();
         assert!(
             focused_count >= 3,
@@ -318,7 +451,14 @@ mod tests {
     fn test_no_interior_focus_caps_without_word_start_or_camel_case_opportunity() {
         let mut rng = SmallRng::seed_from_u64(7);
         let text = "awful claw draw";
-        let result = apply_capitalization(text, &['W'], Some('W'), &mut rng);
+        let result = apply_capitalization(
+            text,
+            &['W'],
+            Some('W'),
+            &EN_TEXT_RULES,
+            &no_nouns(),
+            &mut rng,
+        );
         assert!(result.starts_with('W') || result.contains(" W"));
         assert!(
             !result.contains("aW"),
@@ -330,11 +470,101 @@ mod tests {
     fn test_focused_capital_forced_to_multiple_occurrences() {
         let mut rng = SmallRng::seed_from_u64(11);
         let text = "alpha beta gamma delta epsilon zeta eta theta iota";
-        let result = apply_capitalization(text, &['Q'], Some('Q'), &mut rng);
+        let result = apply_capitalization(
+            text,
+            &['Q'],
+            Some('Q'),
+            &EN_TEXT_RULES,
+            &no_nouns(),
+            &mut rng,
+        );
         let focused_count = result.chars().filter(|&ch| ch == 'Q').count();
         assert!(
             focused_count >= 4,
             "Expected forced focused Q occurrences, got {focused_count} in: {result}"
         );
     }
+
+    #[test]
+    fn test_turkish_dotted_i_is_not_capitalized_to_ascii_i() {
+        for seed in 0..20 {
+            let mut rng = SmallRng::seed_from_u64(seed);
+            let text = "ilk ışık ılık iş ısı";
+            let result =
+                apply_capitalization(text, &['I'], None, &TR_TEXT_RULES, &no_nouns(), &mut rng);
+            assert!(result.contains('I'), "{result}");
+            // Lowering with Turkic rules must give back the source letters,
+            // modulo camelCase joins: an `i` capitalized to ASCII `I` would
+            // come back as `ı`.
+            let lowered: String = result
+                .chars()
+                .filter(|ch| *ch != ' ')
+                .map(|ch| CaseMapping::Turkic.to_lower(ch))
+                .collect();
+            assert_eq!(lowered, text.replace(' ', ""), "{result}");
+        }
+    }
+
+    #[test]
+    fn test_greek_sigma_capitalizes_only_at_word_starts() {
+        for seed in 0..20 {
+            let mut rng = SmallRng::seed_from_u64(seed);
+            let text = "σήμερα ο κόσμος σπίτι στο σχολείο τους";
+            let result = apply_capitalization(
+                text,
+                &['Σ', 'Ο'],
+                Some('Σ'),
+                &EL_TEXT_RULES,
+                &no_nouns(),
+                &mut rng,
+            );
+            assert!(result.contains('Σ'), "{result}");
+            assert!(result.contains("ος") && result.contains("ους"), "{result}");
+            let lowered: String = result
+                .split(' ')
+                .map(|word| CaseMapping::Greek.to_lower_word(word))
+                .collect();
+            assert_eq!(lowered, text.replace(' ', ""), "{result}");
+        }
+    }
+
+    #[test]
+    fn test_german_focused_capital_comes_from_nouns() {
+        let nouns: HashSet<String> = ["quelle", "qualität", "haus"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let caps: Vec<char> = ('A'..='Z').collect();
+        let text = "der mann sieht das haus und die zeitung aber nicht ganz genau";
+        for seed in 0..20 {
+            let mut rng = SmallRng::seed_from_u64(seed);
+            let result =
+                apply_capitalization(text, &caps, Some('Q'), &DE_TEXT_RULES, &nouns, &mut rng);
+            assert!(result.matches('Q').count() >= 4, "{result}");
+            for word in result.split(' ').filter(|w| w.starts_with('Q')) {
+                assert!(["Quelle", "Qualität"].contains(&word), "{result}");
+            }
+            assert!(result.starts_with("Der"), "{result}");
+        }
+
+        // Without a matching noun the focused capital still shows up.
+        let mut rng = SmallRng::seed_from_u64(1);
+        let result = apply_capitalization(text, &caps, Some('X'), &DE_TEXT_RULES, &nouns, &mut rng);
+        assert!(result.contains('X'), "{result}");
+    }
+
+    #[test]
+    fn test_german_nouns_capitalized_and_other_words_left_alone() {
+        let nouns: HashSet<String> = ["haus".to_string()].into_iter().collect();
+        let caps: Vec<char> = ('A'..='Z').collect();
+        for seed in 0..20 {
+            let mut rng = SmallRng::seed_from_u64(seed);
+            let text = "der mann sieht das haus und die zeitung aber nicht ganz genau";
+            let result = apply_capitalization(text, &caps, None, &DE_TEXT_RULES, &nouns, &mut rng);
+            assert_eq!(
+                result,
+                "Der mann sieht das Haus und die Zeitung aber nicht ganz genau"
+            );
+        }
+    }
 }
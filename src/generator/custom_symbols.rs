@@ -0,0 +1,94 @@
+use rand::Rng;
+use rand::rngs::SmallRng;
+
+/// Bracket-like pairs that wrap a word when both halves are in scope.
+const PAIRS: &[(char, char)] = &[
+    ('(', ')'),
+    ('[', ']'),
+    ('{', '}'),
+    ('<', '>'),
+    ('«', '»'),
+    ('“', '”'),
+    ('‘', '’'),
+    ('„', '“'),
+];
+
+/// Post-processing pass that inserts keys of user-defined branches into text.
+/// Unlike `code_patterns::apply_code_symbols` it knows nothing about what the
+/// keys mean, so it uses shapes that suit most symbols: a complete bracket
+/// pair wraps a word, and any other key starts a word, ends it, or stands
+/// between two words (`# title`, `x²`, `a | b`).
+pub fn apply_custom_symbols(
+    text: &str,
+    symbols: &[char],
+    focused: Option<char>,
+    rng: &mut SmallRng,
+) -> String {
+    if symbols.is_empty() {
+        return text.to_string();
+    }
+
+    let focused_symbol = focused.filter(|ch| symbols.contains(ch));
+    let base_prob = if focused_symbol.is_some() { 0.35 } else { 0.20 };
+
+    text.split(' ')
+        .map(|word| {
+            if word.is_empty() || !rng.gen_bool(base_prob) {
+                return word.to_string();
+            }
+            let symbol = match focused_symbol {
+                Some(ch) if rng.gen_bool(0.5) => ch,
+                _ => symbols[rng.gen_range(0..symbols.len())],
+            };
+            decorate(word, symbol, symbols, rng)
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn decorate(word: &str, symbol: char, symbols: &[char], rng: &mut SmallRng) -> String {
+    if let Some(&(open, close)) = PAIRS
+        .iter()
+        .find(|(open, close)| *open == symbol || *close == symbol)
+        && symbols.contains(&open)
+        && symbols.contains(&close)
+    {
+        return format!("{open}{word}{close}");
+    }
+    match rng.gen_range(0..3) {
+        0 => format!("{symbol}{word}"),
+        1 => format!("{word}{symbol}"),
+        _ => format!("{word} {symbol}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    #[test]
+    fn inserts_only_the_given_symbols() {
+        let mut rng = SmallRng::seed_from_u64(3);
+        let text = "the quick brown fox jumps over the lazy dog ".repeat(5);
+        let out = apply_custom_symbols(text.trim_end(), &['#', '√'], Some('√'), &mut rng);
+        assert!(out.contains('√'), "{out}");
+        assert!(
+            out.chars()
+                .all(|ch| ch.is_ascii_lowercase() || ch == ' ' || ch == '#' || ch == '√'),
+            "{out}"
+        );
+    }
+
+    #[test]
+    fn complete_bracket_pairs_wrap_words() {
+        let mut rng = SmallRng::seed_from_u64(9);
+        let text = "alpha beta gamma delta ".repeat(10);
+        let out = apply_custom_symbols(text.trim_end(), &['[', ']'], None, &mut rng);
+        assert!(out.contains('['), "{out}");
+        assert_eq!(out.matches('[').count(), out.matches(']').count());
+        for word in out.split(' ').filter(|w| w.contains('[')) {
+            assert!(word.starts_with('[') && word.ends_with(']'), "{word}");
+        }
+    }
+}
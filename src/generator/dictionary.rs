@@ -1,9 +1,14 @@
+use std::collections::{HashMap, HashSet};
+
 use crate::engine::filter::CharFilter;
+use crate::generator::vocabulary::{DEFAULT_WEIGHT, Vocabulary};
+use crate::l10n::runtime_pack::runtime_words_json;
 use crate::l10n::unicode::normalize_nfc;
 
 const WORDS_CS: &str = include_str!("../../assets/dictionaries/words-cs.json");
 const WORDS_DA: &str = include_str!("../../assets/dictionaries/words-da.json");
 const WORDS_DE: &str = include_str!("../../assets/dictionaries/words-de.json");
+const WORDS_EL: &str = include_str!("../../assets/dictionaries/words-el.json");
 const WORDS_EN: &str = include_str!("../../assets/dictionaries/words-en.json");
 const WORDS_ES: &str = include_str!("../../assets/dictionaries/words-es.json");
 const WORDS_ET: &str = include_str!("../../assets/dictionaries/words-et.json");
@@ -19,12 +24,18 @@ const WORDS_NL: &str = include_str!("../../assets/dictionaries/words-nl.json");
 const WORDS_PL: &str = include_str!("../../assets/dictionaries/words-pl.json");
 const WORDS_PT: &str = include_str!("../../assets/dictionaries/words-pt.json");
 const WORDS_RO: &str = include_str!("../../assets/dictionaries/words-ro.json");
+const WORDS_RU: &str = include_str!("../../assets/dictionaries/words-ru.json");
 const WORDS_SL: &str = include_str!("../../assets/dictionaries/words-sl.json");
 const WORDS_SV: &str = include_str!("../../assets/dictionaries/words-sv.json");
 const WORDS_TR: &str = include_str!("../../assets/dictionaries/words-tr.json");
+const WORDS_UK: &str = include_str!("../../assets/dictionaries/words-uk.json");
 #[derive(Clone, Debug)]
 pub struct Dictionary {
     words: Vec<String>,
+    /// Pick weights above the default, from the user's custom word list or
+    /// `weighted_toward`.
+    weights: HashMap<String, u32>,
+    custom_vocabulary: bool,
 }
 
 impl Dictionary {
@@ -33,6 +44,7 @@ impl Dictionary {
             "cs" => Some(WORDS_CS),
             "da" => Some(WORDS_DA),
             "de" => Some(WORDS_DE),
+            "el" => Some(WORDS_EL),
             "en" => Some(WORDS_EN),
             "es" => Some(WORDS_ES),
             "et" => Some(WORDS_ET),
@@ -48,10 +60,12 @@ impl Dictionary {
             "pl" => Some(WORDS_PL),
             "pt" => Some(WORDS_PT),
             "ro" => Some(WORDS_RO),
+            "ru" => Some(WORDS_RU),
             "sl" => Some(WORDS_SL),
             "sv" => Some(WORDS_SV),
             "tr" => Some(WORDS_TR),
-            _ => None,
+            "uk" => Some(WORDS_UK),
+            _ => runtime_words_json(language_key),
         }
     }
 
@@ -72,7 +86,11 @@ impl Dictionary {
             .filter(|w| !w.chars().any(|c| c.is_whitespace()))
             .collect::<Vec<String>>();
 
-        Some(Self { words })
+        Some(Self {
+            words,
+            weights: HashMap::new(),
+            custom_vocabulary: false,
+        })
     }
 
     pub fn load_for_language(language_key: &str) -> Self {
@@ -80,10 +98,92 @@ impl Dictionary {
             .unwrap_or_else(|| panic!("unsupported dictionary language: {language_key}"))
     }
 
+    /// Merge the user's word list and blocklist. Blocked words are dropped,
+    /// including bundled ones; custom words join the list with their weight.
+    pub fn with_vocabulary(mut self, vocabulary: &Vocabulary) -> Self {
+        if vocabulary.is_empty() {
+            return self;
+        }
+        self.words.retain(|word| !vocabulary.is_blocked(word));
+        for entry in &vocabulary.words {
+            if vocabulary.is_blocked(&entry.word) {
+                continue;
+            }
+            if !self.words.contains(&entry.word) {
+                self.words.push(entry.word.clone());
+            }
+            if entry.weight > DEFAULT_WEIGHT {
+                self.weights.insert(entry.word.clone(), entry.weight);
+            }
+        }
+        self.custom_vocabulary = true;
+        self
+    }
+
+    /// Raise the pick weight of every word containing one of `keys` to at
+    /// least `weight`.
+    pub fn weighted_toward(mut self, keys: &[char], weight: u32) -> Self {
+        if keys.is_empty() || weight <= DEFAULT_WEIGHT {
+            return self;
+        }
+        for word in self
+            .words
+            .iter()
+            .filter(|w| w.chars().any(|c| keys.contains(&c)))
+        {
+            let entry = self.weights.entry(word.clone()).or_insert(weight);
+            *entry = (*entry).max(weight);
+        }
+        self
+    }
+
+    /// Whether a user word list or blocklist shaped this dictionary.
+    pub fn has_custom_vocabulary(&self) -> bool {
+        self.custom_vocabulary
+    }
+
+    pub fn weight(&self, word: &str) -> u32 {
+        self.weights.get(word).copied().unwrap_or(DEFAULT_WEIGHT)
+    }
+
     pub fn words_list(&self) -> &[String] {
         &self.words
     }
 
+    /// Lowercased forms of words that only ever appear capitalized, i.e. nouns
+    /// in languages that capitalize them.
+    pub fn capitalized_forms(&self) -> HashSet<String> {
+        let lowercase: HashSet<&str> = self
+            .words
+            .iter()
+            .filter(|w| w.chars().next().is_some_and(|ch| ch.is_lowercase()))
+            .map(|w| w.as_str())
+            .collect();
+        self.words
+            .iter()
+            .filter(|w| w.chars().next().is_some_and(|ch| ch.is_uppercase()))
+            .map(|w| w.to_lowercase())
+            .filter(|w| !lowercase.contains(w.as_str()))
+            .collect()
+    }
+
+    /// Other dictionary words built on the same stem as `word` (e.g. `definite`
+    /// and `definition` for `definitely`), closest in length first.
+    pub fn morphological_neighbours(&self, word: &str, limit: usize) -> Vec<&str> {
+        let len = word.chars().count();
+        let stem: String = word.chars().take(len.saturating_sub(3).max(4)).collect();
+        let mut neighbours: Vec<&str> = self
+            .words
+            .iter()
+            .map(|w| w.as_str())
+            .filter(|w| *w != word && w.to_lowercase().starts_with(&stem))
+            .collect();
+        neighbours.sort_by_key(|w| (w.chars().count().abs_diff(len), *w));
+        neighbours.dedup();
+        neighbours.truncate(limit);
+        neighbours
+    }
+
     pub fn find_matching(&self, filter: &CharFilter, focused: Option<char>) -> Vec<&str> {
         let mut matching: Vec<&str> = self
             .words
@@ -112,6 +212,27 @@ mod tests {
         let _ = Dictionary::load_for_language("zz");
     }
 
+    #[test]
+    fn capitalized_forms_pick_up_german_nouns_only() {
+        let dictionary = Dictionary::load_for_language("de");
+        let forms = dictionary.capitalized_forms();
+        assert!(forms.contains("zeit"));
+        assert!(!forms.contains("und"));
+    }
+
+    #[test]
+    fn morphological_neighbours_share_a_stem() {
+        let dictionary = Dictionary::load_for_language("en");
+        assert_eq!(
+            dictionary.morphological_neighbours("definitely", 5),
+            vec!["definition", "definite"]
+        );
+        assert_eq!(
+            dictionary.morphological_neighbours("definitely", 1).len(),
+            1
+        );
+    }
+
     #[test]
     fn find_matching_focused_is_sort_only() {
         let dictionary = Dictionary::load_for_language("en");
@@ -159,4 +280,28 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn vocabulary_blocks_bundled_words_and_adds_weighted_ones() {
+        let mut vocabulary = Vocabulary::default();
+        vocabulary.add_word_line("kubectl 4");
+        vocabulary.add_word_line("ingress");
+        vocabulary.add_word_line("Shit");
+        vocabulary.block("the");
+        vocabulary.block("shit");
+
+        let plain = Dictionary::load_for_language("en");
+        assert!(!plain.has_custom_vocabulary());
+        assert!(plain.words_list().iter().any(|w| w == "the"));
+
+        let dictionary = plain.with_vocabulary(&vocabulary);
+        let words = dictionary.words_list();
+        assert!(dictionary.has_custom_vocabulary());
+        assert!(!words.iter().any(|w| w == "the"));
+        assert!(!words.iter().any(|w| w == "Shit"));
+        assert_eq!(words.iter().filter(|w| *w == "kubectl").count(), 1);
+        assert_eq!(dictionary.weight("kubectl"), 4);
+        assert_eq!(dictionary.weight("ingress"), 1);
+        assert_eq!(dictionary.weight("about"), 1);
+    }
 }
@@ -0,0 +1,154 @@
+//! Drills on a hand-picked set of keys, chosen in the keyboard explorer.
+//! Sets can be saved as presets in the `[[key_sets]]` tables of
+//! `config.toml`:
+//!
+//! ```toml
+//! [[key_sets]]
+//! name = "Left pinky"
+//! keys = "qaz1!"
+//! ```
+
+use std::collections::HashSet;
+
+use rand::SeedableRng;
+use rand::rngs::SmallRng;
+use serde::{Deserialize, Serialize};
+
+use crate::engine::filter::CharFilter;
+use crate::generator::TextGenerator;
+use crate::generator::dictionary::Dictionary;
+use crate::generator::phonetic::PhoneticGenerator;
+use crate::generator::transition_table::TransitionTable;
+
+/// Pick weight of dictionary words containing a selected letter; other words
+/// keep their own weight.
+const SELECTED_WORD_WEIGHT: u32 = 4;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeySet {
+    pub name: String,
+    /// The selected keys, written as one string.
+    pub keys: String,
+}
+
+/// Whether `ch` can be part of a key set. Space, Enter, Tab and Backspace
+/// are in every drill already.
+pub fn is_key_set_key(ch: char) -> bool {
+    !ch.is_whitespace() && !ch.is_control()
+}
+
+/// Drop presets without a name or usable keys, and later presets that
+/// repeat an earlier name. Within a preset, repeated keys are removed.
+pub fn normalize_key_sets(sets: &[KeySet]) -> Vec<KeySet> {
+    let mut seen_names: HashSet<&str> = HashSet::new();
+    let mut normalized = Vec::new();
+    for set in sets {
+        let name = set.name.trim();
+        if name.is_empty() || !seen_names.insert(name) {
+            continue;
+        }
+        let mut seen_keys: HashSet<char> = HashSet::new();
+        let keys: String = set
+            .keys
+            .chars()
+            .filter(|&ch| is_key_set_key(ch) && seen_keys.insert(ch))
+            .collect();
+        if keys.is_empty() {
+            continue;
+        }
+        normalized.push(KeySet {
+            name: name.to_string(),
+            keys,
+        });
+    }
+    normalized
+}
+
+/// Lowercase words for a key-set drill, built from the selected letters and
+/// the unlocked `background` letters. Dictionary words with a selected letter
+/// come up more often, and phonetic pseudo-words lean on `focused`. Selected
+/// capitals count as their lowercase letter here; capitals and other keys are
+/// added to the text afterwards.
+pub fn generate_key_set_words(
+    selected: &[char],
+    background: &[char],
+    focused: Option<char>,
+    dictionary: &Dictionary,
+    table: &TransitionTable,
+    word_count: usize,
+    rng: &mut SmallRng,
+) -> String {
+    let selected_letters: Vec<char> = selected
+        .iter()
+        .filter(|ch| ch.is_alphabetic())
+        .flat_map(|ch| ch.to_lowercase())
+        .collect();
+    let mut letters: Vec<char> = background
+        .iter()
+        .copied()
+        .filter(|ch| ch.is_lowercase())
+        .collect();
+    for &ch in &selected_letters {
+        if !letters.contains(&ch) {
+            letters.push(ch);
+        }
+    }
+
+    let dictionary = dictionary
+        .clone()
+        .weighted_toward(&selected_letters, SELECTED_WORD_WEIGHT);
+    let mut generator = PhoneticGenerator::new(
+        table.clone(),
+        dictionary,
+        SmallRng::from_rng(&mut *rng).unwrap(),
+        HashSet::new(),
+    );
+    let focused = focused
+        .and_then(|ch| ch.to_lowercase().next())
+        .filter(|ch| selected_letters.contains(ch));
+    generator.generate(&CharFilter::new(letters), focused, None, word_count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalization_drops_unusable_presets() {
+        let set = |name: &str, keys: &str| KeySet {
+            name: name.to_string(),
+            keys: keys.to_string(),
+        };
+        let normalized = normalize_key_sets(&[
+            set(" Pinky ", "qa qz\t"),
+            set("Pinky", "p"),
+            set("", "xyz"),
+            set("Blank", " \n"),
+        ]);
+        assert_eq!(normalized, vec![set("Pinky", "qaz")]);
+    }
+
+    #[test]
+    fn words_stay_on_the_background_and_favour_the_selection() {
+        let dictionary = Dictionary::load_for_language("en");
+        let table = TransitionTable::build_from_words(dictionary.words_list());
+        let background: Vec<char> = "etaoinshr".chars().collect();
+        let mut rng = SmallRng::seed_from_u64(7);
+        let text = generate_key_set_words(
+            &['w', 'Y', '!'],
+            &background,
+            Some('w'),
+            &dictionary,
+            &table,
+            60,
+            &mut rng,
+        );
+        let allowed = |ch: char| ch == ' ' || ch == 'w' || ch == 'y' || background.contains(&ch);
+        assert!(text.chars().all(allowed), "{text}");
+        let selected_words = text
+            .split(' ')
+            .filter(|w| w.contains('w') || w.contains('y'))
+            .count();
+        assert!(selected_words >= 20, "{selected_words}: {text}");
+    }
+}
@@ -2,12 +2,20 @@ pub mod cache;
 pub mod capitalize;
 pub mod code_patterns;
 pub mod code_syntax;
+pub mod custom_symbols;
 pub mod dictionary;
+pub mod key_set;
+pub mod number_formats;
 pub mod numbers;
+pub mod numpad;
 pub mod passage;
 pub mod phonetic;
+pub mod placement;
+pub mod problem_words;
 pub mod punctuate;
+pub mod technique;
 pub mod transition_table;
+pub mod vocabulary;
 
 use crate::engine::filter::CharFilter;
 
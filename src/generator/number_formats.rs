@@ -0,0 +1,352 @@
+use rand::Rng;
+use rand::rngs::SmallRng;
+
+use crate::l10n::text_rules::TextRules;
+
+/// Real-world numeric formats used by the Numbers branch and data entry drills.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NumberFormat {
+    /// "2024-03-17"
+    IsoDate,
+    /// "17/03/2024" or "17.03.2024", following the language.
+    LocalDate,
+    /// "14:05" or "09:30:15"
+    ClockTime,
+    /// "555 867 5309", "+49 301 234 5678" or "555-867-5309"
+    Phone,
+    /// "192.168.1.42"
+    Ipv4,
+    /// "1,299.99" or "1.299,99 €"
+    Price,
+    /// "42%" or "12,5 %"
+    Percentage,
+    /// "#3fa2c0"
+    HexColor,
+    /// "3f2a9c1e-7b4d"
+    UuidFragment,
+}
+
+impl NumberFormat {
+    pub const ALL: [NumberFormat; 9] = [
+        NumberFormat::IsoDate,
+        NumberFormat::LocalDate,
+        NumberFormat::ClockTime,
+        NumberFormat::Phone,
+        NumberFormat::Ipv4,
+        NumberFormat::Price,
+        NumberFormat::Percentage,
+        NumberFormat::HexColor,
+        NumberFormat::UuidFragment,
+    ];
+
+    /// Whether every non-digit key the format needs is unlocked.
+    pub fn is_available(self, keys: &[char], rules: &TextRules) -> bool {
+        match self {
+            NumberFormat::IsoDate | NumberFormat::UuidFragment => keys.contains(&'-'),
+            NumberFormat::LocalDate => keys.contains(&rules.date_separator),
+            NumberFormat::ClockTime => keys.contains(&':'),
+            NumberFormat::Phone => true,
+            NumberFormat::Ipv4 => keys.contains(&'.'),
+            NumberFormat::Price => keys.contains(&rules.decimal_separator),
+            NumberFormat::Percentage => keys.contains(&'%'),
+            NumberFormat::HexColor => keys.contains(&'#'),
+        }
+    }
+}
+
+/// Formats usable with `keys`; empty when no digit is unlocked.
+pub fn available_formats(keys: &[char], rules: &TextRules) -> Vec<NumberFormat> {
+    if !keys.iter().any(|ch| ch.is_ascii_digit()) {
+        return Vec::new();
+    }
+    NumberFormat::ALL
+        .into_iter()
+        .filter(|format| format.is_available(keys, rules))
+        .collect()
+}
+
+/// A single token in a random available format, or `None` when nothing fits.
+pub fn random_format_token(
+    keys: &[char],
+    focused: Option<char>,
+    rules: &TextRules,
+    rng: &mut SmallRng,
+) -> Option<String> {
+    let formats = available_formats(keys, rules);
+    if formats.is_empty() {
+        return None;
+    }
+    let format = formats[rng.gen_range(0..formats.len())];
+    Some(generate_format(format, keys, focused, rules, rng))
+}
+
+/// Data entry drill text: `word_count` tokens drawn from the available formats.
+/// Phone numbers need nothing but digits, so text is empty only without digits.
+pub fn generate_data_entry(
+    keys: &[char],
+    focused: Option<char>,
+    rules: &TextRules,
+    word_count: usize,
+    rng: &mut SmallRng,
+) -> String {
+    let formats = available_formats(keys, rules);
+    if formats.is_empty() {
+        return String::new();
+    }
+    (0..word_count.max(1))
+        .map(|_| {
+            let format = formats[rng.gen_range(0..formats.len())];
+            generate_format(format, keys, focused, rules, rng)
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Render `format` with realistic values, then swap any digit or hex letter
+/// that is not unlocked for one that is, so the shape survives early in the
+/// Numbers branch even if the values stop being plausible.
+pub fn generate_format(
+    format: NumberFormat,
+    keys: &[char],
+    focused: Option<char>,
+    rules: &TextRules,
+    rng: &mut SmallRng,
+) -> String {
+    let hex_letters: Vec<char> = ('a'..='f').filter(|ch| keys.contains(ch)).collect();
+    let raw = match format {
+        NumberFormat::IsoDate => {
+            let (day, month, year) = random_date(rng);
+            format!("{year}-{month:02}-{day:02}")
+        }
+        NumberFormat::LocalDate => {
+            let (day, month, year) = random_date(rng);
+            let sep = rules.date_separator;
+            format!("{day:02}{sep}{month:02}{sep}{year}")
+        }
+        NumberFormat::ClockTime => {
+            let hour = rng.gen_range(0..24);
+            let minute = rng.gen_range(0..60);
+            if rng.gen_bool(0.25) {
+                let second = rng.gen_range(0..60);
+                format!("{hour:02}:{minute:02}:{second:02}")
+            } else {
+                format!("{hour:02}:{minute:02}")
+            }
+        }
+        NumberFormat::Phone => random_phone(keys, rng),
+        NumberFormat::Ipv4 => {
+            let octets = [
+                [10u16, 172, 192][rng.gen_range(0..3)],
+                rng.gen_range(0..256),
+                rng.gen_range(0..256),
+                rng.gen_range(1..255),
+            ];
+            octets.map(|o| o.to_string()).join(".")
+        }
+        NumberFormat::Price => random_price(keys, rules, rng),
+        NumberFormat::Percentage => {
+            let mut value = rng.gen_range(1..100).to_string();
+            if keys.contains(&rules.decimal_separator) && rng.gen_bool(0.3) {
+                value.push(rules.decimal_separator);
+                value.push_str(&rng.gen_range(1..10).to_string());
+            }
+            if rules.spaced_punctuation {
+                format!("{value} %")
+            } else {
+                format!("{value}%")
+            }
+        }
+        NumberFormat::HexColor => format!("#{}", random_hex(6, &hex_letters, rng)),
+        NumberFormat::UuidFragment => format!(
+            "{}-{}",
+            random_hex(8, &hex_letters, rng),
+            random_hex(4, &hex_letters, rng)
+        ),
+    };
+    restrict_to_keys(&raw, keys, focused, rng)
+}
+
+fn random_date(rng: &mut SmallRng) -> (u32, u32, u32) {
+    let month = rng.gen_range(1..=12);
+    let day = rng.gen_range(1..=28);
+    let year = rng.gen_range(1990..=2030);
+    (day, month, year)
+}
+
+fn random_phone(keys: &[char], rng: &mut SmallRng) -> String {
+    let mut groups: Vec<String> = Vec::new();
+    let plus = keys.contains(&'+') && rng.gen_bool(0.4);
+    if plus {
+        groups.push(format!("+{}", rng.gen_range(1..100)));
+    }
+    groups.push(digits(3, rng));
+    groups.push(digits(3, rng));
+    groups.push(digits(4, rng));
+    let sep = if !plus && keys.contains(&'-') && rng.gen_bool(0.5) {
+        "-"
+    } else {
+        " "
+    };
+    groups.join(sep)
+}
+
+fn random_price(keys: &[char], rules: &TextRules, rng: &mut SmallRng) -> String {
+    let whole: u32 = if rng.gen_bool(0.35) {
+        rng.gen_range(1000..100_000)
+    } else {
+        rng.gen_range(1..1000)
+    };
+    let mut amount = whole.to_string();
+    if whole >= 1000
+        && let Some(group) = rules.group_separator
+        && (group == ' ' || keys.contains(&group))
+    {
+        let split = amount.len() - 3;
+        amount.insert(split, group);
+    }
+    amount.push(rules.decimal_separator);
+    amount.push_str(&format!("{:02}", rng.gen_range(0..100)));
+
+    let currency = rules.currency;
+    if currency.is_empty() || !currency.chars().all(|ch| keys.contains(&ch)) {
+        amount
+    } else if rules.currency_before {
+        format!("{currency}{amount}")
+    } else {
+        format!("{amount} {currency}")
+    }
+}
+
+fn digits(len: usize, rng: &mut SmallRng) -> String {
+    (0..len)
+        .map(|_| char::from(b'0' + rng.gen_range(0..10u8)))
+        .collect()
+}
+
+fn random_hex(len: usize, hex_letters: &[char], rng: &mut SmallRng) -> String {
+    (0..len)
+        .map(|_| {
+            if !hex_letters.is_empty() && rng.gen_bool(0.35) {
+                hex_letters[rng.gen_range(0..hex_letters.len())]
+            } else {
+                char::from(b'0' + rng.gen_range(0..10u8))
+            }
+        })
+        .collect()
+}
+
+fn restrict_to_keys(raw: &str, keys: &[char], focused: Option<char>, rng: &mut SmallRng) -> String {
+    let unlocked_digits: Vec<char> = keys
+        .iter()
+        .copied()
+        .filter(|ch| ch.is_ascii_digit())
+        .collect();
+    let focused_digit = focused.filter(|ch| unlocked_digits.contains(ch));
+    raw.chars()
+        .map(|ch| {
+            if !ch.is_ascii_digit() {
+                return ch;
+            }
+            if let Some(fd) = focused_digit
+                && rng.gen_bool(0.25)
+            {
+                return fd;
+            }
+            if unlocked_digits.contains(&ch) {
+                ch
+            } else {
+                unlocked_digits[rng.gen_range(0..unlocked_digits.len())]
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::l10n::text_rules::{DE_TEXT_RULES, EN_TEXT_RULES, NEUTRAL_TEXT_RULES};
+    use rand::SeedableRng;
+
+    fn all_digits() -> Vec<char> {
+        ('0'..='9').collect()
+    }
+
+    #[test]
+    fn formats_need_their_separators() {
+        let digits = all_digits();
+        let formats = available_formats(&digits, &EN_TEXT_RULES);
+        assert_eq!(formats, vec![NumberFormat::Phone]);
+
+        let mut keys = digits.clone();
+        keys.extend(['/', ':', '-', '.', '%', '#']);
+        assert_eq!(
+            available_formats(&keys, &EN_TEXT_RULES).len(),
+            NumberFormat::ALL.len()
+        );
+        assert!(available_formats(&['/', ':'], &EN_TEXT_RULES).is_empty());
+    }
+
+    #[test]
+    fn generated_tokens_only_use_unlocked_keys() {
+        let mut keys: Vec<char> = vec!['1', '2', '3', '4', '5'];
+        keys.extend(['/', ':', '-', '.', ',', '%', '#', 'a', 'b']);
+        for seed in 0..50 {
+            let mut rng = SmallRng::seed_from_u64(seed);
+            let text = generate_data_entry(&keys, Some('3'), &EN_TEXT_RULES, 12, &mut rng);
+            for ch in text.chars().filter(|ch| *ch != ' ') {
+                assert!(keys.contains(&ch), "unexpected {ch:?} in {text}");
+            }
+        }
+    }
+
+    #[test]
+    fn dates_and_prices_follow_language() {
+        let mut keys = all_digits();
+        keys.extend(['.', ',', '-', '/']);
+        let mut rng = SmallRng::seed_from_u64(7);
+        let de_date = generate_format(
+            NumberFormat::LocalDate,
+            &keys,
+            None,
+            &DE_TEXT_RULES,
+            &mut rng,
+        );
+        assert_eq!(de_date.matches('.').count(), 2, "{de_date}");
+        let en_date = generate_format(
+            NumberFormat::LocalDate,
+            &keys,
+            None,
+            &EN_TEXT_RULES,
+            &mut rng,
+        );
+        assert_eq!(en_date.matches('/').count(), 2, "{en_date}");
+
+        for seed in 0..20 {
+            let mut rng = SmallRng::seed_from_u64(seed);
+            let de_price =
+                generate_format(NumberFormat::Price, &keys, None, &DE_TEXT_RULES, &mut rng);
+            let (_, cents) = de_price.rsplit_once(',').expect("decimal comma");
+            assert_eq!(cents.len(), 2, "{de_price}");
+            assert!(!de_price.contains('€'), "locked currency sign: {de_price}");
+        }
+    }
+
+    #[test]
+    fn neutral_prices_use_a_bare_decimal_point() {
+        let mut keys = all_digits();
+        keys.extend(['.', ',', '€', '$']);
+        for seed in 0..20 {
+            let mut rng = SmallRng::seed_from_u64(seed);
+            let price = generate_format(
+                NumberFormat::Price,
+                &keys,
+                None,
+                &NEUTRAL_TEXT_RULES,
+                &mut rng,
+            );
+            let (whole, cents) = price.split_once('.').expect("decimal point");
+            assert!(whole.chars().all(|ch| ch.is_ascii_digit()), "{price}");
+            assert_eq!(cents.len(), 2, "{price}");
+        }
+    }
+}
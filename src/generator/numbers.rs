@@ -1,13 +1,19 @@
 use rand::Rng;
 use rand::rngs::SmallRng;
 
+use crate::generator::number_formats;
+use crate::l10n::text_rules::TextRules;
+
 /// Post-processing pass that inserts number expressions into text.
-/// Only uses digits from `unlocked_digits`.
+/// Only uses digits from `unlocked_digits`; unit and prefix words and the
+/// decimal separator come from the language's `rules`. Dates, times, prices
+/// and other formats appear once their separators are in `unlocked_keys`.
 pub fn apply_numbers(
     text: &str,
     unlocked_digits: &[char],
-    has_dot: bool,
+    unlocked_keys: &[char],
     focused: Option<char>,
+    rules: &TextRules,
     rng: &mut SmallRng,
 ) -> String {
     if unlocked_digits.is_empty() {
@@ -23,7 +29,8 @@ pub fn apply_numbers(
 
     for word in &words {
         if rng.gen_bool(base_prob) {
-            let expr = generate_number_expr(unlocked_digits, has_dot, focused_digit, rng);
+            let expr =
+                generate_number_expr(unlocked_digits, unlocked_keys, focused_digit, rules, rng);
             result.push(expr);
         } else {
             result.push(word.to_string());
@@ -35,41 +42,54 @@ pub fn apply_numbers(
 
 fn generate_number_expr(
     digits: &[char],
-    has_dot: bool,
+    unlocked_keys: &[char],
     focused_digit: Option<char>,
+    rules: &TextRules,
     rng: &mut SmallRng,
 ) -> String {
-    // Determine how many patterns are available (version pattern needs dot)
-    let max_pattern = if has_dot { 5 } else { 4 };
+    if rng.gen_bool(0.3) {
+        let format_keys: Vec<char> = unlocked_keys
+            .iter()
+            .copied()
+            .filter(|ch| !ch.is_ascii_digit() || digits.contains(ch))
+            .collect();
+        if let Some(token) =
+            number_formats::random_format_token(&format_keys, focused_digit, rules, rng)
+        {
+            return token;
+        }
+    }
+
+    let has_decimal_separator = unlocked_keys.contains(&rules.decimal_separator);
+    // Determine how many patterns are available (decimal pattern needs the separator)
+    let max_pattern = if has_decimal_separator { 5 } else { 4 };
     let pattern = rng.gen_range(0..max_pattern);
     let num = match pattern {
-        0 => {
-            // Simple count: "3" or "42"
-            random_number(digits, 1, 3, focused_digit, rng)
-        }
-        1 => {
-            // Measurement: "7 miles" or "42 items"
+        1 if !rules.units.is_empty() => {
+            // Measurement: "7 miles" or "42 Tage"
             let num = random_number(digits, 1, 2, focused_digit, rng);
-            let units = ["items", "miles", "days", "lines", "times", "parts"];
-            let unit = units[rng.gen_range(0..units.len())];
+            let unit = rules.units[rng.gen_range(0..rules.units.len())];
             return format!("{num} {unit}");
         }
         2 => {
             // Year-like: "2024"
             random_number(digits, 4, 4, focused_digit, rng)
         }
-        3 => {
-            // ID: "room 42" or "page 7"
-            let prefixes = ["room", "page", "step", "item", "line", "port"];
-            let prefix = prefixes[rng.gen_range(0..prefixes.len())];
+        3 if !rules.id_prefixes.is_empty() => {
+            // ID: "room 42" or "Seite 7"
+            let prefix = rules.id_prefixes[rng.gen_range(0..rules.id_prefixes.len())];
             let num = random_number(digits, 1, 3, focused_digit, rng);
             return format!("{prefix} {num}");
         }
-        _ => {
-            // Version-like: "3.14" or "2.0" (only when dot is available)
+        4 => {
+            // Decimal: "3.14" or "2,5" (only when the separator is available)
             let major = random_number(digits, 1, 1, focused_digit, rng);
             let minor = random_number(digits, 1, 2, focused_digit, rng);
-            return format!("{major}.{minor}");
+            return format!("{major}{}{minor}", rules.decimal_separator);
+        }
+        _ => {
+            // Simple count: "3" or "42"
+            random_number(digits, 1, 3, focused_digit, rng)
         }
     };
     num
@@ -99,12 +119,13 @@ fn random_number(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::l10n::text_rules::{DE_TEXT_RULES, EN_TEXT_RULES};
     use rand::SeedableRng;
 
     #[test]
     fn test_no_numbers_when_empty() {
         let mut rng = SmallRng::seed_from_u64(42);
-        let result = apply_numbers("hello world", &[], false, None, &mut rng);
+        let result = apply_numbers("hello world", &[], &[], None, &EN_TEXT_RULES, &mut rng);
         assert_eq!(result, "hello world");
     }
 
@@ -113,7 +134,7 @@ mod tests {
         let mut rng = SmallRng::seed_from_u64(42);
         let digits = ['1', '2', '3'];
         let text = "a b c d e f g h i j k l m n o p q r s t";
-        let result = apply_numbers(text, &digits, false, None, &mut rng);
+        let result = apply_numbers(text, &digits, &digits, None, &EN_TEXT_RULES, &mut rng);
         for ch in result.chars() {
             if ch.is_ascii_digit() {
                 assert!(digits.contains(&ch), "Unexpected digit {ch} in: {result}");
@@ -126,10 +147,61 @@ mod tests {
         let mut rng = SmallRng::seed_from_u64(42);
         let digits = ['1', '2', '3', '4', '5'];
         let text = "a b c d e f g h i j k l m n o p q r s t";
-        let result = apply_numbers(text, &digits, false, None, &mut rng);
+        let result = apply_numbers(text, &digits, &digits, None, &EN_TEXT_RULES, &mut rng);
         assert!(
             !result.contains('.'),
-            "Should not contain dot when has_dot=false: {result}"
+            "Should not contain dot while it is locked: {result}"
         );
     }
+
+    #[test]
+    fn test_numbers_include_formats_once_separators_unlock() {
+        let digits: Vec<char> = ('0'..='9').collect();
+        let mut keys = digits.clone();
+        keys.extend([':', '/']);
+        let text = "a b c d e f g h i j k l m n o p q r s t";
+        let mut saw_time = false;
+        let mut saw_date = false;
+        for seed in 0..30 {
+            let mut rng = SmallRng::seed_from_u64(seed);
+            let result = apply_numbers(text, &digits, &keys, Some('7'), &EN_TEXT_RULES, &mut rng);
+            saw_time |= result.split(' ').any(|w| w.len() >= 5 && w.contains(':'));
+            saw_date |= result.split(' ').any(|w| w.matches('/').count() == 2);
+        }
+        assert!(saw_time);
+        assert!(saw_date);
+    }
+
+    #[test]
+    fn test_numbers_use_language_units_and_decimal_comma() {
+        let digits = ['1', '2', '3', '4', '5'];
+        let keys = ['1', '2', '3', '4', '5', ','];
+        let text = "a b c d e f g h i j k l m n o p q r s t";
+        let mut saw_unit = false;
+        let mut saw_decimal = false;
+        for seed in 0..50 {
+            let mut rng = SmallRng::seed_from_u64(seed);
+            let result = apply_numbers(text, &digits, &keys, None, &DE_TEXT_RULES, &mut rng);
+            assert!(
+                !result.contains('.'),
+                "German decimals use a comma: {result}"
+            );
+            for unit in ["items", "miles", "room", "page"] {
+                assert!(
+                    !result.contains(unit),
+                    "English word in German drill: {result}"
+                );
+            }
+            saw_unit |= DE_TEXT_RULES
+                .units
+                .iter()
+                .chain(DE_TEXT_RULES.id_prefixes)
+                .any(|w| result.split(' ').any(|word| word == *w));
+            saw_decimal |= result
+                .split(' ')
+                .any(|w| w.contains(',') && w.chars().all(|c| c.is_ascii_digit() || c == ','));
+        }
+        assert!(saw_unit);
+        assert!(saw_decimal);
+    }
 }
@@ -0,0 +1,95 @@
+use rand::Rng;
+use rand::rngs::SmallRng;
+
+use crate::keyboard::display::ENTER;
+
+/// Numpad drill text: one entry per line, as when keying figures into a
+/// spreadsheet or adding machine. Entries mix plain amounts, decimals and
+/// short sums using only keys on the keypad; Enter separates them.
+pub fn generate_numpad(focused: Option<char>, line_count: usize, rng: &mut SmallRng) -> String {
+    let focused_digit = focused.filter(|ch| ch.is_ascii_digit());
+    let focused_operator = focused.filter(|ch| matches!(ch, '+' | '-' | '*' | '/'));
+    // Focused operators and the decimal point need entries that contain them.
+    let skip_plain = focused_operator.is_some() || focused == Some('.');
+    let entries: Vec<String> = (0..line_count.max(1))
+        .map(|_| {
+            let entry = match rng.gen_range(0..6) {
+                0 | 1 if !skip_plain => amount(focused_digit, rng),
+                2 if focused_operator.is_none() => decimal(focused_digit, rng),
+                _ => {
+                    let op = focused_operator
+                        .filter(|_| rng.gen_bool(0.7))
+                        .unwrap_or_else(|| ['+', '-', '*', '/'][rng.gen_range(0..4)]);
+                    let lhs = amount(focused_digit, rng);
+                    let rhs = if op == '+' || op == '-' {
+                        decimal(focused_digit, rng)
+                    } else {
+                        digits(rng.gen_range(1..=2), focused_digit, rng)
+                    };
+                    format!("{lhs}{op}{rhs}")
+                }
+            };
+            if focused == Some('.') && !entry.contains('.') {
+                format!("{entry}.{}", digits(2, focused_digit, rng))
+            } else {
+                entry
+            }
+        })
+        .collect();
+    entries.join(&ENTER.to_string())
+}
+
+fn amount(focused_digit: Option<char>, rng: &mut SmallRng) -> String {
+    let len = rng.gen_range(1..=5);
+    let mut out = digits(len, focused_digit, rng);
+    // Leading zeros read oddly in amounts.
+    if len > 1 && out.starts_with('0') {
+        out.replace_range(0..1, &rng.gen_range(1..10).to_string());
+    }
+    out
+}
+
+fn decimal(focused_digit: Option<char>, rng: &mut SmallRng) -> String {
+    format!(
+        "{}.{}",
+        amount(focused_digit, rng),
+        digits(2, focused_digit, rng)
+    )
+}
+
+fn digits(len: usize, focused_digit: Option<char>, rng: &mut SmallRng) -> String {
+    (0..len)
+        .map(|_| match focused_digit {
+            Some(fd) if rng.gen_bool(0.35) => fd,
+            _ => char::from(b'0' + rng.gen_range(0..10u8)),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keyboard::numpad::numpad_chars;
+    use rand::SeedableRng;
+
+    #[test]
+    fn numpad_text_only_uses_keypad_keys() {
+        let keys: Vec<char> = numpad_chars().collect();
+        for seed in 0..30 {
+            let mut rng = SmallRng::seed_from_u64(seed);
+            let text = generate_numpad(None, 12, &mut rng);
+            assert_eq!(text.lines().count(), 12, "{text}");
+            assert!(!text.ends_with(ENTER));
+            for ch in text.chars() {
+                assert!(keys.contains(&ch), "unexpected {ch:?} in {text}");
+            }
+        }
+    }
+
+    #[test]
+    fn focused_operator_shows_up() {
+        let mut rng = SmallRng::seed_from_u64(3);
+        let text = generate_numpad(Some('*'), 10, &mut rng);
+        assert!(text.matches('*').count() >= 5, "{text}");
+    }
+}
@@ -18,6 +18,7 @@ pub struct PhoneticGenerator {
     dictionary: Dictionary,
     rng: SmallRng,
     cross_drill_history: HashSet<String>,
+    focused_trigram: Option<[char; 3]>,
     #[cfg(test)]
     pub dict_picks: usize,
 }
@@ -34,11 +35,19 @@ impl PhoneticGenerator {
             dictionary,
             rng,
             cross_drill_history,
+            focused_trigram: None,
             #[cfg(test)]
             dict_picks: 0,
         }
     }
 
+    /// Also put dictionary words containing this trigram in the focused
+    /// n-gram tier, next to those containing the focused bigram.
+    pub fn with_focused_trigram(mut self, trigram: Option<[char; 3]>) -> Self {
+        self.focused_trigram = trigram;
+        self
+    }
+
     fn pick_weighted_from(
         rng: &mut SmallRng,
         options: &[(char, f64)],
@@ -429,6 +438,7 @@ impl TextGenerator for PhoneticGenerator {
 
         // Pre-categorize words into tiers for dictionary picks
         let bigram_str = focused_bigram.map(|b| format!("{}{}", b[0], b[1]));
+        let trigram_str = self.focused_trigram.map(|t| t.iter().collect::<String>());
         let focus_char_lower = focused_char.filter(|ch| ch.is_lowercase());
 
         let (bigram_indices, char_indices, other_indices) = if use_dict {
@@ -436,13 +446,20 @@ impl TextGenerator for PhoneticGenerator {
             let mut ci = Vec::new();
             let mut oi = Vec::new();
             for (i, w) in matching_words.iter().enumerate() {
-                if bigram_str.as_ref().is_some_and(|b| w.contains(b.as_str())) {
-                    bi.push(i);
+                let in_ngram_tier = [&trigram_str, &bigram_str]
+                    .into_iter()
+                    .flatten()
+                    .any(|ngram| w.contains(ngram.as_str()));
+                let tier = if in_ngram_tier {
+                    &mut bi
                 } else if focus_char_lower.is_some_and(|ch| w.contains(ch)) {
-                    ci.push(i);
+                    &mut ci
                 } else {
-                    oi.push(i);
-                }
+                    &mut oi
+                };
+                // Weighted custom words take several slots in their tier.
+                let weight = self.dictionary.weight(w) as usize;
+                tier.extend(std::iter::repeat_n(i, weight));
             }
             (bi, ci, oi)
         } else {
@@ -528,6 +545,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn cyrillic_transitions_stay_within_unlocked_letters() {
+        let dictionary = Dictionary::load_for_language("ru");
+        let table = TransitionTable::build_from_words(dictionary.words_list());
+        let filter = CharFilter::new("оеаинтсрвл".chars().collect());
+
+        let mut generator = PhoneticGenerator::new(
+            table,
+            dictionary,
+            SmallRng::seed_from_u64(7),
+            HashSet::new(),
+        );
+        let text = generator.generate(&filter, Some('в'), None, 60);
+
+        assert!(!text.is_empty());
+        assert!(
+            text.chars().all(|ch| ch == ' ' || filter.is_allowed(ch)),
+            "unexpected letters in {text:?}"
+        );
+        assert!(text.contains('в'));
+    }
+
     #[test]
     fn test_phonetic_bigram_focus_increases_bigram_words() {
         let dictionary = Dictionary::load_for_language("en");
@@ -564,6 +603,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn trigram_focus_increases_trigram_words() {
+        let dictionary = Dictionary::load_for_language("en");
+        let table = TransitionTable::build_from_words(dictionary.words_list());
+        let filter = CharFilter::new(('a'..='z').collect());
+        let count_ing = |text: &str| {
+            text.split_whitespace()
+                .filter(|w| w.contains("ing"))
+                .count()
+        };
+
+        let mut trigram_gen = PhoneticGenerator::new(
+            table.clone(),
+            Dictionary::load_for_language("en"),
+            SmallRng::seed_from_u64(42),
+            HashSet::new(),
+        )
+        .with_focused_trigram(Some(['i', 'n', 'g']));
+        let trigram_text = trigram_gen.generate(&filter, None, None, 1200);
+
+        let mut baseline_gen = PhoneticGenerator::new(
+            table,
+            Dictionary::load_for_language("en"),
+            SmallRng::seed_from_u64(42),
+            HashSet::new(),
+        );
+        let baseline_text = baseline_gen.generate(&filter, None, None, 1200);
+
+        assert!(
+            count_ing(&trigram_text) > count_ing(&baseline_text),
+            "trigram={}, baseline={}",
+            count_ing(&trigram_text),
+            count_ing(&baseline_text)
+        );
+    }
+
     #[test]
     fn test_phonetic_dual_focus_no_excessive_repeats() {
         let dictionary = Dictionary::load_for_language("en");
@@ -0,0 +1,171 @@
+use std::collections::HashMap;
+
+use rand::Rng;
+use rand::rngs::SmallRng;
+use rand::seq::SliceRandom;
+
+use crate::engine::filter::CharFilter;
+use crate::engine::skill_tree::{BranchId, SkillTree};
+use crate::generator::dictionary::Dictionary;
+
+/// Occurrences of every key in the sample, enough for a first timing estimate
+/// without making the test a chore.
+pub const SAMPLES_PER_KEY: usize = 3;
+const TOKENS_PER_LINE: usize = 8;
+
+/// Placement test text: a calibrated sample in which every key of every
+/// skill-tree branch appears at least `SAMPLES_PER_KEY` times. Symbols are
+/// wrapped around dictionary words the way they appear in prose and code,
+/// capitals start words, and lines alternate with tab indentation so Enter and
+/// Tab are sampled too.
+pub fn generate_placement(tree: &SkillTree, dictionary: &Dictionary, rng: &mut SmallRng) -> String {
+    let letters = tree.primary_letters().to_vec();
+    let filter = CharFilter::new(letters.clone());
+    let words: Vec<&str> = dictionary.find_matching(&filter, None);
+    let mut counts: HashMap<char, usize> = HashMap::new();
+    let mut tokens: Vec<String> = Vec::new();
+    let mut push = |token: String, counts: &mut HashMap<char, usize>| {
+        for ch in token.chars() {
+            *counts.entry(ch).or_default() += 1;
+        }
+        tokens.push(token);
+    };
+
+    let mut symbol_keys: Vec<char> = Vec::new();
+    for id in tree.branch_ids() {
+        if matches!(id, BranchId::Lowercase | BranchId::Whitespace) {
+            continue;
+        }
+        for key in tree.branch_keys(id) {
+            if !symbol_keys.contains(&key) {
+                symbol_keys.push(key);
+            }
+        }
+    }
+    for key in symbol_keys {
+        while counts.get(&key).copied().unwrap_or(0) < SAMPLES_PER_KEY {
+            let token = key_token(key, &words, &letters, rng);
+            push(token, &mut counts);
+        }
+    }
+    // Top up letters that the symbol tokens did not already cover.
+    for &letter in &letters {
+        let containing: Vec<&str> = words
+            .iter()
+            .copied()
+            .filter(|w| w.contains(letter))
+            .collect();
+        while counts.get(&letter).copied().unwrap_or(0) < SAMPLES_PER_KEY {
+            let token = match containing.choose(rng) {
+                Some(word) => word.to_string(),
+                None => letter.to_string().repeat(3),
+            };
+            push(token, &mut counts);
+        }
+    }
+
+    tokens.shuffle(rng);
+    // Enough lines for every tab-indented line to follow an Enter.
+    let min_lines = 2 * SAMPLES_PER_KEY + 1;
+    let per_line = (tokens.len() / min_lines).clamp(1, TOKENS_PER_LINE);
+    tokens
+        .chunks(per_line)
+        .enumerate()
+        .map(|(i, line)| {
+            let indent = if i % 2 == 1 { "\t" } else { "" };
+            format!("{indent}{}", line.join(" "))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// One token that exercises `key`.
+fn key_token(key: char, words: &[&str], letters: &[char], rng: &mut SmallRng) -> String {
+    if key.is_ascii_digit() {
+        return format!("{key}{}{}", rng_digit(rng), rng_digit(rng));
+    }
+    if key == '%' {
+        return format!("{}%", rng.gen_range(1..100));
+    }
+    if key.is_uppercase() {
+        let lower: String = key.to_lowercase().collect();
+        let starting: Vec<&str> = words
+            .iter()
+            .copied()
+            .filter(|w| w.starts_with(lower.as_str()))
+            .collect();
+        let rest = match starting.choose(rng) {
+            Some(w) => w[lower.len()..].to_string(),
+            None => random_word(words, letters, rng),
+        };
+        return format!("{key}{rest}");
+    }
+    let mut word = || random_word(words, letters, rng);
+    match key {
+        '\'' => format!("'{}'", word()),
+        '"' => format!("\"{}\"", word()),
+        '(' | ')' => format!("({})", word()),
+        '{' | '}' => format!("{{{}}}", word()),
+        '[' | ']' => format!("[{}]", word()),
+        '<' | '>' => format!("<{}>", word()),
+        '`' => format!("`{}`", word()),
+        '-' | '/' | '_' | '\\' => format!("{}{key}{}", word(), word()),
+        '=' | '+' | '*' | '&' | '|' | '^' => format!("{} {key} {}", word(), word()),
+        '~' | '@' | '#' | '$' => format!("{key}{}", word()),
+        _ => format!("{}{key}", word()),
+    }
+}
+
+fn random_word(words: &[&str], letters: &[char], rng: &mut SmallRng) -> String {
+    match words.choose(rng) {
+        Some(word) => word.to_string(),
+        None => letters.iter().take(4).collect(),
+    }
+}
+
+fn rng_digit(rng: &mut SmallRng) -> char {
+    char::from(b'0' + rng.gen_range(0..10u8))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::skill_tree::{ALL_BRANCHES, SkillTreeProgress};
+    use rand::SeedableRng;
+
+    #[test]
+    fn placement_covers_every_branch_key() {
+        let tree = SkillTree::default();
+        let dictionary = Dictionary::load_for_language("en");
+        let mut rng = SmallRng::seed_from_u64(11);
+        let text = generate_placement(&tree, &dictionary, &mut rng);
+        for branch in ALL_BRANCHES {
+            let keys: Vec<char> = if branch.id == BranchId::Lowercase {
+                tree.primary_letters().to_vec()
+            } else {
+                tree.branch_keys(branch.id).collect()
+            };
+            for key in keys {
+                let count = text.chars().filter(|&ch| ch == key).count();
+                assert!(count >= SAMPLES_PER_KEY, "{key:?} appears {count} times");
+            }
+        }
+        assert!(!text.ends_with('\n'));
+        assert!(!text.contains("\n\n"));
+    }
+
+    #[test]
+    fn placement_uses_the_tree_alphabet() {
+        let tree = SkillTree::new_with_primary_sequence(
+            SkillTreeProgress::default(),
+            crate::l10n::language_pack::find_language_pack("ru")
+                .unwrap()
+                .primary_letter_sequence,
+        );
+        let dictionary = Dictionary::load_for_language("ru");
+        let mut rng = SmallRng::seed_from_u64(5);
+        let text = generate_placement(&tree, &dictionary, &mut rng);
+        assert!(text.contains('Д'), "{text}");
+        assert!(!text.chars().any(|ch| ch.is_ascii_lowercase()), "{text}");
+    }
+}
@@ -0,0 +1,81 @@
+use rand::Rng;
+use rand::rngs::SmallRng;
+use rand::seq::SliceRandom;
+
+use crate::generator::dictionary::Dictionary;
+
+/// How many times each problem word is repeated per round.
+const REPEATS_PER_WORD: usize = 3;
+/// Dictionary neighbours mixed in per problem word.
+const NEIGHBOURS_PER_WORD: usize = 2;
+
+/// Drill text built from the user's problem words. Each round repeats every
+/// word a few times and mixes in morphological neighbours from `dictionary`
+/// (e.g. `definite` next to `definitely`), so the drill practises the shared
+/// pattern rather than one memorized sequence. The same word never appears
+/// twice in a row.
+pub fn generate_problem_word_drill(
+    words: &[String],
+    dictionary: Option<&Dictionary>,
+    word_count: usize,
+    rng: &mut SmallRng,
+) -> String {
+    if words.is_empty() {
+        return String::new();
+    }
+    let mut pool: Vec<&str> = Vec::new();
+    for word in words {
+        pool.extend(std::iter::repeat_n(word.as_str(), REPEATS_PER_WORD));
+        if let Some(dictionary) = dictionary {
+            pool.extend(dictionary.morphological_neighbours(word, NEIGHBOURS_PER_WORD));
+        }
+    }
+
+    let mut out: Vec<&str> = Vec::with_capacity(word_count);
+    while out.len() < word_count.max(1) {
+        pool.shuffle(rng);
+        for &word in &pool {
+            if out.len() >= word_count.max(1) {
+                break;
+            }
+            if out.last() == Some(&word) {
+                // Pick any other word instead of typing the same one back to back.
+                let others: Vec<&str> = pool.iter().copied().filter(|w| *w != word).collect();
+                if others.is_empty() {
+                    out.push(word);
+                } else {
+                    out.push(others[rng.gen_range(0..others.len())]);
+                }
+                continue;
+            }
+            out.push(word);
+        }
+    }
+    out.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    #[test]
+    fn drill_repeats_problem_words_with_neighbours() {
+        let dictionary = Dictionary::load_for_language("en");
+        let words = vec!["definitely".to_string(), "rhythm".to_string()];
+        let mut rng = SmallRng::seed_from_u64(7);
+        let text = generate_problem_word_drill(&words, Some(&dictionary), 40, &mut rng);
+        let tokens: Vec<&str> = text.split(' ').collect();
+        assert_eq!(tokens.len(), 40);
+        assert!(tokens.iter().filter(|w| **w == "definitely").count() >= 5);
+        assert!(tokens.contains(&"rhythm"));
+        assert!(tokens.contains(&"definite") || tokens.contains(&"definition"));
+        assert!(tokens.windows(2).all(|pair| pair[0] != pair[1]), "{text}");
+    }
+
+    #[test]
+    fn no_words_gives_empty_text() {
+        let mut rng = SmallRng::seed_from_u64(1);
+        assert_eq!(generate_problem_word_drill(&[], None, 10, &mut rng), "");
+    }
+}
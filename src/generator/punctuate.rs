@@ -1,12 +1,19 @@
 use rand::Rng;
 use rand::rngs::SmallRng;
 
+use crate::l10n::text_rules::{Apostrophes, TextRules};
+
 /// Post-processing pass that inserts punctuation into generated text.
-/// Only uses punctuation chars from `unlocked_punct`.
+/// Only uses punctuation chars from `unlocked_punct`; quote marks, mark
+/// spacing, inverted marks and apostrophe usage follow the language's `rules`.
+/// The `"` key unlocks quoting. The language's own quote and inverted marks are
+/// only used once they are in `unlocked_punct` too; until then quotes fall
+/// back to `"` and questions and exclamations go without an inverted mark.
 pub fn apply_punctuation(
     text: &str,
     unlocked_punct: &[char],
     focused: Option<char>,
+    rules: &TextRules,
     rng: &mut SmallRng,
 ) -> String {
     if unlocked_punct.is_empty() {
@@ -32,10 +39,16 @@ pub fn apply_punctuation(
     let has_exclaim = unlocked_punct.contains(&'!');
     let has_open_paren = unlocked_punct.contains(&'(');
     let has_close_paren = unlocked_punct.contains(&')');
+    let quotes = QuoteMarks::new(rules, unlocked_punct);
+    let inverted_question = rules.inverted_marks && unlocked_punct.contains(&'¿');
+    let inverted_exclaim = rules.inverted_marks && unlocked_punct.contains(&'¡');
 
-    let mut result = Vec::new();
+    let mut result: Vec<String> = Vec::new();
     let mut words_since_period = 0;
     let mut words_since_comma = 0;
+    let mut sentence_start = 0;
+    let mut quote_open = false;
+    let mut inverted_mark = None;
 
     for (i, word) in words.iter().enumerate() {
         let mut w = word.to_string();
@@ -47,7 +60,7 @@ pub fn apply_punctuation(
             0.08
         };
         if has_apostrophe && w.len() >= 3 && rng.gen_bool(apostrophe_prob) {
-            w = make_contraction(&w, rng);
+            w = make_contraction(&w, rules.apostrophes, rng);
         }
 
         // Compound words with dash (~5% of words, boosted if dash is focused)
@@ -77,9 +90,11 @@ pub fn apply_punctuation(
                 0.10
             };
             if has_question && rng.gen_bool(q_prob) {
-                w.push('?');
+                push_mark(&mut w, '?', rules);
+                inverted_mark = inverted_question.then_some('¿');
             } else if has_exclaim && rng.gen_bool(excl_prob) {
-                w.push('!');
+                push_mark(&mut w, '!', rules);
+                inverted_mark = inverted_exclaim.then_some('¡');
             } else if has_period {
                 w.push('.');
             }
@@ -113,7 +128,7 @@ pub fn apply_punctuation(
                 && rng.gen_bool(semi_prob)
                 && i < words.len() - 1
             {
-                w.push(';');
+                push_mark(&mut w, ';', rules);
                 words_since_comma = 0;
             }
 
@@ -124,7 +139,7 @@ pub fn apply_punctuation(
                 0.03
             };
             if has_colon && rng.gen_bool(colon_prob) && i < words.len() - 1 {
-                w.push(':');
+                push_mark(&mut w, ':', rules);
             }
         }
 
@@ -135,7 +150,12 @@ pub fn apply_punctuation(
             0.04
         };
         if has_quote && rng.gen_bool(quote_prob) && i + 2 < words.len() {
-            w = format!("\"{w}");
+            if quote_open {
+                quotes.close(&mut w);
+            } else {
+                w = quotes.open(&w);
+            }
+            quote_open = !quote_open;
         }
 
         // Parenthetical asides (rare, boosted if focused)
@@ -149,26 +169,31 @@ pub fn apply_punctuation(
         }
 
         result.push(w);
+
+        // Spanish questions and exclamations also open with an inverted mark.
+        if let Some(mark) = inverted_mark.take() {
+            result[sentence_start].insert(0, mark);
+        }
+        if words_since_period == 0 {
+            sentence_start = result.len();
+        }
     }
 
     // End with period if we have it
     if has_period {
         if let Some(last) = result.last_mut() {
             let last_char = last.chars().last();
-            if !matches!(last_char, Some('.' | '?' | '!' | '"' | ')')) {
+            if !matches!(last_char, Some('.' | '?' | '!' | ')')) && last_char != Some(quotes.close)
+            {
                 last.push('.');
             }
         }
     }
 
     // Close any open quotes/parens
-    let mut open_quotes = 0i32;
     let mut open_parens = 0i32;
     for w in &result {
         for ch in w.chars() {
-            if ch == '"' {
-                open_quotes += 1;
-            }
             if ch == '(' {
                 open_parens += 1;
             }
@@ -178,13 +203,13 @@ pub fn apply_punctuation(
         }
     }
     if let Some(last) = result.last_mut() {
-        if open_quotes % 2 != 0 && has_quote {
+        if quote_open {
             // Remove trailing period to put quote after
             let had_period = last.ends_with('.');
             if had_period {
                 last.pop();
             }
-            last.push('"');
+            quotes.close(last);
             if had_period {
                 last.push('.');
             }
@@ -204,41 +229,115 @@ pub fn apply_punctuation(
     result.join(" ")
 }
 
-fn make_contraction(word: &str, rng: &mut SmallRng) -> String {
-    // Simple contractions based on common patterns
-    let contractions: &[(&str, &str)] = &[
-        ("not", "n't"),
-        ("will", "'ll"),
-        ("would", "'d"),
-        ("have", "'ve"),
-        ("are", "'re"),
-        ("is", "'s"),
-    ];
-
-    for &(base, suffix) in contractions {
-        if word == base {
-            // For "not" -> "don't", "can't", etc. - just return the contraction form
-            return format!("{word}{suffix}");
+/// Append a clause or sentence mark, spaced off the word where the language asks for it.
+fn push_mark(word: &mut String, mark: char, rules: &TextRules) {
+    if rules.spaced_punctuation && matches!(mark, ';' | ':' | '!' | '?') {
+        word.push(' ');
+    }
+    word.push(mark);
+}
+
+/// The quote marks a drill can use: the language's own pair when both keys
+/// are unlocked, plain `"` otherwise.
+struct QuoteMarks {
+    open: char,
+    close: char,
+    spaced: bool,
+}
+
+impl QuoteMarks {
+    fn new(rules: &TextRules, unlocked_punct: &[char]) -> Self {
+        if unlocked_punct.contains(&rules.open_quote) && unlocked_punct.contains(&rules.close_quote)
+        {
+            Self {
+                open: rules.open_quote,
+                close: rules.close_quote,
+                spaced: rules.spaced_punctuation,
+            }
+        } else {
+            Self {
+                open: '"',
+                close: '"',
+                spaced: false,
+            }
+        }
+    }
+
+    fn open(&self, word: &str) -> String {
+        if self.spaced {
+            format!("{} {word}", self.open)
+        } else {
+            format!("{}{word}", self.open)
         }
     }
 
-    // Generic: ~chance to add 's
-    if rng.gen_bool(0.5) {
-        format!("{word}'s")
-    } else {
-        word.to_string()
+    fn close(&self, word: &mut String) {
+        if self.spaced {
+            word.push(' ');
+        }
+        word.push(self.close);
+    }
+}
+
+fn make_contraction(word: &str, apostrophes: Apostrophes, rng: &mut SmallRng) -> String {
+    match apostrophes {
+        Apostrophes::None => word.to_string(),
+        Apostrophes::Suffix {
+            contractions,
+            generic,
+        } => {
+            for &(base, suffix) in contractions {
+                if word == base {
+                    // For "not" -> "don't", "can't", etc. - just return the contraction form
+                    return format!("{word}{suffix}");
+                }
+            }
+
+            // Generic: ~chance to add 's
+            match generic {
+                Some(suffix) if rng.gen_bool(0.5) => format!("{word}{suffix}"),
+                _ => word.to_string(),
+            }
+        }
+        Apostrophes::VowelPlural => {
+            if word.ends_with(['a', 'i', 'o', 'u', 'y']) {
+                format!("{word}'s")
+            } else {
+                word.to_string()
+            }
+        }
+        Apostrophes::Elision(prefixes) => {
+            let starts_with_vowel = word.chars().next().is_some_and(|ch| {
+                matches!(
+                    ch,
+                    'a' | 'e' | 'i' | 'o' | 'u' | 'h' | 'é' | 'è' | 'ê' | 'à' | 'â' | 'î' | 'ô'
+                )
+            });
+            if starts_with_vowel && !prefixes.is_empty() {
+                format!("{}{word}", prefixes[rng.gen_range(0..prefixes.len())])
+            } else {
+                word.to_string()
+            }
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::l10n::text_rules::{DE_TEXT_RULES, EN_TEXT_RULES, ES_TEXT_RULES, FR_TEXT_RULES};
     use rand::SeedableRng;
 
+    const ALL_PUNCT: [char; 11] = ['.', ',', '\'', ';', ':', '"', '-', '?', '!', '(', ')'];
+
+    fn long_text() -> String {
+        (0..60).map(|_| "wort").collect::<Vec<_>>().join(" ")
+    }
+
     #[test]
     fn test_no_punct_when_empty() {
         let mut rng = SmallRng::seed_from_u64(42);
-        let result = apply_punctuation("hello world", &[], None, &mut rng);
+        let result = apply_punctuation("hello world", &[], None, &EN_TEXT_RULES, &mut rng);
         assert_eq!(result, "hello world");
     }
 
@@ -246,7 +345,7 @@ mod tests {
     fn test_adds_period_at_end() {
         let mut rng = SmallRng::seed_from_u64(42);
         let text = "one two three four five six seven eight nine ten";
-        let result = apply_punctuation(text, &['.'], None, &mut rng);
+        let result = apply_punctuation(text, &['.'], None, &EN_TEXT_RULES, &mut rng);
         assert!(result.ends_with('.'));
     }
 
@@ -255,9 +354,108 @@ mod tests {
         let mut rng = SmallRng::seed_from_u64(42);
         let words: Vec<&str> = (0..20).map(|_| "word").collect();
         let text = words.join(" ");
-        let result = apply_punctuation(&text, &['.', ','], None, &mut rng);
+        let result = apply_punctuation(&text, &['.', ','], None, &EN_TEXT_RULES, &mut rng);
         // Should have at least one period somewhere in the middle
         let period_count = result.chars().filter(|&c| c == '.').count();
         assert!(period_count >= 1, "Expected periods in: {result}");
     }
+
+    #[test]
+    fn test_german_quotes_are_balanced_low_high_pairs() {
+        for seed in 0..40 {
+            let mut rng = SmallRng::seed_from_u64(seed);
+            let mut unlocked = ALL_PUNCT.to_vec();
+            unlocked.extend(['„', '“']);
+            let result =
+                apply_punctuation(&long_text(), &unlocked, Some('"'), &DE_TEXT_RULES, &mut rng);
+            assert!(
+                !result.contains('"'),
+                "ASCII quote in German text: {result}"
+            );
+            let opens = result.matches('„').count();
+            let closes = result.matches('“').count();
+            assert_eq!(opens, closes, "Unbalanced quotes: {result}");
+        }
+    }
+
+    #[test]
+    fn test_french_spaces_high_punctuation() {
+        for seed in 0..40 {
+            let mut rng = SmallRng::seed_from_u64(seed);
+            let mut unlocked = ALL_PUNCT.to_vec();
+            unlocked.extend(['«', '»']);
+            let result =
+                apply_punctuation(&long_text(), &unlocked, Some('?'), &FR_TEXT_RULES, &mut rng);
+            for (i, ch) in result.char_indices() {
+                if matches!(ch, ';' | ':' | '!' | '?' | '»') {
+                    assert_eq!(&result[i - 1..i], " ", "Unspaced {ch} in: {result}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_spanish_questions_open_with_inverted_mark() {
+        let mut saw_question = false;
+        for seed in 0..40 {
+            let mut rng = SmallRng::seed_from_u64(seed);
+            let result = apply_punctuation(
+                &long_text(),
+                &['.', '?', '!', '¿', '¡'],
+                Some('?'),
+                &ES_TEXT_RULES,
+                &mut rng,
+            );
+            assert_eq!(
+                result.matches('¿').count(),
+                result.matches('?').count(),
+                "Unpaired question marks: {result}"
+            );
+            assert_eq!(result.matches('¡').count(), result.matches('!').count());
+            saw_question |= result.contains('?');
+        }
+        assert!(saw_question);
+    }
+
+    #[test]
+    fn test_locked_language_marks_are_not_used() {
+        for seed in 0..40 {
+            let mut rng = SmallRng::seed_from_u64(seed);
+            let german = apply_punctuation(
+                &long_text(),
+                &ALL_PUNCT,
+                Some('"'),
+                &DE_TEXT_RULES,
+                &mut rng,
+            );
+            assert!(!german.contains(['„', '“']), "{german}");
+            assert_eq!(german.matches('"').count() % 2, 0, "{german}");
+
+            let spanish = apply_punctuation(
+                &long_text(),
+                &ALL_PUNCT,
+                Some('?'),
+                &ES_TEXT_RULES,
+                &mut rng,
+            );
+            assert!(!spanish.contains(['¿', '¡', '«', '»']), "{spanish}");
+        }
+    }
+
+    #[test]
+    fn test_english_only_contractions_stay_english() {
+        let mut rng = SmallRng::seed_from_u64(3);
+        let text = "das ist wie es geht und gibt";
+        let result = apply_punctuation(text, &['\''], Some('\''), &DE_TEXT_RULES, &mut rng);
+        assert!(
+            !result.contains("n't") && !result.contains("'ll"),
+            "{result}"
+        );
+        for word in result.split(' ').filter(|w| w.contains('\'')) {
+            assert!(
+                word.ends_with("'s"),
+                "Unexpected German contraction: {result}"
+            );
+        }
+    }
 }
@@ -0,0 +1,248 @@
+use std::collections::HashSet;
+
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+
+use crate::engine::filter::CharFilter;
+use crate::generator::TextGenerator;
+use crate::generator::dictionary::Dictionary;
+use crate::generator::phonetic::PhoneticGenerator;
+use crate::generator::transition_table::TransitionTable;
+use crate::i18n::t;
+use crate::keyboard::finger::{Finger, FingerAssignment, Hand};
+use crate::keyboard::model::KeyboardModel;
+
+/// Below this many matching dictionary words the drill falls back to
+/// phonetic pseudo-words, as a bottom-row or single-finger pool would
+/// otherwise repeat the same handful of words.
+const MIN_WORD_POOL: usize = 20;
+/// Share of letter transitions that must switch hands in an alternation word.
+const MIN_ALTERNATION: f64 = 0.8;
+
+/// Letter row of the active layout, counted from the top letter row.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LetterRow {
+    Top,
+    Home,
+    Bottom,
+}
+
+impl LetterRow {
+    /// Index into `KeyboardModel::rows`, where row 0 holds the digits.
+    fn model_row(self) -> usize {
+        match self {
+            LetterRow::Top => 1,
+            LetterRow::Home => 2,
+            LetterRow::Bottom => 3,
+        }
+    }
+}
+
+/// A drill that trains a typing technique rather than a set of unlocked keys.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Technique {
+    Hand(Hand),
+    Row(LetterRow),
+    Finger(FingerAssignment),
+    /// Words with two different letters in a row typed by the same finger.
+    SameFingerBigrams,
+    /// Words whose letters mostly alternate between the hands.
+    Alternation,
+}
+
+impl Technique {
+    /// Every technique in menu order: hands, rows, the eight typing fingers
+    /// from left pinky to right pinky, then the whole-layout drills.
+    pub fn all() -> Vec<Technique> {
+        let mut all = vec![
+            Technique::Hand(Hand::Left),
+            Technique::Hand(Hand::Right),
+            Technique::Row(LetterRow::Top),
+            Technique::Row(LetterRow::Home),
+            Technique::Row(LetterRow::Bottom),
+        ];
+        let fingers = [Finger::Pinky, Finger::Ring, Finger::Middle, Finger::Index];
+        all.extend(
+            fingers
+                .iter()
+                .map(|&f| Technique::Finger(FingerAssignment::new(Hand::Left, f))),
+        );
+        all.extend(
+            fingers
+                .iter()
+                .rev()
+                .map(|&f| Technique::Finger(FingerAssignment::new(Hand::Right, f))),
+        );
+        all.push(Technique::SameFingerBigrams);
+        all.push(Technique::Alternation);
+        all
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            Technique::Hand(Hand::Left) => t!("technique.left_hand").to_string(),
+            Technique::Hand(Hand::Right) => t!("technique.right_hand").to_string(),
+            Technique::Row(LetterRow::Top) => t!("technique.top_row").to_string(),
+            Technique::Row(LetterRow::Home) => t!("technique.home_row").to_string(),
+            Technique::Row(LetterRow::Bottom) => t!("technique.bottom_row").to_string(),
+            Technique::Finger(finger) => {
+                t!("technique.finger", finger = finger.localized_description()).to_string()
+            }
+            Technique::SameFingerBigrams => t!("technique.same_finger_bigrams").to_string(),
+            Technique::Alternation => t!("technique.alternation").to_string(),
+        }
+    }
+
+    /// Lowercase letters of `model` this technique may use.
+    pub fn letters(&self, model: &KeyboardModel) -> Vec<char> {
+        let mut letters = Vec::new();
+        for (row_idx, row) in model.rows.iter().enumerate() {
+            for (col_idx, key) in row.iter().enumerate() {
+                if !key.base.is_alphabetic() || !key.base.is_lowercase() {
+                    continue;
+                }
+                let finger = model.finger_for_position(row_idx, col_idx);
+                let included = match self {
+                    Technique::Hand(hand) => finger.hand == *hand,
+                    Technique::Row(row) => row_idx == row.model_row(),
+                    Technique::Finger(assignment) => finger == *assignment,
+                    Technique::SameFingerBigrams | Technique::Alternation => true,
+                };
+                if included && !letters.contains(&key.base) {
+                    letters.push(key.base);
+                }
+            }
+        }
+        letters
+    }
+
+    /// Whether a word made of allowed letters trains this technique.
+    fn fits(&self, word: &str, model: &KeyboardModel) -> bool {
+        let chars: Vec<char> = word.chars().collect();
+        match self {
+            Technique::SameFingerBigrams => chars.windows(2).any(|pair| {
+                pair[0] != pair[1]
+                    && model.finger_for_char(pair[0]) == model.finger_for_char(pair[1])
+            }),
+            Technique::Alternation => {
+                if chars.len() < 3 {
+                    return false;
+                }
+                let switches = chars
+                    .windows(2)
+                    .filter(|pair| {
+                        model.finger_for_char(pair[0]).hand != model.finger_for_char(pair[1]).hand
+                    })
+                    .count();
+                switches as f64 / (chars.len() - 1) as f64 >= MIN_ALTERNATION
+            }
+            _ => true,
+        }
+    }
+}
+
+/// Text for a technique drill: dictionary words made only of the technique's
+/// letters (and fitting its pattern), or phonetic pseudo-words from the same
+/// letters when too few real words qualify. The same word never appears
+/// twice in a row.
+pub fn generate_technique_drill(
+    technique: Technique,
+    model: &KeyboardModel,
+    dictionary: &Dictionary,
+    table: &TransitionTable,
+    word_count: usize,
+    rng: &mut SmallRng,
+) -> String {
+    let filter = CharFilter::new(technique.letters(model));
+    let pool: Vec<&str> = dictionary
+        .find_matching(&filter, None)
+        .into_iter()
+        .filter(|word| word.chars().count() >= 2 && technique.fits(word, model))
+        .collect();
+
+    if pool.len() < MIN_WORD_POOL {
+        let mut generator = PhoneticGenerator::new(
+            table.clone(),
+            dictionary.clone(),
+            SmallRng::from_rng(&mut *rng).unwrap(),
+            HashSet::new(),
+        );
+        return generator.generate(&filter, None, None, word_count);
+    }
+
+    let mut words: Vec<&str> = Vec::with_capacity(word_count);
+    while words.len() < word_count.max(1) {
+        let word = pool[rng.gen_range(0..pool.len())];
+        if words.last() != Some(&word) {
+            words.push(word);
+        }
+    }
+    words.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generate(technique: Technique, seed: u64) -> String {
+        let model = KeyboardModel::qwerty();
+        let dictionary = Dictionary::load_for_language("en");
+        let table = TransitionTable::build_from_words(dictionary.words_list());
+        let mut rng = SmallRng::seed_from_u64(seed);
+        generate_technique_drill(technique, &model, &dictionary, &table, 30, &mut rng)
+    }
+
+    #[test]
+    fn letters_follow_the_layout_finger_map() {
+        let qwerty = KeyboardModel::qwerty();
+        let left = Technique::Hand(Hand::Left).letters(&qwerty);
+        assert_eq!(left.len(), 15);
+        assert!(left.contains(&'t') && left.contains(&'b') && !left.contains(&'y'));
+        assert_eq!(
+            Technique::Row(LetterRow::Home).letters(&qwerty),
+            vec!['a', 's', 'd', 'f', 'g', 'h', 'j', 'k', 'l']
+        );
+        let right_ring = Technique::Finger(FingerAssignment::new(Hand::Right, Finger::Ring));
+        assert_eq!(right_ring.letters(&qwerty), vec!['o', 'l']);
+
+        let dvorak = KeyboardModel::dvorak();
+        assert_eq!(
+            Technique::Row(LetterRow::Home).letters(&dvorak),
+            vec!['a', 'o', 'e', 'u', 'i', 'd', 'h', 't', 'n', 's']
+        );
+    }
+
+    #[test]
+    fn hand_and_row_drills_use_only_their_letters() {
+        for technique in [
+            Technique::Hand(Hand::Left),
+            Technique::Hand(Hand::Right),
+            Technique::Row(LetterRow::Top),
+            Technique::Row(LetterRow::Bottom),
+            Technique::Finger(FingerAssignment::new(Hand::Left, Finger::Pinky)),
+        ] {
+            let allowed = technique.letters(&KeyboardModel::qwerty());
+            let text = generate(technique, 5);
+            assert_eq!(text.split(' ').count(), 30, "{technique:?}: {text}");
+            assert!(
+                text.chars().all(|ch| ch == ' ' || allowed.contains(&ch)),
+                "{technique:?}: {text}"
+            );
+        }
+    }
+
+    #[test]
+    fn pattern_drills_pick_matching_words() {
+        let model = KeyboardModel::qwerty();
+        let text = generate(Technique::SameFingerBigrams, 11);
+        for word in text.split(' ') {
+            assert!(Technique::SameFingerBigrams.fits(word, &model), "{word}");
+        }
+        let text = generate(Technique::Alternation, 11);
+        assert!(text.split(' ').all(|w| w.chars().count() >= 3), "{text}");
+        assert!(Technique::Alternation.fits("the", &model));
+        assert!(!Technique::Alternation.fits("were", &model));
+        assert!(Technique::SameFingerBigrams.fits("many", &model));
+        assert!(!Technique::SameFingerBigrams.fits("all", &model));
+    }
+}
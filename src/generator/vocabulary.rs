@@ -0,0 +1,195 @@
+//! Per-language user vocabulary: extra words, optionally weighted, and a
+//! blocklist. Both are plain text files under
+//! `~/.config/keydr/vocabulary/<language>/` so they can be edited by hand too.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use crate::l10n::unicode::normalize_nfc;
+
+pub const WORDS_FILE: &str = "words.txt";
+pub const BLOCKLIST_FILE: &str = "blocklist.txt";
+pub const DEFAULT_WEIGHT: u32 = 1;
+pub const MAX_WEIGHT: u32 = 10;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CustomWord {
+    pub word: String,
+    /// How many times more often than a bundled word the generator picks it.
+    pub weight: u32,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Vocabulary {
+    pub words: Vec<CustomWord>,
+    pub blocked: Vec<String>,
+}
+
+impl Vocabulary {
+    pub fn dir_for(language_key: &str) -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("keydr").join("vocabulary").join(language_key))
+    }
+
+    pub fn load_from(dir: &Path) -> Self {
+        let read = |file: &str| fs::read_to_string(dir.join(file)).unwrap_or_default();
+        Self {
+            words: parse_words(&read(WORDS_FILE)),
+            blocked: parse_blocklist(&read(BLOCKLIST_FILE)),
+        }
+    }
+
+    pub fn save_to(&self, dir: &Path) -> Result<()> {
+        fs::create_dir_all(dir)?;
+        let mut words = String::new();
+        for entry in &self.words {
+            if entry.weight == DEFAULT_WEIGHT {
+                words.push_str(&format!("{}\n", entry.word));
+            } else {
+                words.push_str(&format!("{} {}\n", entry.word, entry.weight));
+            }
+        }
+        fs::write(dir.join(WORDS_FILE), words)?;
+        let blocked: String = self
+            .blocked
+            .iter()
+            .map(|word| format!("{word}\n"))
+            .collect();
+        fs::write(dir.join(BLOCKLIST_FILE), blocked)?;
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty() && self.blocked.is_empty()
+    }
+
+    /// Add a word from a `word [weight]` line. Returns false if the line holds
+    /// no word or the word is already listed.
+    pub fn add_word_line(&mut self, line: &str) -> bool {
+        let Some(entry) = parse_word_line(line) else {
+            return false;
+        };
+        if self
+            .words
+            .iter()
+            .any(|existing| existing.word == entry.word)
+        {
+            return false;
+        }
+        self.words.push(entry);
+        true
+    }
+
+    pub fn block(&mut self, word: &str) -> bool {
+        let word = normalize_nfc(word.trim());
+        if word.is_empty() || word.chars().any(char::is_whitespace) || self.is_blocked(&word) {
+            return false;
+        }
+        self.blocked.push(word);
+        true
+    }
+
+    pub fn is_blocked(&self, word: &str) -> bool {
+        let lower = word.to_lowercase();
+        self.blocked
+            .iter()
+            .any(|blocked| blocked.to_lowercase() == lower)
+    }
+
+    pub fn set_weight(&mut self, index: usize, weight: u32) {
+        if let Some(entry) = self.words.get_mut(index) {
+            entry.weight = weight.clamp(DEFAULT_WEIGHT, MAX_WEIGHT);
+        }
+    }
+}
+
+/// One word per line with an optional weight, e.g. `myocardium 3`.
+/// Blank lines and `#` comments are ignored.
+pub fn parse_words(content: &str) -> Vec<CustomWord> {
+    let mut words: Vec<CustomWord> = Vec::new();
+    for entry in content.lines().filter_map(parse_word_line) {
+        if !words.iter().any(|existing| existing.word == entry.word) {
+            words.push(entry);
+        }
+    }
+    words
+}
+
+fn parse_word_line(line: &str) -> Option<CustomWord> {
+    let line = line.split('#').next().unwrap_or("").trim();
+    let mut parts = line.split_whitespace();
+    let word = normalize_nfc(parts.next()?);
+    let weight = parts
+        .next()
+        .and_then(|weight| weight.parse::<u32>().ok())
+        .unwrap_or(DEFAULT_WEIGHT)
+        .clamp(DEFAULT_WEIGHT, MAX_WEIGHT);
+    Some(CustomWord { word, weight })
+}
+
+pub fn parse_blocklist(content: &str) -> Vec<String> {
+    let mut vocabulary = Vocabulary::default();
+    for line in content.lines() {
+        vocabulary.block(line.split('#').next().unwrap_or(""));
+    }
+    vocabulary.blocked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn word_lines_parse_weights_comments_and_duplicates() {
+        let words = parse_words("# jargon\nmyocardium 3\nstent\n\nstent 2\nbiopsy 99 # capped\n");
+        assert_eq!(
+            words,
+            vec![
+                CustomWord {
+                    word: "myocardium".into(),
+                    weight: 3
+                },
+                CustomWord {
+                    word: "stent".into(),
+                    weight: 1
+                },
+                CustomWord {
+                    word: "biopsy".into(),
+                    weight: MAX_WEIGHT
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn blocklist_matches_case_insensitively() {
+        let vocabulary = Vocabulary {
+            words: Vec::new(),
+            blocked: parse_blocklist("Damn\n# names\nalice\ntwo words\n"),
+        };
+        assert_eq!(vocabulary.blocked, ["Damn", "alice"]);
+        assert!(vocabulary.is_blocked("damn"));
+        assert!(vocabulary.is_blocked("Alice"));
+        assert!(!vocabulary.is_blocked("alicia"));
+    }
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut vocabulary = Vocabulary::default();
+        assert!(vocabulary.add_word_line("kubectl 4"));
+        assert!(vocabulary.add_word_line("ingress"));
+        assert!(!vocabulary.add_word_line("ingress 2"));
+        assert!(!vocabulary.add_word_line("   "));
+        assert!(vocabulary.block("synergy"));
+        vocabulary.save_to(dir.path()).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(dir.path().join(WORDS_FILE)).unwrap(),
+            "kubectl 4\ningress\n"
+        );
+        assert_eq!(Vocabulary::load_from(dir.path()), vocabulary);
+        assert!(Vocabulary::load_from(&dir.path().join("missing")).is_empty());
+    }
+}
@@ -1,3 +1,8 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
 pub use rust_i18n::t;
 
 /// Available UI locale codes. Separate from dictionary language support.
@@ -6,8 +11,14 @@ pub const SUPPORTED_UI_LOCALES: &[&str] = &[
     "hu", "lt", "lv", "sl", "et", "tr",
 ];
 
+/// The compiled English catalog, the reference every other catalog is checked against.
+const EN_CATALOG: &str = include_str!("../locales/en.yml");
+
+/// Flattened translation keys mapped to their strings.
+pub type Catalog = BTreeMap<String, String>;
+
 pub fn set_ui_locale(locale: &str) {
-    let effective = if SUPPORTED_UI_LOCALES.contains(&locale) {
+    let effective = if ui_locales().contains(&locale) {
         locale
     } else {
         "en"
@@ -15,10 +26,102 @@ pub fn set_ui_locale(locale: &str) {
     rust_i18n::set_locale(effective);
 }
 
-/// Retrieve the set of all translation keys for a given locale.
-/// Used by the catalog parity test to verify every key exists in every locale.
-#[cfg(test)]
-fn collect_yaml_keys(value: &serde_yaml::Value, prefix: &str, keys: &mut std::collections::BTreeSet<String>) {
+/// Directory holding user `<locale>.yml` files, layered over the compiled catalog.
+pub fn user_locales_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("keydr").join("locales"))
+}
+
+/// User catalogs, loaded once per process. Unit tests never read them, so
+/// every `t!` renders the compiled strings on every machine.
+fn user_catalogs() -> &'static BTreeMap<String, Catalog> {
+    static CATALOGS: OnceLock<BTreeMap<String, Catalog>> = OnceLock::new();
+    CATALOGS.get_or_init(|| {
+        if cfg!(test) {
+            return BTreeMap::new();
+        }
+        user_locales_dir()
+            .map(|dir| load_user_catalogs(&dir))
+            .unwrap_or_default()
+    })
+}
+
+/// Read every parseable `<locale>.yml` in `dir`. Broken files are skipped here;
+/// `keydr i18n check` explains what is wrong with them.
+fn load_user_catalogs(dir: &Path) -> BTreeMap<String, Catalog> {
+    let mut catalogs = BTreeMap::new();
+    let Ok(entries) = fs::read_dir(dir) else {
+        return catalogs;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Some(locale) = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_suffix(".yml"))
+        else {
+            continue;
+        };
+        if !is_valid_locale_code(locale) {
+            continue;
+        }
+        if let Some(catalog) = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| parse_catalog(&content).ok())
+        {
+            catalogs.insert(locale.to_string(), catalog);
+        }
+    }
+    catalogs
+}
+
+fn is_valid_locale_code(code: &str) -> bool {
+    !code.is_empty()
+        && code
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+}
+
+/// Backend that `i18n!` layers over the compiled catalog; user strings win and
+/// keys a user file leaves out fall through to the compiled locale, then English.
+pub fn user_locale_backend() -> rust_i18n::SimpleBackend {
+    let mut backend = rust_i18n::SimpleBackend::new();
+    for (locale, catalog) in user_catalogs() {
+        let data: HashMap<&str, &str> = catalog
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+            .collect();
+        backend.add_translations(locale, &data);
+    }
+    backend
+}
+
+/// Compiled locales followed by the new locales found in the user directory.
+pub fn ui_locales() -> &'static [&'static str] {
+    static LOCALES: OnceLock<Vec<&'static str>> = OnceLock::new();
+    LOCALES
+        .get_or_init(|| {
+            let user = user_catalogs()
+                .keys()
+                .map(|locale| locale.as_str())
+                .filter(|locale| !SUPPORTED_UI_LOCALES.contains(locale));
+            SUPPORTED_UI_LOCALES.iter().copied().chain(user).collect()
+        })
+        .as_slice()
+}
+
+/// Whether `locale` exists only as a user file, not in the compiled catalog.
+pub fn is_user_locale(locale: &str) -> bool {
+    !SUPPORTED_UI_LOCALES.contains(&locale) && user_catalogs().contains_key(locale)
+}
+
+pub fn parse_catalog(content: &str) -> Result<Catalog, serde_yaml::Error> {
+    let root: serde_yaml::Value = serde_yaml::from_str(content)?;
+    let mut catalog = Catalog::new();
+    collect_leaf_values(&root, "", &mut catalog);
+    Ok(catalog)
+}
+
+fn collect_leaf_values(value: &serde_yaml::Value, prefix: &str, catalog: &mut Catalog) {
     match value {
         serde_yaml::Value::Mapping(map) => {
             for (k, v) in map {
@@ -28,15 +131,93 @@ fn collect_yaml_keys(value: &serde_yaml::Value, prefix: &str, keys: &mut std::co
                 } else {
                     format!("{prefix}.{key_str}")
                 };
-                collect_yaml_keys(v, &full, keys);
+                collect_leaf_values(v, &full, catalog);
             }
         }
+        serde_yaml::Value::String(s) => {
+            catalog.insert(prefix.to_string(), s.clone());
+        }
+        serde_yaml::Value::Bool(b) => {
+            catalog.insert(prefix.to_string(), b.to_string());
+        }
+        serde_yaml::Value::Number(n) => {
+            catalog.insert(prefix.to_string(), n.to_string());
+        }
         _ => {
-            keys.insert(prefix.to_string());
+            catalog.insert(prefix.to_string(), String::new());
         }
     }
 }
 
+/// `%{name}` placeholders used in a translated string.
+fn placeholders(value: &str) -> BTreeSet<String> {
+    let mut names = BTreeSet::new();
+    let mut rest = value;
+    while let Some(start) = rest.find("%{") {
+        rest = &rest[start + 2..];
+        let Some(end) = rest.find('}') else {
+            break;
+        };
+        let name = &rest[..end];
+        if !name.is_empty() && name.chars().all(|ch| ch.is_alphanumeric() || ch == '_') {
+            names.insert(name.to_string());
+        }
+        rest = &rest[end + 1..];
+    }
+    names
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PlaceholderMismatch {
+    pub key: String,
+    pub expected: BTreeSet<String>,
+    pub found: BTreeSet<String>,
+}
+
+/// Differences between a catalog and the reference it translates.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CatalogReport {
+    pub missing: Vec<String>,
+    pub extra: Vec<String>,
+    pub placeholder_mismatches: Vec<PlaceholderMismatch>,
+}
+
+pub fn compare_catalogs(reference: &Catalog, other: &Catalog) -> CatalogReport {
+    let missing = reference
+        .keys()
+        .filter(|key| !other.contains_key(*key))
+        .cloned()
+        .collect();
+    let extra = other
+        .keys()
+        .filter(|key| !reference.contains_key(*key))
+        .cloned()
+        .collect();
+    let placeholder_mismatches = reference
+        .iter()
+        .filter_map(|(key, reference_value)| {
+            let expected = placeholders(reference_value);
+            let found = placeholders(other.get(key)?);
+            (expected != found).then(|| PlaceholderMismatch {
+                key: key.clone(),
+                expected,
+                found,
+            })
+        })
+        .collect();
+    CatalogReport {
+        missing,
+        extra,
+        placeholder_mismatches,
+    }
+}
+
+/// Check a locale file against the compiled English catalog.
+pub fn check_catalog(content: &str) -> Result<CatalogReport, serde_yaml::Error> {
+    let reference = parse_catalog(EN_CATALOG)?;
+    Ok(compare_catalogs(&reference, &parse_catalog(content)?))
+}
+
 /// Translate a LanguageLayoutValidationError for display in the UI.
 pub fn localized_language_layout_error(
     err: &crate::l10n::language_pack::LanguageLayoutValidationError,
@@ -63,50 +244,40 @@ pub fn localized_language_layout_error(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::BTreeSet;
 
-    fn locale_keys(locale: &str) -> BTreeSet<String> {
+    fn locale_catalog(locale: &str) -> Catalog {
         let path = format!("locales/{locale}.yml");
         let content = std::fs::read_to_string(&path)
             .unwrap_or_else(|e| panic!("Failed to read {path}: {e}"));
-        let root: serde_yaml::Value = serde_yaml::from_str(&content)
-            .unwrap_or_else(|e| panic!("Failed to parse {path}: {e}"));
-        let mut keys = BTreeSet::new();
-        collect_yaml_keys(&root, "", &mut keys);
-        keys
+        parse_catalog(&content).unwrap_or_else(|e| panic!("Failed to parse {path}: {e}"))
+    }
+
+    #[test]
+    fn tests_ignore_user_locale_overrides() {
+        assert!(user_catalogs().is_empty());
     }
 
     #[test]
     fn catalog_parity_all_locales() {
-        let en = locale_keys("en");
+        let en = locale_catalog("en");
         let mut errors = Vec::new();
 
         for locale in SUPPORTED_UI_LOCALES {
             if *locale == "en" {
                 continue;
             }
-            let other = locale_keys(locale);
-            let missing: Vec<_> = en.difference(&other).collect();
-            let extra: Vec<_> = other.difference(&en).collect();
+            let report = compare_catalogs(&en, &locale_catalog(locale));
 
-            if !missing.is_empty() {
+            if !report.missing.is_empty() {
                 errors.push(format!(
                     "Keys in en.yml missing from {locale}.yml:\n    {}",
-                    missing
-                        .iter()
-                        .map(|k| k.as_str())
-                        .collect::<Vec<_>>()
-                        .join("\n    ")
+                    report.missing.join("\n    ")
                 ));
             }
-            if !extra.is_empty() {
+            if !report.extra.is_empty() {
                 errors.push(format!(
                     "Keys in {locale}.yml not present in en.yml:\n    {}",
-                    extra
-                        .iter()
-                        .map(|k| k.as_str())
-                        .collect::<Vec<_>>()
-                        .join("\n    ")
+                    report.extra.join("\n    ")
                 ));
             }
         }
@@ -116,39 +287,19 @@ mod tests {
 
     #[test]
     fn placeholder_parity_all_locales() {
-        let en_content = std::fs::read_to_string("locales/en.yml").unwrap();
-        let en_root: serde_yaml::Value = serde_yaml::from_str(&en_content).unwrap();
-        let mut en_map = std::collections::BTreeMap::new();
-        collect_leaf_values(&en_root, "", &mut en_map);
-
-        let placeholder_re = regex::Regex::new(r"%\{(\w+)\}").unwrap();
+        let en = locale_catalog("en");
         let mut all_mismatches = Vec::new();
 
         for locale in SUPPORTED_UI_LOCALES {
             if *locale == "en" {
                 continue;
             }
-            let other_content = std::fs::read_to_string(format!("locales/{locale}.yml")).unwrap();
-            let other_root: serde_yaml::Value = serde_yaml::from_str(&other_content).unwrap();
-            let mut other_map = std::collections::BTreeMap::new();
-            collect_leaf_values(&other_root, "", &mut other_map);
-
-            for (key, en_val) in &en_map {
-                if let Some(other_val) = other_map.get(key) {
-                    let en_placeholders: BTreeSet<_> = placeholder_re
-                        .captures_iter(en_val)
-                        .map(|c| c[1].to_string())
-                        .collect();
-                    let other_placeholders: BTreeSet<_> = placeholder_re
-                        .captures_iter(other_val)
-                        .map(|c| c[1].to_string())
-                        .collect();
-                    if en_placeholders != other_placeholders {
-                        all_mismatches.push(format!(
-                            "  {locale}/{key}: en={en_placeholders:?} {locale}={other_placeholders:?}"
-                        ));
-                    }
-                }
+            let report = compare_catalogs(&en, &locale_catalog(locale));
+            for mismatch in report.placeholder_mismatches {
+                all_mismatches.push(format!(
+                    "  {locale}/{}: en={:?} {locale}={:?}",
+                    mismatch.key, mismatch.expected, mismatch.found
+                ));
             }
         }
 
@@ -159,28 +310,43 @@ mod tests {
         );
     }
 
-    fn collect_leaf_values(
-        value: &serde_yaml::Value,
-        prefix: &str,
-        map: &mut std::collections::BTreeMap<String, String>,
-    ) {
-        match value {
-            serde_yaml::Value::Mapping(m) => {
-                for (k, v) in m {
-                    let key_str = k.as_str().unwrap_or("");
-                    let full = if prefix.is_empty() {
-                        key_str.to_string()
-                    } else {
-                        format!("{prefix}.{key_str}")
-                    };
-                    collect_leaf_values(v, &full, map);
-                }
-            }
-            serde_yaml::Value::String(s) => {
-                map.insert(prefix.to_string(), s.clone());
-            }
-            _ => {}
-        }
+    #[test]
+    fn placeholder_scan_matches_named_placeholders_only() {
+        let names = placeholders("%{count} of %{total_keys} (100%) %{} %{bad name}");
+        assert_eq!(
+            names.into_iter().collect::<Vec<_>>(),
+            ["count", "total_keys"]
+        );
+    }
+
+    #[test]
+    fn check_catalog_reports_missing_extra_and_placeholder_drift() {
+        let content = "menu:\n  subtitle: 'Tajpa trejnilo'\n  bogus: 'x'\nselect:\n  more_above: 'pli supre'\n";
+        let report = check_catalog(content).unwrap();
+        assert!(report.missing.contains(&"menu.adaptive_drill".to_string()));
+        assert_eq!(report.extra, ["menu.bogus"]);
+        assert_eq!(report.placeholder_mismatches.len(), 1);
+        assert_eq!(report.placeholder_mismatches[0].key, "select.more_above");
+
+        let en_report = check_catalog(EN_CATALOG).unwrap();
+        assert_eq!(en_report, CatalogReport::default());
+        assert!(check_catalog("menu: [unclosed").is_err());
+    }
+
+    #[test]
+    fn user_catalogs_load_valid_locale_files_only() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("eo.yml"),
+            "menu:\n  subtitle: 'Tajpa trejnilo'\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("broken.yml"), "menu: [").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "menu: {}").unwrap();
+
+        let catalogs = load_user_catalogs(dir.path());
+        assert_eq!(catalogs.keys().collect::<Vec<_>>(), ["eo"]);
+        assert_eq!(catalogs["eo"]["menu.subtitle"], "Tajpa trejnilo");
     }
 
     #[test]
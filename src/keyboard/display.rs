@@ -17,7 +17,7 @@ pub fn key_display_name(ch: char) -> &'static str {
         '\t' => "Tab",
         '\n' => "Enter",
         ' ' => "Space",
-        _ => "",
+        _ => numpad_entry(ch).map_or("", |(_, name, _)| name),
     }
 }
 
@@ -29,7 +29,7 @@ pub fn key_short_label(ch: char) -> &'static str {
         '\t' => "Tab",
         '\n' => "Ent",
         ' ' => "Spc",
-        _ => "",
+        _ => numpad_entry(ch).map_or("", |(_, _, short)| short),
     }
 }
 
@@ -45,6 +45,39 @@ pub const ENTER: char = '\n';
 /// Space character (not a sentinel, but treated as a special key for display).
 pub const SPACE: char = ' ';
 
+/// Keys typed on the numeric keypad are stored under Private Use Area sentinels
+/// (`U+E000` onwards, in this order) so they keep stats apart from the number row.
+const NUMPAD_SENTINEL_BASE: u32 = 0xE000;
+const NUMPAD_KEYS: [(char, &str, &str); 16] = [
+    ('0', "Num 0", "N0"),
+    ('1', "Num 1", "N1"),
+    ('2', "Num 2", "N2"),
+    ('3', "Num 3", "N3"),
+    ('4', "Num 4", "N4"),
+    ('5', "Num 5", "N5"),
+    ('6', "Num 6", "N6"),
+    ('7', "Num 7", "N7"),
+    ('8', "Num 8", "N8"),
+    ('9', "Num 9", "N9"),
+    ('.', "Num .", "N."),
+    ('+', "Num +", "N+"),
+    ('-', "Num -", "N-"),
+    ('*', "Num *", "N*"),
+    ('/', "Num /", "N/"),
+    (ENTER, "Num Enter", "NEnt"),
+];
+
+fn numpad_entry(sentinel: char) -> Option<(char, &'static str, &'static str)> {
+    let idx = (sentinel as u32).checked_sub(NUMPAD_SENTINEL_BASE)?;
+    NUMPAD_KEYS.get(idx as usize).copied()
+}
+
+/// Sentinel under which a keypad press of `ch` is recorded.
+pub fn numpad_sentinel(ch: char) -> Option<char> {
+    let idx = NUMPAD_KEYS.iter().position(|(key, _, _)| *key == ch)?;
+    char::from_u32(NUMPAD_SENTINEL_BASE + idx as u32)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -68,6 +101,17 @@ mod tests {
         assert_eq!(key_short_label('z'), "");
     }
 
+    #[test]
+    fn test_numpad_sentinels_roundtrip() {
+        let seven = numpad_sentinel('7').unwrap();
+        assert_ne!(seven, '7');
+        assert_eq!(numpad_entry(seven).map(|(ch, _, _)| ch), Some('7'));
+        assert_eq!(key_display_name(seven), "Num 7");
+        assert_eq!(key_short_label(numpad_sentinel(ENTER).unwrap()), "NEnt");
+        assert_eq!(numpad_sentinel('a'), None);
+        assert_eq!(key_display_name('7'), "");
+    }
+
     #[test]
     fn test_modifier_sentinels() {
         assert_eq!(MODIFIER_SENTINELS.len(), 3);
@@ -1,3 +1,4 @@
 pub mod display;
 pub mod finger;
 pub mod model;
+pub mod numpad;
@@ -2,6 +2,7 @@ use std::sync::OnceLock;
 
 use crate::keyboard::display::{BACKSPACE, ENTER, SPACE, TAB};
 use crate::keyboard::finger::{Finger, FingerAssignment, Hand};
+use crate::l10n::language_pack::Script;
 
 #[derive(Clone, Copy, Debug)]
 pub struct PhysicalKey {
@@ -15,6 +16,15 @@ struct ProfileKeySpec {
     finger: FingerAssignment,
 }
 
+/// A dead key: pressing it types nothing until the next key, which it
+/// composes into an accented character (Greek tonos + α = ά).
+#[derive(Clone, Copy, Debug)]
+pub struct DeadKey {
+    pub key: char,
+    /// `(base, composed)` pairs.
+    pub compositions: &'static [(char, char)],
+}
+
 #[allow(dead_code)]
 #[derive(Clone, Copy, Debug)]
 pub struct KeyboardGeometryHints {
@@ -34,7 +44,9 @@ pub struct ModifierPlacementMetadata {
 #[derive(Clone, Copy, Debug)]
 struct KeyboardProfile {
     key: &'static str,
+    script: Script,
     rows: &'static [&'static [ProfileKeySpec]],
+    dead_keys: &'static [DeadKey],
     geometry_hints: KeyboardGeometryHints,
     modifier_placement: ModifierPlacementMetadata,
 }
@@ -44,6 +56,7 @@ struct KeyboardProfile {
 pub struct KeyboardModel {
     pub rows: Vec<Vec<PhysicalKey>>,
     finger_rows: Vec<Vec<FingerAssignment>>,
+    pub dead_keys: &'static [DeadKey],
     pub layout_key: &'static str,
     pub geometry_hints: KeyboardGeometryHints,
     pub modifier_placement: ModifierPlacementMetadata,
@@ -358,6 +371,285 @@ const FR_AZERTY_ROW3: &[ProfileKeySpec] = &[
     key!('!', '§', Right, Pinky),
 ];
 
+const RU_JCUKEN_ROW0: &[ProfileKeySpec] = &[
+    key!('ё', 'Ё', Left, Pinky),
+    key!('1', '!', Left, Pinky),
+    key!('2', '"', Left, Ring),
+    key!('3', '№', Left, Middle),
+    key!('4', ';', Left, Index),
+    key!('5', '%', Left, Index),
+    key!('6', ':', Right, Index),
+    key!('7', '?', Right, Index),
+    key!('8', '*', Right, Middle),
+    key!('9', '(', Right, Ring),
+    key!('0', ')', Right, Pinky),
+    key!('-', '_', Right, Pinky),
+    key!('=', '+', Right, Pinky),
+];
+
+const RU_JCUKEN_ROW1: &[ProfileKeySpec] = &[
+    key!('й', 'Й', Left, Pinky),
+    key!('ц', 'Ц', Left, Ring),
+    key!('у', 'У', Left, Middle),
+    key!('к', 'К', Left, Index),
+    key!('е', 'Е', Left, Index),
+    key!('н', 'Н', Right, Index),
+    key!('г', 'Г', Right, Index),
+    key!('ш', 'Ш', Right, Middle),
+    key!('щ', 'Щ', Right, Ring),
+    key!('з', 'З', Right, Pinky),
+    key!('х', 'Х', Right, Pinky),
+    key!('ъ', 'Ъ', Right, Pinky),
+    key!('\\', '/', Right, Pinky),
+];
+
+const RU_JCUKEN_ROW2: &[ProfileKeySpec] = &[
+    key!('ф', 'Ф', Left, Pinky),
+    key!('ы', 'Ы', Left, Ring),
+    key!('в', 'В', Left, Middle),
+    key!('а', 'А', Left, Index),
+    key!('п', 'П', Left, Index),
+    key!('р', 'Р', Right, Index),
+    key!('о', 'О', Right, Index),
+    key!('л', 'Л', Right, Middle),
+    key!('д', 'Д', Right, Ring),
+    key!('ж', 'Ж', Right, Pinky),
+    key!('э', 'Э', Right, Pinky),
+];
+
+const RU_JCUKEN_ROW3: &[ProfileKeySpec] = &[
+    key!('я', 'Я', Left, Pinky),
+    key!('ч', 'Ч', Left, Ring),
+    key!('с', 'С', Left, Middle),
+    key!('м', 'М', Left, Index),
+    key!('и', 'И', Left, Index),
+    key!('т', 'Т', Right, Index),
+    key!('ь', 'Ь', Right, Index),
+    key!('б', 'Б', Right, Middle),
+    key!('ю', 'Ю', Right, Ring),
+    key!('.', ',', Right, Pinky),
+];
+
+// Ukrainian: apostrophe on the grave key and ґ in place of the Russian
+// backslash key, as on the enhanced Windows layout.
+const UK_JCUKEN_ROW0: &[ProfileKeySpec] = &[
+    key!('\'', '₴', Left, Pinky),
+    key!('1', '!', Left, Pinky),
+    key!('2', '"', Left, Ring),
+    key!('3', '№', Left, Middle),
+    key!('4', ';', Left, Index),
+    key!('5', '%', Left, Index),
+    key!('6', ':', Right, Index),
+    key!('7', '?', Right, Index),
+    key!('8', '*', Right, Middle),
+    key!('9', '(', Right, Ring),
+    key!('0', ')', Right, Pinky),
+    key!('-', '_', Right, Pinky),
+    key!('=', '+', Right, Pinky),
+];
+
+const UK_JCUKEN_ROW1: &[ProfileKeySpec] = &[
+    key!('й', 'Й', Left, Pinky),
+    key!('ц', 'Ц', Left, Ring),
+    key!('у', 'У', Left, Middle),
+    key!('к', 'К', Left, Index),
+    key!('е', 'Е', Left, Index),
+    key!('н', 'Н', Right, Index),
+    key!('г', 'Г', Right, Index),
+    key!('ш', 'Ш', Right, Middle),
+    key!('щ', 'Щ', Right, Ring),
+    key!('з', 'З', Right, Pinky),
+    key!('х', 'Х', Right, Pinky),
+    key!('ї', 'Ї', Right, Pinky),
+    key!('ґ', 'Ґ', Right, Pinky),
+];
+
+const UK_JCUKEN_ROW2: &[ProfileKeySpec] = &[
+    key!('ф', 'Ф', Left, Pinky),
+    key!('і', 'І', Left, Ring),
+    key!('в', 'В', Left, Middle),
+    key!('а', 'А', Left, Index),
+    key!('п', 'П', Left, Index),
+    key!('р', 'Р', Right, Index),
+    key!('о', 'О', Right, Index),
+    key!('л', 'Л', Right, Middle),
+    key!('д', 'Д', Right, Ring),
+    key!('ж', 'Ж', Right, Pinky),
+    key!('є', 'Є', Right, Pinky),
+];
+
+const UK_JCUKEN_ROW3: &[ProfileKeySpec] = &[
+    key!('я', 'Я', Left, Pinky),
+    key!('ч', 'Ч', Left, Ring),
+    key!('с', 'С', Left, Middle),
+    key!('м', 'М', Left, Index),
+    key!('и', 'И', Left, Index),
+    key!('т', 'Т', Right, Index),
+    key!('ь', 'Ь', Right, Index),
+    key!('б', 'Б', Right, Middle),
+    key!('ю', 'Ю', Right, Ring),
+    key!('.', ',', Right, Pinky),
+];
+
+// Greek: the Q key types `;`, which doubles as the Greek question mark, and the
+// Latin semicolon key holds the tonos/dialytika dead keys.
+const EL_GREEK_ROW0: &[ProfileKeySpec] = QWERTY_ROW0;
+
+const EL_GREEK_ROW1: &[ProfileKeySpec] = &[
+    key!(';', ':', Left, Pinky),
+    key!('ς', '΅', Left, Ring),
+    key!('ε', 'Ε', Left, Middle),
+    key!('ρ', 'Ρ', Left, Index),
+    key!('τ', 'Τ', Left, Index),
+    key!('υ', 'Υ', Right, Index),
+    key!('θ', 'Θ', Right, Index),
+    key!('ι', 'Ι', Right, Middle),
+    key!('ο', 'Ο', Right, Ring),
+    key!('π', 'Π', Right, Pinky),
+    key!('[', '{', Right, Pinky),
+    key!(']', '}', Right, Pinky),
+    key!('\\', '|', Right, Pinky),
+];
+
+const EL_GREEK_ROW2: &[ProfileKeySpec] = &[
+    key!('α', 'Α', Left, Pinky),
+    key!('σ', 'Σ', Left, Ring),
+    key!('δ', 'Δ', Left, Middle),
+    key!('φ', 'Φ', Left, Index),
+    key!('γ', 'Γ', Left, Index),
+    key!('η', 'Η', Right, Index),
+    key!('ξ', 'Ξ', Right, Index),
+    key!('κ', 'Κ', Right, Middle),
+    key!('λ', 'Λ', Right, Ring),
+    key!(GREEK_TONOS, GREEK_DIALYTIKA, Right, Pinky),
+    key!('\'', '"', Right, Pinky),
+];
+
+const EL_GREEK_ROW3: &[ProfileKeySpec] = &[
+    key!('ζ', 'Ζ', Left, Pinky),
+    key!('χ', 'Χ', Left, Ring),
+    key!('ψ', 'Ψ', Left, Middle),
+    key!('ω', 'Ω', Left, Index),
+    key!('β', 'Β', Left, Index),
+    key!('ν', 'Ν', Right, Index),
+    key!('μ', 'Μ', Right, Index),
+    key!(',', '<', Right, Middle),
+    key!('.', '>', Right, Ring),
+    key!('/', '?', Right, Pinky),
+];
+
+const DE_QWERTZ_DEAD_KEYS: &[DeadKey] = &[
+    DeadKey {
+        key: '^',
+        compositions: &[
+            ('a', 'â'),
+            ('e', 'ê'),
+            ('i', 'î'),
+            ('o', 'ô'),
+            ('u', 'û'),
+            ('A', 'Â'),
+            ('E', 'Ê'),
+            ('I', 'Î'),
+            ('O', 'Ô'),
+            ('U', 'Û'),
+        ],
+    },
+    DeadKey {
+        key: '´',
+        compositions: &[
+            ('a', 'á'),
+            ('e', 'é'),
+            ('i', 'í'),
+            ('o', 'ó'),
+            ('u', 'ú'),
+            ('A', 'Á'),
+            ('E', 'É'),
+            ('I', 'Í'),
+            ('O', 'Ó'),
+            ('U', 'Ú'),
+        ],
+    },
+    DeadKey {
+        key: '`',
+        compositions: &[
+            ('a', 'à'),
+            ('e', 'è'),
+            ('i', 'ì'),
+            ('o', 'ò'),
+            ('u', 'ù'),
+            ('A', 'À'),
+            ('E', 'È'),
+            ('I', 'Ì'),
+            ('O', 'Ò'),
+            ('U', 'Ù'),
+        ],
+    },
+];
+
+const FR_AZERTY_DEAD_KEYS: &[DeadKey] = &[
+    DeadKey {
+        key: '^',
+        compositions: &[
+            ('a', 'â'),
+            ('e', 'ê'),
+            ('i', 'î'),
+            ('o', 'ô'),
+            ('u', 'û'),
+            ('A', 'Â'),
+            ('E', 'Ê'),
+            ('I', 'Î'),
+            ('O', 'Ô'),
+            ('U', 'Û'),
+        ],
+    },
+    DeadKey {
+        key: '¨',
+        compositions: &[
+            ('e', 'ë'),
+            ('i', 'ï'),
+            ('u', 'ü'),
+            ('y', 'ÿ'),
+            ('E', 'Ë'),
+            ('I', 'Ï'),
+            ('U', 'Ü'),
+        ],
+    },
+];
+
+const GREEK_TONOS: char = '΄';
+const GREEK_DIALYTIKA: char = '¨';
+const GREEK_DIALYTIKA_TONOS: char = '΅';
+
+const EL_GREEK_DEAD_KEYS: &[DeadKey] = &[
+    DeadKey {
+        key: GREEK_TONOS,
+        compositions: &[
+            ('α', 'ά'),
+            ('ε', 'έ'),
+            ('η', 'ή'),
+            ('ι', 'ί'),
+            ('ο', 'ό'),
+            ('υ', 'ύ'),
+            ('ω', 'ώ'),
+            ('Α', 'Ά'),
+            ('Ε', 'Έ'),
+            ('Η', 'Ή'),
+            ('Ι', 'Ί'),
+            ('Ο', 'Ό'),
+            ('Υ', 'Ύ'),
+            ('Ω', 'Ώ'),
+        ],
+    },
+    DeadKey {
+        key: GREEK_DIALYTIKA,
+        compositions: &[('ι', 'ϊ'), ('υ', 'ϋ'), ('Ι', 'Ϊ'), ('Υ', 'Ϋ')],
+    },
+    DeadKey {
+        key: GREEK_DIALYTIKA_TONOS,
+        compositions: &[('ι', 'ΐ'), ('υ', 'ΰ')],
+    },
+];
+
 const QWERTY_ROWS: &[&[ProfileKeySpec]] = &[QWERTY_ROW0, QWERTY_ROW1, QWERTY_ROW2, QWERTY_ROW3];
 const DVORAK_ROWS: &[&[ProfileKeySpec]] = &[DVORAK_ROW0, DVORAK_ROW1, DVORAK_ROW2, DVORAK_ROW3];
 const COLEMAK_ROWS: &[&[ProfileKeySpec]] =
@@ -374,38 +666,89 @@ const FR_AZERTY_ROWS: &[&[ProfileKeySpec]] = &[
     FR_AZERTY_ROW2,
     FR_AZERTY_ROW3,
 ];
+const EL_GREEK_ROWS: &[&[ProfileKeySpec]] =
+    &[EL_GREEK_ROW0, EL_GREEK_ROW1, EL_GREEK_ROW2, EL_GREEK_ROW3];
+const RU_JCUKEN_ROWS: &[&[ProfileKeySpec]] = &[
+    RU_JCUKEN_ROW0,
+    RU_JCUKEN_ROW1,
+    RU_JCUKEN_ROW2,
+    RU_JCUKEN_ROW3,
+];
+const UK_JCUKEN_ROWS: &[&[ProfileKeySpec]] = &[
+    UK_JCUKEN_ROW0,
+    UK_JCUKEN_ROW1,
+    UK_JCUKEN_ROW2,
+    UK_JCUKEN_ROW3,
+];
 
 const QWERTY_PROFILE: KeyboardProfile = KeyboardProfile {
     key: "qwerty",
+    script: Script::Latin,
     rows: QWERTY_ROWS,
+    dead_keys: &[],
     geometry_hints: STAGGERED_GEOMETRY,
     modifier_placement: STANDARD_MODIFIERS,
 };
 
 const DVORAK_PROFILE: KeyboardProfile = KeyboardProfile {
     key: "dvorak",
+    script: Script::Latin,
     rows: DVORAK_ROWS,
+    dead_keys: &[],
     geometry_hints: STAGGERED_GEOMETRY,
     modifier_placement: STANDARD_MODIFIERS,
 };
 
 const COLEMAK_PROFILE: KeyboardProfile = KeyboardProfile {
     key: "colemak",
+    script: Script::Latin,
     rows: COLEMAK_ROWS,
+    dead_keys: &[],
     geometry_hints: STAGGERED_GEOMETRY,
     modifier_placement: STANDARD_MODIFIERS,
 };
 
 const DE_QWERTZ_PROFILE: KeyboardProfile = KeyboardProfile {
     key: "de_qwertz",
+    script: Script::Latin,
     rows: DE_QWERTZ_ROWS,
+    dead_keys: DE_QWERTZ_DEAD_KEYS,
     geometry_hints: STAGGERED_GEOMETRY,
     modifier_placement: STANDARD_MODIFIERS,
 };
 
 const FR_AZERTY_PROFILE: KeyboardProfile = KeyboardProfile {
     key: "fr_azerty",
+    script: Script::Latin,
     rows: FR_AZERTY_ROWS,
+    dead_keys: FR_AZERTY_DEAD_KEYS,
+    geometry_hints: STAGGERED_GEOMETRY,
+    modifier_placement: STANDARD_MODIFIERS,
+};
+
+const EL_GREEK_PROFILE: KeyboardProfile = KeyboardProfile {
+    key: "el_greek",
+    script: Script::Greek,
+    rows: EL_GREEK_ROWS,
+    dead_keys: EL_GREEK_DEAD_KEYS,
+    geometry_hints: STAGGERED_GEOMETRY,
+    modifier_placement: STANDARD_MODIFIERS,
+};
+
+const RU_JCUKEN_PROFILE: KeyboardProfile = KeyboardProfile {
+    key: "ru_jcuken",
+    script: Script::Cyrillic,
+    rows: RU_JCUKEN_ROWS,
+    dead_keys: &[],
+    geometry_hints: STAGGERED_GEOMETRY,
+    modifier_placement: STANDARD_MODIFIERS,
+};
+
+const UK_JCUKEN_PROFILE: KeyboardProfile = KeyboardProfile {
+    key: "uk_jcuken",
+    script: Script::Cyrillic,
+    rows: UK_JCUKEN_ROWS,
+    dead_keys: &[],
     geometry_hints: STAGGERED_GEOMETRY,
     modifier_placement: STANDARD_MODIFIERS,
 };
@@ -416,6 +759,9 @@ const KEYBOARD_PROFILES: &[KeyboardProfile] = &[
     COLEMAK_PROFILE,
     DE_QWERTZ_PROFILE,
     FR_AZERTY_PROFILE,
+    EL_GREEK_PROFILE,
+    RU_JCUKEN_PROFILE,
+    UK_JCUKEN_PROFILE,
 ];
 
 const EXTRA_LAYOUT_KEYS: &[&str] = &[
@@ -480,6 +826,18 @@ impl KeyboardModel {
         .as_slice()
     }
 
+    /// Script a layout's letter keys belong to. The `*_intl` overlays are
+    /// all QWERTY-based, so Latin.
+    pub fn layout_script(key: &str) -> Option<Script> {
+        if EXTRA_LAYOUT_KEYS.contains(&key) {
+            return Some(Script::Latin);
+        }
+        KEYBOARD_PROFILES
+            .iter()
+            .find(|p| p.key == key)
+            .map(|p| p.script)
+    }
+
     #[allow(dead_code)]
     pub fn qwerty() -> Self {
         Self::from_key("qwerty").expect("qwerty profile must be registered")
@@ -565,6 +923,7 @@ impl KeyboardModel {
         Some(Self {
             rows,
             finger_rows,
+            dead_keys: profile.dead_keys,
             layout_key: profile.key,
             geometry_hints: profile.geometry_hints,
             modifier_placement: profile.modifier_placement,
@@ -591,6 +950,34 @@ impl KeyboardModel {
         self.find_key_position(ch).map(|(r, c)| &self.rows[r][c])
     }
 
+    /// The `(dead key, base)` strokes that compose `ch` on this layout.
+    pub fn dead_key_sequence(&self, ch: char) -> Option<(char, char)> {
+        self.dead_keys.iter().find_map(|dead| {
+            dead.compositions
+                .iter()
+                .find(|&&(_, composed)| composed == ch)
+                .map(|&(base, _)| (dead.key, base))
+        })
+    }
+
+    /// The key to strike next for `ch`: its dead key first, then the base letter
+    /// once the dead key is pending. Directly typeable chars are returned as is.
+    pub fn next_stroke(&self, ch: char, dead_key_pending: bool) -> char {
+        if self.physical_key_for(ch).is_some() {
+            return ch;
+        }
+        match self.dead_key_sequence(ch) {
+            Some((_, base)) if dead_key_pending => base,
+            Some((dead, _)) => dead,
+            None => ch,
+        }
+    }
+
+    /// Whether `ch` can be typed, directly or through a dead key.
+    pub fn can_type(&self, ch: char) -> bool {
+        self.physical_key_for(ch).is_some() || self.dead_key_sequence(ch).is_some()
+    }
+
     fn find_key_position(&self, ch: char) -> Option<(usize, usize)> {
         for (row_idx, row) in self.rows.iter().enumerate() {
             for (col_idx, key) in row.iter().enumerate() {
@@ -622,6 +1009,9 @@ impl KeyboardModel {
             _ => {
                 if let Some((row_idx, col_idx)) = self.find_key_position(ch) {
                     self.finger_for_position(row_idx, col_idx)
+                } else if let Some((_, base)) = self.dead_key_sequence(ch) {
+                    // The finger that strikes the base letter finishes the character.
+                    self.finger_for_char(base)
                 } else {
                     FingerAssignment::new(Hand::Right, Finger::Index)
                 }
@@ -654,6 +1044,9 @@ mod tests {
                 "colemak",
                 "de_qwertz",
                 "fr_azerty",
+                "el_greek",
+                "ru_jcuken",
+                "uk_jcuken",
                 "es_intl",
                 "it_intl",
                 "pt_intl",
@@ -707,6 +1100,43 @@ mod tests {
         assert_eq!(fr.shifted_to_base('2'), Some('é'));
         assert_eq!(fr.shifted_to_base('0'), Some('à'));
 
+        let ru = KeyboardModel::from_key("ru_jcuken").expect("ru_jcuken must be registered");
+        assert_eq!(ru.base_to_shifted('ё'), Some('Ё'));
+        assert_eq!(ru.base_to_shifted('3'), Some('№'));
+        assert_eq!(
+            ru.finger_for_char('а'),
+            FingerAssignment::new(Hand::Left, Finger::Index)
+        );
+        assert_eq!(
+            ru.finger_for_char('о'),
+            FingerAssignment::new(Hand::Right, Finger::Index)
+        );
+
+        let uk = KeyboardModel::from_key("uk_jcuken").expect("uk_jcuken must be registered");
+        for ch in ['і', 'ї', 'є', 'ґ', '\'', 'Ґ'] {
+            assert!(
+                uk.physical_key_for(ch).is_some(),
+                "uk_jcuken missing locale char {ch}"
+            );
+        }
+
+        let el = KeyboardModel::from_key("el_greek").expect("el_greek must be registered");
+        assert_eq!(el.base_to_shifted('σ'), Some('Σ'));
+        assert_eq!(el.dead_key_sequence('ά'), Some(('΄', 'α')));
+        assert_eq!(el.dead_key_sequence('Ώ'), Some(('΄', 'Ω')));
+        assert_eq!(el.dead_key_sequence('ΰ'), Some(('΅', 'υ')));
+        assert!(el.can_type('ϊ'));
+        assert!(!el.can_type('a'));
+        assert_eq!(el.finger_for_char('ό'), el.finger_for_char('ο'));
+        assert_eq!(el.next_stroke('ά', false), '΄');
+        assert_eq!(el.next_stroke('ά', true), 'α');
+        assert_eq!(el.next_stroke('α', false), 'α');
+
+        let fr = KeyboardModel::from_key("fr_azerty").expect("fr_azerty must be registered");
+        assert_eq!(fr.dead_key_sequence('ê'), Some(('^', 'e')));
+        assert_eq!(fr.next_stroke('é', false), 'é');
+        assert!(fr.can_type('ï'));
+
         let tr = KeyboardModel::from_key("tr_intl").expect("tr_intl must be registered");
         for ch in ['ç', 'ğ', 'ı', 'ö', 'ş', 'ü'] {
             assert!(
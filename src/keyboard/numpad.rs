@@ -0,0 +1,110 @@
+use crate::keyboard::display::ENTER;
+use crate::keyboard::finger::{Finger, FingerAssignment, Hand};
+
+/// One key of the standard 17-key numeric keypad, placed on a 4x5 grid.
+#[derive(Clone, Copy, Debug)]
+pub struct NumpadKey {
+    /// Character the key types; `None` for Num Lock.
+    pub ch: Option<char>,
+    pub label: &'static str,
+    pub col: u16,
+    pub row: u16,
+    /// `0` spans two columns.
+    pub wide: bool,
+    /// `+` and Enter span two rows.
+    pub tall: bool,
+    pub finger: FingerAssignment,
+}
+
+pub const NUMPAD_COLS: u16 = 4;
+pub const NUMPAD_ROWS: u16 = 5;
+
+macro_rules! pad {
+    ($ch:expr, $label:expr, $col:expr, $row:expr, $finger:ident) => {
+        pad!($ch, $label, $col, $row, $finger, false, false)
+    };
+    ($ch:expr, $label:expr, $col:expr, $row:expr, $finger:ident, $wide:expr, $tall:expr) => {
+        NumpadKey {
+            ch: $ch,
+            label: $label,
+            col: $col,
+            row: $row,
+            wide: $wide,
+            tall: $tall,
+            finger: FingerAssignment {
+                hand: Hand::Right,
+                finger: Finger::$finger,
+            },
+        }
+    };
+}
+
+/// Home row is 4-5-6 with the index finger on 4; the thumb takes 0 and the
+/// pinky covers the operator column and Enter.
+pub const NUMPAD_KEYS: &[NumpadKey] = &[
+    pad!(None, "Num", 0, 0, Index),
+    pad!(Some('/'), "/", 1, 0, Middle),
+    pad!(Some('*'), "*", 2, 0, Ring),
+    pad!(Some('-'), "-", 3, 0, Pinky),
+    pad!(Some('7'), "7", 0, 1, Index),
+    pad!(Some('8'), "8", 1, 1, Middle),
+    pad!(Some('9'), "9", 2, 1, Ring),
+    pad!(Some('+'), "+", 3, 1, Pinky, false, true),
+    pad!(Some('4'), "4", 0, 2, Index),
+    pad!(Some('5'), "5", 1, 2, Middle),
+    pad!(Some('6'), "6", 2, 2, Ring),
+    pad!(Some('1'), "1", 0, 3, Index),
+    pad!(Some('2'), "2", 1, 3, Middle),
+    pad!(Some('3'), "3", 2, 3, Ring),
+    pad!(Some(ENTER), "Ent", 3, 3, Pinky, false, true),
+    pad!(Some('0'), "0", 0, 4, Thumb, true, false),
+    pad!(Some('.'), ".", 2, 4, Ring),
+];
+
+/// Characters a numpad drill may ask for, in keypad order.
+pub fn numpad_chars() -> impl Iterator<Item = char> {
+    NUMPAD_KEYS.iter().filter_map(|key| key.ch)
+}
+
+pub fn numpad_key(ch: char) -> Option<&'static NumpadKey> {
+    NUMPAD_KEYS.iter().find(|key| key.ch == Some(ch))
+}
+
+pub fn numpad_finger(ch: char) -> Option<FingerAssignment> {
+    numpad_key(ch).map(|key| key.finger)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keyboard::display::{key_display_name, numpad_sentinel};
+
+    #[test]
+    fn every_numpad_key_has_a_sentinel_and_a_cell() {
+        let mut cells = std::collections::HashSet::new();
+        for key in NUMPAD_KEYS {
+            let cols = if key.wide { 2 } else { 1 };
+            let rows = if key.tall { 2 } else { 1 };
+            for dc in 0..cols {
+                for dr in 0..rows {
+                    assert!(cells.insert((key.col + dc, key.row + dr)), "{key:?}");
+                }
+            }
+            if let Some(ch) = key.ch {
+                let sentinel = numpad_sentinel(ch).expect("numpad key has a sentinel");
+                assert!(key_display_name(sentinel).starts_with("Num "), "{key:?}");
+            }
+        }
+        assert_eq!(cells.len(), (NUMPAD_COLS * NUMPAD_ROWS) as usize);
+    }
+
+    #[test]
+    fn zero_is_the_thumb_and_home_row_is_four_five_six() {
+        assert_eq!(numpad_finger('0').unwrap().finger, Finger::Thumb);
+        assert_eq!(numpad_finger('4').unwrap().finger, Finger::Index);
+        assert_eq!(numpad_finger('5').unwrap().finger, Finger::Middle);
+        assert_eq!(numpad_finger('6').unwrap().finger, Finger::Ring);
+        assert_eq!(numpad_finger(ENTER).unwrap().finger, Finger::Pinky);
+        assert!(numpad_finger('a').is_none());
+    }
+}
@@ -3,11 +3,18 @@
 use std::fmt;
 use std::sync::OnceLock;
 
+use serde::Deserialize;
+
 use crate::keyboard::model::KeyboardModel;
+use crate::l10n::runtime_pack::runtime_packs;
+use crate::l10n::text_rules::*;
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Script {
     Latin,
+    Cyrillic,
+    Greek,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -84,6 +91,7 @@ pub struct LanguagePack {
     pub supported_keyboard_layout_keys: &'static [&'static str],
     pub primary_letter_sequence: &'static str,
     pub support_level: SupportLevel,
+    pub text_rules: &'static TextRules,
 }
 
 pub const DEFAULT_LATIN_PRIMARY_SEQUENCE: &str = "etaoinshrdlcumwfgypbvkjxqz";
@@ -107,6 +115,9 @@ const LV_PRIMARY_SEQUENCE: &str = "asiternlkopmuādīvzēgjbcšfūņļķģžhč"
 const SL_PRIMARY_SEQUENCE: &str = "aeiotnrsvpkldjzmučbgcšžhf";
 const ET_PRIMARY_SEQUENCE: &str = "aeistulmnkrovpdhgäjõüböfš";
 const TR_PRIMARY_SEQUENCE: &str = "aeinrlımkdysutobşzügğcçöhpvfj";
+const EL_PRIMARY_SEQUENCE: &str = "αοιετσνηυρπκμλςάόίέωδγήύχθώφβξζψϊϋ";
+const RU_PRIMARY_SEQUENCE: &str = "оеаинтсрвлкмдпуяыьгзбчйхжшюцщэфъё";
+const UK_PRIMARY_SEQUENCE: &str = "оаніивтерсклудмпзяьгбчхцїйжшюєщфґ";
 
 const EN_LAYOUTS: &[&str] = &["qwerty", "dvorak", "colemak"];
 const DE_LAYOUTS: &[&str] = &["de_qwertz", "qwerty"];
@@ -129,6 +140,9 @@ const LV_LAYOUTS: &[&str] = &["lv_intl", "qwerty"];
 const SL_LAYOUTS: &[&str] = &["sl_intl", "qwerty"];
 const ET_LAYOUTS: &[&str] = &["et_intl", "qwerty"];
 const TR_LAYOUTS: &[&str] = &["tr_intl", "qwerty"];
+const EL_LAYOUTS: &[&str] = &["el_greek"];
+const RU_LAYOUTS: &[&str] = &["ru_jcuken"];
+const UK_LAYOUTS: &[&str] = &["uk_jcuken"];
 
 // Seed registry for phase 0. Support levels will be tightened as keyboard
 // profiles and Unicode handling phases are implemented.
@@ -142,6 +156,7 @@ static LANGUAGE_PACKS: &[LanguagePack] = &[
         supported_keyboard_layout_keys: EN_LAYOUTS,
         primary_letter_sequence: DEFAULT_LATIN_PRIMARY_SEQUENCE,
         support_level: SupportLevel::Full,
+        text_rules: &EN_TEXT_RULES,
     },
     LanguagePack {
         language_key: "de",
@@ -152,6 +167,7 @@ static LANGUAGE_PACKS: &[LanguagePack] = &[
         supported_keyboard_layout_keys: DE_LAYOUTS,
         primary_letter_sequence: DE_PRIMARY_SEQUENCE,
         support_level: SupportLevel::Full,
+        text_rules: &DE_TEXT_RULES,
     },
     LanguagePack {
         language_key: "es",
@@ -162,6 +178,7 @@ static LANGUAGE_PACKS: &[LanguagePack] = &[
         supported_keyboard_layout_keys: ES_LAYOUTS,
         primary_letter_sequence: ES_PRIMARY_SEQUENCE,
         support_level: SupportLevel::Full,
+        text_rules: &ES_TEXT_RULES,
     },
     LanguagePack {
         language_key: "fr",
@@ -172,6 +189,7 @@ static LANGUAGE_PACKS: &[LanguagePack] = &[
         supported_keyboard_layout_keys: FR_LAYOUTS,
         primary_letter_sequence: FR_PRIMARY_SEQUENCE,
         support_level: SupportLevel::Full,
+        text_rules: &FR_TEXT_RULES,
     },
     LanguagePack {
         language_key: "it",
@@ -182,6 +200,7 @@ static LANGUAGE_PACKS: &[LanguagePack] = &[
         supported_keyboard_layout_keys: IT_LAYOUTS,
         primary_letter_sequence: IT_PRIMARY_SEQUENCE,
         support_level: SupportLevel::Full,
+        text_rules: &IT_TEXT_RULES,
     },
     LanguagePack {
         language_key: "pt",
@@ -192,6 +211,7 @@ static LANGUAGE_PACKS: &[LanguagePack] = &[
         supported_keyboard_layout_keys: PT_LAYOUTS,
         primary_letter_sequence: PT_PRIMARY_SEQUENCE,
         support_level: SupportLevel::Full,
+        text_rules: &PT_TEXT_RULES,
     },
     LanguagePack {
         language_key: "nl",
@@ -202,6 +222,7 @@ static LANGUAGE_PACKS: &[LanguagePack] = &[
         supported_keyboard_layout_keys: NL_LAYOUTS,
         primary_letter_sequence: NL_PRIMARY_SEQUENCE,
         support_level: SupportLevel::Full,
+        text_rules: &NL_TEXT_RULES,
     },
     LanguagePack {
         language_key: "sv",
@@ -212,6 +233,7 @@ static LANGUAGE_PACKS: &[LanguagePack] = &[
         supported_keyboard_layout_keys: SV_LAYOUTS,
         primary_letter_sequence: SV_PRIMARY_SEQUENCE,
         support_level: SupportLevel::Full,
+        text_rules: &SV_TEXT_RULES,
     },
     LanguagePack {
         language_key: "da",
@@ -222,6 +244,7 @@ static LANGUAGE_PACKS: &[LanguagePack] = &[
         supported_keyboard_layout_keys: DA_LAYOUTS,
         primary_letter_sequence: DA_PRIMARY_SEQUENCE,
         support_level: SupportLevel::Full,
+        text_rules: &DA_TEXT_RULES,
     },
     LanguagePack {
         language_key: "nb",
@@ -232,6 +255,7 @@ static LANGUAGE_PACKS: &[LanguagePack] = &[
         supported_keyboard_layout_keys: NB_LAYOUTS,
         primary_letter_sequence: NB_PRIMARY_SEQUENCE,
         support_level: SupportLevel::Full,
+        text_rules: &NB_TEXT_RULES,
     },
     LanguagePack {
         language_key: "fi",
@@ -242,6 +266,7 @@ static LANGUAGE_PACKS: &[LanguagePack] = &[
         supported_keyboard_layout_keys: FI_LAYOUTS,
         primary_letter_sequence: FI_PRIMARY_SEQUENCE,
         support_level: SupportLevel::Full,
+        text_rules: &FI_TEXT_RULES,
     },
     LanguagePack {
         language_key: "pl",
@@ -252,6 +277,7 @@ static LANGUAGE_PACKS: &[LanguagePack] = &[
         supported_keyboard_layout_keys: PL_LAYOUTS,
         primary_letter_sequence: PL_PRIMARY_SEQUENCE,
         support_level: SupportLevel::Full,
+        text_rules: &PL_TEXT_RULES,
     },
     LanguagePack {
         language_key: "cs",
@@ -262,6 +288,7 @@ static LANGUAGE_PACKS: &[LanguagePack] = &[
         supported_keyboard_layout_keys: CS_LAYOUTS,
         primary_letter_sequence: CS_PRIMARY_SEQUENCE,
         support_level: SupportLevel::Full,
+        text_rules: &CS_TEXT_RULES,
     },
     LanguagePack {
         language_key: "ro",
@@ -272,6 +299,7 @@ static LANGUAGE_PACKS: &[LanguagePack] = &[
         supported_keyboard_layout_keys: RO_LAYOUTS,
         primary_letter_sequence: RO_PRIMARY_SEQUENCE,
         support_level: SupportLevel::Full,
+        text_rules: &RO_TEXT_RULES,
     },
     LanguagePack {
         language_key: "hr",
@@ -282,6 +310,7 @@ static LANGUAGE_PACKS: &[LanguagePack] = &[
         supported_keyboard_layout_keys: HR_LAYOUTS,
         primary_letter_sequence: HR_PRIMARY_SEQUENCE,
         support_level: SupportLevel::Full,
+        text_rules: &HR_TEXT_RULES,
     },
     LanguagePack {
         language_key: "hu",
@@ -292,6 +321,7 @@ static LANGUAGE_PACKS: &[LanguagePack] = &[
         supported_keyboard_layout_keys: HU_LAYOUTS,
         primary_letter_sequence: HU_PRIMARY_SEQUENCE,
         support_level: SupportLevel::Full,
+        text_rules: &HU_TEXT_RULES,
     },
     LanguagePack {
         language_key: "lt",
@@ -302,6 +332,7 @@ static LANGUAGE_PACKS: &[LanguagePack] = &[
         supported_keyboard_layout_keys: LT_LAYOUTS,
         primary_letter_sequence: LT_PRIMARY_SEQUENCE,
         support_level: SupportLevel::Full,
+        text_rules: &LT_TEXT_RULES,
     },
     LanguagePack {
         language_key: "lv",
@@ -312,6 +343,7 @@ static LANGUAGE_PACKS: &[LanguagePack] = &[
         supported_keyboard_layout_keys: LV_LAYOUTS,
         primary_letter_sequence: LV_PRIMARY_SEQUENCE,
         support_level: SupportLevel::Full,
+        text_rules: &LV_TEXT_RULES,
     },
     LanguagePack {
         language_key: "sl",
@@ -322,6 +354,7 @@ static LANGUAGE_PACKS: &[LanguagePack] = &[
         supported_keyboard_layout_keys: SL_LAYOUTS,
         primary_letter_sequence: SL_PRIMARY_SEQUENCE,
         support_level: SupportLevel::Full,
+        text_rules: &SL_TEXT_RULES,
     },
     LanguagePack {
         language_key: "et",
@@ -332,6 +365,7 @@ static LANGUAGE_PACKS: &[LanguagePack] = &[
         supported_keyboard_layout_keys: ET_LAYOUTS,
         primary_letter_sequence: ET_PRIMARY_SEQUENCE,
         support_level: SupportLevel::Full,
+        text_rules: &ET_TEXT_RULES,
     },
     LanguagePack {
         language_key: "tr",
@@ -342,15 +376,64 @@ static LANGUAGE_PACKS: &[LanguagePack] = &[
         supported_keyboard_layout_keys: TR_LAYOUTS,
         primary_letter_sequence: TR_PRIMARY_SEQUENCE,
         support_level: SupportLevel::Full,
+        text_rules: &TR_TEXT_RULES,
+    },
+    LanguagePack {
+        language_key: "el",
+        display_name: "Greek",
+        autonym: "Ελληνικά",
+        script: Script::Greek,
+        dictionary_asset_id: "words-el",
+        supported_keyboard_layout_keys: EL_LAYOUTS,
+        primary_letter_sequence: EL_PRIMARY_SEQUENCE,
+        support_level: SupportLevel::Full,
+        text_rules: &EL_TEXT_RULES,
+    },
+    LanguagePack {
+        language_key: "ru",
+        display_name: "Russian",
+        autonym: "Русский",
+        script: Script::Cyrillic,
+        dictionary_asset_id: "words-ru",
+        supported_keyboard_layout_keys: RU_LAYOUTS,
+        primary_letter_sequence: RU_PRIMARY_SEQUENCE,
+        support_level: SupportLevel::Full,
+        text_rules: &RU_TEXT_RULES,
+    },
+    LanguagePack {
+        language_key: "uk",
+        display_name: "Ukrainian",
+        autonym: "Українська",
+        script: Script::Cyrillic,
+        dictionary_asset_id: "words-uk",
+        supported_keyboard_layout_keys: UK_LAYOUTS,
+        primary_letter_sequence: UK_PRIMARY_SEQUENCE,
+        support_level: SupportLevel::Full,
+        text_rules: &UK_TEXT_RULES,
     },
 ];
 
+/// Bundled packs followed by the valid packs from the user's languages directory.
 pub fn language_packs() -> &'static [LanguagePack] {
+    static PACKS: OnceLock<Vec<LanguagePack>> = OnceLock::new();
+    PACKS
+        .get_or_init(|| {
+            LANGUAGE_PACKS
+                .iter()
+                .copied()
+                .chain(runtime_packs().packs.iter().map(|runtime| runtime.pack))
+                .collect()
+        })
+        .as_slice()
+}
+
+/// Packs compiled into the binary, without any runtime additions.
+pub fn bundled_language_packs() -> &'static [LanguagePack] {
     LANGUAGE_PACKS
 }
 
 pub fn find_language_pack(language_key: &str) -> Option<&'static LanguagePack> {
-    LANGUAGE_PACKS
+    language_packs()
         .iter()
         .find(|pack| pack.language_key == language_key)
 }
@@ -359,7 +442,7 @@ pub fn supported_dictionary_languages() -> &'static [&'static str] {
     static SUPPORTED: OnceLock<Vec<&'static str>> = OnceLock::new();
     SUPPORTED
         .get_or_init(|| {
-            LANGUAGE_PACKS
+            language_packs()
                 .iter()
                 .filter(|pack| matches!(pack.support_level, SupportLevel::Full))
                 .map(|pack| pack.language_key)
@@ -369,7 +452,7 @@ pub fn supported_dictionary_languages() -> &'static [&'static str] {
 }
 
 pub fn dictionary_languages_for_layout(layout_key: &str) -> Vec<&'static str> {
-    LANGUAGE_PACKS
+    language_packs()
         .iter()
         .filter_map(
             |pack| match validate_language_layout_pair(pack.language_key, layout_key) {
@@ -394,11 +477,60 @@ pub fn validate_language_layout_pair(
             language_key.to_string(),
         ));
     };
+    validate_pack_layout(pack, layout_key)
+}
 
-    if !KeyboardModel::supported_layout_keys().contains(&layout_key) {
+fn validate_pack_layout(
+    pack: &LanguagePack,
+    layout_key: &str,
+) -> Result<CapabilityState, LanguageLayoutValidationError> {
+    let language_key = pack.language_key;
+    let Some(layout_script) = KeyboardModel::layout_script(layout_key) else {
         return Err(LanguageLayoutValidationError::UnknownLayout(
             layout_key.to_string(),
         ));
+    };
+
+    // A Latin layout has no keys for Cyrillic letters and vice versa.
+    if layout_script != pack.script {
+        return Err(
+            LanguageLayoutValidationError::UnsupportedLanguageLayoutPair {
+                language_key: language_key.to_string(),
+                layout_key: layout_key.to_string(),
+            },
+        );
+    }
+
+    // Runtime packs are only offered on the layouts their manifest lists; the
+    // bundled Latin packs can fall back to any Latin layout.
+    let bundled = LANGUAGE_PACKS
+        .iter()
+        .any(|bundled| bundled.language_key == language_key);
+    if !bundled && !pack.supported_keyboard_layout_keys.contains(&layout_key) {
+        return Err(
+            LanguageLayoutValidationError::UnsupportedLanguageLayoutPair {
+                language_key: language_key.to_string(),
+                layout_key: layout_key.to_string(),
+            },
+        );
+    }
+
+    // Cyrillic alphabets differ per language (ы, ъ, э against і, ї, є, ґ), so
+    // a Cyrillic layout must have a key for every primary letter of the pack.
+    if pack.script != Script::Latin {
+        let typable = KeyboardModel::from_key(layout_key).is_some_and(|model| {
+            normalized_primary_letter_sequence(pack.primary_letter_sequence)
+                .into_iter()
+                .all(|ch| model.can_type(ch))
+        });
+        if !typable {
+            return Err(
+                LanguageLayoutValidationError::UnsupportedLanguageLayoutPair {
+                    language_key: language_key.to_string(),
+                    layout_key: layout_key.to_string(),
+                },
+            );
+        }
     }
 
     if matches!(pack.support_level, SupportLevel::Blocked) {
@@ -464,6 +596,16 @@ mod tests {
         pairs
     }
 
+    #[test]
+    fn tests_only_see_bundled_packs() {
+        let bundled = bundled_language_packs();
+        let all = language_packs();
+        assert_eq!(all.len(), bundled.len());
+        for (runtime, bundled) in all.iter().zip(bundled) {
+            assert_eq!(runtime.language_key, bundled.language_key);
+        }
+    }
+
     #[test]
     fn language_pack_keys_are_unique() {
         let mut seen = HashSet::new();
@@ -472,7 +614,14 @@ mod tests {
             assert!(pack.primary_letter_sequence.len() >= 10);
             assert!(!pack.dictionary_asset_id.is_empty());
             assert!(!pack.supported_keyboard_layout_keys.is_empty());
-            assert!(matches!(pack.script, Script::Latin));
+            for &layout_key in pack.supported_keyboard_layout_keys {
+                assert_eq!(
+                    KeyboardModel::layout_script(layout_key),
+                    Some(pack.script),
+                    "layout {layout_key} does not match the script of {}",
+                    pack.language_key
+                );
+            }
         }
     }
 
@@ -516,6 +665,8 @@ mod tests {
             let native_layout_key = match pack.language_key {
                 "de" => "de_qwertz".to_string(),
                 "fr" => "fr_azerty".to_string(),
+                "el" => "el_greek".to_string(),
+                "ru" | "uk" => format!("{}_jcuken", pack.language_key),
                 key => format!("{key}_intl"),
             };
 
@@ -526,7 +677,7 @@ mod tests {
                     continue;
                 }
                 assert!(
-                    model.physical_key_for(ch).is_some(),
+                    model.can_type(ch),
                     "native layout {} should type locale letter '{}' for language {}",
                     native_layout_key,
                     ch,
@@ -584,13 +735,69 @@ mod tests {
 
     #[test]
     fn dictionary_languages_for_layout_contains_full_language_set_for_supported_layouts() {
+        let latin_count = language_packs()
+            .iter()
+            .filter(|pack| pack.script == Script::Latin)
+            .count();
+
         let de = dictionary_languages_for_layout("de_qwertz");
-        assert_eq!(de.len(), supported_dictionary_languages().len());
+        assert_eq!(de.len(), latin_count);
         assert!(de.contains(&"de"));
 
         let fr = dictionary_languages_for_layout("fr_azerty");
-        assert_eq!(fr.len(), supported_dictionary_languages().len());
+        assert_eq!(fr.len(), latin_count);
         assert!(fr.contains(&"fr"));
+
+        assert_eq!(dictionary_languages_for_layout("ru_jcuken"), vec!["ru"]);
+        assert_eq!(dictionary_languages_for_layout("uk_jcuken"), vec!["uk"]);
+    }
+
+    #[test]
+    fn validate_language_layout_pair_rejects_untypable_pairs() {
+        for (language_key, layout_key) in [
+            ("ru", "qwerty"),
+            ("en", "uk_jcuken"),
+            ("ru", "uk_jcuken"),
+            ("uk", "ru_jcuken"),
+        ] {
+            let err = validate_language_layout_pair(language_key, layout_key).unwrap_err();
+            assert!(matches!(
+                err,
+                LanguageLayoutValidationError::UnsupportedLanguageLayoutPair { .. }
+            ));
+        }
+        assert_eq!(
+            validate_language_layout_pair("uk", "uk_jcuken"),
+            Ok(CapabilityState::Enabled)
+        );
+    }
+
+    #[test]
+    fn runtime_packs_only_pair_with_their_listed_layouts() {
+        let eo = LanguagePack {
+            language_key: "eo",
+            display_name: "Esperanto",
+            autonym: "Esperanto",
+            script: Script::Latin,
+            dictionary_asset_id: "words-eo",
+            supported_keyboard_layout_keys: &["colemak"],
+            primary_letter_sequence: "aieonlsrtkjumdpvgbcfzĉĝĥĵŝŭh",
+            support_level: SupportLevel::Full,
+            text_rules: &EN_TEXT_RULES,
+        };
+        assert_eq!(
+            validate_pack_layout(&eo, "colemak"),
+            Ok(CapabilityState::Enabled)
+        );
+        assert!(matches!(
+            validate_pack_layout(&eo, "qwerty"),
+            Err(LanguageLayoutValidationError::UnsupportedLanguageLayoutPair { .. })
+        ));
+        // Bundled Latin packs keep working on unlisted Latin layouts.
+        assert_eq!(
+            validate_language_layout_pair("de", "dvorak"),
+            Ok(CapabilityState::Enabled)
+        );
     }
 
     #[test]
@@ -1,2 +1,4 @@
 pub mod language_pack;
+pub mod runtime_pack;
+pub mod text_rules;
 pub mod unicode;
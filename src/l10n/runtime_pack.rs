@@ -0,0 +1,282 @@
+//! Language packs loaded at startup from `~/.config/keydr/languages/<key>/`.
+//!
+//! Each pack directory holds a `manifest.toml` and a `words.json` in the same
+//! format as the bundled dictionaries. A pack that fails validation is skipped
+//! and recorded in the report instead of aborting startup.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+
+use crate::keyboard::model::KeyboardModel;
+use crate::l10n::language_pack::{
+    LanguagePack, Script, SupportLevel, bundled_language_packs, has_usable_primary_letter_sequence,
+};
+use crate::l10n::text_rules::{NEUTRAL_TEXT_RULES, TextRules};
+
+pub const MANIFEST_FILE: &str = "manifest.toml";
+pub const WORDS_FILE: &str = "words.json";
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Manifest {
+    display_name: String,
+    autonym: String,
+    script: Script,
+    primary_letter_sequence: String,
+    layouts: Vec<String>,
+    /// Key of a bundled language whose quotes, numbers and casing to reuse.
+    #[serde(default)]
+    text_rules: Option<String>,
+}
+
+pub struct RuntimeLanguagePack {
+    pub pack: LanguagePack,
+    pub words_json: String,
+    pub word_count: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PackIssue {
+    pub dir: PathBuf,
+    pub message: String,
+}
+
+#[derive(Default)]
+pub struct RuntimePackReport {
+    pub packs: Vec<RuntimeLanguagePack>,
+    pub issues: Vec<PackIssue>,
+}
+
+pub fn runtime_packs_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("keydr").join("languages"))
+}
+
+/// Packs from the user's languages directory, loaded once per process. Unit
+/// tests never read it, so they see the same packs on every machine.
+pub fn runtime_packs() -> &'static RuntimePackReport {
+    static REPORT: OnceLock<RuntimePackReport> = OnceLock::new();
+    REPORT.get_or_init(|| {
+        if cfg!(test) {
+            return RuntimePackReport::default();
+        }
+        runtime_packs_dir()
+            .map(|dir| load_runtime_packs(&dir))
+            .unwrap_or_default()
+    })
+}
+
+/// Raw `words.json` of a runtime pack, for the dictionary loader.
+pub fn runtime_words_json(language_key: &str) -> Option<&'static str> {
+    runtime_packs()
+        .packs
+        .iter()
+        .find(|runtime| runtime.pack.language_key == language_key)
+        .map(|runtime| runtime.words_json.as_str())
+}
+
+pub fn load_runtime_packs(dir: &Path) -> RuntimePackReport {
+    let mut report = RuntimePackReport::default();
+    let Ok(entries) = fs::read_dir(dir) else {
+        return report;
+    };
+    let mut pack_dirs: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect();
+    pack_dirs.sort();
+
+    for pack_dir in pack_dirs {
+        match load_pack(&pack_dir) {
+            Ok(pack) => report.packs.push(pack),
+            Err(err) => report.issues.push(PackIssue {
+                dir: pack_dir,
+                message: format!("{err:#}"),
+            }),
+        }
+    }
+    report
+}
+
+fn load_pack(dir: &Path) -> Result<RuntimeLanguagePack> {
+    let key = dir
+        .file_name()
+        .and_then(|name| name.to_str())
+        .context("directory name is not valid UTF-8")?;
+    if !key
+        .chars()
+        .all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '-' || ch == '_')
+    {
+        bail!("language key \"{key}\" may only use a-z, 0-9, '-' and '_'");
+    }
+    if bundled_language_packs()
+        .iter()
+        .any(|pack| pack.language_key == key)
+    {
+        bail!("\"{key}\" is a bundled language and cannot be replaced");
+    }
+
+    let manifest_text = fs::read_to_string(dir.join(MANIFEST_FILE))
+        .with_context(|| format!("cannot read {MANIFEST_FILE}"))?;
+    let manifest: Manifest =
+        toml::from_str(&manifest_text).with_context(|| format!("malformed {MANIFEST_FILE}"))?;
+
+    if manifest.display_name.trim().is_empty() || manifest.autonym.trim().is_empty() {
+        bail!("display_name and autonym must not be empty");
+    }
+    if !has_usable_primary_letter_sequence(&manifest.primary_letter_sequence) {
+        bail!("primary_letter_sequence contains no letters");
+    }
+    if manifest.layouts.is_empty() {
+        bail!("layouts must list at least one keyboard layout");
+    }
+    for layout_key in &manifest.layouts {
+        match KeyboardModel::layout_script(layout_key) {
+            None => bail!("unknown keyboard layout \"{layout_key}\""),
+            Some(script) if script != manifest.script => {
+                bail!("layout \"{layout_key}\" does not match the pack's script")
+            }
+            Some(_) => {}
+        }
+    }
+    let text_rules = resolve_text_rules(manifest.text_rules.as_deref())?;
+
+    let words_json = fs::read_to_string(dir.join(WORDS_FILE))
+        .with_context(|| format!("cannot read {WORDS_FILE}"))?;
+    let words: Vec<String> = serde_json::from_str(&words_json)
+        .with_context(|| format!("{WORDS_FILE} must be a JSON array of strings"))?;
+    if words.is_empty() {
+        bail!("{WORDS_FILE} has no words");
+    }
+
+    // Packs live for the whole process, like the bundled ones they join.
+    let layouts: Vec<&'static str> = manifest.layouts.into_iter().map(leak).collect();
+    let pack = LanguagePack {
+        language_key: leak(key.to_string()),
+        display_name: leak(manifest.display_name),
+        autonym: leak(manifest.autonym),
+        script: manifest.script,
+        dictionary_asset_id: leak(format!("words-{key}")),
+        supported_keyboard_layout_keys: Box::leak(layouts.into_boxed_slice()),
+        primary_letter_sequence: leak(manifest.primary_letter_sequence),
+        support_level: SupportLevel::Full,
+        text_rules,
+    };
+    Ok(RuntimeLanguagePack {
+        pack,
+        words_json,
+        word_count: words.len(),
+    })
+}
+
+fn resolve_text_rules(language_key: Option<&str>) -> Result<&'static TextRules> {
+    let Some(language_key) = language_key else {
+        return Ok(&NEUTRAL_TEXT_RULES);
+    };
+    bundled_language_packs()
+        .iter()
+        .find(|pack| pack.language_key == language_key)
+        .map(|pack| pack.text_rules)
+        .with_context(|| format!("text_rules names unknown bundled language \"{language_key}\""))
+}
+
+fn leak(value: String) -> &'static str {
+    Box::leak(value.into_boxed_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_pack(root: &Path, key: &str, manifest: &str, words: &str) {
+        let dir = root.join(key);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join(MANIFEST_FILE), manifest).unwrap();
+        fs::write(dir.join(WORDS_FILE), words).unwrap();
+    }
+
+    const EO_MANIFEST: &str = r#"
+display_name = "Esperanto"
+autonym = "Esperanto"
+script = "latin"
+primary_letter_sequence = "aieonlsrtkjumdpvgbcfzĉĝĥĵŝŭh"
+layouts = ["qwerty", "colemak"]
+text_rules = "en"
+"#;
+
+    #[test]
+    fn valid_pack_loads_with_manifest_fields() {
+        let root = tempfile::tempdir().unwrap();
+        write_pack(
+            root.path(),
+            "eo",
+            EO_MANIFEST,
+            r#"["kaj", "estas", "ĉiuj"]"#,
+        );
+
+        let report = load_runtime_packs(root.path());
+        assert!(report.issues.is_empty(), "{:?}", report.issues);
+        assert_eq!(report.packs.len(), 1);
+        let runtime = &report.packs[0];
+        assert_eq!(runtime.word_count, 3);
+        assert_eq!(runtime.pack.language_key, "eo");
+        assert_eq!(runtime.pack.autonym, "Esperanto");
+        assert_eq!(runtime.pack.script, Script::Latin);
+        assert_eq!(
+            runtime.pack.supported_keyboard_layout_keys,
+            &["qwerty", "colemak"]
+        );
+        assert_eq!(runtime.pack.text_rules.currency, "$");
+    }
+
+    #[test]
+    fn malformed_packs_are_reported_and_skipped() {
+        let root = tempfile::tempdir().unwrap();
+        write_pack(root.path(), "eo", EO_MANIFEST, r#"["kaj"]"#);
+        write_pack(root.path(), "en", EO_MANIFEST, r#"["and"]"#);
+        write_pack(root.path(), "is", "display_name = ", r#"["og"]"#);
+        write_pack(
+            root.path(),
+            "ca",
+            &EO_MANIFEST.replace("\"colemak\"", "\"ru_jcuken\""),
+            r#"["i"]"#,
+        );
+        write_pack(root.path(), "cy", EO_MANIFEST, r#"{"words": []}"#);
+        fs::create_dir_all(root.path().join("Bad Key")).unwrap();
+
+        let report = load_runtime_packs(root.path());
+        let keys: Vec<&str> = report
+            .packs
+            .iter()
+            .map(|runtime| runtime.pack.language_key)
+            .collect();
+        assert_eq!(keys, ["eo"]);
+
+        let issue_for = |key: &str| {
+            report
+                .issues
+                .iter()
+                .find(|issue| issue.dir.ends_with(key))
+                .map(|issue| issue.message.clone())
+                .unwrap_or_else(|| panic!("no issue reported for {key}"))
+        };
+        assert_eq!(report.issues.len(), 5);
+        assert!(issue_for("en").contains("bundled"));
+        assert!(issue_for("is").contains(MANIFEST_FILE));
+        assert!(issue_for("ca").contains("ru_jcuken"));
+        assert!(issue_for("cy").contains(WORDS_FILE));
+        assert!(issue_for("Bad Key").contains("language key"));
+    }
+
+    #[test]
+    fn missing_directory_yields_empty_report() {
+        let root = tempfile::tempdir().unwrap();
+        let report = load_runtime_packs(&root.path().join("languages"));
+        assert!(report.packs.is_empty());
+        assert!(report.issues.is_empty());
+    }
+}
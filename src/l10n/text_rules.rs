@@ -0,0 +1,442 @@
+//! Per-language typographic rules consumed by the capitalization, punctuation
+//! and number post-processing passes.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CaseMapping {
+    /// Unicode default case mapping.
+    Default,
+    /// Turkish/Azerbaijani: `i` ↔ `İ` and `ı` ↔ `I`.
+    Turkic,
+    /// Greek: `σ` and word-final `ς` share the capital `Σ`.
+    Greek,
+}
+
+impl CaseMapping {
+    pub fn to_upper(self, ch: char) -> char {
+        match (self, ch) {
+            (Self::Turkic, 'i') => 'İ',
+            (Self::Turkic, 'ı') => 'I',
+            _ => ch.to_uppercase().next().unwrap_or(ch),
+        }
+    }
+
+    pub fn to_lower(self, ch: char) -> char {
+        match (self, ch) {
+            (Self::Turkic, 'İ') => 'i',
+            (Self::Turkic, 'I') => 'ı',
+            _ => ch.to_lowercase().next().unwrap_or(ch),
+        }
+    }
+
+    pub fn eq_ignore_case(self, a: char, b: char) -> bool {
+        let fold = |ch| match (self, self.to_lower(ch)) {
+            (Self::Greek, 'ς') => 'σ',
+            (_, lower) => lower,
+        };
+        fold(a) == fold(b)
+    }
+
+    /// Lowercase a whole word. Greek turns a capital sigma that ends the word
+    /// into `ς`, which `to_lower` can't tell from a single char.
+    pub fn to_lower_word(self, word: &str) -> String {
+        let mut lower: Vec<char> = word.chars().map(|ch| self.to_lower(ch)).collect();
+        if self == Self::Greek {
+            for i in 1..lower.len() {
+                let ends_word = lower.get(i + 1).is_none_or(|next| !next.is_alphabetic());
+                if lower[i] == 'σ' && ends_word && lower[i - 1].is_alphabetic() {
+                    lower[i] = 'ς';
+                }
+            }
+        }
+        lower.into_iter().collect()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Apostrophes {
+    /// The language has no productive apostrophe use in running prose.
+    None,
+    /// Whole-word contractions plus an optional generic suffix (English `'s`).
+    Suffix {
+        contractions: &'static [(&'static str, &'static str)],
+        generic: Option<&'static str>,
+    },
+    /// Dutch-style plural `'s` after words ending in a long vowel (`auto's`).
+    VowelPlural,
+    /// Elided articles/pronouns glued to a following vowel-initial word (`l'homme`).
+    Elision(&'static [&'static str]),
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct TextRules {
+    pub open_quote: char,
+    pub close_quote: char,
+    /// French typography: a space before `; : ! ?` and inside `« »`.
+    pub spaced_punctuation: bool,
+    /// Spanish `¿…?` and `¡…!`.
+    pub inverted_marks: bool,
+    pub decimal_separator: char,
+    /// Thousands separator in prices and large amounts; `None` leaves them ungrouped.
+    pub group_separator: Option<char>,
+    /// Separator in day-month-year dates ("17/03/2024", "17.03.2024").
+    pub date_separator: char,
+    /// Currency sign for prices; empty for bare amounts.
+    pub currency: &'static str,
+    /// Currency written before the amount ("$12.50") rather than after ("12,50 €").
+    pub currency_before: bool,
+    pub apostrophes: Apostrophes,
+    /// Words placed after a number, e.g. "7 miles".
+    pub units: &'static [&'static str],
+    /// Words placed before a number, e.g. "room 42".
+    pub id_prefixes: &'static [&'static str],
+    pub case_mapping: CaseMapping,
+    /// Nouns are always capitalized (German).
+    pub capitalizes_nouns: bool,
+    /// Endings that mark a word as a noun when `capitalizes_nouns` is set.
+    pub noun_suffixes: &'static [&'static str],
+}
+
+const DEFAULT_RULES: TextRules = TextRules {
+    open_quote: '"',
+    close_quote: '"',
+    spaced_punctuation: false,
+    inverted_marks: false,
+    decimal_separator: ',',
+    group_separator: Some('.'),
+    date_separator: '.',
+    currency: "€",
+    currency_before: false,
+    apostrophes: Apostrophes::None,
+    units: &[],
+    id_prefixes: &[],
+    case_mapping: CaseMapping::Default,
+    capitalizes_nouns: false,
+    noun_suffixes: &[],
+};
+
+/// Neutral rules for runtime language packs that don't borrow a bundled set:
+/// a decimal point, no thousands grouping and no currency sign.
+pub static NEUTRAL_TEXT_RULES: TextRules = TextRules {
+    decimal_separator: '.',
+    group_separator: None,
+    currency: "",
+    ..DEFAULT_RULES
+};
+
+pub static EN_TEXT_RULES: TextRules = TextRules {
+    decimal_separator: '.',
+    group_separator: Some(','),
+    date_separator: '/',
+    currency: "$",
+    currency_before: true,
+    apostrophes: Apostrophes::Suffix {
+        contractions: &[
+            ("not", "n't"),
+            ("will", "'ll"),
+            ("would", "'d"),
+            ("have", "'ve"),
+            ("are", "'re"),
+            ("is", "'s"),
+        ],
+        generic: Some("'s"),
+    },
+    units: &["items", "miles", "days", "lines", "times", "parts"],
+    id_prefixes: &["room", "page", "step", "item", "line", "port"],
+    ..DEFAULT_RULES
+};
+
+pub static DE_TEXT_RULES: TextRules = TextRules {
+    open_quote: '„',
+    close_quote: '“',
+    apostrophes: Apostrophes::Suffix {
+        contractions: &[
+            ("geht", "'s"),
+            ("gibt", "'s"),
+            ("wie", "'s"),
+            ("hat", "'s"),
+            ("war", "'s"),
+        ],
+        generic: None,
+    },
+    units: &["Stück", "km", "Tage", "Zeilen", "Mal", "Teile"],
+    id_prefixes: &["Zimmer", "Seite", "Schritt", "Punkt", "Zeile", "Gleis"],
+    capitalizes_nouns: true,
+    noun_suffixes: &[
+        "ung", "heit", "keit", "schaft", "tion", "tät", "ling", "nis", "tum", "chen", "ismus",
+    ],
+    ..DEFAULT_RULES
+};
+
+pub static ES_TEXT_RULES: TextRules = TextRules {
+    open_quote: '«',
+    close_quote: '»',
+    inverted_marks: true,
+    date_separator: '/',
+    units: &["unidades", "km", "días", "líneas", "veces", "partes"],
+    id_prefixes: &["habitación", "página", "paso", "punto", "línea", "puerto"],
+    ..DEFAULT_RULES
+};
+
+pub static FR_TEXT_RULES: TextRules = TextRules {
+    open_quote: '«',
+    close_quote: '»',
+    spaced_punctuation: true,
+    group_separator: Some(' '),
+    date_separator: '/',
+    apostrophes: Apostrophes::Elision(&["l'", "d'", "j'", "n'", "s'", "c'", "qu'"]),
+    units: &["articles", "km", "jours", "lignes", "fois", "parties"],
+    id_prefixes: &["chambre", "page", "étape", "point", "ligne", "port"],
+    ..DEFAULT_RULES
+};
+
+pub static IT_TEXT_RULES: TextRules = TextRules {
+    open_quote: '«',
+    close_quote: '»',
+    date_separator: '/',
+    apostrophes: Apostrophes::Elision(&["l'", "un'", "dell'", "all'", "nell'", "quest'"]),
+    units: &["pezzi", "km", "giorni", "righe", "volte", "parti"],
+    id_prefixes: &["stanza", "pagina", "passo", "punto", "riga", "porta"],
+    ..DEFAULT_RULES
+};
+
+pub static PT_TEXT_RULES: TextRules = TextRules {
+    open_quote: '«',
+    close_quote: '»',
+    group_separator: Some(' '),
+    date_separator: '/',
+    units: &["itens", "km", "dias", "linhas", "vezes", "partes"],
+    id_prefixes: &["quarto", "página", "passo", "item", "linha", "porta"],
+    ..DEFAULT_RULES
+};
+
+pub static NL_TEXT_RULES: TextRules = TextRules {
+    open_quote: '“',
+    close_quote: '”',
+    date_separator: '-',
+    currency_before: true,
+    apostrophes: Apostrophes::VowelPlural,
+    units: &["stuks", "km", "dagen", "regels", "keer", "delen"],
+    id_prefixes: &["kamer", "pagina", "stap", "punt", "regel", "poort"],
+    ..DEFAULT_RULES
+};
+
+pub static SV_TEXT_RULES: TextRules = TextRules {
+    open_quote: '”',
+    close_quote: '”',
+    group_separator: Some(' '),
+    date_separator: '-',
+    currency: "kr",
+    units: &["st", "km", "dagar", "rader", "gånger", "delar"],
+    id_prefixes: &["rum", "sida", "steg", "punkt", "rad", "port"],
+    ..DEFAULT_RULES
+};
+
+pub static DA_TEXT_RULES: TextRules = TextRules {
+    open_quote: '»',
+    close_quote: '«',
+    currency: "kr.",
+    units: &["stk", "km", "dage", "linjer", "gange", "dele"],
+    id_prefixes: &["værelse", "side", "trin", "punkt", "linje", "port"],
+    ..DEFAULT_RULES
+};
+
+pub static NB_TEXT_RULES: TextRules = TextRules {
+    open_quote: '«',
+    close_quote: '»',
+    group_separator: Some(' '),
+    currency: "kr",
+    units: &["stk", "km", "dager", "linjer", "ganger", "deler"],
+    id_prefixes: &["rom", "side", "trinn", "punkt", "linje", "port"],
+    ..DEFAULT_RULES
+};
+
+pub static FI_TEXT_RULES: TextRules = TextRules {
+    open_quote: '”',
+    close_quote: '”',
+    group_separator: Some(' '),
+    units: &["kpl", "km", "päivää", "riviä", "kertaa", "osaa"],
+    id_prefixes: &["huone", "sivu", "vaihe", "kohta", "rivi", "portti"],
+    ..DEFAULT_RULES
+};
+
+pub static PL_TEXT_RULES: TextRules = TextRules {
+    open_quote: '„',
+    close_quote: '”',
+    group_separator: Some(' '),
+    currency: "zł",
+    units: &["szt", "km", "dni", "linii", "razy", "części"],
+    id_prefixes: &["pokój", "strona", "krok", "punkt", "linia", "port"],
+    ..DEFAULT_RULES
+};
+
+pub static CS_TEXT_RULES: TextRules = TextRules {
+    open_quote: '„',
+    close_quote: '“',
+    group_separator: Some(' '),
+    currency: "Kč",
+    units: &["ks", "km", "dní", "řádků", "krát", "částí"],
+    id_prefixes: &["pokoj", "strana", "krok", "bod", "řádek", "port"],
+    ..DEFAULT_RULES
+};
+
+pub static RO_TEXT_RULES: TextRules = TextRules {
+    open_quote: '„',
+    close_quote: '”',
+    currency: "lei",
+    units: &["bucăți", "km", "zile", "rânduri", "ori", "părți"],
+    id_prefixes: &["camera", "pagina", "pasul", "punctul", "linia", "portul"],
+    ..DEFAULT_RULES
+};
+
+pub static HR_TEXT_RULES: TextRules = TextRules {
+    open_quote: '„',
+    close_quote: '“',
+    units: &["kom", "km", "dana", "redaka", "puta", "dijelova"],
+    id_prefixes: &["soba", "stranica", "korak", "točka", "redak", "port"],
+    ..DEFAULT_RULES
+};
+
+pub static HU_TEXT_RULES: TextRules = TextRules {
+    open_quote: '„',
+    close_quote: '”',
+    group_separator: Some(' '),
+    currency: "Ft",
+    units: &["db", "km", "nap", "sor", "alkalom", "rész"],
+    id_prefixes: &["szoba", "oldal", "lépés", "pont", "sor", "port"],
+    ..DEFAULT_RULES
+};
+
+pub static LT_TEXT_RULES: TextRules = TextRules {
+    open_quote: '„',
+    close_quote: '“',
+    group_separator: Some(' '),
+    date_separator: '-',
+    units: &["vnt", "km", "dienos", "eilutės", "kartai", "dalys"],
+    id_prefixes: &[
+        "kambarys",
+        "puslapis",
+        "žingsnis",
+        "punktas",
+        "eilutė",
+        "prievadas",
+    ],
+    ..DEFAULT_RULES
+};
+
+pub static LV_TEXT_RULES: TextRules = TextRules {
+    open_quote: '„',
+    close_quote: '“',
+    group_separator: Some(' '),
+    units: &["gab", "km", "dienas", "rindas", "reizes", "daļas"],
+    id_prefixes: &["istaba", "lapa", "solis", "punkts", "rinda", "ports"],
+    ..DEFAULT_RULES
+};
+
+pub static SL_TEXT_RULES: TextRules = TextRules {
+    open_quote: '„',
+    close_quote: '“',
+    units: &["kos", "km", "dni", "vrstic", "krat", "delov"],
+    id_prefixes: &["soba", "stran", "korak", "točka", "vrstica", "vrata"],
+    ..DEFAULT_RULES
+};
+
+pub static ET_TEXT_RULES: TextRules = TextRules {
+    open_quote: '„',
+    close_quote: '“',
+    group_separator: Some(' '),
+    units: &["tk", "km", "päeva", "rida", "korda", "osa"],
+    id_prefixes: &["tuba", "lehekülg", "samm", "punkt", "rida", "port"],
+    ..DEFAULT_RULES
+};
+
+pub static TR_TEXT_RULES: TextRules = TextRules {
+    open_quote: '“',
+    close_quote: '”',
+    currency: "₺",
+    currency_before: true,
+    units: &["adet", "km", "gün", "satır", "kez", "parça"],
+    id_prefixes: &["oda", "sayfa", "adım", "madde", "satır", "port"],
+    case_mapping: CaseMapping::Turkic,
+    ..DEFAULT_RULES
+};
+
+pub static EL_TEXT_RULES: TextRules = TextRules {
+    open_quote: '«',
+    close_quote: '»',
+    units: &["τεμάχια", "χλμ", "μέρες", "γραμμές", "φορές", "μέρη"],
+    id_prefixes: &["δωμάτιο", "σελίδα", "βήμα", "σημείο", "γραμμή", "θύρα"],
+    case_mapping: CaseMapping::Greek,
+    ..DEFAULT_RULES
+};
+
+pub static RU_TEXT_RULES: TextRules = TextRules {
+    open_quote: '«',
+    close_quote: '»',
+    group_separator: Some(' '),
+    currency: "₽",
+    units: &["шт", "км", "дней", "строк", "раз", "частей"],
+    id_prefixes: &["комната", "страница", "шаг", "пункт", "строка", "порт"],
+    ..DEFAULT_RULES
+};
+
+pub static UK_TEXT_RULES: TextRules = TextRules {
+    open_quote: '«',
+    close_quote: '»',
+    group_separator: Some(' '),
+    currency: "₴",
+    units: &["шт", "км", "днів", "рядків", "разів", "частин"],
+    id_prefixes: &["кімната", "сторінка", "крок", "пункт", "рядок", "порт"],
+    ..DEFAULT_RULES
+};
+
+impl TextRules {
+    /// Whether `word` (already lowercased) reads as a noun by its ending.
+    pub fn has_noun_suffix(&self, word: &str) -> bool {
+        self.capitalizes_nouns
+            && self
+                .noun_suffixes
+                .iter()
+                .any(|suffix| word.len() > suffix.len() + 1 && word.ends_with(suffix))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turkic_case_mapping_keeps_dots() {
+        let tr = CaseMapping::Turkic;
+        assert_eq!(tr.to_upper('i'), 'İ');
+        assert_eq!(tr.to_upper('ı'), 'I');
+        assert_eq!(tr.to_lower('I'), 'ı');
+        assert_eq!(tr.to_lower('İ'), 'i');
+        assert!(!tr.eq_ignore_case('I', 'i'));
+
+        let default = CaseMapping::Default;
+        assert_eq!(default.to_upper('i'), 'I');
+        assert!(default.eq_ignore_case('I', 'i'));
+    }
+
+    #[test]
+    fn greek_case_mapping_handles_final_sigma() {
+        let el = CaseMapping::Greek;
+        assert_eq!(el.to_upper('ς'), 'Σ');
+        assert_eq!(el.to_upper('σ'), 'Σ');
+        assert!(el.eq_ignore_case('Σ', 'ς'));
+        assert!(el.eq_ignore_case('σ', 'ς'));
+        assert!(!CaseMapping::Default.eq_ignore_case('σ', 'ς'));
+
+        assert_eq!(el.to_lower_word("ΚΟΣΜΟΣ"), "κοσμος");
+        assert_eq!(el.to_lower_word("ΣΑΣ,"), "σας,");
+        assert_eq!(el.to_lower_word("Σ"), "σ");
+        assert_eq!(CaseMapping::Default.to_lower_word("ΚΟΣΜΟΣ"), "κοσμοσ");
+    }
+
+    #[test]
+    fn noun_suffixes_only_apply_when_nouns_are_capitalized() {
+        assert!(DE_TEXT_RULES.has_noun_suffix("zeitung"));
+        assert!(!DE_TEXT_RULES.has_noun_suffix("ung"));
+        assert!(!EN_TEXT_RULES.has_noun_suffix("zeitung"));
+    }
+}
@@ -1,4 +1,4 @@
-use icu_normalizer::ComposingNormalizerBorrowed;
+use icu_normalizer::{ComposingNormalizerBorrowed, DecomposingNormalizerBorrowed};
 
 pub fn normalize_nfc(input: &str) -> String {
     ComposingNormalizerBorrowed::new_nfc()
@@ -6,6 +6,12 @@ pub fn normalize_nfc(input: &str) -> String {
         .into_owned()
 }
 
+pub fn normalize_nfd(input: &str) -> String {
+    DecomposingNormalizerBorrowed::new_nfd()
+        .normalize(input)
+        .into_owned()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -22,4 +28,11 @@ mod tests {
         assert_eq!(normalize_nfc("Árvíztűrő"), "Árvíztűrő");
         assert_eq!(normalize_nfc("abc"), "abc");
     }
+
+    #[test]
+    fn normalize_nfd_splits_base_letter_and_mark() {
+        assert_eq!(normalize_nfd("é"), "e\u{0301}");
+        assert_eq!(normalize_nfd("ά"), "α\u{0301}");
+        assert_eq!(normalize_nfd("abc"), "abc");
+    }
 }
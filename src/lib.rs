@@ -4,7 +4,11 @@
 // Most code is only exercised through the binary, so suppress dead_code warnings.
 #![allow(dead_code)]
 
-rust_i18n::i18n!("locales", fallback = "en");
+rust_i18n::i18n!(
+    "locales",
+    fallback = "en",
+    backend = crate::i18n::user_locale_backend()
+);
 
 // Public: used by benchmarks and the generate_test_profiles binary
 pub mod config;
@@ -19,4 +23,7 @@ mod app;
 mod event;
 mod generator;
 mod i18n;
+mod race;
+mod report;
+mod shortcut;
 mod ui;
@@ -1,4 +1,8 @@
-rust_i18n::i18n!("locales", fallback = "en");
+rust_i18n::i18n!(
+    "locales",
+    fallback = "en",
+    backend = crate::i18n::user_locale_backend()
+);
 
 mod app;
 mod config;
@@ -8,7 +12,10 @@ mod generator;
 mod i18n;
 mod keyboard;
 mod l10n;
+mod race;
+mod report;
 mod session;
+mod shortcut;
 mod store;
 mod ui;
 
@@ -16,7 +23,7 @@ use std::io;
 use std::time::{Duration, Instant};
 
 use anyhow::Result;
-use clap::Parser;
+use clap::{Parser, Subcommand, ValueEnum};
 use crossterm::event::{
     DisableMouseCapture, EnableMouseCapture, KeyCode, KeyEvent, KeyEventKind, KeyEventState,
     KeyModifiers, KeyboardEnhancementFlags, ModifierKeyCode, MouseButton, MouseEvent,
@@ -35,25 +42,32 @@ use ratatui::widgets::{Block, Padding, Paragraph, Widget, Wrap};
 
 use app::{App, AppScreen, DrillMode, MilestoneKind, SettingItem, StatusKind};
 use i18n::t;
-use engine::skill_tree::{BranchStatus, DrillScope, find_key_branch, get_branch_definition};
+use engine::ngram_stats::{NgramAnomaly, NgramKey};
+use engine::skill_tree::{BranchStatus, DrillScope};
 use event::{AppEvent, EventHandler};
 use generator::code_syntax::{code_language_options, is_language_cached, language_by_key};
 use generator::passage::{is_book_cached, passage_options};
+use generator::technique::Technique;
 use keyboard::display::key_display_name;
+use shortcut::{Chord, format_chords};
+use store::importers::ExternalSource;
+use store::tabular::TableFormat;
 use keyboard::finger::Hand;
 use l10n::language_pack::{
     CapabilityState, default_keyboard_layout_for_language, dictionary_languages_for_layout,
     find_language_pack, language_packs, validate_language_layout_pair,
 };
+use l10n::runtime_pack::{runtime_packs, runtime_packs_dir};
 use ui::components::dashboard::Dashboard;
 use ui::components::keyboard_diagram::KeyboardDiagram;
 use ui::components::menu::Menu;
+use ui::components::numpad_diagram::NumpadDiagram;
 use ui::components::skill_tree::{
     SkillTreeWidget, branch_list_spacing_flags, detail_line_count_with_level_spacing_for_tree,
     selectable_branches, use_expanded_level_spacing_for_tree, use_side_by_side_layout,
 };
 use ui::components::stats_dashboard::{
-    AnomalyBigramRow, NgramTabData, StatsDashboard, history_page_size_for_terminal,
+    AnomalyRow, NgramTabData, StatsDashboard, history_page_size_for_terminal,
 };
 use ui::components::stats_sidebar::StatsSidebar;
 use ui::components::typing_area::TypingArea;
@@ -80,6 +94,102 @@ struct Cli {
 
     #[arg(short, long, help = "Number of words per drill")]
     words: Option<usize>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    #[command(about = "Host or join a typing race on the local network")]
+    Race {
+        #[command(subcommand)]
+        action: RaceCommand,
+    },
+    #[command(about = "Export drills, key stats and bigram stats as CSV/TSV tables and exit")]
+    Export {
+        #[arg(help = "Directory to write drills, key_stats and bigram_stats tables into")]
+        dir: std::path::PathBuf,
+
+        #[arg(long, help = "Write tab-separated .tsv files instead of CSV")]
+        tsv: bool,
+    },
+    #[command(about = "Write a progress report and exit")]
+    Report {
+        #[arg(
+            long,
+            value_name = "FILE",
+            help = "Write a self-contained HTML report to FILE"
+        )]
+        html: std::path::PathBuf,
+    },
+    #[command(about = "Import drill history exported by another typing trainer and exit")]
+    ImportHistory {
+        #[arg(value_enum, help = "Trainer the file was exported from")]
+        source: HistorySource,
+
+        #[arg(help = "Monkeytype results CSV or keybr.com JSON download")]
+        file: std::path::PathBuf,
+
+        #[arg(long, help = "Show what would be imported without writing anything")]
+        dry_run: bool,
+    },
+    #[command(about = "List language packs from the user languages directory, report invalid ones and exit")]
+    Languages,
+    #[command(about = "Work with UI translation files")]
+    I18n {
+        #[command(subcommand)]
+        action: I18nCommand,
+    },
+}
+
+#[derive(Subcommand)]
+enum I18nCommand {
+    #[command(about = "Check a locale YAML file for missing keys and placeholder drift against English")]
+    Check {
+        #[arg(help = "Locale file, e.g. ~/.config/keydr/locales/eo.yml")]
+        file: std::path::PathBuf,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum HistorySource {
+    Monkeytype,
+    Keybr,
+}
+
+impl From<HistorySource> for ExternalSource {
+    fn from(source: HistorySource) -> Self {
+        match source {
+            HistorySource::Monkeytype => ExternalSource::Monkeytype,
+            HistorySource::Keybr => ExternalSource::Keybr,
+        }
+    }
+}
+
+#[derive(Subcommand)]
+enum RaceCommand {
+    #[command(about = "Host a race that other keydr instances can join")]
+    Host {
+        #[arg(short, long, default_value_t = race::DEFAULT_RACE_PORT, help = "TCP port to listen on")]
+        port: u16,
+
+        #[arg(short, long, help = "Name shown to other racers")]
+        name: Option<String>,
+    },
+    #[command(about = "Join a race by host address (host or host:port)")]
+    Join {
+        address: String,
+
+        #[arg(short, long, help = "Name shown to other racers")]
+        name: Option<String>,
+    },
+}
+
+fn racer_name(name: Option<String>) -> String {
+    name.or_else(|| std::env::var("USER").ok())
+        .or_else(|| std::env::var("USERNAME").ok())
+        .unwrap_or_else(|| "racer".to_string())
 }
 
 fn main() -> Result<()> {
@@ -108,6 +218,165 @@ fn main() -> Result<()> {
             }
         }
     }
+    match cli.command {
+        Some(Command::Race { action }) => {
+            let entered = match action {
+                RaceCommand::Host { port, name } => app.host_race(port, &racer_name(name)),
+                RaceCommand::Join { address, name } => {
+                    app.join_race(&race::normalize_race_address(&address), &racer_name(name))
+                }
+            };
+            if let Err(err) = entered {
+                eprintln!("Could not start race: {err}");
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Export { dir, tsv }) => {
+            let format = if tsv {
+                TableFormat::Tsv
+            } else {
+                TableFormat::Csv
+            };
+            match app.write_tables(&dir, "", format) {
+                Ok(written) => {
+                    for path in written {
+                        println!("Wrote {}", path.display());
+                    }
+                    return Ok(());
+                }
+                Err(err) => {
+                    eprintln!("Could not export tables to {}: {err}", dir.display());
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Command::Report { html }) => {
+            if let Err(err) = std::fs::write(&html, app.render_html_report()) {
+                eprintln!("Could not write report to {}: {err}", html.display());
+                std::process::exit(1);
+            }
+            println!("Report written to {}", html.display());
+            return Ok(());
+        }
+        Some(Command::ImportHistory {
+            source,
+            file,
+            dry_run,
+        }) => {
+            let source = ExternalSource::from(source);
+            match app.import_external_history(source, &file, dry_run) {
+                Ok(summary) => {
+                    println!(
+                        "{}: {} drills read, {} skipped, {} already imported",
+                        source.label(),
+                        summary.parsed,
+                        summary.skipped,
+                        summary.duplicates
+                    );
+                    if let (Some(first), Some(last)) = (summary.first, summary.last) {
+                        println!(
+                            "{} new drills ({} to {}) tagged \"{}\", {} keys with per-key stats",
+                            summary.added,
+                            first.format("%Y-%m-%d"),
+                            last.format("%Y-%m-%d"),
+                            source.drill_mode(),
+                            summary.keys_seeded
+                        );
+                    } else {
+                        println!("Nothing new to import");
+                    }
+                    if summary.dropped > 0 {
+                        println!(
+                            "{} oldest drills dropped to keep history at {}",
+                            summary.dropped,
+                            store::merge::MAX_HISTORY_DRILLS
+                        );
+                    }
+                    if dry_run {
+                        println!("Dry run: nothing was written");
+                    }
+                    return Ok(());
+                }
+                Err(err) => {
+                    eprintln!("Could not import {}: {err}", file.display());
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Command::Languages) => {
+            let report = runtime_packs();
+            match runtime_packs_dir() {
+                Some(dir) => println!("Language packs in {}", dir.display()),
+                None => println!("No config directory; runtime language packs are unavailable"),
+            }
+            if report.packs.is_empty() && report.issues.is_empty() {
+                println!("  none found");
+            }
+            for runtime in &report.packs {
+                let pack = &runtime.pack;
+                println!(
+                    "  ok       {}: {} ({}), {} words, layouts: {}",
+                    pack.language_key,
+                    pack.display_name,
+                    pack.autonym,
+                    runtime.word_count,
+                    pack.supported_keyboard_layout_keys.join(", ")
+                );
+            }
+            for issue in &report.issues {
+                println!("  skipped  {}: {}", issue.dir.display(), issue.message);
+            }
+            if !report.issues.is_empty() {
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        Some(Command::I18n {
+            action: I18nCommand::Check { file },
+        }) => {
+            let content = match std::fs::read_to_string(&file) {
+                Ok(content) => content,
+                Err(err) => {
+                    eprintln!("Could not read {}: {err}", file.display());
+                    std::process::exit(1);
+                }
+            };
+            let report = match i18n::check_catalog(&content) {
+                Ok(report) => report,
+                Err(err) => {
+                    eprintln!("{} is not valid YAML: {err}", file.display());
+                    std::process::exit(1);
+                }
+            };
+            // Overrides of a bundled locale may leave keys out; they fall
+            // through to the compiled strings.
+            let overrides_bundled = file
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .is_some_and(|stem| i18n::SUPPORTED_UI_LOCALES.contains(&stem));
+            for key in &report.missing {
+                println!("missing      {key}");
+            }
+            for key in &report.extra {
+                println!("unknown key  {key}");
+            }
+            for mismatch in &report.placeholder_mismatches {
+                println!(
+                    "placeholders {}: expected {:?}, found {:?}",
+                    mismatch.key, mismatch.expected, mismatch.found
+                );
+            }
+            let failed = !report.extra.is_empty()
+                || !report.placeholder_mismatches.is_empty()
+                || (!overrides_bundled && !report.missing.is_empty());
+            if failed {
+                std::process::exit(1);
+            }
+            println!("{} is consistent with the English catalog", file.display());
+            return Ok(());
+        }
+        None => {}
+    }
 
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -165,6 +434,11 @@ fn run_app(
     events: &EventHandler,
 ) -> Result<()> {
     loop {
+        // Race state is polled every iteration: ticks stop arriving while keys stream in,
+        // and opponents' progress must keep moving during fast typing.
+        if app.race.is_some() {
+            app.process_race_tick();
+        }
         terminal.draw(|frame| render(frame, app))?;
 
         match events.next()? {
@@ -241,6 +515,30 @@ fn handle_key(app: &mut App, key: KeyEvent) {
         }
     };
 
+    // Keypad keys are reported as ordinary chars/Enter with KEYPAD state; they
+    // get numpad sentinels for the diagram and for key stats.
+    let keypad = key.state.contains(KeyEventState::KEYPAD);
+    if key.kind == KeyEventKind::Press {
+        app.keypad_input = keypad;
+    }
+    let keypad_sentinel = match key.code {
+        KeyCode::Char(ch) if keypad => keyboard::display::numpad_sentinel(ch),
+        KeyCode::Enter if keypad => keyboard::display::numpad_sentinel(keyboard::display::ENTER),
+        _ => None,
+    };
+    if let Some(sentinel) = keypad_sentinel {
+        match key.kind {
+            KeyEventKind::Press => {
+                app.depressed_keys.insert(sentinel);
+                app.last_key_time = Some(Instant::now());
+            }
+            KeyEventKind::Release => {
+                app.depressed_keys.remove(&sentinel);
+            }
+            KeyEventKind::Repeat => {}
+        }
+    }
+
     // Track depressed keys and shift state for keyboard diagram
     match (&key.code, key.kind) {
         (
@@ -258,13 +556,13 @@ fn handle_key(app: &mut App, key: KeyEvent) {
             app.shift_held = false;
             return;
         }
-        (KeyCode::Char(ch), KeyEventKind::Press) => {
+        (KeyCode::Char(ch), KeyEventKind::Press) if !keypad => {
             let normalized = app.keyboard_model.shifted_to_base(*ch).unwrap_or(*ch);
             app.depressed_keys.insert(normalized);
             app.last_key_time = Some(Instant::now());
             app.shift_held = infer_shift(*ch, key.modifiers, app.caps_lock);
         }
-        (KeyCode::Char(ch), KeyEventKind::Release) => {
+        (KeyCode::Char(ch), KeyEventKind::Release) if !keypad => {
             let normalized = app.keyboard_model.shifted_to_base(*ch).unwrap_or(*ch);
             app.depressed_keys.remove(&normalized);
             return; // Don't process Release events as input
@@ -287,12 +585,12 @@ fn handle_key(app: &mut App, key: KeyEvent) {
             app.depressed_keys.remove(&'\t');
             return;
         }
-        (KeyCode::Enter, KeyEventKind::Press) => {
+        (KeyCode::Enter, KeyEventKind::Press) if !keypad => {
             app.depressed_keys.insert('\n');
             app.last_key_time = Some(Instant::now());
             app.shift_held = key.modifiers.contains(KeyModifiers::SHIFT);
         }
-        (KeyCode::Enter, KeyEventKind::Release) => {
+        (KeyCode::Enter, KeyEventKind::Release) if !keypad => {
             app.depressed_keys.remove(&'\n');
             return;
         }
@@ -318,6 +616,7 @@ fn handle_key(app: &mut App, key: KeyEvent) {
     if app.post_drill_input_lock_remaining_ms().is_some()
         && (!app.milestone_queue.is_empty()
             || app.screen == AppScreen::DrillResult
+            || app.screen == AppScreen::RaceResult
             || app.screen == AppScreen::Drill)
     {
         return;
@@ -342,6 +641,12 @@ fn handle_key(app: &mut App, key: KeyEvent) {
                 let _ = app.config.save();
                 app.go_to_menu();
             }
+            KeyCode::Char('p') => {
+                app.show_adaptive_intro = false;
+                app.config.adaptive_intro_done = true;
+                let _ = app.config.save();
+                app.start_placement_test();
+            }
             _ => {
                 app.show_adaptive_intro = false;
                 app.config.adaptive_intro_done = true;
@@ -380,6 +685,11 @@ fn handle_key(app: &mut App, key: KeyEvent) {
         AppScreen::CodeDownloadProgress => handle_code_download_progress_key(app, key),
         AppScreen::Keyboard => handle_keyboard_explorer_key(app, key),
         AppScreen::UiLanguageSelect => handle_ui_language_key(app, key),
+        AppScreen::VocabularyEditor => handle_vocabulary_key(app, key),
+        AppScreen::RaceLobby => handle_race_lobby_key(app, key),
+        AppScreen::RaceResult => handle_race_result_key(app, key),
+        AppScreen::ShortcutDrill => handle_shortcut_drill_key(app, key),
+        AppScreen::TechniqueSelect => handle_technique_select_key(app, key),
     }
 }
 
@@ -539,6 +849,7 @@ fn handle_mouse(app: &mut App, mouse: MouseEvent) {
     if app.post_drill_input_lock_remaining_ms().is_some()
         && (!app.milestone_queue.is_empty()
             || app.screen == AppScreen::DrillResult
+            || app.screen == AppScreen::RaceResult
             || app.screen == AppScreen::Drill)
     {
         return;
@@ -579,6 +890,11 @@ fn handle_mouse(app: &mut App, mouse: MouseEvent) {
         AppScreen::CodeDownloadProgress => handle_code_download_progress_mouse(app, mouse),
         AppScreen::Keyboard => handle_keyboard_explorer_mouse(app, mouse),
         AppScreen::UiLanguageSelect => handle_ui_language_mouse(app, mouse),
+        AppScreen::VocabularyEditor => handle_vocabulary_mouse(app, mouse),
+        AppScreen::RaceLobby => handle_race_lobby_mouse(app, mouse),
+        AppScreen::RaceResult => handle_race_result_mouse(app, mouse),
+        AppScreen::ShortcutDrill => handle_shortcut_drill_mouse(app, mouse),
+        AppScreen::TechniqueSelect => handle_technique_select_mouse(app, mouse),
     }
 }
 
@@ -605,10 +921,15 @@ fn activate_menu_selected(app: &mut App) {
                 app.go_to_passage_intro();
             }
         }
-        3 => app.go_to_skill_tree(),
-        4 => app.go_to_keyboard(),
-        5 => app.go_to_stats(),
-        6 => app.go_to_settings(),
+        3 => app.start_data_entry_drill(),
+        4 => app.start_numpad_drill(),
+        5 => app.start_shortcut_drill(),
+        6 => app.start_problem_words_drill(),
+        7 => app.go_to_technique_select(),
+        8 => app.go_to_skill_tree(),
+        9 => app.go_to_keyboard(),
+        10 => app.go_to_stats(),
+        11 => app.go_to_settings(),
         _ => {}
     }
 }
@@ -620,7 +941,7 @@ fn handle_menu_mouse(app: &mut App, mouse: MouseEvent) {
         MouseEventKind::Down(MouseButton::Left) | MouseEventKind::Down(MouseButton::Right) => {
             let is_secondary = matches!(mouse.kind, MouseEventKind::Down(MouseButton::Right));
             let area = terminal_area();
-            let mh_start = ui::hint::hint(ui::hint::K_1_3, t!("menu.hint_start").as_ref());
+            let mh_start = ui::hint::hint(ui::hint::K_1_8, t!("menu.hint_start").as_ref());
             let mh_tree = ui::hint::hint(ui::hint::K_T, t!("menu.hint_skill_tree").as_ref());
             let mh_kbd = ui::hint::hint(ui::hint::K_B, t!("menu.hint_keyboard").as_ref());
             let mh_stats = ui::hint::hint(ui::hint::K_S, t!("menu.hint_stats").as_ref());
@@ -647,30 +968,30 @@ fn handle_menu_mouse(app: &mut App, mouse: MouseEvent) {
                 .split(area);
             if let Some(token) = hint_token_at(layout[2], &menu_hints, mouse.column, mouse.row) {
                 match token.as_str() {
-                    "1-3" => {
-                        let mut selected = app.menu.selected.min(2);
+                    "1-8" => {
+                        let mut selected = app.menu.selected.min(7);
                         selected = if is_secondary {
-                            if selected == 0 { 2 } else { selected - 1 }
+                            if selected == 0 { 7 } else { selected - 1 }
                         } else {
-                            (selected + 1) % 3
+                            (selected + 1) % 8
                         };
                         app.menu.selected = selected;
                         activate_menu_selected(app);
                     }
                     "t" => {
-                        app.menu.selected = 3;
+                        app.menu.selected = 8;
                         activate_menu_selected(app);
                     }
                     "b" => {
-                        app.menu.selected = 4;
+                        app.menu.selected = 9;
                         activate_menu_selected(app);
                     }
                     "s" => {
-                        app.menu.selected = 5;
+                        app.menu.selected = 10;
                         activate_menu_selected(app);
                     }
                     "c" => {
-                        app.menu.selected = 6;
+                        app.menu.selected = 11;
                         activate_menu_selected(app);
                     }
                     "q" => app.should_quit = true,
@@ -1031,6 +1352,11 @@ fn settings_fields(app: &App) -> Vec<(SettingItem, String, String)> {
             t!("settings.target_wpm").to_string(),
             format!("{}", app.config.target_wpm),
         ),
+        (
+            SettingItem::ForgettingCurve,
+            t!("settings.forgetting_curve").to_string(),
+            app.config.forgetting_curve.localized_name(),
+        ),
         (
             SettingItem::Theme,
             t!("settings.theme").to_string(),
@@ -1053,11 +1379,26 @@ fn settings_fields(app: &App) -> Vec<(SettingItem, String, String)> {
             t!("settings.dictionary_language").to_string(),
             dictionary_language_label,
         ),
+        (
+            SettingItem::Vocabulary,
+            t!("settings.vocabulary").to_string(),
+            t!(
+                "settings.vocabulary_summary",
+                words = app.vocabulary.words.len(),
+                blocked = app.vocabulary.blocked.len()
+            )
+            .to_string(),
+        ),
         (
             SettingItem::KeyboardLayout,
             t!("settings.keyboard_layout").to_string(),
             keyboard_layout_label,
         ),
+        (
+            SettingItem::ShortcutPack,
+            t!("settings.shortcut_pack").to_string(),
+            app.config.shortcut_pack.clone(),
+        ),
         (
             SettingItem::CodeLanguage,
             t!("settings.code_language").to_string(),
@@ -1129,6 +1470,16 @@ fn settings_fields(app: &App) -> Vec<(SettingItem, String, String)> {
             t!("settings.export_data").to_string(),
             t!("settings.export_now").to_string(),
         ),
+        (
+            SettingItem::TableFormat,
+            t!("settings.table_format").to_string(),
+            app.settings_table_format.label().to_string(),
+        ),
+        (
+            SettingItem::ExportTables,
+            t!("settings.export_tables").to_string(),
+            t!("settings.export_now").to_string(),
+        ),
         (
             SettingItem::ImportPath,
             t!("settings.import_path").to_string(),
@@ -1139,6 +1490,11 @@ fn settings_fields(app: &App) -> Vec<(SettingItem, String, String)> {
             t!("settings.import_data").to_string(),
             t!("settings.import_now").to_string(),
         ),
+        (
+            SettingItem::MergeImport,
+            t!("settings.merge_data").to_string(),
+            t!("settings.merge_now").to_string(),
+        ),
     ]
 }
 
@@ -1168,7 +1524,7 @@ fn handle_settings_mouse(app: &mut App, mouse: MouseEvent) {
         return;
     }
 
-    if app.settings_export_conflict {
+    if app.settings_export_conflict.is_some() {
         let area = terminal_area();
         let dialog_width = 52u16.min(area.width.saturating_sub(4));
         let dialog_height = 6u16;
@@ -1178,13 +1534,11 @@ fn handle_settings_mouse(app: &mut App, mouse: MouseEvent) {
         if point_in_rect(mouse.column, mouse.row, dialog) {
             let third = dialog.width / 3;
             if mouse.column < dialog.x + third {
-                app.settings_export_conflict = false;
-                app.export_data_overwrite();
+                app.export_conflict_overwrite();
             } else if mouse.column < dialog.x + 2 * third {
-                app.settings_export_conflict = false;
-                app.export_data_rename();
+                app.export_conflict_rename();
             } else {
-                app.settings_export_conflict = false;
+                app.settings_export_conflict = None;
             }
         }
         return;
@@ -1209,6 +1563,19 @@ fn handle_settings_mouse(app: &mut App, mouse: MouseEvent) {
         return;
     }
 
+    if app.settings_merge_pending.is_some() {
+        let area = terminal_area();
+        let dialog = merge_dialog_rect(area);
+        if point_in_rect(mouse.column, mouse.row, dialog) {
+            if mouse.column < dialog.x + dialog.width / 2 {
+                app.commit_merge_import();
+            } else {
+                app.settings_merge_pending = None;
+            }
+        }
+        return;
+    }
+
     let area = terminal_area();
     let centered = ui::layout::centered_rect(60, 80, area);
     let inner = Block::bordered().inner(centered);
@@ -1366,22 +1733,42 @@ fn handle_menu_key(app: &mut App, key: KeyEvent) {
             app.menu.selected = 2;
             activate_menu_selected(app);
         }
-        KeyCode::Char('t') => {
+        KeyCode::Char('4') => {
             app.menu.selected = 3;
             activate_menu_selected(app);
         }
-        KeyCode::Char('b') => {
+        KeyCode::Char('5') => {
             app.menu.selected = 4;
             activate_menu_selected(app);
         }
-        KeyCode::Char('s') => {
+        KeyCode::Char('6') => {
             app.menu.selected = 5;
             activate_menu_selected(app);
         }
-        KeyCode::Char('c') => {
+        KeyCode::Char('7') => {
             app.menu.selected = 6;
             activate_menu_selected(app);
         }
+        KeyCode::Char('8') => {
+            app.menu.selected = 7;
+            activate_menu_selected(app);
+        }
+        KeyCode::Char('t') => {
+            app.menu.selected = 8;
+            activate_menu_selected(app);
+        }
+        KeyCode::Char('b') => {
+            app.menu.selected = 9;
+            activate_menu_selected(app);
+        }
+        KeyCode::Char('s') => {
+            app.menu.selected = 10;
+            activate_menu_selected(app);
+        }
+        KeyCode::Char('c') => {
+            app.menu.selected = 11;
+            activate_menu_selected(app);
+        }
         KeyCode::Up | KeyCode::Char('k') => app.menu.prev(),
         KeyCode::Down | KeyCode::Char('j') => app.menu.next(),
         KeyCode::Enter => activate_menu_selected(app),
@@ -1546,6 +1933,7 @@ fn activate_settings_selected(app: &mut App) {
     match SettingItem::from_index(app.settings_selected) {
         SettingItem::UiLanguage => app.go_to_ui_language_select(),
         SettingItem::DictionaryLanguage => app.go_to_dictionary_language_select(),
+        SettingItem::Vocabulary => app.go_to_vocabulary_editor(),
         SettingItem::KeyboardLayout => app.go_to_keyboard_layout_select(),
         SettingItem::CodeDownloadDir => {
             app.clear_settings_modals();
@@ -1573,6 +1961,9 @@ fn activate_settings_selected(app: &mut App) {
         SettingItem::ExportData => {
             app.export_data();
         }
+        SettingItem::ExportTables => {
+            app.export_tables();
+        }
         SettingItem::ImportPath => {
             app.clear_settings_modals();
             app.settings_editing_path = Some((
@@ -1584,6 +1975,10 @@ fn activate_settings_selected(app: &mut App) {
             app.clear_settings_modals();
             app.settings_confirm_import = true;
         }
+        SettingItem::MergeImport => {
+            app.clear_settings_modals();
+            app.prepare_merge_import();
+        }
         _ => app.settings_cycle_forward(),
     }
 }
@@ -1598,18 +1993,16 @@ fn handle_settings_key(app: &mut App, key: KeyEvent) {
     }
 
     // Priority 2: export conflict dialog
-    if app.settings_export_conflict {
+    if app.settings_export_conflict.is_some() {
         match key.code {
             KeyCode::Char('d') => {
-                app.settings_export_conflict = false;
-                app.export_data_overwrite();
+                app.export_conflict_overwrite();
             }
             KeyCode::Char('r') => {
-                app.settings_export_conflict = false;
-                app.export_data_rename();
+                app.export_conflict_rename();
             }
             KeyCode::Esc => {
-                app.settings_export_conflict = false;
+                app.settings_export_conflict = None;
             }
             _ => {}
         }
@@ -1632,6 +2025,16 @@ fn handle_settings_key(app: &mut App, key: KeyEvent) {
         return;
     }
 
+    // Priority 3b: merge import summary
+    if app.settings_merge_pending.is_some() {
+        match key.code {
+            KeyCode::Char('y') => app.commit_merge_import(),
+            KeyCode::Char('n') | KeyCode::Esc => app.settings_merge_pending = None,
+            _ => {}
+        }
+        return;
+    }
+
     // Priority 4: editing a path field
     if let Some((field, ref mut input)) = app.settings_editing_path {
         match input.handle(key) {
@@ -1869,7 +2272,7 @@ fn handle_dictionary_language_mouse(app: &mut App, mouse: MouseEvent) {
 // --- UI Language Select ---
 
 fn confirm_ui_language_selection(app: &mut App) {
-    let locales = i18n::SUPPORTED_UI_LOCALES;
+    let locales = i18n::ui_locales();
     if app.ui_language_selected >= locales.len() {
         return;
     }
@@ -1882,7 +2285,7 @@ fn confirm_ui_language_selection(app: &mut App) {
 }
 
 fn handle_ui_language_key(app: &mut App, key: KeyEvent) {
-    let locales = i18n::SUPPORTED_UI_LOCALES;
+    let locales = i18n::ui_locales();
     let len = locales.len();
     if len == 0 {
         return;
@@ -1906,7 +2309,7 @@ fn handle_ui_language_key(app: &mut App, key: KeyEvent) {
 }
 
 fn handle_ui_language_mouse(app: &mut App, mouse: MouseEvent) {
-    let locales = i18n::SUPPORTED_UI_LOCALES;
+    let locales = i18n::ui_locales();
     if locales.is_empty() {
         return;
     }
@@ -1923,6 +2326,155 @@ fn handle_ui_language_mouse(app: &mut App, mouse: MouseEvent) {
     }
 }
 
+// --- Technique Drills ---
+
+fn technique_select_hints() -> [String; 3] {
+    [
+        ui::hint::hint(ui::hint::K_UP_DOWN, t!("select.hint_navigate").as_ref()),
+        ui::hint::hint(ui::hint::K_ENTER, t!("select.hint_confirm").as_ref()),
+        ui::hint::hint(ui::hint::K_Q_ESC, t!("select.hint_back").as_ref()),
+    ]
+}
+
+/// List and footer areas of the technique picker.
+fn technique_select_areas(area: Rect) -> (Rect, Option<Rect>) {
+    let centered = ui::layout::centered_rect(50, 70, area);
+    let inner = Block::bordered().inner(centered);
+    let hints = technique_select_hints();
+    let hint_refs: Vec<&str> = hints.iter().map(String::as_str).collect();
+    let footer_height = pack_hint_lines(&hint_refs, inner.width as usize).len() as u16;
+    if footer_height > 0 && inner.height > footer_height {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(footer_height)])
+            .split(inner);
+        (chunks[0], Some(chunks[1]))
+    } else {
+        (inner, None)
+    }
+}
+
+fn technique_list_start(app: &App, list_area: Rect) -> usize {
+    app.technique_selected
+        .saturating_sub((list_area.height as usize).saturating_sub(1))
+}
+
+fn confirm_technique_selection(app: &mut App) {
+    if let Some(&technique) = Technique::all().get(app.technique_selected) {
+        app.start_technique_drill(technique);
+    }
+}
+
+fn handle_technique_select_key(app: &mut App, key: KeyEvent) {
+    let len = Technique::all().len();
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => app.go_to_menu(),
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.technique_selected = app.technique_selected.saturating_sub(1);
+        }
+        KeyCode::Down | KeyCode::Char('j') if app.technique_selected + 1 < len => {
+            app.technique_selected += 1;
+        }
+        KeyCode::Char(ch @ '1'..='9') => {
+            let idx = ch as usize - '1' as usize;
+            if idx < len {
+                app.technique_selected = idx;
+                confirm_technique_selection(app);
+            }
+        }
+        KeyCode::Enter => confirm_technique_selection(app),
+        _ => {}
+    }
+}
+
+fn handle_technique_select_mouse(app: &mut App, mouse: MouseEvent) {
+    let len = Technique::all().len();
+    match mouse.kind {
+        MouseEventKind::ScrollUp => {
+            app.technique_selected = app.technique_selected.saturating_sub(1);
+        }
+        MouseEventKind::ScrollDown if app.technique_selected + 1 < len => {
+            app.technique_selected += 1;
+        }
+        MouseEventKind::Down(MouseButton::Left) => {
+            let (list_area, footer_area) = technique_select_areas(terminal_area());
+            if let Some(footer) = footer_area {
+                let hints = technique_select_hints();
+                let hint_refs: Vec<&str> = hints.iter().map(String::as_str).collect();
+                if let Some(token) = hint_token_at(footer, &hint_refs, mouse.column, mouse.row) {
+                    match token.as_str() {
+                        "Enter" => confirm_technique_selection(app),
+                        "q/ESC" => app.go_to_menu(),
+                        _ => {}
+                    }
+                    return;
+                }
+            }
+            if !point_in_rect(mouse.column, mouse.row, list_area) {
+                return;
+            }
+            let idx = technique_list_start(app, list_area) + (mouse.row - list_area.y) as usize;
+            if idx < len {
+                let selected_before = app.technique_selected;
+                app.technique_selected = idx;
+                if selected_before == idx {
+                    confirm_technique_selection(app);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+// --- Custom Vocabulary ---
+
+fn handle_vocabulary_key(app: &mut App, key: KeyEvent) {
+    if let Some(ref mut input) = app.vocabulary_input {
+        match input.handle(key) {
+            InputResult::Submit => {
+                let line = input.value().to_string();
+                app.vocabulary_input = None;
+                app.submit_vocabulary_entry(&line);
+            }
+            InputResult::Cancel => app.vocabulary_input = None,
+            InputResult::Continue => {}
+        }
+        return;
+    }
+
+    let len = app.vocabulary_len();
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => {
+            app.go_to_settings();
+            app.settings_selected = SettingItem::Vocabulary.index();
+        }
+        KeyCode::Tab | KeyCode::BackTab => app.switch_vocabulary_tab(),
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.vocabulary_selected = app.vocabulary_selected.saturating_sub(1);
+        }
+        KeyCode::Down | KeyCode::Char('j') if app.vocabulary_selected + 1 < len => {
+            app.vocabulary_selected += 1;
+        }
+        KeyCode::Char('a') => app.vocabulary_input = Some(LineInput::new("")),
+        KeyCode::Char('d') | KeyCode::Delete => app.remove_selected_vocabulary_entry(),
+        KeyCode::Char('+') | KeyCode::Char('=') => app.adjust_selected_vocabulary_weight(1),
+        KeyCode::Char('-') => app.adjust_selected_vocabulary_weight(-1),
+        _ => {}
+    }
+}
+
+fn handle_vocabulary_mouse(app: &mut App, mouse: MouseEvent) {
+    match mouse.kind {
+        MouseEventKind::ScrollUp => {
+            app.vocabulary_selected = app.vocabulary_selected.saturating_sub(1);
+        }
+        MouseEventKind::ScrollDown if app.vocabulary_selected + 1 < app.vocabulary_len() => {
+            app.vocabulary_selected += 1;
+        }
+        _ => {}
+    }
+}
+
 // --- Keyboard Layout ---
 
 fn is_keyboard_layout_disabled(layout_key: &str) -> bool {
@@ -2938,9 +3490,12 @@ fn handle_skill_tree_key(app: &mut App, key: KeyEvent) {
 
     let max_scroll = skill_tree_detail_max_scroll(app);
     app.skill_tree_detail_scroll = app.skill_tree_detail_scroll.min(max_scroll);
-    let branches = selectable_branches();
+    let branches = selectable_branches(&app.skill_tree);
     match key.code {
         KeyCode::Esc | KeyCode::Char('q') => app.go_to_menu(),
+        KeyCode::Char('p') => {
+            app.start_placement_test();
+        }
         KeyCode::Up | KeyCode::Char('k') => {
             app.skill_tree_selected = app.skill_tree_selected.saturating_sub(1);
             app.skill_tree_detail_scroll = 0;
@@ -3004,7 +3559,7 @@ fn locked_branch_notice(app: &App) -> String {
 fn skill_tree_interactive_areas(app: &App, area: Rect) -> SkillTreeMouseLayout {
     let centered = skill_tree_popup_rect(area);
     let inner = Block::bordered().inner(centered);
-    let branches = selectable_branches();
+    let branches = selectable_branches(&app.skill_tree);
     let selected = app
         .skill_tree_selected
         .min(branches.len().saturating_sub(1));
@@ -3016,12 +3571,14 @@ fn skill_tree_interactive_areas(app: &App, area: Rect) -> SkillTreeMouseLayout {
     let st_back = ui::hint::hint(ui::hint::K_Q_ESC, t!("skill_tree.hint_back").as_ref());
     let st_unlock = ui::hint::hint(ui::hint::K_ENTER, t!("skill_tree.hint_unlock").as_ref());
     let st_drill = ui::hint::hint(ui::hint::K_ENTER, t!("skill_tree.hint_start_drill").as_ref());
+    let st_placement = ui::hint::hint(ui::hint::K_P, t!("skill_tree.hint_placement").as_ref());
     let (footer_hints, footer_notice): (Vec<&str>, Option<String>) =
         match bp.map(|b| b.status.clone()) {
             Some(BranchStatus::Locked) => (
                 vec![
                     st_nav.as_str(),
                     st_scroll.as_str(),
+                    st_placement.as_str(),
                     st_back.as_str(),
                 ],
                 Some(locked_branch_notice(app)),
@@ -3031,6 +3588,7 @@ fn skill_tree_interactive_areas(app: &App, area: Rect) -> SkillTreeMouseLayout {
                     st_unlock.as_str(),
                     st_nav.as_str(),
                     st_scroll.as_str(),
+                    st_placement.as_str(),
                     st_back.as_str(),
                 ],
                 None,
@@ -3040,6 +3598,7 @@ fn skill_tree_interactive_areas(app: &App, area: Rect) -> SkillTreeMouseLayout {
                     st_drill.as_str(),
                     st_nav.as_str(),
                     st_scroll.as_str(),
+                    st_placement.as_str(),
                     st_back.as_str(),
                 ],
                 None,
@@ -3048,6 +3607,7 @@ fn skill_tree_interactive_areas(app: &App, area: Rect) -> SkillTreeMouseLayout {
                 vec![
                     st_nav.as_str(),
                     st_scroll.as_str(),
+                    st_placement.as_str(),
                     st_back.as_str(),
                 ],
                 None,
@@ -3197,7 +3757,7 @@ fn handle_skill_tree_mouse(app: &mut App, mouse: MouseEvent) {
             let screen = terminal_area();
             let centered = skill_tree_popup_rect(screen);
             let inner = Block::bordered().inner(centered);
-            let branches = selectable_branches();
+            let branches = selectable_branches(&app.skill_tree);
             let selected = app
                 .skill_tree_selected
                 .min(branches.len().saturating_sub(1));
@@ -3207,6 +3767,8 @@ fn handle_skill_tree_mouse(app: &mut App, mouse: MouseEvent) {
             let st_back = ui::hint::hint(ui::hint::K_Q_ESC, t!("skill_tree.hint_back").as_ref());
             let st_unlock = ui::hint::hint(ui::hint::K_ENTER, t!("skill_tree.hint_unlock").as_ref());
             let st_drill = ui::hint::hint(ui::hint::K_ENTER, t!("skill_tree.hint_start_drill").as_ref());
+            let st_placement =
+                ui::hint::hint(ui::hint::K_P, t!("skill_tree.hint_placement").as_ref());
             let (footer_hints, footer_notice): (Vec<&str>, Option<String>) =
                 if *app.skill_tree.branch_status(branches[selected])
                     == engine::skill_tree::BranchStatus::Locked
@@ -3215,6 +3777,7 @@ fn handle_skill_tree_mouse(app: &mut App, mouse: MouseEvent) {
                         vec![
                             st_nav.as_str(),
                             st_scroll.as_str(),
+                            st_placement.as_str(),
                             st_back.as_str(),
                         ],
                         Some(locked_branch_notice(app)),
@@ -3225,6 +3788,7 @@ fn handle_skill_tree_mouse(app: &mut App, mouse: MouseEvent) {
                             st_unlock.as_str(),
                             st_nav.as_str(),
                             st_scroll.as_str(),
+                            st_placement.as_str(),
                             st_back.as_str(),
                         ],
                         None,
@@ -3235,6 +3799,7 @@ fn handle_skill_tree_mouse(app: &mut App, mouse: MouseEvent) {
                             st_drill.as_str(),
                             st_nav.as_str(),
                             st_scroll.as_str(),
+                            st_placement.as_str(),
                             st_back.as_str(),
                         ],
                         None,
@@ -3244,6 +3809,7 @@ fn handle_skill_tree_mouse(app: &mut App, mouse: MouseEvent) {
                         vec![
                             st_nav.as_str(),
                             st_scroll.as_str(),
+                            st_placement.as_str(),
                             st_back.as_str(),
                         ],
                         None,
@@ -3269,6 +3835,9 @@ fn handle_skill_tree_mouse(app: &mut App, mouse: MouseEvent) {
             if let Some(token) = hint_token_at(footer_area, &footer_hints, mouse.column, mouse.row) {
                 match token.as_str() {
                     "q/ESC" => app.go_to_menu(),
+                    "p" => {
+                        app.start_placement_test();
+                    }
                     "Enter" => {
                         let branch_id = branches[selected];
                         let status = app.skill_tree.branch_status(branch_id).clone();
@@ -3303,7 +3872,7 @@ fn handle_skill_tree_mouse(app: &mut App, mouse: MouseEvent) {
                 }
                 return;
             }
-            let branches = selectable_branches();
+            let branches = selectable_branches(&app.skill_tree);
             let layout = skill_tree_interactive_areas(app, terminal_area());
             if point_in_rect(mouse.column, mouse.row, layout.branch_area) {
                 if let Some(idx) = skill_tree_branch_index_from_y(
@@ -3346,7 +3915,7 @@ fn skill_tree_detail_max_scroll(app: &App) -> usize {
         centered.height.saturating_sub(2),
     );
 
-    let branches = selectable_branches();
+    let branches = selectable_branches(&app.skill_tree);
     if branches.is_empty() {
         return 0;
     }
@@ -3359,6 +3928,7 @@ fn skill_tree_detail_max_scroll(app: &App) -> usize {
     let st_back = ui::hint::hint(ui::hint::K_Q_ESC, t!("skill_tree.hint_back").as_ref());
     let st_unlock = ui::hint::hint(ui::hint::K_ENTER, t!("skill_tree.hint_unlock").as_ref());
     let st_drill = ui::hint::hint(ui::hint::K_ENTER, t!("skill_tree.hint_start_drill").as_ref());
+    let st_placement = ui::hint::hint(ui::hint::K_P, t!("skill_tree.hint_placement").as_ref());
     let (footer_hints, footer_notice): (Vec<&str>, Option<String>) =
         if *app.skill_tree.branch_status(branches[selected])
             == engine::skill_tree::BranchStatus::Locked
@@ -3367,6 +3937,7 @@ fn skill_tree_detail_max_scroll(app: &App) -> usize {
                 vec![
                     st_nav.as_str(),
                     st_scroll.as_str(),
+                    st_placement.as_str(),
                     st_back.as_str(),
                 ],
                 Some(locked_branch_notice(app)),
@@ -3377,6 +3948,7 @@ fn skill_tree_detail_max_scroll(app: &App) -> usize {
                     st_unlock.as_str(),
                     st_nav.as_str(),
                     st_scroll.as_str(),
+                    st_placement.as_str(),
                     st_back.as_str(),
                 ],
                 None,
@@ -3387,6 +3959,7 @@ fn skill_tree_detail_max_scroll(app: &App) -> usize {
                     st_drill.as_str(),
                     st_nav.as_str(),
                     st_scroll.as_str(),
+                    st_placement.as_str(),
                     st_back.as_str(),
                 ],
                 None,
@@ -3396,6 +3969,7 @@ fn skill_tree_detail_max_scroll(app: &App) -> usize {
                 vec![
                     st_nav.as_str(),
                     st_scroll.as_str(),
+                    st_placement.as_str(),
                     st_back.as_str(),
                 ],
                 None,
@@ -3487,6 +4061,11 @@ fn render(frame: &mut ratatui::Frame, app: &App) {
         AppScreen::CodeDownloadProgress => render_code_download_progress(frame, app),
         AppScreen::Keyboard => render_keyboard_explorer(frame, app),
         AppScreen::UiLanguageSelect => render_ui_language_select(frame, app),
+        AppScreen::VocabularyEditor => render_vocabulary_editor(frame, app),
+        AppScreen::RaceLobby => render_race_lobby(frame, app),
+        AppScreen::RaceResult => render_race_result(frame, app),
+        AppScreen::ShortcutDrill => render_shortcut_drill(frame, app),
+        AppScreen::TechniqueSelect => render_technique_select(frame, app),
     }
 }
 
@@ -3494,7 +4073,7 @@ fn render_menu(frame: &mut ratatui::Frame, app: &App) {
     let area = frame.area();
     let colors = &app.theme.colors;
 
-    let mh_start = ui::hint::hint(ui::hint::K_1_3, t!("menu.hint_start").as_ref());
+    let mh_start = ui::hint::hint(ui::hint::K_1_8, t!("menu.hint_start").as_ref());
     let mh_tree = ui::hint::hint(ui::hint::K_T, t!("menu.hint_skill_tree").as_ref());
     let mh_kbd = ui::hint::hint(ui::hint::K_B, t!("menu.hint_keyboard").as_ref());
     let mh_stats = ui::hint::hint(ui::hint::K_S, t!("menu.hint_stats").as_ref());
@@ -3582,12 +4161,19 @@ fn render_drill(frame: &mut ratatui::Frame, app: &App) {
             DrillMode::Adaptive => t!("drill.mode_adaptive"),
             DrillMode::Code => t!("drill.mode_code"),
             DrillMode::Passage => t!("drill.mode_passage"),
+            DrillMode::Race => t!("drill.mode_race"),
+            DrillMode::DataEntry => t!("drill.mode_data_entry"),
+            DrillMode::Numpad => t!("drill.mode_numpad"),
+            DrillMode::ProblemWords => t!("drill.mode_problem_words"),
+            DrillMode::Placement => t!("drill.mode_placement"),
+            DrillMode::Technique => t!("drill.mode_technique", technique = app.technique.label()),
+            DrillMode::KeySet => t!("drill.mode_key_set", keys = key_set_text(&app.key_set)),
         };
         let mode_name = mode_name_t.as_ref();
 
         // Compute focus text from stored selection (what generated this drill's text)
         let focus_text = if let Some(ref focus) = app.current_focus {
-            match (&focus.char_focus, &focus.bigram_focus) {
+            let text = match (&focus.char_focus, &focus.bigram_focus) {
                 (Some(ch), Some((key, _, _))) => {
                     let bigram = format!("{}{}", key.0[0], key.0[1]);
                     format!(" | {}", t!("drill.focus_both", ch = ch, bigram = bigram))
@@ -3598,10 +4184,33 @@ fn render_drill(frame: &mut ratatui::Frame, app: &App) {
                     format!(" | {}", t!("drill.focus_bigram", bigram = bigram))
                 }
                 (None, None) => String::new(),
+            };
+            match &focus.trigram_focus {
+                Some((key, _, _)) if text.is_empty() => {
+                    format!(" | {}", t!("drill.focus_bigram", bigram = key.label()))
+                }
+                Some((key, _, _)) => format!("{text} + \"{}\"", key.label()),
+                None => text,
             }
         } else {
             String::new()
         };
+        let focus_text = if app.drill_mode == DrillMode::Adaptive && !app.current_review.is_empty()
+        {
+            let items: Vec<String> = app.current_review.iter().map(|item| item.label()).collect();
+            format!(
+                "{focus_text} | {}",
+                t!("drill.review_items", items = items.join(", "))
+            )
+        } else {
+            focus_text
+        };
+        let focus_text = if app.drill_mode == DrillMode::Adaptive && app.custom_vocabulary_active()
+        {
+            format!("{focus_text} | {}", t!("drill.custom_vocabulary"))
+        } else {
+            focus_text
+        };
 
         // For medium/narrow: show compact stats in header
         if !tier.show_sidebar() {
@@ -3649,21 +4258,23 @@ fn render_drill(frame: &mut ratatui::Frame, app: &App) {
         let show_progress = tier.show_progress_bar(area.height);
 
         // Compute active branch count for progress area height
-        let active_branches: Vec<engine::skill_tree::BranchId> =
-            engine::skill_tree::BranchId::all()
-                .iter()
-                .copied()
-                .filter(|&id| {
-                    matches!(
-                        app.skill_tree.branch_status(id),
-                        engine::skill_tree::BranchStatus::InProgress
-                            | engine::skill_tree::BranchStatus::Complete
-                    )
-                })
-                .collect();
+        let active_branches: Vec<engine::skill_tree::BranchId> = app
+            .skill_tree
+            .branch_ids()
+            .into_iter()
+            .filter(|&id| {
+                matches!(
+                    app.skill_tree.branch_status(id),
+                    engine::skill_tree::BranchStatus::InProgress
+                        | engine::skill_tree::BranchStatus::Complete
+                )
+            })
+            .collect();
 
         let kbd_height = if show_kbd {
-            if tier.compact_keyboard() {
+            if app.drill_mode == DrillMode::Numpad {
+                7 // 5 keypad rows + 2 border
+            } else if tier.compact_keyboard() {
                 6 // 3 rows + 2 border + 1 modifier space
             } else {
                 8 // 5 rows (4 + space bar) + 2 border + 1 spacing
@@ -3685,10 +4296,11 @@ fn render_drill(frame: &mut ratatui::Frame, app: &App) {
             } else {
                 0
             };
-            let desired = if app.drill_mode == DrillMode::Adaptive {
-                (branch_rows + 2).max(2)
-            } else {
-                1
+            let desired = match (app.drill_mode, app.race.as_ref()) {
+                (DrillMode::Adaptive, _) => (branch_rows + 2).max(2),
+                (DrillMode::Race, Some(race)) => race.opponents().count().max(1) as u16,
+                (DrillMode::DataEntry | DrillMode::Numpad, _) => 0,
+                _ => 1,
             };
             // Keep at least 5 lines for typing area.
             let max_budget = app_layout
@@ -3729,6 +4341,14 @@ fn render_drill(frame: &mut ratatui::Frame, app: &App) {
                     height: progress_height,
                 };
                 frame.render_widget(progress_widget, main_layout[idx]);
+            } else if let Some(ref race) = app.race
+                && app.drill_mode == DrillMode::Race
+            {
+                let bars = ui::components::race_standings::RaceProgressBars {
+                    session: race,
+                    theme: app.theme,
+                };
+                frame.render_widget(bars, main_layout[idx]);
             } else {
                 let source = app.drill_source_info.as_deref().unwrap_or("unknown source");
                 let label_t = if app.drill_mode == DrillMode::Code {
@@ -3751,9 +4371,19 @@ fn render_drill(frame: &mut ratatui::Frame, app: &App) {
             idx += 1;
         }
 
-        if show_kbd {
-            let next_char = drill.target.get(drill.cursor).copied();
+        if show_kbd && app.drill_mode == DrillMode::Numpad {
+            let numpad = NumpadDiagram {
+                next_key: drill.target.get(drill.cursor).copied(),
+                depressed_keys: &app.depressed_keys,
+                theme: app.theme,
+            };
+            frame.render_widget(numpad, main_layout[idx]);
+        } else if show_kbd {
+            let next_char = drill.target.get(drill.cursor).map(|&ch| {
+                app.keyboard_model.next_stroke(ch, !drill.pending_compose.is_empty())
+            });
             let unlocked_keys = app.skill_tree.unlocked_keys(app.drill_scope);
+            let regressed_keys = app.skill_tree.regressed_keys();
             let kbd = KeyboardDiagram::new(
                 next_char,
                 &unlocked_keys,
@@ -3761,6 +4391,7 @@ fn render_drill(frame: &mut ratatui::Frame, app: &App) {
                 app.theme,
                 &app.keyboard_model,
             )
+            .regressed_keys(&regressed_keys)
             .compact(tier.compact_keyboard())
             .shift_held(app.shift_held)
             .caps_lock(app.caps_lock);
@@ -3812,7 +4443,7 @@ fn render_adaptive_intro_overlay(frame: &mut ratatui::Frame, app: &App) {
     let area = frame.area();
     let colors = &app.theme.colors;
 
-    let overlay_height = 20u16.min(area.height.saturating_sub(2));
+    let overlay_height = 24u16.min(area.height.saturating_sub(2));
     let overlay_width = 62u16.min(area.width.saturating_sub(4));
 
     let left = area.x + (area.width.saturating_sub(overlay_width)) / 2;
@@ -3833,9 +4464,12 @@ fn render_adaptive_intro_overlay(frame: &mut ratatui::Frame, app: &App) {
     let hint_h_back = ui::hint::hint(ui::hint::K_Q_ESC, t!("adaptive_intro.hint_back").as_ref());
     let hint_h_adjust = ui::hint::hint(ui::hint::K_ARROW_LR, t!("adaptive_intro.hint_adjust").as_ref());
     let hint_h_start = ui::hint::hint(ui::hint::K_ENTER_SPACE, t!("adaptive_intro.hint_start").as_ref());
+    let hint_h_placement =
+        ui::hint::hint(ui::hint::K_P, t!("adaptive_intro.hint_placement").as_ref());
     let hints: Vec<&str> = vec![
         hint_h_adjust.as_str(),
         hint_h_start.as_str(),
+        hint_h_placement.as_str(),
         hint_h_back.as_str(),
     ];
     let hint_lines_text = pack_hint_lines(&hints, inner.width as usize);
@@ -3887,6 +4521,11 @@ fn render_adaptive_intro_overlay(frame: &mut ratatui::Frame, app: &App) {
         t!("adaptive_intro.target_wpm_desc").to_string(),
         Style::default().fg(colors.fg()),
     )));
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        t!("adaptive_intro.placement_desc").to_string(),
+        Style::default().fg(colors.fg()),
+    )));
 
     frame.render_widget(
         Paragraph::new(lines).wrap(Wrap { trim: false }),
@@ -4062,7 +4701,7 @@ fn render_milestone_overlay(
                 Style::default().fg(colors.fg()),
             )));
             for &branch_id in &milestone.branch_ids {
-                let name = get_branch_definition(branch_id).display_name();
+                let name = app.skill_tree.branch_definition(branch_id).display_name();
                 lines.push(Line::from(Span::styled(
                     format!("    \u{2022} {name}"),
                     Style::default().fg(colors.focused_key()),
@@ -4092,7 +4731,7 @@ fn render_milestone_overlay(
             let branch_names: Vec<String> = milestone
                 .branch_ids
                 .iter()
-                .map(|&id| get_branch_definition(id).display_name())
+                .map(|&id| app.skill_tree.branch_definition(id).display_name())
                 .collect();
             let branch_text = if branch_names.len() == 1 {
                 branch_names[0].to_string()
@@ -4253,6 +4892,7 @@ fn overlay_keyboard_mode(height: u16) -> u8 {
 #[cfg(test)]
 mod review_tests {
     use super::*;
+    use crate::app::ExportConflict;
     use crate::engine::skill_tree::SkillTreeProgress;
     use crate::session::result::DrillResult;
     use chrono::{TimeDelta, Utc};
@@ -4544,7 +5184,10 @@ mod review_tests {
         if app.settings_confirm_import {
             count += 1;
         }
-        if app.settings_export_conflict {
+        if app.settings_export_conflict.is_some() {
+            count += 1;
+        }
+        if app.settings_merge_pending.is_some() {
             count += 1;
         }
         if app.is_editing_path() {
@@ -4579,6 +5222,44 @@ mod review_tests {
         assert!(!app.is_editing_path());
     }
 
+    #[test]
+    fn settings_export_tables_writes_tables_next_to_export_path() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut app = test_app();
+        app.screen = AppScreen::Settings;
+        app.settings_export_path = dir
+            .path()
+            .join("keydr-export.json")
+            .to_string_lossy()
+            .to_string();
+
+        app.settings_selected = SettingItem::TableFormat.index();
+        handle_settings_key(&mut app, KeyEvent::new(KeyCode::Right, KeyModifiers::NONE));
+        assert_eq!(app.settings_table_format, TableFormat::Tsv);
+
+        app.settings_selected = SettingItem::ExportTables.index();
+        handle_settings_key(&mut app, KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        let status = app.settings_status_message.as_ref().unwrap();
+        assert_eq!(status.kind, StatusKind::Success);
+        for name in ["drills", "key_stats", "bigram_stats"] {
+            assert!(dir.path().join(format!("keydr-export-{name}.tsv")).exists());
+        }
+
+        // A second export asks before touching the existing tables.
+        handle_settings_key(&mut app, KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        handle_settings_key(&mut app, KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert_eq!(app.settings_export_conflict, Some(ExportConflict::Tables));
+        handle_settings_key(&mut app, KeyEvent::new(KeyCode::Char('r'), KeyModifiers::NONE));
+        assert!(app.settings_export_conflict.is_none());
+        assert_eq!(
+            app.settings_status_message.as_ref().unwrap().kind,
+            StatusKind::Success
+        );
+        for name in ["drills", "key_stats", "bigram_stats"] {
+            assert!(dir.path().join(format!("keydr-export-1-{name}.tsv")).exists());
+        }
+    }
+
     #[test]
     fn settings_modal_invariant_enter_import_path_clears_others() {
         let mut app = test_app();
@@ -5081,7 +5762,7 @@ mod review_tests {
         }
 
         // Verify 'ao' appears in error anomalies (high error rate, above min samples)
-        let ao_row = data.error_anomalies.iter().find(|r| r.bigram == "ao");
+        let ao_row = data.error_anomalies.iter().find(|r| r.ngram == "ao");
         if let Some(ao) = ao_row {
             assert_eq!(ao.sample_count, 10);
             assert!(!ao.confirmed, "ao should not be confirmed (low samples)");
@@ -5109,7 +5790,7 @@ mod review_tests {
         let data2 = build_ngram_tab_data(&app);
 
         // Verify speed anomalies contain our bigram with correct field mapping
-        let ni_row = data2.speed_anomalies.iter().find(|r| r.bigram == "ni");
+        let ni_row = data2.speed_anomalies.iter().find(|r| r.ngram == "ni");
         assert!(ni_row.is_some(), "ni should appear in speed_anomalies");
         let ni = ni_row.unwrap();
         assert_eq!(ni.sample_count, 25);
@@ -5126,14 +5807,46 @@ mod review_tests {
     }
 
     #[test]
-    fn drill_screen_input_lock_blocks_normal_keys() {
-        let mut app = test_app();
-        app.screen = AppScreen::Drill;
-        app.drill = Some(crate::session::drill::DrillState::new("abc"));
-        app.post_drill_input_lock_until =
-            Some(Instant::now() + std::time::Duration::from_millis(500));
+    fn build_ngram_tab_data_includes_trigrams() {
+        use crate::engine::ngram_stats::{ANOMALY_STREAK_REQUIRED, TrigramKey};
 
-        let before_cursor = app.drill.as_ref().unwrap().cursor;
+        let mut app = test_app();
+        for &ch in &['e', 't', 'a', 'o', 'n', 'i'] {
+            let stat = app.ranked_key_stats.stats.entry(ch).or_default();
+            stat.confidence = 0.95;
+            stat.sample_count = 50;
+            stat.error_rate_ema = 0.03;
+        }
+        let stat = app
+            .ranked_trigram_stats
+            .stats
+            .entry(TrigramKey(['t', 'e', 'n']))
+            .or_default();
+        stat.sample_count = 30;
+        stat.error_rate_ema = 0.80;
+        stat.error_anomaly_streak = ANOMALY_STREAK_REQUIRED;
+        app.drill_scope = DrillScope::Global;
+
+        let data = build_ngram_tab_data(&app);
+
+        assert_eq!(data.total_trigrams, 1);
+        let row = &data.trigram_error_anomalies[0];
+        assert_eq!(row.ngram, "ten");
+        assert!(row.confirmed);
+        assert!(data.error_anomalies.is_empty());
+        let (focus, _, _) = data.focus.trigram_focus.expect("trigram focus");
+        assert_eq!(focus, TrigramKey(['t', 'e', 'n']));
+    }
+
+    #[test]
+    fn drill_screen_input_lock_blocks_normal_keys() {
+        let mut app = test_app();
+        app.screen = AppScreen::Drill;
+        app.drill = Some(crate::session::drill::DrillState::new("abc"));
+        app.post_drill_input_lock_until =
+            Some(Instant::now() + std::time::Duration::from_millis(500));
+
+        let before_cursor = app.drill.as_ref().unwrap().cursor;
         handle_key(
             &mut app,
             KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE),
@@ -5421,7 +6134,7 @@ mod review_tests {
         app.skill_tree
             .branch_progress_mut(engine::skill_tree::BranchId::Capitals)
             .status = engine::skill_tree::BranchStatus::Available;
-        app.skill_tree_selected = selectable_branches()
+        app.skill_tree_selected = selectable_branches(&app.skill_tree)
             .iter()
             .position(|id| *id == engine::skill_tree::BranchId::Capitals)
             .unwrap();
@@ -5470,7 +6183,7 @@ mod review_tests {
         app.skill_tree
             .branch_progress_mut(engine::skill_tree::BranchId::Capitals)
             .status = engine::skill_tree::BranchStatus::InProgress;
-        app.skill_tree_selected = selectable_branches()
+        app.skill_tree_selected = selectable_branches(&app.skill_tree)
             .iter()
             .position(|id| *id == engine::skill_tree::BranchId::Capitals)
             .unwrap();
@@ -5492,7 +6205,7 @@ mod review_tests {
         app.skill_tree
             .branch_progress_mut(engine::skill_tree::BranchId::Capitals)
             .status = engine::skill_tree::BranchStatus::Available;
-        app.skill_tree_selected = selectable_branches()
+        app.skill_tree_selected = selectable_branches(&app.skill_tree)
             .iter()
             .position(|id| *id == engine::skill_tree::BranchId::Capitals)
             .unwrap();
@@ -5567,6 +6280,24 @@ mod review_tests {
         assert!(app.shift_held);
     }
 
+    #[test]
+    fn keyboard_explorer_builds_a_key_set_and_drills_it() {
+        let mut app = test_app();
+        app.go_to_keyboard();
+        let ctrl = |ch| KeyEvent::new(KeyCode::Char(ch), KeyModifiers::CONTROL);
+        for ch in ['w', 'y', 'w', 'x'] {
+            keyboard_explorer_select_key(&mut app, ch);
+            handle_keyboard_explorer_key(&mut app, ctrl('a'));
+        }
+        assert_eq!(app.explorer_key_set, vec!['y', 'x']);
+        assert_eq!(app.keyboard_explorer_selected, Some('x'));
+
+        handle_keyboard_explorer_key(&mut app, ctrl('d'));
+        assert_eq!(app.screen, AppScreen::Drill);
+        assert_eq!(app.drill_mode, DrillMode::KeySet);
+        assert_eq!(app.key_set, vec!['y', 'x']);
+    }
+
     #[test]
     fn skill_tree_layout_switches_with_width() {
         assert!(!use_side_by_side_layout(99));
@@ -5606,7 +6337,8 @@ fn render_result(frame: &mut ratatui::Frame, app: &App) {
 
     if let Some(ref result) = app.last_result {
         let centered = ui::layout::centered_rect(60, 70, area);
-        let dashboard = Dashboard::new(result, app.theme, app.post_drill_input_lock_remaining_ms());
+        let dashboard = Dashboard::new(result, app.theme, app.post_drill_input_lock_remaining_ms())
+            .with_needs_review(&app.last_regressed_keys);
         frame.render_widget(dashboard, centered);
 
         if app.history_confirm_delete && !app.drill_history.is_empty() {
@@ -5648,6 +6380,12 @@ fn render_stats(frame: &mut ratatui::Frame, app: &App) {
     } else {
         None
     };
+    // The panel shows as many as fit; more than a screenful is never visible.
+    let problem_words = if app.stats_tab == 3 {
+        app.word_stats.problem_words(30)
+    } else {
+        Vec::new()
+    };
     let dashboard = StatsDashboard::new(
         &app.drill_history,
         &app.key_stats,
@@ -5662,7 +6400,8 @@ fn render_stats(frame: &mut ratatui::Frame, app: &App) {
         app.history_confirm_delete,
         &app.keyboard_model,
         ngram_data.as_ref(),
-    );
+    )
+    .with_problem_words(&problem_words);
     frame.render_widget(dashboard, area);
 }
 
@@ -5682,6 +6421,22 @@ fn current_history_page_size() -> usize {
     }
 }
 
+fn anomaly_rows<K: NgramKey>(anomalies: &[NgramAnomaly<K>]) -> Vec<AnomalyRow> {
+    anomalies
+        .iter()
+        .map(|a| AnomalyRow {
+            ngram: a.key.label(),
+            anomaly_pct: a.anomaly_pct,
+            sample_count: a.sample_count,
+            error_count: a.error_count,
+            error_rate_ema: a.error_rate_ema,
+            speed_ms: a.speed_ms,
+            expected_baseline: a.expected_baseline,
+            confirmed: a.confirmed,
+        })
+        .collect()
+}
+
 fn build_ngram_tab_data(app: &App) -> NgramTabData {
     use engine::ngram_stats::{self, select_focus};
 
@@ -5690,44 +6445,28 @@ fn build_ngram_tab_data(app: &App) -> NgramTabData {
         app.drill_scope,
         &app.ranked_key_stats,
         &app.ranked_bigram_stats,
+        &app.ranked_trigram_stats,
     );
 
     let unlocked = app.skill_tree.unlocked_keys(app.drill_scope);
 
     let error_anomalies_raw = app
         .ranked_bigram_stats
-        .error_anomaly_bigrams(&app.ranked_key_stats, &unlocked);
+        .error_anomalies(&app.ranked_key_stats, &unlocked);
     let speed_anomalies_raw = app
         .ranked_bigram_stats
-        .speed_anomaly_bigrams(&app.ranked_key_stats, &unlocked);
-
-    let error_anomalies: Vec<AnomalyBigramRow> = error_anomalies_raw
-        .iter()
-        .map(|a| AnomalyBigramRow {
-            bigram: format!("{}{}", a.key.0[0], a.key.0[1]),
-            anomaly_pct: a.anomaly_pct,
-            sample_count: a.sample_count,
-            error_count: a.error_count,
-            error_rate_ema: a.error_rate_ema,
-            speed_ms: a.speed_ms,
-            expected_baseline: a.expected_baseline,
-            confirmed: a.confirmed,
-        })
-        .collect();
+        .speed_anomalies(&app.ranked_key_stats, &unlocked);
 
-    let speed_anomalies: Vec<AnomalyBigramRow> = speed_anomalies_raw
-        .iter()
-        .map(|a| AnomalyBigramRow {
-            bigram: format!("{}{}", a.key.0[0], a.key.0[1]),
-            anomaly_pct: a.anomaly_pct,
-            sample_count: a.sample_count,
-            error_count: a.error_count,
-            error_rate_ema: a.error_rate_ema,
-            speed_ms: a.speed_ms,
-            expected_baseline: a.expected_baseline,
-            confirmed: a.confirmed,
-        })
-        .collect();
+    let error_anomalies = anomaly_rows(&error_anomalies_raw);
+    let speed_anomalies = anomaly_rows(&speed_anomalies_raw);
+    let trigram_error_anomalies = anomaly_rows(
+        &app.ranked_trigram_stats
+            .error_anomalies(&app.ranked_key_stats, &unlocked),
+    );
+    let trigram_speed_anomalies = anomaly_rows(
+        &app.ranked_trigram_stats
+            .speed_anomalies(&app.ranked_key_stats, &unlocked),
+    );
 
     let scope_label = match app.drill_scope {
         DrillScope::Global => "Global".to_string(),
@@ -5741,6 +6480,9 @@ fn build_ngram_tab_data(app: &App) -> NgramTabData {
         error_anomalies,
         speed_anomalies,
         total_bigrams: app.ranked_bigram_stats.stats.len(),
+        trigram_error_anomalies,
+        trigram_speed_anomalies,
+        total_trigrams: app.ranked_trigram_stats.stats.len(),
         hesitation_threshold_ms,
         scope_label,
     }
@@ -5965,7 +6707,7 @@ fn render_settings(frame: &mut ratatui::Frame, app: &App) {
                 .style(Style::default().bg(colors.bg())),
         );
         frame.render_widget(dialog, dialog_area);
-    } else if app.settings_export_conflict {
+    } else if app.settings_export_conflict.is_some() {
         let dialog_width = 52u16.min(area.width.saturating_sub(4));
         let dialog_height = 6u16;
         let dialog_x = area.x + area.width.saturating_sub(dialog_width) / 2;
@@ -6032,9 +6774,67 @@ fn render_settings(frame: &mut ratatui::Frame, app: &App) {
                 .style(Style::default().bg(colors.bg()))
         });
         frame.render_widget(dialog, dialog_area);
+    } else if let Some(ref pending) = app.settings_merge_pending {
+        let dialog_area = merge_dialog_rect(area);
+        frame.render_widget(ratatui::widgets::Clear, dialog_area);
+
+        let added = match (pending.history.first_added, pending.history.last_added) {
+            (Some(first), Some(last)) => t!(
+                "settings.merge_adds",
+                count = pending.history.added,
+                from = first.format("%Y-%m-%d").to_string(),
+                to = last.format("%Y-%m-%d").to_string()
+            ),
+            _ => t!("settings.merge_nothing_new"),
+        };
+        let skipped = t!("settings.merge_skipped", count = pending.history.duplicates);
+        let branches = t!("settings.merge_branches", count = pending.branches_advanced);
+        let keeps = t!("settings.merge_keeps_settings");
+        let proceed_yn = t!("settings.proceed_yn");
+        let dialog = Paragraph::new(vec![
+            Line::from(""),
+            Line::from(Span::styled(
+                format!("  {added}"),
+                Style::default().fg(colors.fg()),
+            )),
+            Line::from(Span::styled(
+                format!("  {skipped}"),
+                Style::default().fg(colors.text_pending()),
+            )),
+            Line::from(Span::styled(
+                format!("  {branches}"),
+                Style::default().fg(colors.text_pending()),
+            )),
+            Line::from(Span::styled(
+                format!("  {keeps}"),
+                Style::default().fg(colors.text_pending()),
+            )),
+            Line::from(""),
+            Line::from(Span::styled(
+                format!("  {proceed_yn}"),
+                Style::default().fg(colors.fg()),
+            )),
+        ])
+        .style(Style::default().bg(colors.bg()))
+        .block({
+            let cm_title = t!("settings.confirm_merge_title");
+            Block::bordered()
+                .title(cm_title.to_string())
+                .border_style(Style::default().fg(colors.accent()))
+                .style(Style::default().bg(colors.bg()))
+        });
+        frame.render_widget(dialog, dialog_area);
     }
 }
 
+fn merge_dialog_rect(area: Rect) -> Rect {
+    let dialog_width = 64u16.min(area.width.saturating_sub(4));
+    let dialog_height = 9u16;
+    let dialog_x = area.x + area.width.saturating_sub(dialog_width) / 2;
+    let dialog_y = area.y + area.height.saturating_sub(dialog_height) / 2;
+    Rect::new(dialog_x, dialog_y, dialog_width, dialog_height)
+}
+
 fn render_dictionary_language_select(frame: &mut ratatui::Frame, app: &App) {
     let area = frame.area();
     let colors = &app.theme.colors;
@@ -6053,8 +6853,13 @@ fn render_dictionary_language_select(frame: &mut ratatui::Frame, app: &App) {
     let h_confirm = ui::hint::hint(ui::hint::K_ENTER, t!("select.hint_confirm").as_ref());
     let h_back = ui::hint::hint(ui::hint::K_Q_ESC, t!("select.hint_back").as_ref());
     let footer_hints: Vec<&str> = vec![h_nav.as_str(), h_confirm.as_str(), h_back.as_str()];
-    let support_notice_t = t!("select.language_resets_layout");
-    let support_notice = support_notice_t.as_ref();
+    let mut support_notice_t = t!("select.language_resets_layout").to_string();
+    let skipped_packs = runtime_packs().issues.len();
+    if skipped_packs > 0 {
+        support_notice_t.push(' ');
+        support_notice_t.push_str(&t!("select.runtime_packs_skipped", count = skipped_packs));
+    }
+    let support_notice = support_notice_t.as_str();
     let width = inner.width as usize;
     let hint_lines_vec = pack_hint_lines(&footer_hints, width);
     let hint_lines = hint_lines_vec.len();
@@ -6162,6 +6967,159 @@ fn render_dictionary_language_select(frame: &mut ratatui::Frame, app: &App) {
     }
 }
 
+fn render_vocabulary_editor(frame: &mut ratatui::Frame, app: &App) {
+    let area = frame.area();
+    let colors = &app.theme.colors;
+    let centered = ui::layout::centered_rect(60, 70, area);
+
+    let language = find_language_pack(&app.config.dictionary_language)
+        .map(|pack| pack.autonym.to_string())
+        .unwrap_or_else(|| app.config.dictionary_language.clone());
+    let title = t!("vocabulary.title", language = language);
+    let block = Block::bordered()
+        .title(title.as_ref())
+        .border_style(Style::default().fg(colors.accent()))
+        .style(Style::default().bg(colors.bg()));
+    let inner = block.inner(centered);
+    block.render(centered, frame.buffer_mut());
+
+    let h_switch = ui::hint::hint(ui::hint::K_TAB, t!("vocabulary.hint_switch").as_ref());
+    let h_add = ui::hint::hint(ui::hint::K_A, t!("vocabulary.hint_add").as_ref());
+    let h_remove = ui::hint::hint(ui::hint::K_D_DEL, t!("vocabulary.hint_remove").as_ref());
+    let h_weight = ui::hint::hint(
+        ui::hint::K_PLUS_MINUS,
+        t!("vocabulary.hint_weight").as_ref(),
+    );
+    let h_back = ui::hint::hint(ui::hint::K_Q_ESC, t!("select.hint_back").as_ref());
+    let mut footer_hints: Vec<&str> = vec![h_switch.as_str(), h_add.as_str(), h_remove.as_str()];
+    if app.vocabulary_tab == 0 {
+        footer_hints.push(h_weight.as_str());
+    }
+    footer_hints.push(h_back.as_str());
+    let mut footer_lines: Vec<Line> = pack_hint_lines(&footer_hints, inner.width as usize)
+        .into_iter()
+        .map(|line| {
+            Line::from(Span::styled(
+                line,
+                Style::default().fg(colors.text_pending()),
+            ))
+        })
+        .collect();
+    if let Some(ref status) = app.settings_status_message {
+        footer_lines.insert(
+            0,
+            Line::from(Span::styled(
+                status.text.clone(),
+                Style::default().fg(colors.error()),
+            )),
+        );
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2),
+            Constraint::Min(1),
+            Constraint::Length(footer_lines.len() as u16),
+        ])
+        .split(inner);
+
+    let tab_labels = [t!("vocabulary.tab_words"), t!("vocabulary.tab_blocklist")];
+    let tabs: Vec<Span> = tab_labels
+        .iter()
+        .enumerate()
+        .map(|(i, label)| {
+            let style = if i == app.vocabulary_tab {
+                Style::default()
+                    .fg(colors.accent())
+                    .add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+            } else {
+                Style::default().fg(colors.text_pending())
+            };
+            Span::styled(format!(" {label} "), style)
+        })
+        .collect();
+    Paragraph::new(Line::from(tabs)).render(chunks[0], frame.buffer_mut());
+
+    let mut lines: Vec<Line> = Vec::new();
+    if let Some(ref input) = app.vocabulary_input {
+        let prompt = if app.vocabulary_tab == 0 {
+            t!("vocabulary.adding_word")
+        } else {
+            t!("vocabulary.adding_block")
+        };
+        let (before, cursor_ch, after) = input.render_parts();
+        let cursor_style = Style::default().fg(colors.bg()).bg(colors.focused_key());
+        let value_style = Style::default().fg(colors.fg());
+        lines.push(Line::from(Span::styled(
+            format!(" {prompt}"),
+            Style::default().fg(colors.accent()),
+        )));
+        lines.push(Line::from(vec![
+            Span::styled(format!("   {before}"), value_style),
+            Span::styled(cursor_ch.unwrap_or(' ').to_string(), cursor_style),
+            Span::styled(after.to_string(), value_style),
+        ]));
+        lines.push(Line::from(""));
+    }
+
+    let entries: Vec<String> = if app.vocabulary_tab == 0 {
+        app.vocabulary
+            .words
+            .iter()
+            .map(|entry| {
+                if entry.weight > 1 {
+                    format!(
+                        "{}  {}",
+                        entry.word,
+                        t!("vocabulary.weight", weight = entry.weight)
+                    )
+                } else {
+                    entry.word.clone()
+                }
+            })
+            .collect()
+    } else {
+        app.vocabulary.blocked.clone()
+    };
+    if entries.is_empty() {
+        let empty = if app.vocabulary_tab == 0 {
+            t!("vocabulary.empty_words")
+        } else {
+            t!("vocabulary.empty_blocklist")
+        };
+        lines.push(Line::from(Span::styled(
+            format!(" {empty}"),
+            Style::default().fg(colors.text_pending()),
+        )));
+    }
+    let viewport = (chunks[1].height as usize)
+        .saturating_sub(lines.len())
+        .max(1);
+    let scroll = app
+        .vocabulary_selected
+        .saturating_sub(viewport.saturating_sub(1));
+    for (i, entry) in entries.iter().enumerate().skip(scroll).take(viewport) {
+        let is_selected = i == app.vocabulary_selected;
+        let indicator = if is_selected { " > " } else { "   " };
+        let style = if is_selected {
+            Style::default()
+                .fg(colors.accent())
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(colors.fg())
+        };
+        lines.push(Line::from(Span::styled(
+            format!("{indicator}{entry}"),
+            style,
+        )));
+    }
+    Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .render(chunks[1], frame.buffer_mut());
+    Paragraph::new(footer_lines).render(chunks[2], frame.buffer_mut());
+}
+
 fn render_ui_language_select(frame: &mut ratatui::Frame, app: &App) {
     use crate::i18n::t;
     let area = frame.area();
@@ -6176,7 +7134,7 @@ fn render_ui_language_select(frame: &mut ratatui::Frame, app: &App) {
     let inner = block.inner(centered);
     block.render(centered, frame.buffer_mut());
 
-    let locales = i18n::SUPPORTED_UI_LOCALES;
+    let locales = i18n::ui_locales();
     let hint_back = t!("select.hint_back");
     let hint_confirm = t!("select.hint_confirm");
     let footer_hints = [hint_back.as_ref(), hint_confirm.as_ref()];
@@ -6195,11 +7153,14 @@ fn render_ui_language_select(frame: &mut ratatui::Frame, app: &App) {
         let autonym = find_language_pack(locale)
             .map(|p| p.autonym)
             .unwrap_or(locale);
-        let suffix = if is_current {
+        let mut suffix = if is_current {
             t!("select.current").to_string()
         } else {
             String::new()
         };
+        if i18n::is_user_locale(locale) {
+            suffix.push_str(&t!("select.user_locale"));
+        }
         let indicator = if is_selected { "> " } else { "  " };
         let label = format!("{indicator}{autonym}{suffix}");
         let style = if is_selected {
@@ -6222,6 +7183,55 @@ fn render_ui_language_select(frame: &mut ratatui::Frame, app: &App) {
     Paragraph::new(footer).render(chunks[1], frame.buffer_mut());
 }
 
+fn render_technique_select(frame: &mut ratatui::Frame, app: &App) {
+    let area = frame.area();
+    let colors = &app.theme.colors;
+    let centered = ui::layout::centered_rect(50, 70, area);
+
+    let title = t!("technique.title");
+    let block = Block::bordered()
+        .title(title.as_ref())
+        .border_style(Style::default().fg(colors.accent()))
+        .style(Style::default().bg(colors.bg()));
+    block.render(centered, frame.buffer_mut());
+
+    let (list_area, footer_area) = technique_select_areas(area);
+    let techniques = Technique::all();
+    let start = technique_list_start(app, list_area);
+    let mut lines: Vec<Line> = Vec::new();
+    for (i, technique) in techniques
+        .iter()
+        .enumerate()
+        .skip(start)
+        .take(list_area.height as usize)
+    {
+        let is_selected = i == app.technique_selected;
+        let indicator = if is_selected { " > " } else { "   " };
+        let style = if is_selected {
+            Style::default()
+                .fg(colors.accent())
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(colors.fg())
+        };
+        lines.push(Line::from(Span::styled(
+            format!("{indicator}[{}] {}", i + 1, technique.label()),
+            style,
+        )));
+    }
+    Paragraph::new(lines).render(list_area, frame.buffer_mut());
+
+    if let Some(footer) = footer_area {
+        let hints = technique_select_hints();
+        let hint_refs: Vec<&str> = hints.iter().map(String::as_str).collect();
+        let footer_lines: Vec<Line> = pack_hint_lines(&hint_refs, footer.width as usize)
+            .into_iter()
+            .map(|l| Line::from(Span::styled(l, Style::default().fg(colors.text_pending()))))
+            .collect();
+        Paragraph::new(footer_lines).render(footer, frame.buffer_mut());
+    }
+}
+
 fn render_keyboard_layout_select(frame: &mut ratatui::Frame, app: &App) {
     let area = frame.area();
     let colors = &app.theme.colors;
@@ -7102,7 +8112,7 @@ fn render_skill_tree(frame: &mut ratatui::Frame, app: &App) {
         let sentence_one = sentence_one_t.as_ref();
         let sentence_two_t = t!("skill_tree.unlock_msg_2");
         let sentence_two = sentence_two_t.as_ref();
-        let branch_name = engine::skill_tree::get_branch_definition(branch_id).display_name();
+        let branch_name = app.skill_tree.branch_definition(branch_id).display_name();
         let dialog_width = 72u16.min(area.width.saturating_sub(4));
         let content_width = dialog_width.saturating_sub(6).max(1) as usize; // border + side margins
         let body_required = 4 // blank + title + blank + blank-between-sentences
@@ -7186,7 +8196,95 @@ fn render_skill_tree(frame: &mut ratatui::Frame, app: &App) {
     }
 }
 
+fn keyboard_explorer_hints() -> Vec<String> {
+    vec![
+        ui::hint::hint(ui::hint::K_ESC, t!("keyboard.hint_back").as_ref()),
+        ui::hint::hint(ui::hint::K_CTRL_A, t!("keyboard.hint_toggle_key").as_ref()),
+        ui::hint::hint(
+            ui::hint::K_CTRL_D,
+            t!("keyboard.hint_key_set_drill").as_ref(),
+        ),
+        ui::hint::hint(
+            ui::hint::K_CTRL_S,
+            t!("keyboard.hint_save_key_set").as_ref(),
+        ),
+        ui::hint::hint(
+            ui::hint::K_CTRL_P,
+            t!("keyboard.hint_next_key_set").as_ref(),
+        ),
+    ]
+}
+
+/// Header, keyboard diagram, detail panel, key set line and footer.
+fn keyboard_explorer_layout(area: Rect) -> std::rc::Rc<[Rect]> {
+    let hints = keyboard_explorer_hints();
+    let hint_refs: Vec<&str> = hints.iter().map(String::as_str).collect();
+    let footer_height = pack_hint_lines(&hint_refs, area.width as usize)
+        .len()
+        .max(1) as u16;
+    Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Length(8),
+            Constraint::Min(3),
+            Constraint::Length(1),
+            Constraint::Length(footer_height),
+        ])
+        .split(area)
+}
+
+fn key_set_text(keys: &[char]) -> String {
+    keys.iter()
+        .map(|ch| ch.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn toggle_keyboard_explorer_key(app: &mut App) {
+    if let Some(ch) = app.keyboard_explorer_selected {
+        app.toggle_explorer_key(ch);
+    }
+}
+
+fn start_keyboard_explorer_drill(app: &mut App) {
+    app.start_key_set_drill(app.explorer_key_set.clone());
+}
+
+fn begin_key_set_name_input(app: &mut App) {
+    if !app.explorer_key_set.is_empty() {
+        let name = app.explorer_key_set_name.clone().unwrap_or_default();
+        app.key_set_name_input = Some(LineInput::new(&name));
+    }
+}
+
 fn handle_keyboard_explorer_key(app: &mut App, key: KeyEvent) {
+    if let Some(ref mut input) = app.key_set_name_input {
+        match input.handle(key) {
+            InputResult::Submit => {
+                let name = input.value().to_string();
+                app.key_set_name_input = None;
+                if app.save_key_set_preset(&name) {
+                    let _ = app.config.save();
+                }
+            }
+            InputResult::Cancel => app.key_set_name_input = None,
+            InputResult::Continue => {}
+        }
+        return;
+    }
+
+    if key.modifiers.contains(KeyModifiers::CONTROL) {
+        match key.code {
+            KeyCode::Char('a') => toggle_keyboard_explorer_key(app),
+            KeyCode::Char('d') => start_keyboard_explorer_drill(app),
+            KeyCode::Char('s') => begin_key_set_name_input(app),
+            KeyCode::Char('p') => app.load_next_key_set_preset(),
+            _ => {}
+        }
+        return;
+    }
+
     match key.code {
         KeyCode::Esc => app.go_to_menu(),
         KeyCode::Char('q') if app.keyboard_explorer_selected.is_none() => app.go_to_menu(),
@@ -7222,22 +8320,21 @@ fn handle_keyboard_explorer_mouse(app: &mut App, mouse: MouseEvent) {
     ) {
         return;
     }
-    let area = terminal_area();
-    let layout = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3),
-            Constraint::Length(8),
-            Constraint::Min(3),
-            Constraint::Length(1),
-        ])
-        .split(area);
-    let h_back = ui::hint::hint(ui::hint::K_ESC, t!("keyboard.hint_back").as_ref());
-    let footer_hints: Vec<&str> = vec![h_back.as_str()];
-    if hint_token_at(layout[3], &footer_hints, mouse.column, mouse.row).is_some()
-        || point_in_rect(mouse.column, mouse.row, layout[3])
-    {
-        app.go_to_menu();
+    if app.key_set_name_input.is_some() {
+        return;
+    }
+    let is_secondary = matches!(mouse.kind, MouseEventKind::Down(MouseButton::Right));
+    let layout = keyboard_explorer_layout(terminal_area());
+    let hints = keyboard_explorer_hints();
+    let footer_hints: Vec<&str> = hints.iter().map(String::as_str).collect();
+    if point_in_rect(mouse.column, mouse.row, layout[4]) {
+        match hint_token_at(layout[4], &footer_hints, mouse.column, mouse.row).as_deref() {
+            Some(ui::hint::K_CTRL_A) => toggle_keyboard_explorer_key(app),
+            Some(ui::hint::K_CTRL_D) => start_keyboard_explorer_drill(app),
+            Some(ui::hint::K_CTRL_S) => begin_key_set_name_input(app),
+            Some(ui::hint::K_CTRL_P) => app.load_next_key_set_preset(),
+            _ => app.go_to_menu(),
+        }
         return;
     }
 
@@ -7266,6 +8363,9 @@ fn handle_keyboard_explorer_mouse(app: &mut App, mouse: MouseEvent) {
                 ch = shifted;
             }
             keyboard_explorer_select_key(app, ch);
+            if is_secondary {
+                app.toggle_explorer_key(ch);
+            }
         }
     }
 }
@@ -7274,15 +8374,7 @@ fn render_keyboard_explorer(frame: &mut ratatui::Frame, app: &App) {
     let area = frame.area();
     let colors = &app.theme.colors;
 
-    let layout = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3), // header
-            Constraint::Length(8), // keyboard diagram
-            Constraint::Min(3),    // detail panel
-            Constraint::Length(1), // footer
-        ])
-        .split(area);
+    let layout = keyboard_explorer_layout(area);
 
     // Header
     let kbd_title = t!("keyboard.title");
@@ -7305,6 +8397,7 @@ fn render_keyboard_explorer(frame: &mut ratatui::Frame, app: &App) {
 
     // Keyboard diagram
     let unlocked = app.skill_tree.unlocked_keys(DrillScope::Global);
+    let regressed = app.skill_tree.regressed_keys();
     let kbd = KeyboardDiagram::new(
         None,
         &unlocked,
@@ -7312,7 +8405,9 @@ fn render_keyboard_explorer(frame: &mut ratatui::Frame, app: &App) {
         app.theme,
         &app.keyboard_model,
     )
+    .regressed_keys(&regressed)
     .selected_key(app.keyboard_explorer_selected)
+    .marked_keys(&app.explorer_key_set)
     .shift_held(app.shift_held)
     .caps_lock(app.caps_lock);
     frame.render_widget(kbd, layout[1]);
@@ -7320,13 +8415,46 @@ fn render_keyboard_explorer(frame: &mut ratatui::Frame, app: &App) {
     // Detail panel
     render_keyboard_detail_panel(frame, app, layout[2]);
 
+    // Key set
+    let key_set_line = if let Some(ref input) = app.key_set_name_input {
+        let (before, cursor_ch, after) = input.render_parts();
+        let value_style = Style::default().fg(colors.fg());
+        Line::from(vec![
+            Span::styled(
+                format!(" {}", t!("keyboard.key_set_name_prompt")),
+                Style::default().fg(colors.accent()),
+            ),
+            Span::styled(before.to_string(), value_style),
+            Span::styled(
+                cursor_ch.unwrap_or(' ').to_string(),
+                Style::default().fg(colors.bg()).bg(colors.focused_key()),
+            ),
+            Span::styled(after.to_string(), value_style),
+        ])
+    } else {
+        let keys = key_set_text(&app.explorer_key_set);
+        let text = match (&app.explorer_key_set_name, keys.is_empty()) {
+            (_, true) => t!("keyboard.key_set_empty").to_string(),
+            (Some(name), false) => {
+                t!("keyboard.key_set_named", name = name, keys = keys).to_string()
+            }
+            (None, false) => t!("keyboard.key_set", keys = keys).to_string(),
+        };
+        Line::from(Span::styled(
+            format!(" {text}"),
+            Style::default().fg(colors.accent()),
+        ))
+    };
+    frame.render_widget(Paragraph::new(key_set_line), layout[3]);
+
     // Footer
-    let kbd_back = ui::hint::hint(ui::hint::K_ESC, t!("keyboard.hint_back").as_ref());
-    let footer = Paragraph::new(Line::from(vec![Span::styled(
-        format!(" {} ", kbd_back.as_str()),
-        Style::default().fg(colors.text_pending()),
-    )]));
-    frame.render_widget(footer, layout[3]);
+    let hints = keyboard_explorer_hints();
+    let hint_refs: Vec<&str> = hints.iter().map(String::as_str).collect();
+    let footer_lines: Vec<Line> = pack_hint_lines(&hint_refs, layout[4].width as usize)
+        .into_iter()
+        .map(|l| Line::from(Span::styled(l, Style::default().fg(colors.text_pending()))))
+        .collect();
+    frame.render_widget(Paragraph::new(footer_lines), layout[4]);
 }
 
 fn render_keyboard_detail_panel(frame: &mut ratatui::Frame, app: &App, area: Rect) {
@@ -7435,11 +8563,36 @@ fn render_keyboard_detail_panel(frame: &mut ratatui::Frame, app: &App, area: Rec
         t!("keyboard.no_data_short").to_string()
     };
 
-    let branch_info = find_key_branch(selected)
-        .map(|(branch, level, pos)| (branch.display_name(), format!("{} (key #{pos})", level.display_name())));
+    let now = chrono::Utc::now();
+    let last_practiced_text = match overall_stat.and_then(|s| s.last_practiced) {
+        Some(last) => match (now - last).num_days() {
+            0 => t!("keyboard.today").to_string(),
+            days => t!("keyboard.days_ago", days = days).to_string(),
+        },
+        None => t!("keyboard.never").to_string(),
+    };
+    let retention_text = format!(
+        "{:.0}% ({})",
+        app.key_stats.retention(selected, now) * 100.0,
+        app.config.forgetting_curve.localized_name()
+    );
+
+    let branch_info = app
+        .skill_tree
+        .find_key_branch(selected)
+        .map(|(branch, level, pos)| {
+            (
+                branch.display_name(),
+                format!("{} (key #{pos})", level.display_name()),
+            )
+        });
 
     // Ranked-only mastery display (same semantics as skill tree per-key progress)
-    let ranked_conf = app.ranked_key_stats.get_confidence(selected).min(1.0);
+    let home_branch = app.skill_tree.home_branch(selected);
+    let ranked_conf = app
+        .skill_tree
+        .key_progress(home_branch, selected, &app.ranked_key_stats)
+        .min(1.0);
     let mastery_bar_width = 10usize;
     let filled = (ranked_conf * mastery_bar_width as f64).round() as usize;
     let mastery_bar = format!(
@@ -7456,6 +8609,8 @@ fn render_keyboard_detail_panel(frame: &mut ratatui::Frame, app: &App, area: Rec
         format!("{}{}", t!("keyboard.overall_best_time"), fmt_best_time(overall_stat)),
         format!("{}{}", t!("keyboard.overall_samples"), fmt_samples(overall_stat)),
         format!("{}{}", t!("keyboard.overall_accuracy_label"), fmt_acc(overall_acc)),
+        format!("{}{last_practiced_text}", t!("keyboard.last_practiced_label")),
+        format!("{}{retention_text}", t!("keyboard.retention_label")),
     ];
 
     let mut right_col: Vec<String> = Vec::new();
@@ -7520,3 +8675,401 @@ fn render_keyboard_detail_panel(frame: &mut ratatui::Frame, app: &App, area: Rec
     let paragraph = Paragraph::new(lines).wrap(Wrap { trim: false });
     frame.render_widget(paragraph, inner);
 }
+
+fn race_screen_layout(area: Rect) -> (Rect, Rect) {
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(area);
+    (ui::layout::centered_rect(60, 70, layout[0]), layout[1])
+}
+
+fn race_lobby_hints(app: &App) -> Vec<String> {
+    let can_start = app
+        .race
+        .as_ref()
+        .is_some_and(|race| race.is_host() && race.phase() == race::RacePhase::Lobby);
+    let mut hints = Vec::new();
+    if can_start {
+        hints.push(ui::hint::hint(
+            ui::hint::K_ENTER,
+            t!("race.hint_start").as_ref(),
+        ));
+    }
+    hints.push(ui::hint::hint(
+        ui::hint::K_Q_ESC,
+        t!("race.hint_leave").as_ref(),
+    ));
+    hints
+}
+
+fn handle_race_lobby_key(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Enter => app.start_race(),
+        KeyCode::Esc | KeyCode::Char('q') => app.leave_race(),
+        _ => {}
+    }
+}
+
+fn handle_race_lobby_mouse(app: &mut App, mouse: MouseEvent) {
+    if !matches!(mouse.kind, MouseEventKind::Down(MouseButton::Left)) {
+        return;
+    }
+    let (_, footer) = race_screen_layout(terminal_area());
+    let hints = race_lobby_hints(app);
+    let hint_refs: Vec<&str> = hints.iter().map(String::as_str).collect();
+    match hint_token_at(footer, &hint_refs, mouse.column, mouse.row).as_deref() {
+        Some(ui::hint::K_ENTER) => app.start_race(),
+        Some(ui::hint::K_Q_ESC) => app.leave_race(),
+        _ => {}
+    }
+}
+
+fn handle_race_result_key(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter => app.leave_race(),
+        _ => {}
+    }
+}
+
+fn handle_race_result_mouse(app: &mut App, mouse: MouseEvent) {
+    if !matches!(mouse.kind, MouseEventKind::Down(MouseButton::Left)) {
+        return;
+    }
+    let (_, footer) = race_screen_layout(terminal_area());
+    let hint_leave = ui::hint::hint(ui::hint::K_Q_ESC, t!("race.hint_leave").as_ref());
+    if hint_token_at(footer, &[hint_leave.as_str()], mouse.column, mouse.row).is_some() {
+        app.leave_race();
+    }
+}
+
+fn render_race_footer(frame: &mut ratatui::Frame, app: &App, hints: &[String], area: Rect) {
+    let footer = Paragraph::new(Line::from(Span::styled(
+        format!(" {} ", hints.join("  ")),
+        Style::default().fg(app.theme.colors.text_pending()),
+    )));
+    frame.render_widget(footer, area);
+}
+
+fn render_race_lobby(frame: &mut ratatui::Frame, app: &App) {
+    let Some(ref race) = app.race else {
+        return;
+    };
+    let colors = &app.theme.colors;
+    let (panel, footer) = race_screen_layout(frame.area());
+
+    let title = t!("race.lobby_title");
+    let block = Block::bordered()
+        .title(title.as_ref())
+        .border_style(Style::default().fg(colors.accent()))
+        .style(Style::default().bg(colors.bg()))
+        .padding(Padding::horizontal(1));
+    let inner = block.inner(panel);
+    block.render(panel, frame.buffer_mut());
+
+    let address_line = if race.is_host() {
+        t!("race.hosting_on", address = &race.address)
+    } else {
+        t!("race.joined", address = &race.address)
+    };
+    let mut lines = vec![
+        Line::from(Span::styled(
+            address_line.to_string(),
+            Style::default()
+                .fg(colors.fg())
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            t!("race.racers", count = race.racers.len()).to_string(),
+            Style::default().fg(colors.accent()),
+        )),
+    ];
+    for racer in &race.racers {
+        let you = if racer.id == race.local_id {
+            format!(" {}", t!("race.you"))
+        } else {
+            String::new()
+        };
+        lines.push(Line::from(Span::styled(
+            format!("  \u{2022} {}{you}", racer.name),
+            Style::default().fg(colors.fg()),
+        )));
+    }
+    lines.push(Line::from(""));
+
+    let (status, status_color) = if race.host_lost {
+        (t!("race.host_lost").to_string(), colors.error())
+    } else {
+        match race.phase() {
+            race::RacePhase::Lobby if race.is_host() => (
+                t!("race.press_enter_to_start").to_string(),
+                colors.text_pending(),
+            ),
+            race::RacePhase::Lobby => (
+                t!("race.waiting_for_host").to_string(),
+                colors.text_pending(),
+            ),
+            _ => (
+                t!("race.starting_in", secs = race.countdown_secs()).to_string(),
+                colors.focused_key(),
+            ),
+        }
+    };
+    lines.push(Line::from(Span::styled(
+        status,
+        Style::default()
+            .fg(status_color)
+            .add_modifier(Modifier::BOLD),
+    )));
+
+    Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .render(inner, frame.buffer_mut());
+
+    render_race_footer(frame, app, &race_lobby_hints(app), footer);
+}
+
+fn render_race_result(frame: &mut ratatui::Frame, app: &App) {
+    let Some(ref race) = app.race else {
+        return;
+    };
+    let (panel, footer) = race_screen_layout(frame.area());
+    let standings = ui::components::race_standings::RaceStandings {
+        session: race,
+        theme: app.theme,
+    };
+    frame.render_widget(standings, panel);
+
+    let hints = if let Some(ms) = app.post_drill_input_lock_remaining_ms() {
+        vec![t!("drill.keys_reenabled", ms = ms).to_string()]
+    } else {
+        vec![ui::hint::hint(
+            ui::hint::K_Q_ESC,
+            t!("race.hint_leave").as_ref(),
+        )]
+    };
+    render_race_footer(frame, app, &hints, footer);
+}
+
+fn shortcut_screen_layout(area: Rect) -> (Rect, Rect) {
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(area);
+    (ui::layout::centered_rect(60, 70, layout[0]), layout[1])
+}
+
+fn shortcut_round_over(app: &App) -> bool {
+    app.shortcut_session
+        .as_ref()
+        .is_none_or(|session| session.is_finished())
+}
+
+fn shortcut_drill_hints(app: &App) -> Vec<String> {
+    if !shortcut_round_over(app) {
+        // `q` may well be part of a shortcut, so only Esc leaves mid-round.
+        return vec![ui::hint::hint(
+            ui::hint::K_ESC,
+            t!("shortcuts.hint_back").as_ref(),
+        )];
+    }
+    let mut hints = Vec::new();
+    if app.shortcut_session.is_some() {
+        hints.push(ui::hint::hint(
+            ui::hint::K_ENTER_SPACE,
+            t!("shortcuts.hint_next_round").as_ref(),
+        ));
+    }
+    hints.push(ui::hint::hint(
+        ui::hint::K_Q_ESC,
+        t!("shortcuts.hint_back").as_ref(),
+    ));
+    hints
+}
+
+fn handle_shortcut_drill_key(app: &mut App, key: KeyEvent) {
+    if shortcut_round_over(app) {
+        match key.code {
+            KeyCode::Enter | KeyCode::Char(' ') if app.shortcut_session.is_some() => {
+                app.start_shortcut_drill()
+            }
+            KeyCode::Esc | KeyCode::Char('q') => app.leave_shortcut_drill(),
+            _ => {}
+        }
+        return;
+    }
+
+    let Some(chord) = Chord::from_key_event(&key) else {
+        return;
+    };
+    let esc_expected = app
+        .shortcut_session
+        .as_ref()
+        .and_then(|session| session.expected())
+        .is_some_and(|expected| expected == chord);
+    if key.code == KeyCode::Esc && !esc_expected {
+        app.leave_shortcut_drill();
+        return;
+    }
+    app.shortcut_press(chord);
+}
+
+fn handle_shortcut_drill_mouse(app: &mut App, mouse: MouseEvent) {
+    if !matches!(mouse.kind, MouseEventKind::Down(MouseButton::Left)) {
+        return;
+    }
+    let (_, footer) = shortcut_screen_layout(terminal_area());
+    let hints = shortcut_drill_hints(app);
+    let hint_refs: Vec<&str> = hints.iter().map(String::as_str).collect();
+    match hint_token_at(footer, &hint_refs, mouse.column, mouse.row).as_deref() {
+        Some(ui::hint::K_ENTER_SPACE) => app.start_shortcut_drill(),
+        Some(ui::hint::K_ESC) | Some(ui::hint::K_Q_ESC) => app.leave_shortcut_drill(),
+        _ => {}
+    }
+}
+
+fn render_shortcut_drill(frame: &mut ratatui::Frame, app: &App) {
+    let colors = &app.theme.colors;
+    let (panel, footer) = shortcut_screen_layout(frame.area());
+    let session = app.shortcut_session.as_ref();
+
+    let pack_name = session.map_or(app.config.shortcut_pack.as_str(), |s| s.pack.name.as_str());
+    let title = t!("shortcuts.title", pack = pack_name);
+    let block = Block::bordered()
+        .title(title.as_ref())
+        .border_style(Style::default().fg(colors.accent()))
+        .style(Style::default().bg(colors.bg()))
+        .padding(Padding::horizontal(1));
+    let inner = block.inner(panel);
+    block.render(panel, frame.buffer_mut());
+
+    let pending = Style::default().fg(colors.text_pending());
+    let mut lines: Vec<Line> = Vec::new();
+    match session {
+        None => {
+            let error = app.shortcut_load_error.as_deref().unwrap_or_default();
+            lines.push(Line::from(Span::styled(
+                t!("shortcuts.load_failed", error = error).to_string(),
+                Style::default().fg(colors.error()),
+            )));
+        }
+        Some(session) if !session.is_finished() => {
+            let Some(shortcut) = session.current() else {
+                return;
+            };
+            lines.push(Line::from(Span::styled(
+                t!(
+                    "shortcuts.prompt_count",
+                    current = session.position + 1,
+                    total = session.queue.len()
+                )
+                .to_string(),
+                pending,
+            )));
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                shortcut.action.clone(),
+                Style::default()
+                    .fg(colors.fg())
+                    .add_modifier(Modifier::BOLD),
+            )));
+            lines.push(Line::from(""));
+            if session.progress > 0 {
+                lines.push(Line::from(Span::styled(
+                    format!(
+                        "{} \u{2026}",
+                        format_chords(&shortcut.chords[..session.progress])
+                    ),
+                    Style::default().fg(colors.focused_key()),
+                )));
+            }
+            if session.missed {
+                lines.push(Line::from(Span::styled(
+                    t!("shortcuts.keys_are", keys = shortcut.keys()).to_string(),
+                    Style::default().fg(colors.warning()),
+                )));
+                if let Some(wrong) = session.last_wrong {
+                    lines.push(Line::from(Span::styled(
+                        t!("shortcuts.you_pressed", keys = wrong.to_string()).to_string(),
+                        Style::default().fg(colors.error()),
+                    )));
+                }
+            } else if session.progress == 0 {
+                lines.push(Line::from(Span::styled(
+                    t!("shortcuts.press_keys").to_string(),
+                    pending,
+                )));
+            }
+            if let Some((last, attempt)) = session.last_attempt() {
+                lines.push(Line::from(""));
+                lines.push(Line::from(Span::styled(
+                    t!(
+                        "shortcuts.last_attempt",
+                        action = &last.action,
+                        ms = format!("{:.0}", attempt.time_ms)
+                    )
+                    .to_string(),
+                    pending,
+                )));
+            }
+        }
+        Some(session) => {
+            lines.push(Line::from(Span::styled(
+                t!("shortcuts.round_complete").to_string(),
+                Style::default()
+                    .fg(colors.accent())
+                    .add_modifier(Modifier::BOLD),
+            )));
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                t!(
+                    "shortcuts.clean_rate",
+                    percent = format!("{:.0}", session.accuracy() * 100.0)
+                )
+                .to_string(),
+                Style::default().fg(colors.fg()),
+            )));
+            lines.push(Line::from(Span::styled(
+                t!(
+                    "shortcuts.average_time",
+                    ms = format!("{:.0}", session.average_ms())
+                )
+                .to_string(),
+                Style::default().fg(colors.fg()),
+            )));
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                t!("shortcuts.needs_work").to_string(),
+                Style::default().fg(colors.accent()),
+            )));
+            for (shortcut, attempt) in session.needs_work(5) {
+                let mut spans = vec![
+                    Span::styled(
+                        format!("  {}  ", shortcut.action),
+                        Style::default().fg(colors.fg()),
+                    ),
+                    Span::styled(shortcut.keys(), Style::default().fg(colors.focused_key())),
+                    Span::styled(format!("  {:.0} ms", attempt.time_ms), pending),
+                ];
+                if !attempt.clean {
+                    spans.push(Span::styled(
+                        format!("  {}", t!("shortcuts.missed")),
+                        Style::default().fg(colors.error()),
+                    ));
+                }
+                lines.push(Line::from(spans));
+            }
+        }
+    }
+
+    Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .render(inner, frame.buffer_mut());
+
+    let footer_line = Paragraph::new(Line::from(Span::styled(
+        format!(" {} ", shortcut_drill_hints(app).join("  ")),
+        pending,
+    )));
+    frame.render_widget(footer_line, footer);
+}
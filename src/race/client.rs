@@ -0,0 +1,111 @@
+use std::io::BufReader;
+use std::net::{Shutdown, TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{self, Sender};
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{Result, bail};
+
+use crate::race::RaceFinish;
+use crate::race::protocol::{self, ClientMessage, HostMessage, PROTOCOL_VERSION};
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A connection to a race host.
+///
+/// Host messages are read on a background thread and queued; the UI drains them
+/// with [`RaceClient::drain`] on each tick. A closed channel means the host is gone.
+/// Outgoing messages go through a writer thread so a host that stops reading
+/// cannot stall the UI.
+pub struct RaceClient {
+    /// Kept to close the connection when the client goes away.
+    socket: TcpStream,
+    outbox: Sender<ClientMessage>,
+    racer_id: u32,
+    rx: mpsc::Receiver<HostMessage>,
+}
+
+impl RaceClient {
+    pub fn connect(addr: impl ToSocketAddrs, name: &str) -> Result<Self> {
+        let Some(socket_addr) = addr.to_socket_addrs()?.next() else {
+            bail!("could not resolve race host address");
+        };
+        let stream = TcpStream::connect_timeout(&socket_addr, CONNECT_TIMEOUT)?;
+        let _ = stream.set_nodelay(true);
+        stream.set_write_timeout(Some(protocol::WRITE_TIMEOUT))?;
+        let mut writer = stream.try_clone()?;
+        let socket = stream.try_clone()?;
+        let mut reader = BufReader::new(stream);
+
+        protocol::send(
+            &mut writer,
+            &ClientMessage::Join {
+                name: name.to_string(),
+                version: PROTOCOL_VERSION,
+            },
+        )?;
+
+        reader.get_ref().set_read_timeout(Some(CONNECT_TIMEOUT))?;
+        let racer_id = match protocol::recv::<HostMessage>(&mut reader)? {
+            Some(HostMessage::Welcome { racer_id }) => racer_id,
+            Some(HostMessage::Rejected { reason }) => bail!("host rejected join: {reason}"),
+            Some(_) => bail!("unexpected message from race host"),
+            None => bail!("race host closed the connection"),
+        };
+        reader.get_ref().set_read_timeout(None)?;
+
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            while let Ok(Some(message)) = protocol::recv::<HostMessage>(&mut reader) {
+                if tx.send(message).is_err() {
+                    return;
+                }
+            }
+        });
+
+        Ok(Self {
+            socket,
+            outbox: protocol::spawn_writer(writer),
+            racer_id,
+            rx,
+        })
+    }
+
+    pub fn racer_id(&self) -> u32 {
+        self.racer_id
+    }
+
+    /// Take all queued host messages. The flag is false once the host has disconnected.
+    pub fn drain(&self) -> (Vec<HostMessage>, bool) {
+        let mut messages = Vec::new();
+        loop {
+            match self.rx.try_recv() {
+                Ok(message) => messages.push(message),
+                Err(mpsc::TryRecvError::Empty) => return (messages, true),
+                Err(mpsc::TryRecvError::Disconnected) => return (messages, false),
+            }
+        }
+    }
+
+    pub fn send_progress(&mut self, cursor: usize) -> Result<()> {
+        self.send(ClientMessage::Progress { cursor })
+    }
+
+    pub fn send_finish(&mut self, finish: RaceFinish) -> Result<()> {
+        self.send(ClientMessage::Finish { finish })
+    }
+
+    /// Queue a message for the writer thread. Fails once the connection is gone.
+    fn send(&self, message: ClientMessage) -> Result<()> {
+        if self.outbox.send(message).is_err() {
+            bail!("race host connection lost");
+        }
+        Ok(())
+    }
+}
+
+impl Drop for RaceClient {
+    fn drop(&mut self) {
+        let _ = self.socket.shutdown(Shutdown::Both);
+    }
+}
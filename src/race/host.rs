@@ -0,0 +1,307 @@
+use std::io::{BufReader, ErrorKind};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs, UdpSocket};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+
+use crate::race::protocol::{self, ClientMessage, HostMessage, PROTOCOL_VERSION};
+use crate::race::{RaceFinish, RacerStatus};
+
+/// The host always races as racer 0.
+pub const HOST_RACER_ID: u32 = 0;
+
+const ACCEPT_POLL_INTERVAL: Duration = Duration::from_millis(25);
+const JOIN_TIMEOUT: Duration = Duration::from_secs(5);
+
+struct Peer {
+    racer_id: u32,
+    /// Kept to close the connection when the host goes away.
+    socket: TcpStream,
+    /// Messages for the peer's writer thread, so no socket I/O happens under
+    /// the state lock or on the UI thread.
+    outbox: Sender<HostMessage>,
+}
+
+struct StartedRace {
+    text_len: usize,
+    start_at: Instant,
+}
+
+#[derive(Default)]
+struct HostState {
+    racers: Vec<RacerStatus>,
+    peers: Vec<Peer>,
+    next_id: u32,
+    started: Option<StartedRace>,
+}
+
+impl HostState {
+    fn racer_mut(&mut self, racer_id: u32) -> Option<&mut RacerStatus> {
+        self.racers.iter_mut().find(|r| r.id == racer_id)
+    }
+
+    /// Queue a message for every connected peer, dropping peers whose writer
+    /// has given up.
+    fn broadcast(&mut self, message: &HostMessage) {
+        let mut dead = Vec::new();
+        for peer in &self.peers {
+            if peer.outbox.send(message.clone()).is_err() {
+                dead.push(peer.racer_id);
+            }
+        }
+        if !dead.is_empty() {
+            self.peers.retain(|p| !dead.contains(&p.racer_id));
+            for id in dead {
+                if let Some(racer) = self.racer_mut(id) {
+                    racer.connected = false;
+                }
+            }
+        }
+    }
+
+    fn broadcast_racers(&mut self) {
+        let message = HostMessage::Racers {
+            racers: self.racers.clone(),
+        };
+        self.broadcast(&message);
+    }
+
+    fn clamp_cursor(&self, cursor: usize) -> usize {
+        self.started
+            .as_ref()
+            .map_or(0, |started| cursor.min(started.text_len))
+    }
+}
+
+/// Listens for joining racers and relays progress between them.
+///
+/// The host instance is itself a racer; its own progress is fed in through
+/// [`RaceHost::report_progress`] / [`RaceHost::report_finish`] rather than the socket.
+pub struct RaceHost {
+    state: Arc<Mutex<HostState>>,
+    shutdown: Arc<AtomicBool>,
+    local_addr: SocketAddr,
+    accept_handle: Option<thread::JoinHandle<()>>,
+}
+
+impl RaceHost {
+    pub fn bind(addr: impl ToSocketAddrs, host_name: &str) -> Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        listener.set_nonblocking(true)?;
+        let local_addr = listener.local_addr()?;
+
+        let state = Arc::new(Mutex::new(HostState {
+            racers: vec![RacerStatus::new(HOST_RACER_ID, host_name)],
+            next_id: HOST_RACER_ID + 1,
+            ..HostState::default()
+        }));
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        let accept_state = Arc::clone(&state);
+        let accept_shutdown = Arc::clone(&shutdown);
+        let accept_handle = thread::spawn(move || {
+            while !accept_shutdown.load(Ordering::Relaxed) {
+                match listener.accept() {
+                    Ok((stream, _)) => {
+                        let peer_state = Arc::clone(&accept_state);
+                        thread::spawn(move || serve_peer(stream, peer_state));
+                    }
+                    Err(e) if e.kind() == ErrorKind::WouldBlock => {
+                        thread::sleep(ACCEPT_POLL_INTERVAL);
+                    }
+                    Err(_) => thread::sleep(ACCEPT_POLL_INTERVAL),
+                }
+            }
+        });
+
+        Ok(Self {
+            state,
+            shutdown,
+            local_addr,
+            accept_handle: Some(accept_handle),
+        })
+    }
+
+    /// Address other machines should join. When bound to the wildcard address,
+    /// resolve the LAN interface by routing a UDP socket (nothing is sent).
+    pub fn advertised_addr(&self) -> SocketAddr {
+        if !self.local_addr.ip().is_unspecified() {
+            return self.local_addr;
+        }
+        UdpSocket::bind("0.0.0.0:0")
+            .and_then(|socket| {
+                socket.connect("192.0.2.1:9")?;
+                socket.local_addr()
+            })
+            .map(|lan| SocketAddr::new(lan.ip(), self.local_addr.port()))
+            .unwrap_or(self.local_addr)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HostState> {
+        lock_state(&self.state)
+    }
+
+    pub fn racers(&self) -> Vec<RacerStatus> {
+        self.lock().racers.clone()
+    }
+
+    pub fn start_at(&self) -> Option<Instant> {
+        self.lock().started.as_ref().map(|s| s.start_at)
+    }
+
+    /// Close the lobby and broadcast the race text. Joins are refused afterwards.
+    pub fn start(&self, seed: u64, text: &str, countdown: Duration) {
+        let mut state = self.lock();
+        if state.started.is_some() {
+            return;
+        }
+        state.started = Some(StartedRace {
+            text_len: text.chars().count(),
+            start_at: Instant::now() + countdown,
+        });
+        state.broadcast(&HostMessage::Start {
+            seed,
+            text: text.to_string(),
+            countdown_ms: countdown.as_millis() as u64,
+        });
+    }
+
+    pub fn report_progress(&self, cursor: usize) {
+        let mut state = self.lock();
+        let cursor = state.clamp_cursor(cursor);
+        let Some(racer) = state.racer_mut(HOST_RACER_ID) else {
+            return;
+        };
+        if racer.finish.is_some() {
+            return;
+        }
+        racer.cursor = cursor;
+        state.broadcast_racers();
+    }
+
+    pub fn report_finish(&self, finish: RaceFinish) {
+        let mut state = self.lock();
+        let text_len = state.started.as_ref().map_or(0, |s| s.text_len);
+        if let Some(racer) = state.racer_mut(HOST_RACER_ID) {
+            racer.cursor = text_len;
+            racer.finish.get_or_insert(finish);
+        }
+        state.broadcast_racers();
+    }
+}
+
+impl Drop for RaceHost {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+        // Closing the sockets unblocks every peer reader thread.
+        for peer in &self.lock().peers {
+            let _ = peer.socket.shutdown(Shutdown::Both);
+        }
+        if let Some(handle) = self.accept_handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// A panicking peer thread must not take the whole race down with a poisoned lock.
+fn lock_state(state: &Mutex<HostState>) -> MutexGuard<'_, HostState> {
+    state.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn serve_peer(stream: TcpStream, state: Arc<Mutex<HostState>>) {
+    // Accepted sockets may inherit the listener's non-blocking flag on some platforms.
+    let _ = stream.set_nonblocking(false);
+    let _ = stream.set_nodelay(true);
+    let _ = stream.set_write_timeout(Some(protocol::WRITE_TIMEOUT));
+    let Ok(mut writer) = stream.try_clone() else {
+        return;
+    };
+    let mut reader = BufReader::new(stream);
+
+    let _ = reader.get_ref().set_read_timeout(Some(JOIN_TIMEOUT));
+    let name = match protocol::recv::<ClientMessage>(&mut reader) {
+        Ok(Some(ClientMessage::Join { name, version })) if version == PROTOCOL_VERSION => name,
+        Ok(Some(ClientMessage::Join { version, .. })) => {
+            let _ = protocol::send(
+                &mut writer,
+                &HostMessage::Rejected {
+                    reason: format!(
+                        "protocol version {version} does not match host version {PROTOCOL_VERSION}"
+                    ),
+                },
+            );
+            return;
+        }
+        _ => return,
+    };
+    let _ = reader.get_ref().set_read_timeout(None);
+
+    let racer_id = {
+        let mut state = lock_state(&state);
+        if state.started.is_some() {
+            let _ = protocol::send(
+                &mut writer,
+                &HostMessage::Rejected {
+                    reason: "race already started".to_string(),
+                },
+            );
+            return;
+        }
+        let Ok(socket) = writer.try_clone() else {
+            return;
+        };
+        let racer_id = state.next_id;
+        state.next_id += 1;
+        let outbox = protocol::spawn_writer(writer);
+        if outbox.send(HostMessage::Welcome { racer_id }).is_err() {
+            return;
+        }
+        state.racers.push(RacerStatus::new(racer_id, &name));
+        state.peers.push(Peer {
+            racer_id,
+            socket,
+            outbox,
+        });
+        state.broadcast_racers();
+        racer_id
+    };
+
+    loop {
+        let message = match protocol::recv::<ClientMessage>(&mut reader) {
+            Ok(Some(message)) => message,
+            // EOF, socket error or garbage all end the peer's race.
+            _ => break,
+        };
+        let mut state = lock_state(&state);
+        match message {
+            ClientMessage::Progress { cursor } => {
+                let cursor = state.clamp_cursor(cursor);
+                if let Some(racer) = state.racer_mut(racer_id)
+                    && racer.finish.is_none()
+                {
+                    racer.cursor = cursor;
+                }
+            }
+            ClientMessage::Finish { finish } => {
+                let text_len = state.started.as_ref().map_or(0, |s| s.text_len);
+                if let Some(racer) = state.racer_mut(racer_id) {
+                    racer.cursor = text_len;
+                    racer.finish.get_or_insert(finish);
+                }
+            }
+            ClientMessage::Join { .. } => continue,
+        }
+        state.broadcast_racers();
+    }
+
+    let mut state = lock_state(&state);
+    state.peers.retain(|p| p.racer_id != racer_id);
+    if let Some(racer) = state.racer_mut(racer_id) {
+        racer.connected = false;
+    }
+    state.broadcast_racers();
+}
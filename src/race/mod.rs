@@ -0,0 +1,419 @@
+//! LAN typing races.
+//!
+//! One keydr instance hosts a race on a TCP port and the others join by address;
+//! there is no central service. The host picks a seed, generates the race text
+//! with the regular generators and broadcasts it together with a countdown.
+//! Every racer streams its cursor position to the host, which relays the full
+//! racer table to everyone. Messages are newline-delimited JSON (see [`protocol`]).
+
+pub mod client;
+pub mod host;
+pub mod protocol;
+
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::race::client::RaceClient;
+use crate::race::host::{HOST_RACER_ID, RaceHost};
+use crate::race::protocol::HostMessage;
+
+pub const DEFAULT_RACE_PORT: u16 = 7878;
+pub const RACE_COUNTDOWN: Duration = Duration::from_secs(3);
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RaceFinish {
+    /// Seconds from the end of the countdown until the racer completed the text.
+    pub elapsed_secs: f64,
+    pub wpm: f64,
+    pub accuracy: f64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RacerStatus {
+    pub id: u32,
+    pub name: String,
+    pub cursor: usize,
+    pub finish: Option<RaceFinish>,
+    pub connected: bool,
+}
+
+impl RacerStatus {
+    pub fn new(id: u32, name: &str) -> Self {
+        Self {
+            id,
+            name: name.to_string(),
+            cursor: 0,
+            finish: None,
+            connected: true,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RacePhase {
+    Lobby,
+    Countdown,
+    Racing,
+    Finished,
+}
+
+/// Append the default race port when the user typed a bare host name or IPv4 address.
+pub fn normalize_race_address(address: &str) -> String {
+    let has_port = address
+        .rsplit_once(':')
+        .is_some_and(|(host, port)| !host.is_empty() && port.parse::<u16>().is_ok());
+    if has_port {
+        address.to_string()
+    } else {
+        format!("{address}:{DEFAULT_RACE_PORT}")
+    }
+}
+
+/// Order racers for the standings table: finishers by time, then everyone else
+/// by how far they got. Ties keep join order.
+pub fn placings(racers: &[RacerStatus]) -> Vec<&RacerStatus> {
+    let mut ordered: Vec<&RacerStatus> = racers.iter().collect();
+    ordered.sort_by(|a, b| match (&a.finish, &b.finish) {
+        (Some(fa), Some(fb)) => fa.elapsed_secs.total_cmp(&fb.elapsed_secs),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => b.cursor.cmp(&a.cursor),
+    });
+    ordered
+}
+
+enum RaceRole {
+    Host(RaceHost),
+    Client(RaceClient),
+}
+
+/// One side of a race as seen by the UI, regardless of whether this instance hosts.
+pub struct RaceSession {
+    role: RaceRole,
+    /// Address shown in the lobby: where the host listens, or which host we joined.
+    pub address: String,
+    pub local_id: u32,
+    pub racers: Vec<RacerStatus>,
+    pub text: Option<String>,
+    pub start_at: Option<Instant>,
+    pub host_lost: bool,
+}
+
+impl RaceSession {
+    pub fn host(bind_addr: &str, name: &str) -> Result<Self> {
+        let host = RaceHost::bind(bind_addr, name)?;
+        let racers = host.racers();
+        Ok(Self {
+            address: host.advertised_addr().to_string(),
+            role: RaceRole::Host(host),
+            local_id: HOST_RACER_ID,
+            racers,
+            text: None,
+            start_at: None,
+            host_lost: false,
+        })
+    }
+
+    pub fn join(address: &str, name: &str) -> Result<Self> {
+        let client = RaceClient::connect(address, name)?;
+        Ok(Self {
+            address: address.to_string(),
+            local_id: client.racer_id(),
+            role: RaceRole::Client(client),
+            racers: Vec::new(),
+            text: None,
+            start_at: None,
+            host_lost: false,
+        })
+    }
+
+    pub fn is_host(&self) -> bool {
+        matches!(self.role, RaceRole::Host(_))
+    }
+
+    /// Pull the latest racer table and start signal from the network side.
+    pub fn poll(&mut self) {
+        match &self.role {
+            RaceRole::Host(host) => {
+                self.racers = host.racers();
+            }
+            RaceRole::Client(client) => {
+                let (messages, connected) = client.drain();
+                for message in messages {
+                    match message {
+                        HostMessage::Racers { racers } => self.racers = racers,
+                        HostMessage::Start {
+                            text, countdown_ms, ..
+                        } => {
+                            self.text = Some(text);
+                            self.start_at =
+                                Some(Instant::now() + Duration::from_millis(countdown_ms));
+                        }
+                        HostMessage::Welcome { .. } | HostMessage::Rejected { .. } => {}
+                    }
+                }
+                if !connected {
+                    self.host_lost = true;
+                }
+            }
+        }
+    }
+
+    pub fn phase(&self) -> RacePhase {
+        let Some(start_at) = self.start_at else {
+            return RacePhase::Lobby;
+        };
+        if Instant::now() < start_at {
+            return RacePhase::Countdown;
+        }
+        let all_done = self
+            .racers
+            .iter()
+            .filter(|r| r.connected)
+            .all(|r| r.finish.is_some());
+        if all_done && !self.racers.is_empty() {
+            RacePhase::Finished
+        } else {
+            RacePhase::Racing
+        }
+    }
+
+    /// Seconds left before typing opens, rounded up for display.
+    pub fn countdown_secs(&self) -> u64 {
+        self.start_at
+            .and_then(|at| at.checked_duration_since(Instant::now()))
+            .map_or(0, |left| left.as_millis().div_ceil(1000) as u64)
+    }
+
+    pub fn text_len(&self) -> usize {
+        self.text.as_ref().map_or(0, |t| t.chars().count())
+    }
+
+    pub fn opponents(&self) -> impl Iterator<Item = &RacerStatus> {
+        self.racers.iter().filter(move |r| r.id != self.local_id)
+    }
+
+    /// Host only: close the lobby and broadcast the race text.
+    pub fn start(&mut self, seed: u64, text: &str) {
+        if let RaceRole::Host(host) = &self.role {
+            host.start(seed, text, RACE_COUNTDOWN);
+            self.text = Some(text.to_string());
+            self.start_at = host.start_at();
+        }
+    }
+
+    pub fn report_progress(&mut self, cursor: usize) {
+        match &mut self.role {
+            RaceRole::Host(host) => host.report_progress(cursor),
+            RaceRole::Client(client) => {
+                if client.send_progress(cursor).is_err() {
+                    self.host_lost = true;
+                }
+            }
+        }
+    }
+
+    pub fn report_finish(&mut self, finish: RaceFinish) {
+        match &mut self.role {
+            RaceRole::Host(host) => host.report_finish(finish),
+            RaceRole::Client(client) => {
+                if client.send_finish(finish).is_err() {
+                    self.host_lost = true;
+                }
+            }
+        }
+    }
+
+    /// Time since typing opened, used as the race clock for finish times.
+    pub fn elapsed_secs(&self) -> f64 {
+        self.start_at.map_or(0.0, |at| {
+            Instant::now().saturating_duration_since(at).as_secs_f64()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    fn wait_until(session: &mut RaceSession, mut done: impl FnMut(&RaceSession) -> bool) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        loop {
+            session.poll();
+            if done(session) {
+                return;
+            }
+            assert!(
+                Instant::now() < deadline,
+                "timed out waiting for race state"
+            );
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    fn finish(elapsed_secs: f64) -> RaceFinish {
+        RaceFinish {
+            elapsed_secs,
+            wpm: 60.0,
+            accuracy: 100.0,
+        }
+    }
+
+    #[test]
+    fn normalize_race_address_appends_default_port() {
+        assert_eq!(normalize_race_address("10.0.0.5"), "10.0.0.5:7878");
+        assert_eq!(normalize_race_address("desk.local"), "desk.local:7878");
+        assert_eq!(normalize_race_address("10.0.0.5:9000"), "10.0.0.5:9000");
+    }
+
+    #[test]
+    fn placings_rank_finishers_by_time_then_progress() {
+        let mut slow = RacerStatus::new(0, "slow");
+        slow.finish = Some(finish(20.0));
+        let mut fast = RacerStatus::new(1, "fast");
+        fast.finish = Some(finish(12.5));
+        let mut behind = RacerStatus::new(2, "behind");
+        behind.cursor = 3;
+        let mut ahead = RacerStatus::new(3, "ahead");
+        ahead.cursor = 9;
+
+        let racers = vec![behind, slow, ahead, fast];
+        let names: Vec<&str> = placings(&racers).iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["fast", "slow", "ahead", "behind"]);
+    }
+
+    #[test]
+    fn host_and_clients_race_over_localhost() {
+        let mut host = RaceSession::host("127.0.0.1:0", "host").unwrap();
+        let addr = host.address.clone();
+        let mut alice = RaceSession::join(&addr, "alice").unwrap();
+        let mut bob = RaceSession::join(&addr, "bob").unwrap();
+        assert_ne!(alice.local_id, bob.local_id);
+
+        wait_until(&mut host, |s| s.racers.len() == 3);
+        wait_until(&mut alice, |s| s.racers.len() == 3);
+
+        host.start(42, "the quick fox");
+        assert_eq!(host.phase(), RacePhase::Countdown);
+        wait_until(&mut alice, |s| s.text.is_some());
+        wait_until(&mut bob, |s| s.text.is_some());
+        assert_eq!(alice.text.as_deref(), Some("the quick fox"));
+        assert_eq!(bob.text, alice.text);
+        assert!(alice.countdown_secs() <= RACE_COUNTDOWN.as_secs());
+
+        alice.report_progress(4);
+        let alice_id = alice.local_id;
+        wait_until(&mut bob, |s| {
+            s.opponents().any(|r| r.id == alice_id && r.cursor == 4)
+        });
+
+        alice.report_finish(finish(5.0));
+        host.report_finish(finish(7.0));
+        wait_until(&mut bob, |s| {
+            s.racers.iter().filter(|r| r.finish.is_some()).count() == 2
+        });
+        let order: Vec<u32> = placings(&bob.racers).iter().map(|r| r.id).collect();
+        assert_eq!(order[0], alice_id);
+        assert_eq!(order[1], HOST_RACER_ID);
+        assert_eq!(order[2], bob.local_id);
+
+        drop(bob);
+        let bob_gone = |s: &RaceSession| s.racers.iter().any(|r| !r.connected);
+        wait_until(&mut host, bob_gone);
+    }
+
+    #[test]
+    fn progress_is_clamped_to_race_text() {
+        let mut host = RaceSession::host("127.0.0.1:0", "host").unwrap();
+        let mut client = RaceSession::join(&host.address.clone(), "c").unwrap();
+        host.start(1, "abc");
+        wait_until(&mut client, |s| s.text.is_some());
+        client.report_progress(99);
+        let id = client.local_id;
+        wait_until(&mut host, |s| {
+            s.racers.iter().any(|r| r.id == id && r.cursor == 3)
+        });
+    }
+
+    #[test]
+    fn stalled_peer_does_not_block_host_progress() {
+        use std::io::Write;
+        use std::net::TcpStream;
+
+        let host = RaceHost::bind("127.0.0.1:0", "host").unwrap();
+        // Joins, then never reads, so its receive buffer fills up.
+        let mut stalled = TcpStream::connect(host.advertised_addr()).unwrap();
+        writeln!(
+            stalled,
+            r#"{{"type":"join","name":"stalled","version":{}}}"#,
+            protocol::PROTOCOL_VERSION
+        )
+        .unwrap();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while host.racers().len() < 2 {
+            assert!(Instant::now() < deadline, "stalled peer never joined");
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        host.start(3, &"x".repeat(50_000), Duration::ZERO);
+        let started = Instant::now();
+        for cursor in 0..50_000 {
+            host.report_progress(cursor);
+        }
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn client_sends_return_when_host_stops_reading() {
+        use std::io::BufReader;
+        use std::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        // Welcomes the client, then never reads again.
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let _ = protocol::recv::<protocol::ClientMessage>(&mut reader).unwrap();
+            protocol::send(&mut stream, &HostMessage::Welcome { racer_id: 1 }).unwrap();
+            thread::sleep(Duration::from_secs(10));
+            drop(stream);
+        });
+
+        let mut client = client::RaceClient::connect(addr, "alice").unwrap();
+        let started = Instant::now();
+        for cursor in 0..200_000 {
+            let _ = client.send_progress(cursor);
+        }
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn host_progress_after_finishing_is_ignored() {
+        let host = RaceHost::bind("127.0.0.1:0", "host").unwrap();
+        host.start(5, "abcdef", Duration::ZERO);
+        host.report_progress(2);
+        host.report_finish(finish(3.0));
+        host.report_progress(4);
+        assert_eq!(host.racers()[0].cursor, 6);
+    }
+
+    #[test]
+    fn joins_are_rejected_once_race_has_started() {
+        let mut host = RaceSession::host("127.0.0.1:0", "host").unwrap();
+        host.start(7, "abc");
+        let err = RaceSession::join(&host.address, "late").err().unwrap();
+        assert!(err.to_string().contains("race already started"));
+    }
+
+    #[test]
+    fn client_notices_when_host_goes_away() {
+        let host = RaceSession::host("127.0.0.1:0", "host").unwrap();
+        let mut client = RaceSession::join(&host.address, "c").unwrap();
+        drop(host);
+        wait_until(&mut client, |s| s.host_lost);
+    }
+}
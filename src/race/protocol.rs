@@ -0,0 +1,111 @@
+use std::io::{BufRead, Write};
+use std::net::{Shutdown, TcpStream};
+use std::sync::mpsc::{self, Sender};
+use std::thread;
+use std::time::Duration;
+
+use anyhow::Result;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::race::{RaceFinish, RacerStatus};
+
+/// Bumped whenever a message shape changes so mismatched builds fail at join time
+/// instead of mid-race.
+pub const PROTOCOL_VERSION: u32 = 1;
+/// A peer that takes longer than this to accept a message is dropped.
+pub const WRITE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Messages sent from a joining instance to the host.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    Join { name: String, version: u32 },
+    Progress { cursor: usize },
+    Finish { finish: RaceFinish },
+}
+
+/// Messages broadcast by the host.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum HostMessage {
+    Welcome {
+        racer_id: u32,
+    },
+    Rejected {
+        reason: String,
+    },
+    /// Full racer table. Sent on every join, progress update, finish and disconnect.
+    Racers {
+        racers: Vec<RacerStatus>,
+    },
+    /// Race text plus the delay until typing opens, relative to receipt.
+    Start {
+        seed: u64,
+        text: String,
+        countdown_ms: u64,
+    },
+}
+
+/// Write one message as a single line of JSON.
+pub fn send<T: Serialize>(writer: &mut impl Write, message: &T) -> Result<()> {
+    let mut line = serde_json::to_string(message)?;
+    line.push('\n');
+    writer.write_all(line.as_bytes())?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Hand `writer` to a thread that sends queued messages until the socket fails
+/// or times out, then closes it so the reading side ends too. Keeps socket
+/// writes off the UI thread and out of any lock.
+pub fn spawn_writer<T: Serialize + Send + 'static>(mut writer: TcpStream) -> Sender<T> {
+    let _ = writer.set_write_timeout(Some(WRITE_TIMEOUT));
+    let (outbox, messages) = mpsc::channel::<T>();
+    thread::spawn(move || {
+        for message in messages {
+            if send(&mut writer, &message).is_err() {
+                let _ = writer.shutdown(Shutdown::Both);
+                break;
+            }
+        }
+    });
+    outbox
+}
+
+/// Read the next message. Returns `Ok(None)` when the peer closed the connection.
+pub fn recv<T: DeserializeOwned>(reader: &mut impl BufRead) -> Result<Option<T>> {
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    Ok(Some(serde_json::from_str(line.trim_end())?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn messages_round_trip_as_json_lines() {
+        let mut buf = Vec::new();
+        send(
+            &mut buf,
+            &ClientMessage::Join {
+                name: "ada".to_string(),
+                version: PROTOCOL_VERSION,
+            },
+        )
+        .unwrap();
+        send(&mut buf, &ClientMessage::Progress { cursor: 12 }).unwrap();
+        assert_eq!(buf.iter().filter(|b| **b == b'\n').count(), 2);
+
+        let mut reader = Cursor::new(buf);
+        let first: ClientMessage = recv(&mut reader).unwrap().unwrap();
+        assert!(matches!(first, ClientMessage::Join { ref name, .. } if name == "ada"));
+        let second: ClientMessage = recv(&mut reader).unwrap().unwrap();
+        assert_eq!(second, ClientMessage::Progress { cursor: 12 });
+        assert!(recv::<ClientMessage>(&mut reader).unwrap().is_none());
+    }
+}
@@ -0,0 +1,763 @@
+//! Static HTML progress report.
+//!
+//! Produces a single self-contained HTML document (inline CSS and SVG, no
+//! scripts or external assets) that mirrors the stats dashboard so it can be
+//! opened in any browser or attached to a review.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+
+use crate::engine::key_stats::KeyStatsStore;
+use crate::engine::ngram_stats::{BigramAnomaly, BigramStatsStore};
+use crate::engine::skill_tree::{BranchId, BranchStatus, DrillScope, SkillTree};
+use crate::i18n::t;
+use crate::keyboard::display::{self, BACKSPACE, ENTER, SPACE, TAB};
+use crate::keyboard::model::KeyboardModel;
+use crate::session::result::DrillResult;
+use crate::ui::components::stats_dashboard::format_duration;
+
+const CHART_WIDTH: f64 = 720.0;
+const CHART_HEIGHT: f64 = 220.0;
+const CHART_PAD_LEFT: f64 = 40.0;
+const CHART_PAD_BOTTOM: f64 = 24.0;
+const CHART_PAD_TOP: f64 = 12.0;
+
+const CALENDAR_WEEKS: i64 = 53;
+const CALENDAR_CELL: f64 = 12.0;
+const CALENDAR_GAP: f64 = 2.0;
+
+const KEY_SIZE: f64 = 44.0;
+const KEY_GAP: f64 = 4.0;
+
+const COLOR_ACCENT: &str = "#3b6fd4";
+const COLOR_SUCCESS: &str = "#2e9e4f";
+const COLOR_WARNING: &str = "#d9a21b";
+const COLOR_ERROR: &str = "#d1453b";
+const COLOR_EMPTY: &str = "#e6e8eb";
+const COLOR_GRID: &str = "#d5d8dc";
+
+const STYLE: &str = "\
+body{font-family:system-ui,-apple-system,'Segoe UI',sans-serif;color:#1f2328;background:#fff;max-width:800px;margin:2em auto;padding:0 1em}
+h1{margin-bottom:0.2em}
+h2{margin-top:1.8em;border-bottom:1px solid #d5d8dc;padding-bottom:0.2em;font-size:1.2em}
+.meta{color:#8a8f98;margin-top:0}
+.summary{display:flex;flex-wrap:wrap;gap:1.5em}
+.summary div{min-width:8em}
+.summary b{display:block;font-size:1.4em}
+table{border-collapse:collapse;width:100%;font-size:0.9em}
+th,td{text-align:right;padding:0.25em 0.6em;border-bottom:1px solid #eceef0}
+th:first-child,td:first-child{text-align:left}
+td.mono{font-family:ui-monospace,monospace}
+tr.confirmed td{color:#d1453b}
+svg text{font-family:ui-monospace,monospace;font-size:11px;fill:#57606a}
+.empty{color:#8a8f98}
+";
+
+/// Everything the report reads. Borrowed from `App` so the CLI can render a
+/// report without starting the TUI.
+pub struct ReportData<'a> {
+    pub history: &'a [DrillResult],
+    pub key_stats: &'a KeyStatsStore,
+    pub ranked_key_stats: &'a KeyStatsStore,
+    pub ranked_bigram_stats: &'a BigramStatsStore,
+    pub skill_tree: &'a SkillTree,
+    pub keyboard_model: &'a KeyboardModel,
+    pub target_wpm: u32,
+    pub generated_at: DateTime<Utc>,
+}
+
+pub fn render_html(data: &ReportData) -> String {
+    let mut out = String::new();
+    let title = t!("report.title");
+    let _ = write!(
+        out,
+        "<!DOCTYPE html>\n<html lang=\"{}\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>\n{STYLE}</style>\n</head>\n<body>\n",
+        escape_html(&rust_i18n::locale()),
+        escape_html(&title),
+    );
+    let _ = writeln!(out, "<h1>{}</h1>", escape_html(&title));
+    let generated = t!(
+        "report.generated",
+        date = data.generated_at.format("%Y-%m-%d %H:%M UTC").to_string()
+    );
+    let layout = t!(
+        "report.keyboard_layout",
+        layout = data.keyboard_model.layout_key
+    );
+    let _ = writeln!(
+        out,
+        "<p class=\"meta\">{} &middot; {}</p>",
+        escape_html(&generated),
+        escape_html(&layout)
+    );
+
+    render_summary(&mut out, data);
+    render_wpm_section(&mut out, data);
+    render_accuracy_section(&mut out, data);
+    render_calendar_section(&mut out, data);
+    render_keyboard_sections(&mut out, data);
+    render_bigram_sections(&mut out, data);
+    render_skill_tree_section(&mut out, data);
+
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+fn section_heading(out: &mut String, title: &str) {
+    let _ = writeln!(out, "<h2>{}</h2>", escape_html(title.trim()));
+}
+
+fn empty_note(out: &mut String) {
+    let _ = writeln!(
+        out,
+        "<p class=\"empty\">{}</p>",
+        escape_html(&t!("report.no_drills"))
+    );
+}
+
+/// Strip the padding and trailing colon the dashboard labels carry for TUI layout.
+fn bare_label(label: &str) -> &str {
+    label.trim().trim_end_matches(':').trim_end()
+}
+
+fn render_summary(out: &mut String, data: &ReportData) {
+    section_heading(out, &t!("stats.summary_title"));
+    if data.history.is_empty() {
+        empty_note(out);
+        return;
+    }
+    let count = data.history.len() as f64;
+    let avg_wpm = data.history.iter().map(|r| r.wpm).sum::<f64>() / count;
+    let best_wpm = data.history.iter().map(|r| r.wpm).fold(0.0f64, f64::max);
+    let avg_accuracy = data.history.iter().map(|r| r.accuracy).sum::<f64>() / count;
+    let total_time: f64 = data.history.iter().map(|r| r.elapsed_secs).sum();
+
+    let items = [
+        (t!("stats.drills"), data.history.len().to_string()),
+        (t!("stats.avg_wpm"), format!("{avg_wpm:.0}")),
+        (t!("stats.best_wpm"), format!("{best_wpm:.0}")),
+        (t!("stats.accuracy_label"), format!("{avg_accuracy:.1}%")),
+        (t!("stats.total_time"), format_duration(total_time)),
+    ];
+    out.push_str("<div class=\"summary\">\n");
+    for (label, value) in &items {
+        let _ = writeln!(
+            out,
+            "<div>{}<b>{}</b></div>",
+            escape_html(bare_label(label)),
+            escape_html(value)
+        );
+    }
+    out.push_str("</div>\n");
+}
+
+fn render_wpm_section(out: &mut String, data: &ReportData) {
+    section_heading(out, &t!("chart.wpm_over_time"));
+    if data.history.is_empty() {
+        empty_note(out);
+        return;
+    }
+    let values: Vec<f64> = data.history.iter().map(|r| r.wpm).collect();
+    let target = data.target_wpm as f64;
+    let max = values.iter().fold(target, |a, &b| a.max(b)).max(10.0) * 1.1;
+    out.push_str(&line_chart(&values, 0.0, max, Some(target), COLOR_ACCENT));
+    let _ = writeln!(
+        out,
+        "<p class=\"meta\">{}</p>",
+        escape_html(&t!("report.target_wpm", wpm = data.target_wpm))
+    );
+}
+
+fn render_accuracy_section(out: &mut String, data: &ReportData) {
+    section_heading(out, &t!("report.accuracy_over_time"));
+    if data.history.is_empty() {
+        empty_note(out);
+        return;
+    }
+    let values: Vec<f64> = data.history.iter().map(|r| r.accuracy).collect();
+    let lowest = values.iter().fold(100.0f64, |a, &b| a.min(b));
+    let min = ((lowest / 10.0).floor() * 10.0).clamp(0.0, 80.0);
+    out.push_str(&line_chart(&values, min, 100.0, None, COLOR_SUCCESS));
+}
+
+/// Line chart over drill index with min/mid/max Y labels and an optional
+/// dashed guide line (the WPM target).
+fn line_chart(values: &[f64], y_min: f64, y_max: f64, guide: Option<f64>, color: &str) -> String {
+    let plot_w = CHART_WIDTH - CHART_PAD_LEFT - 8.0;
+    let plot_h = CHART_HEIGHT - CHART_PAD_TOP - CHART_PAD_BOTTOM;
+    let span = (y_max - y_min).max(f64::EPSILON);
+    let x_at = |i: usize| {
+        if values.len() <= 1 {
+            CHART_PAD_LEFT + plot_w / 2.0
+        } else {
+            CHART_PAD_LEFT + plot_w * i as f64 / (values.len() - 1) as f64
+        }
+    };
+    let y_at = |v: f64| CHART_PAD_TOP + plot_h * (1.0 - ((v - y_min) / span).clamp(0.0, 1.0));
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {CHART_WIDTH} {CHART_HEIGHT}\" width=\"100%\" role=\"img\">"
+    );
+    for value in [y_min, (y_min + y_max) / 2.0, y_max] {
+        let y = y_at(value);
+        let _ = writeln!(
+            svg,
+            "<line x1=\"{CHART_PAD_LEFT}\" y1=\"{y:.1}\" x2=\"{:.1}\" y2=\"{y:.1}\" stroke=\"{COLOR_GRID}\"/><text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"end\">{value:.0}</text>",
+            CHART_PAD_LEFT + plot_w,
+            CHART_PAD_LEFT - 6.0,
+            y + 4.0,
+        );
+    }
+    let _ = writeln!(
+        svg,
+        "<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\">{}</text>",
+        CHART_PAD_LEFT + plot_w / 2.0,
+        CHART_HEIGHT - 6.0,
+        escape_html(&t!("chart.drill_number")),
+    );
+    if let Some(guide) = guide {
+        let y = y_at(guide);
+        let _ = writeln!(
+            svg,
+            "<line x1=\"{CHART_PAD_LEFT}\" y1=\"{y:.1}\" x2=\"{:.1}\" y2=\"{y:.1}\" stroke=\"{COLOR_WARNING}\" stroke-dasharray=\"6 4\"/>",
+            CHART_PAD_LEFT + plot_w,
+        );
+    }
+    let points: Vec<String> = values
+        .iter()
+        .enumerate()
+        .map(|(i, &v)| format!("{:.1},{:.1}", x_at(i), y_at(v)))
+        .collect();
+    let _ = writeln!(
+        svg,
+        "<polyline fill=\"none\" stroke=\"{color}\" stroke-width=\"2\" points=\"{}\"/>",
+        points.join(" ")
+    );
+    // Individual markers only while they stay readable.
+    if values.len() <= 60 {
+        for (i, &v) in values.iter().enumerate() {
+            let _ = writeln!(
+                svg,
+                "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"2.5\" fill=\"{color}\"><title>#{}: {v:.1}</title></circle>",
+                x_at(i),
+                y_at(v),
+                i + 1,
+            );
+        }
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn render_calendar_section(out: &mut String, data: &ReportData) {
+    section_heading(out, &t!("heatmap.title"));
+
+    let mut day_counts: HashMap<NaiveDate, usize> = HashMap::new();
+    for result in data.history.iter().filter(|r| !r.partial) {
+        *day_counts.entry(result.timestamp.date_naive()).or_insert(0) += 1;
+    }
+
+    let end_date = data.generated_at.date_naive();
+    let start_date = end_date - Duration::weeks(CALENDAR_WEEKS - 1);
+    let start_date =
+        start_date - Duration::days(start_date.weekday().num_days_from_monday() as i64);
+
+    let label_w = 24.0;
+    let top = 16.0;
+    let step = CALENDAR_CELL + CALENDAR_GAP;
+    let width = label_w + CALENDAR_WEEKS as f64 * step + step;
+    let height = top + 7.0 * step;
+
+    let _ = writeln!(
+        out,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {width} {height}\" width=\"100%\" role=\"img\">"
+    );
+    for (row, label) in [
+        (0, t!("report.day_mon")),
+        (2, t!("report.day_wed")),
+        (4, t!("report.day_fri")),
+    ] {
+        let _ = writeln!(
+            out,
+            "<text x=\"0\" y=\"{:.1}\">{}</text>",
+            top + row as f64 * step + CALENDAR_CELL - 2.0,
+            escape_html(&label),
+        );
+    }
+
+    let mut week_start = start_date;
+    let mut col = 0usize;
+    let mut last_month = 0u32;
+    while week_start <= end_date {
+        let x = label_w + col as f64 * step;
+        if week_start.month() != last_month {
+            last_month = week_start.month();
+            let _ = writeln!(
+                out,
+                "<text x=\"{x:.1}\" y=\"11\">{}</text>",
+                escape_html(&month_name(last_month)),
+            );
+        }
+        for day in 0..7 {
+            let date = week_start + Duration::days(day);
+            if date > end_date {
+                break;
+            }
+            let count = day_counts.get(&date).copied().unwrap_or(0);
+            let _ = writeln!(
+                out,
+                "<rect x=\"{x:.1}\" y=\"{:.1}\" width=\"{CALENDAR_CELL}\" height=\"{CALENDAR_CELL}\" rx=\"2\" fill=\"{}\"><title>{date}: {count}</title></rect>",
+                top + day as f64 * step,
+                activity_color(count),
+            );
+        }
+        week_start += Duration::weeks(1);
+        col += 1;
+    }
+    out.push_str("</svg>\n");
+}
+
+fn month_name(month: u32) -> String {
+    match month {
+        1 => t!("heatmap.jan"),
+        2 => t!("heatmap.feb"),
+        3 => t!("heatmap.mar"),
+        4 => t!("heatmap.apr"),
+        5 => t!("heatmap.may"),
+        6 => t!("heatmap.jun"),
+        7 => t!("heatmap.jul"),
+        8 => t!("heatmap.aug"),
+        9 => t!("heatmap.sep"),
+        10 => t!("heatmap.oct"),
+        11 => t!("heatmap.nov"),
+        12 => t!("heatmap.dec"),
+        _ => "".into(),
+    }
+    .to_string()
+}
+
+/// Same session-count buckets as the TUI activity heatmap.
+fn activity_color(count: usize) -> &'static str {
+    match count {
+        0 => COLOR_EMPTY,
+        1..=2 => "#b7e4c2",
+        3..=5 => "#77c98e",
+        6..=15 => "#3fa561",
+        _ => "#1f7a3e",
+    }
+}
+
+fn accuracy_color(accuracy: Option<f64>) -> &'static str {
+    match accuracy {
+        None => COLOR_EMPTY,
+        Some(acc) if acc >= 98.0 => COLOR_SUCCESS,
+        Some(acc) if acc >= 90.0 => COLOR_WARNING,
+        Some(_) => COLOR_ERROR,
+    }
+}
+
+fn timing_color(time_ms: Option<f64>) -> &'static str {
+    match time_ms {
+        None => COLOR_EMPTY,
+        Some(ms) if ms <= 200.0 => COLOR_SUCCESS,
+        Some(ms) if ms <= 400.0 => COLOR_WARNING,
+        Some(_) => COLOR_ERROR,
+    }
+}
+
+fn key_accuracies(history: &[DrillResult]) -> HashMap<char, f64> {
+    let mut counts: HashMap<char, (usize, usize)> = HashMap::new();
+    for result in history {
+        for kt in &result.per_key_times {
+            let entry = counts.entry(kt.key).or_insert((0, 0));
+            entry.1 += 1;
+            if kt.correct {
+                entry.0 += 1;
+            }
+        }
+    }
+    counts
+        .into_iter()
+        .filter(|(_, (_, total))| *total > 0)
+        .map(|(key, (correct, total))| (key, correct as f64 / total as f64 * 100.0))
+        .collect()
+}
+
+fn render_keyboard_sections(out: &mut String, data: &ReportData) {
+    let accuracies = key_accuracies(data.history);
+    section_heading(out, &t!("report.key_accuracy_title"));
+    out.push_str(&keyboard_svg(data.keyboard_model, |key| {
+        let accuracy = accuracies.get(&key).copied();
+        (
+            accuracy.map(|acc| format!("{acc:.0}")),
+            accuracy_color(accuracy),
+        )
+    }));
+
+    section_heading(out, &t!("report.key_speed_title"));
+    out.push_str(&keyboard_svg(data.keyboard_model, |key| {
+        let time_ms = data
+            .key_stats
+            .stats
+            .get(&key)
+            .filter(|s| s.sample_count > 0)
+            .map(|s| s.filtered_time_ms);
+        (time_ms.map(|ms| format!("{ms:.0}")), timing_color(time_ms))
+    }));
+}
+
+/// Draw the layout's base-layer keys, staggered per the model's geometry hints,
+/// followed by a row of the modifier keys the stats track.
+fn keyboard_svg(
+    model: &KeyboardModel,
+    cell: impl Fn(char) -> (Option<String>, &'static str),
+) -> String {
+    let pitch = KEY_SIZE + KEY_GAP;
+    let hints = model.geometry_hints;
+    let unit = (hints.key_unit_width + hints.key_unit_gap).max(1) as f64;
+    let row_x =
+        |row_idx: usize| hints.row_offsets.get(row_idx).copied().unwrap_or(0) as f64 / unit * pitch;
+    let width = model
+        .rows
+        .iter()
+        .enumerate()
+        .map(|(i, row)| row_x(i) + row.len() as f64 * pitch)
+        .fold(4.0 * 2.0 * pitch, f64::max);
+    let height = (model.rows.len() + 1) as f64 * pitch;
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {width:.0} {height:.0}\" width=\"100%\" role=\"img\">"
+    );
+    let mut draw_key = |x: f64, y: f64, w: f64, key: char, label: &str| {
+        let (value, fill) = cell(key);
+        let _ = writeln!(
+            svg,
+            "<g><rect x=\"{x:.1}\" y=\"{y:.1}\" width=\"{w:.1}\" height=\"{KEY_SIZE}\" rx=\"5\" fill=\"{fill}\" fill-opacity=\"0.85\"/><text x=\"{:.1}\" y=\"{:.1}\">{}</text>",
+            x + 5.0,
+            y + 14.0,
+            escape_html(label),
+        );
+        if let Some(value) = value {
+            let _ = write!(
+                svg,
+                "<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"end\">{}</text>",
+                x + w - 5.0,
+                y + KEY_SIZE - 7.0,
+                escape_html(&value),
+            );
+        }
+        svg.push_str("</g>\n");
+    };
+
+    for (row_idx, row) in model.rows.iter().enumerate() {
+        let y = row_idx as f64 * pitch;
+        for (col_idx, key) in row.iter().enumerate() {
+            let x = row_x(row_idx) + col_idx as f64 * pitch;
+            draw_key(x, y, KEY_SIZE, key.base, &key.base.to_string());
+        }
+    }
+    let y = model.rows.len() as f64 * pitch;
+    let modifiers = [TAB, SPACE, ENTER, BACKSPACE];
+    let slot = width / modifiers.len() as f64;
+    for (i, key) in modifiers.into_iter().enumerate() {
+        draw_key(
+            i as f64 * slot,
+            y,
+            slot - KEY_GAP,
+            key,
+            display::key_display_name(key),
+        );
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn render_bigram_sections(out: &mut String, data: &ReportData) {
+    let unlocked = data.skill_tree.unlocked_keys(DrillScope::Global);
+    let error_rows = data
+        .ranked_bigram_stats
+        .error_anomalies(data.ranked_key_stats, &unlocked);
+    let speed_rows = data
+        .ranked_bigram_stats
+        .speed_anomalies(data.ranked_key_stats, &unlocked);
+
+    section_heading(
+        out,
+        &t!("stats.error_anomalies_title", count = error_rows.len()),
+    );
+    if error_rows.is_empty() {
+        let _ = writeln!(
+            out,
+            "<p class=\"empty\">{}</p>",
+            escape_html(t!("stats.no_error_anomalies").trim())
+        );
+    } else {
+        anomaly_table(
+            out,
+            &error_rows,
+            &[
+                t!("report.col_errors"),
+                t!("report.col_samples"),
+                t!("report.col_error_rate"),
+                t!("report.col_expected"),
+                t!("report.col_anomaly"),
+            ],
+            |a| {
+                vec![
+                    a.error_count.to_string(),
+                    a.sample_count.to_string(),
+                    format!("{:.0}%", a.error_rate_ema * 100.0),
+                    format!("{:.0}%", a.expected_baseline * 100.0),
+                    format!("{:.0}%", a.anomaly_pct),
+                ]
+            },
+        );
+    }
+
+    section_heading(
+        out,
+        &t!("stats.speed_anomalies_title", count = speed_rows.len()),
+    );
+    if speed_rows.is_empty() {
+        let _ = writeln!(
+            out,
+            "<p class=\"empty\">{}</p>",
+            escape_html(t!("stats.no_speed_anomalies").trim())
+        );
+    } else {
+        anomaly_table(
+            out,
+            &speed_rows,
+            &[
+                t!("report.col_speed"),
+                t!("report.col_expected"),
+                t!("report.col_samples"),
+                t!("report.col_anomaly"),
+            ],
+            |a| {
+                vec![
+                    format!("{:.0} ms", a.speed_ms),
+                    format!("{:.0} ms", a.expected_baseline),
+                    a.sample_count.to_string(),
+                    format!("{:.0}%", a.anomaly_pct),
+                ]
+            },
+        );
+    }
+}
+
+/// Confirmed anomalies (the ones that can drive focus) are highlighted.
+fn anomaly_table(
+    out: &mut String,
+    rows: &[BigramAnomaly],
+    headers: &[std::borrow::Cow<'_, str>],
+    cells: impl Fn(&BigramAnomaly) -> Vec<String>,
+) {
+    out.push_str("<table>\n<tr>");
+    let _ = write!(out, "<th>{}</th>", escape_html(&t!("report.col_bigram")));
+    for header in headers {
+        let _ = write!(out, "<th>{}</th>", escape_html(header));
+    }
+    out.push_str("</tr>\n");
+    for row in rows {
+        let class = if row.confirmed {
+            " class=\"confirmed\""
+        } else {
+            ""
+        };
+        let bigram: String = row.key.0.iter().map(|&ch| display_char(ch)).collect();
+        let _ = write!(
+            out,
+            "<tr{class}><td class=\"mono\">{}</td>",
+            escape_html(&bigram)
+        );
+        for cell in cells(row) {
+            let _ = write!(out, "<td>{}</td>", escape_html(&cell));
+        }
+        out.push_str("</tr>\n");
+    }
+    out.push_str("</table>\n");
+}
+
+fn display_char(ch: char) -> String {
+    let label = display::key_short_label(ch);
+    if label.is_empty() {
+        ch.to_string()
+    } else {
+        format!("[{label}]")
+    }
+}
+
+fn render_skill_tree_section(out: &mut String, data: &ReportData) {
+    section_heading(out, &t!("skill_tree.title"));
+    let tree = data.skill_tree;
+    let stats = data.ranked_key_stats;
+
+    let _ = writeln!(
+        out,
+        "<table>\n<tr><th>{}</th><th>{}</th><th>{}</th><th>{}</th><th></th></tr>",
+        escape_html(&t!("report.col_branch")),
+        escape_html(&t!("report.col_status")),
+        escape_html(&t!("report.col_unlocked")),
+        escape_html(&t!("report.col_mastered")),
+    );
+    for def in tree.branches() {
+        let total = tree.branch_total_keys_for(def.id);
+        let unlocked = tree.branch_unlocked_count(def.id);
+        let mastered = tree.branch_confident_keys(def.id, stats);
+        let _ = writeln!(
+            out,
+            "<tr><td>{}</td><td>{}</td><td>{unlocked}/{total}</td><td>{mastered}</td><td>{}</td></tr>",
+            escape_html(&def.display_name()),
+            escape_html(&branch_status_label(tree, def.id)),
+            progress_bar(mastered, unlocked, total),
+        );
+    }
+    let total = tree.total_unique_keys;
+    let unlocked = tree.total_unlocked_count();
+    let mastered = tree.total_confident_keys(stats);
+    let _ = writeln!(
+        out,
+        "<tr><td><b>{}</b></td><td></td><td>{unlocked}/{total}</td><td>{mastered}</td><td>{}</td></tr>\n</table>",
+        escape_html(&t!("progress.overall_key_progress")),
+        progress_bar(mastered, unlocked, total),
+    );
+}
+
+fn branch_status_label(tree: &SkillTree, id: BranchId) -> String {
+    match tree.branch_status(id) {
+        BranchStatus::Locked => t!("skill_tree.locked_status"),
+        BranchStatus::Available => t!("skill_tree.unlocked"),
+        BranchStatus::InProgress => t!("skill_tree.in_progress"),
+        BranchStatus::Complete => t!("skill_tree.complete"),
+    }
+    .to_string()
+}
+
+/// Dual bar like the TUI's branch list: mastered, then unlocked-but-not-mastered.
+fn progress_bar(mastered: usize, unlocked: usize, total: usize) -> String {
+    let width = 160.0;
+    let ratio = |n: usize| {
+        if total == 0 {
+            0.0
+        } else {
+            (n.min(total) as f64 / total as f64) * width
+        }
+    };
+    let mastered_w = ratio(mastered);
+    let unlocked_w = ratio(unlocked).max(mastered_w);
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"10\"><rect width=\"{width}\" height=\"10\" rx=\"3\" fill=\"{COLOR_EMPTY}\"/><rect width=\"{unlocked_w:.1}\" height=\"10\" rx=\"3\" fill=\"{COLOR_ACCENT}\" fill-opacity=\"0.45\"/><rect width=\"{mastered_w:.1}\" height=\"10\" rx=\"3\" fill=\"{COLOR_SUCCESS}\"/></svg>"
+    )
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::result::KeyTime;
+
+    fn drill(wpm: f64, accuracy: f64, days_ago: i64, now: DateTime<Utc>) -> DrillResult {
+        DrillResult {
+            wpm,
+            cpm: wpm * 5.0,
+            accuracy,
+            correct: 50,
+            incorrect: 1,
+            total_chars: 51,
+            elapsed_secs: 30.0,
+            timestamp: now - Duration::days(days_ago),
+            per_key_times: vec![
+                KeyTime {
+                    key: 'a',
+                    time_ms: 150.0,
+                    correct: true,
+                },
+                KeyTime {
+                    key: 'a',
+                    time_ms: 150.0,
+                    correct: false,
+                },
+            ],
+            drill_mode: "adaptive".to_string(),
+            ranked: true,
+            partial: false,
+            completion_percent: 100.0,
+        }
+    }
+
+    fn render(history: &[DrillResult], now: DateTime<Utc>) -> String {
+        let key_stats = KeyStatsStore::default();
+        let bigrams = BigramStatsStore::default();
+        let tree = SkillTree::default();
+        let model = KeyboardModel::qwerty();
+        render_html(&ReportData {
+            history,
+            key_stats: &key_stats,
+            ranked_key_stats: &key_stats,
+            ranked_bigram_stats: &bigrams,
+            skill_tree: &tree,
+            keyboard_model: &model,
+            target_wpm: 40,
+            generated_at: now,
+        })
+    }
+
+    #[test]
+    fn report_is_a_single_self_contained_document() {
+        let now = Utc::now();
+        let history = vec![drill(35.0, 96.0, 3, now), drill(42.0, 99.0, 0, now)];
+        let html = render(&history, now);
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.trim_end().ends_with("</html>"));
+        assert!(!html.contains("<script"));
+        assert!(!html.contains("src=\"http"));
+        // WPM, accuracy, calendar, two keyboards
+        assert_eq!(html.matches("<polyline").count(), 2);
+        assert!(html.matches("<svg").count() >= 5);
+        // 'a' was typed correctly half the time.
+        assert!(html.contains(">50</text>"));
+    }
+
+    #[test]
+    fn calendar_counts_sessions_per_day() {
+        let now = Utc::now();
+        let history = vec![drill(30.0, 95.0, 1, now), drill(31.0, 95.0, 1, now)];
+        let html = render(&history, now);
+        let day = (now - Duration::days(1)).date_naive();
+        assert!(html.contains(&format!("<title>{day}: 2</title>")));
+    }
+
+    #[test]
+    fn empty_history_still_renders() {
+        let html = render(&[], Utc::now());
+        assert!(html.contains("</html>"));
+        assert_eq!(html.matches("<polyline").count(), 0);
+    }
+
+    #[test]
+    fn escape_html_escapes_markup() {
+        assert_eq!(escape_html("<a & 'b'>"), "&lt;a &amp; &#39;b&#39;&gt;");
+    }
+}
@@ -18,6 +18,8 @@ pub struct DrillState {
     pub typo_flags: HashSet<usize>,
     pub synthetic_spans: Vec<SyntheticSpan>,
     pub auto_indent_after_newline: bool,
+    /// Strokes of a dead-key or combining sequence still waiting to compose.
+    pub pending_compose: Vec<char>,
 }
 
 impl DrillState {
@@ -31,6 +33,7 @@ impl DrillState {
             typo_flags: HashSet::new(),
             synthetic_spans: Vec::new(),
             auto_indent_after_newline: true,
+            pending_compose: Vec::new(),
         }
     }
 
@@ -97,6 +100,7 @@ impl DrillState {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::keyboard::model::KeyboardModel;
     use crate::session::input;
 
     #[test]
@@ -293,4 +297,72 @@ mod tests {
         assert_eq!(drill.typo_count(), 1);
         assert!(drill.typo_flags.contains(&1));
     }
+
+    #[test]
+    fn test_decomposed_accent_composes_before_comparison() {
+        let mut drill = DrillState::new("é");
+        assert!(input::process_input(&mut drill, 'e', &[]).is_empty());
+        assert_eq!(drill.cursor, 0);
+        let events = input::process_input(&mut drill, '\u{0301}', &[]);
+        assert_eq!(events.len(), 1);
+        assert!(events[0].correct);
+        assert_eq!(events[0].expected, 'é');
+        assert_eq!(drill.typo_count(), 0);
+        assert!(drill.is_complete());
+    }
+
+    #[test]
+    fn test_letter_with_two_marks_waits_for_both() {
+        // ΐ decomposes to ι, combining diaeresis, combining acute.
+        let mut drill = DrillState::new("ΐ");
+        assert!(input::process_input(&mut drill, 'ι', &[]).is_empty());
+        assert!(input::process_input(&mut drill, '\u{0308}', &[]).is_empty());
+        assert_eq!(drill.cursor, 0);
+        let events = input::process_input(&mut drill, '\u{0301}', &[]);
+        assert_eq!(events.len(), 1);
+        assert!(events[0].correct);
+        assert_eq!(events[0].expected, 'ΐ');
+        assert_eq!(drill.typo_count(), 0);
+        assert!(drill.is_complete());
+    }
+
+    #[test]
+    fn test_dead_key_then_base_letter_is_one_keystroke() {
+        let model = KeyboardModel::from_key("el_greek").unwrap();
+        let mut drill = DrillState::new("άλ");
+        assert!(input::process_input(&mut drill, '΄', model.dead_keys).is_empty());
+        assert!(drill.started_at.is_some());
+        let events = input::process_input(&mut drill, 'α', model.dead_keys);
+        assert_eq!(events.len(), 1);
+        assert!(events[0].correct);
+        assert_eq!(drill.cursor, 1);
+
+        // A dead key that is itself the target char is typed directly.
+        let fr = KeyboardModel::from_key("fr_azerty").unwrap();
+        let mut drill = DrillState::new("^");
+        assert_eq!(input::process_input(&mut drill, '^', fr.dead_keys).len(), 1);
+        assert!(drill.is_complete());
+    }
+
+    #[test]
+    fn test_uncomposable_sequence_is_replayed_as_typed() {
+        let mut drill = DrillState::new("éx");
+        input::process_input(&mut drill, 'e', &[]);
+        let events = input::process_input(&mut drill, 'x', &[]);
+        assert_eq!(events.len(), 2);
+        assert!(!events[0].correct);
+        assert!(events[1].correct);
+        assert_eq!(drill.typo_count(), 1);
+    }
+
+    #[test]
+    fn test_cancel_pending_drops_half_typed_sequence() {
+        let model = KeyboardModel::from_key("el_greek").unwrap();
+        let mut drill = DrillState::new("ά");
+        input::process_input(&mut drill, '΄', model.dead_keys);
+        assert!(input::cancel_pending(&mut drill));
+        assert!(!input::cancel_pending(&mut drill));
+        assert!(drill.pending_compose.is_empty());
+        assert_eq!(drill.cursor, 0);
+    }
 }
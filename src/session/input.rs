@@ -1,5 +1,7 @@
 use std::time::Instant;
 
+use crate::keyboard::model::DeadKey;
+use crate::l10n::unicode::{normalize_nfc, normalize_nfd};
 use crate::session::drill::{DrillState, SyntheticSpan};
 
 #[derive(Clone, Debug)]
@@ -69,6 +71,101 @@ pub fn process_char(drill: &mut DrillState, ch: char) -> Option<KeystrokeEvent>
     Some(event)
 }
 
+/// Feed one terminal char through dead-key and combining-sequence composition.
+///
+/// Depending on the terminal, an accented letter arrives precomposed, as its
+/// base letter followed by a combining mark, or as a lone dead-key char followed
+/// by the base letter. Partial sequences are held in `drill.pending_compose`
+/// until they resolve, so the composed char is judged as one keystroke whose
+/// time covers every stroke. Letters with several marks (ΐ) stay pending until
+/// the last mark arrives. Sequences that do not compose are replayed as typed.
+pub fn process_input(
+    drill: &mut DrillState,
+    ch: char,
+    dead_keys: &[DeadKey],
+) -> Vec<KeystrokeEvent> {
+    if drill.is_complete() {
+        return Vec::new();
+    }
+
+    if drill.pending_compose.is_empty() {
+        let expected = drill.target[drill.cursor];
+        if ch != expected && starts_sequence(expected, ch, dead_keys) {
+            if drill.started_at.is_none() {
+                drill.started_at = Some(Instant::now());
+            }
+            drill.pending_compose.push(ch);
+            return Vec::new();
+        }
+        return process_char(drill, ch).into_iter().collect();
+    }
+
+    let mut sequence = std::mem::take(&mut drill.pending_compose);
+    sequence.push(ch);
+    if awaits_more_marks(drill.target[drill.cursor], &sequence) {
+        drill.pending_compose = sequence;
+        return Vec::new();
+    }
+    match compose_sequence(&sequence, dead_keys) {
+        Some(composed) => process_char(drill, composed).into_iter().collect(),
+        None => sequence
+            .into_iter()
+            .filter_map(|c| process_char(drill, c))
+            .collect(),
+    }
+}
+
+/// Drop a half-typed compose sequence. Returns whether anything was pending.
+pub fn cancel_pending(drill: &mut DrillState) -> bool {
+    let had_pending = !drill.pending_compose.is_empty();
+    drill.pending_compose.clear();
+    had_pending
+}
+
+fn starts_sequence(expected: char, ch: char, dead_keys: &[DeadKey]) -> bool {
+    let via_dead_key = dead_keys.iter().any(|dead| {
+        dead.key == ch
+            && dead
+                .compositions
+                .iter()
+                .any(|&(_, composed)| composed == expected)
+    });
+    if via_dead_key {
+        return true;
+    }
+    let decomposed = normalize_nfd(&expected.to_string());
+    let mut chars = decomposed.chars();
+    chars.next() == Some(ch) && chars.next().is_some()
+}
+
+/// Whether `sequence` is a base letter and some, but not all, of the
+/// combining marks of `expected`.
+fn awaits_more_marks(expected: char, sequence: &[char]) -> bool {
+    let expected: Vec<char> = normalize_nfd(&expected.to_string()).chars().collect();
+    let typed: Vec<char> = normalize_nfd(&sequence.iter().collect::<String>())
+        .chars()
+        .collect();
+    typed.len() < expected.len() && expected.starts_with(&typed)
+}
+
+fn compose_sequence(sequence: &[char], dead_keys: &[DeadKey]) -> Option<char> {
+    if let [first, base] = sequence
+        && let Some(dead) = dead_keys.iter().find(|dead| dead.key == *first)
+    {
+        return dead
+            .compositions
+            .iter()
+            .find(|&&(b, _)| b == *base)
+            .map(|&(_, composed)| composed);
+    }
+    let composed = normalize_nfc(&sequence.iter().collect::<String>());
+    let mut chars = composed.chars();
+    match (chars.next(), chars.next()) {
+        (Some(single), None) => Some(single),
+        _ => None,
+    }
+}
+
 fn tab_indent_completion_len(drill: &DrillState) -> usize {
     if drill.cursor >= drill.target.len() {
         return 0;
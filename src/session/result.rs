@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 use crate::keyboard::display::BACKSPACE;
 use crate::session::drill::DrillState;
 use crate::session::input::KeystrokeEvent;
+use crate::store::importers::ExternalSource;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct DrillResult {
@@ -46,6 +47,13 @@ pub struct KeyTime {
 }
 
 impl DrillResult {
+    /// Whether `per_key_times` is the order the keys were typed in. keybr.com
+    /// imports only carry per-key aggregates, expanded one key at a time, so
+    /// n-grams and words must not be read from them.
+    pub fn has_keystroke_order(&self) -> bool {
+        self.drill_mode != ExternalSource::Keybr.drill_mode()
+    }
+
     pub fn from_drill(
         drill: &DrillState,
         events: &[KeystrokeEvent],
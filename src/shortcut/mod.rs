@@ -0,0 +1,495 @@
+//! Keyboard shortcut training.
+//!
+//! A shortcut pack is a TOML file naming an editor or tool and listing its
+//! actions with the keys that trigger them. Bundled packs live in
+//! `assets/shortcuts/`; packs in `<config dir>/keydr/shortcuts/` are picked up
+//! alongside them and win when the file names clash.
+//!
+//! Keys are written the way editors document them: `Ctrl+Shift+P` is a chord,
+//! whitespace separates chords (`Ctrl+X Ctrl+S`), and a bare word that is not a
+//! named key is typed one character at a time (`dd`, `:wq Enter`).
+
+pub mod session;
+pub mod stats;
+
+use std::fmt;
+use std::fs;
+
+use anyhow::{Result, bail};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use rust_embed::Embed;
+use serde::Deserialize;
+
+#[derive(Embed)]
+#[folder = "assets/shortcuts/"]
+struct ShortcutAssets;
+
+pub const DEFAULT_SHORTCUT_PACK: &str = "vim";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NamedKey {
+    Esc,
+    Enter,
+    Tab,
+    Backspace,
+    Delete,
+    Insert,
+    Up,
+    Down,
+    Left,
+    Right,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    F(u8),
+}
+
+impl NamedKey {
+    const NAMES: [(&'static str, NamedKey); 14] = [
+        ("Esc", NamedKey::Esc),
+        ("Enter", NamedKey::Enter),
+        ("Tab", NamedKey::Tab),
+        ("Backspace", NamedKey::Backspace),
+        ("Delete", NamedKey::Delete),
+        ("Insert", NamedKey::Insert),
+        ("Up", NamedKey::Up),
+        ("Down", NamedKey::Down),
+        ("Left", NamedKey::Left),
+        ("Right", NamedKey::Right),
+        ("Home", NamedKey::Home),
+        ("End", NamedKey::End),
+        ("PageUp", NamedKey::PageUp),
+        ("PageDown", NamedKey::PageDown),
+    ];
+
+    fn parse(name: &str) -> Option<ChordKey> {
+        let aliases = [("escape", "esc"), ("return", "enter"), ("del", "delete")];
+        let lower = name.to_ascii_lowercase();
+        let lower = aliases
+            .iter()
+            .find(|(alias, _)| *alias == lower)
+            .map_or(lower.as_str(), |(_, canonical)| canonical);
+        if lower == "space" {
+            return Some(ChordKey::Char(' '));
+        }
+        if let Some(n) = lower.strip_prefix('f').and_then(|n| n.parse::<u8>().ok())
+            && (1..=12).contains(&n)
+        {
+            return Some(ChordKey::Named(NamedKey::F(n)));
+        }
+        Self::NAMES
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(lower))
+            .map(|(_, key)| ChordKey::Named(*key))
+    }
+
+    fn name(self) -> String {
+        match self {
+            NamedKey::F(n) => format!("F{n}"),
+            key => Self::NAMES
+                .iter()
+                .find(|(_, k)| *k == key)
+                .map_or_else(String::new, |(n, _)| n.to_string()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ChordKey {
+    /// Letters are stored lowercase; Shift is carried by the chord.
+    Char(char),
+    Named(NamedKey),
+}
+
+/// One key press together with its modifiers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Chord {
+    pub ctrl: bool,
+    pub alt: bool,
+    /// Only meaningful for letters and named keys: for `%` or `:` the shifted
+    /// character already says it all, and keyboards disagree on which
+    /// punctuation needs Shift.
+    pub shift: bool,
+    pub key: ChordKey,
+}
+
+impl Chord {
+    fn plain(key: ChordKey) -> Self {
+        Self {
+            ctrl: false,
+            alt: false,
+            shift: false,
+            key,
+        }
+        .normalized()
+    }
+
+    /// Fold an uppercase letter into lowercase + Shift and drop Shift from
+    /// non-letter characters, so parsed and pressed chords compare equal.
+    fn normalized(mut self) -> Self {
+        if let ChordKey::Char(ch) = self.key {
+            if ch.is_alphabetic() {
+                if ch.is_uppercase() {
+                    self.shift = true;
+                    self.key = ChordKey::Char(ch.to_lowercase().next().unwrap_or(ch));
+                }
+            } else {
+                self.shift = false;
+            }
+        }
+        self
+    }
+
+    /// Parse one `Ctrl+Alt+Shift+key` token.
+    pub fn parse(token: &str) -> Result<Self> {
+        // "Ctrl++" binds the plus key itself.
+        let (mods, key) = match token.strip_suffix("++") {
+            Some(mods) => (mods, "+"),
+            None => match token.rsplit_once('+') {
+                Some((mods, key)) if !mods.is_empty() => (mods, key),
+                _ => ("", token),
+            },
+        };
+        let key = match NamedKey::parse(key) {
+            Some(key) => key,
+            None => {
+                let mut chars = key.chars();
+                match (chars.next(), chars.next()) {
+                    // "Ctrl+X" means x: with modifiers, case is just notation.
+                    (Some(ch), None) if !mods.is_empty() => {
+                        ChordKey::Char(ch.to_lowercase().next().unwrap_or(ch))
+                    }
+                    (Some(ch), None) => ChordKey::Char(ch),
+                    _ => bail!("unknown key \"{key}\" in \"{token}\""),
+                }
+            }
+        };
+        let mut chord = Self::plain(key);
+        for modifier in mods.split('+').filter(|m| !m.is_empty()) {
+            match modifier.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => chord.ctrl = true,
+                "alt" | "meta" | "option" => chord.alt = true,
+                "shift" => chord.shift = true,
+                other => bail!("unknown modifier \"{other}\" in \"{token}\""),
+            }
+        }
+        Ok(chord.normalized())
+    }
+
+    /// The chord a key press stands for, or `None` for keys that cannot appear
+    /// in a pack (lone modifiers, media keys).
+    pub fn from_key_event(key: &KeyEvent) -> Option<Self> {
+        let mut shift = key.modifiers.contains(KeyModifiers::SHIFT);
+        let key_code = match key.code {
+            KeyCode::Char(ch) => ChordKey::Char(ch),
+            KeyCode::Esc => ChordKey::Named(NamedKey::Esc),
+            KeyCode::Enter => ChordKey::Named(NamedKey::Enter),
+            KeyCode::Tab => ChordKey::Named(NamedKey::Tab),
+            KeyCode::BackTab => {
+                shift = true;
+                ChordKey::Named(NamedKey::Tab)
+            }
+            KeyCode::Backspace => ChordKey::Named(NamedKey::Backspace),
+            KeyCode::Delete => ChordKey::Named(NamedKey::Delete),
+            KeyCode::Insert => ChordKey::Named(NamedKey::Insert),
+            KeyCode::Up => ChordKey::Named(NamedKey::Up),
+            KeyCode::Down => ChordKey::Named(NamedKey::Down),
+            KeyCode::Left => ChordKey::Named(NamedKey::Left),
+            KeyCode::Right => ChordKey::Named(NamedKey::Right),
+            KeyCode::Home => ChordKey::Named(NamedKey::Home),
+            KeyCode::End => ChordKey::Named(NamedKey::End),
+            KeyCode::PageUp => ChordKey::Named(NamedKey::PageUp),
+            KeyCode::PageDown => ChordKey::Named(NamedKey::PageDown),
+            KeyCode::F(n) if (1..=12).contains(&n) => ChordKey::Named(NamedKey::F(n)),
+            _ => return None,
+        };
+        Some(
+            Self {
+                ctrl: key.modifiers.contains(KeyModifiers::CONTROL),
+                alt: key.modifiers.contains(KeyModifiers::ALT),
+                shift,
+                key: key_code,
+            }
+            .normalized(),
+        )
+    }
+
+    /// Ctrl+C always quits keydr, so packs cannot ask for it.
+    pub fn is_reserved(&self) -> bool {
+        self.ctrl && self.key == ChordKey::Char('c')
+    }
+}
+
+impl fmt::Display for Chord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.ctrl {
+            f.write_str("Ctrl+")?;
+        }
+        if self.alt {
+            f.write_str("Alt+")?;
+        }
+        let modified = self.ctrl || self.alt;
+        match self.key {
+            ChordKey::Char(' ') => {
+                if self.shift {
+                    f.write_str("Shift+")?;
+                }
+                f.write_str("Space")
+            }
+            // A bare shifted letter reads best as the capital vim users type.
+            ChordKey::Char(ch) if self.shift && !modified => {
+                write!(f, "{}", ch.to_uppercase())
+            }
+            ChordKey::Char(ch) => {
+                if self.shift {
+                    f.write_str("Shift+")?;
+                }
+                if modified {
+                    write!(f, "{}", ch.to_uppercase())
+                } else {
+                    write!(f, "{ch}")
+                }
+            }
+            ChordKey::Named(key) => {
+                if self.shift {
+                    f.write_str("Shift+")?;
+                }
+                f.write_str(&key.name())
+            }
+        }
+    }
+}
+
+/// Parse a full key spec into the chords to press in order.
+pub fn parse_keys(spec: &str) -> Result<Vec<Chord>> {
+    let mut chords = Vec::new();
+    for token in spec.split_whitespace() {
+        let is_chord = token.chars().skip(1).any(|ch| ch == '+');
+        if is_chord || NamedKey::parse(token).is_some() || token.chars().count() == 1 {
+            chords.push(Chord::parse(token)?);
+        } else {
+            chords.extend(token.chars().map(|ch| Chord::plain(ChordKey::Char(ch))));
+        }
+    }
+    if chords.is_empty() {
+        bail!("empty key spec");
+    }
+    if chords.iter().any(Chord::is_reserved) {
+        bail!("\"{spec}\" uses Ctrl+C, which is reserved for quitting");
+    }
+    Ok(chords)
+}
+
+/// Chords rendered back into pack notation, runs of plain characters joined up.
+pub fn format_chords(chords: &[Chord]) -> String {
+    let mut out = String::new();
+    let mut prev_plain = false;
+    for chord in chords {
+        let text = chord.to_string();
+        let plain =
+            !chord.ctrl && !chord.alt && matches!(chord.key, ChordKey::Char(ch) if ch != ' ');
+        let joins_previous = plain && prev_plain;
+        if !out.is_empty() && !joins_previous {
+            out.push(' ');
+        }
+        out.push_str(&text);
+        prev_plain = plain;
+    }
+    out
+}
+
+#[derive(Deserialize)]
+struct PackFile {
+    name: String,
+    shortcuts: Vec<ShortcutEntry>,
+}
+
+#[derive(Deserialize)]
+struct ShortcutEntry {
+    action: String,
+    keys: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct Shortcut {
+    pub action: String,
+    pub chords: Vec<Chord>,
+}
+
+impl Shortcut {
+    /// Canonical key notation, used for display and as the stats key.
+    pub fn keys(&self) -> String {
+        format_chords(&self.chords)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ShortcutPack {
+    /// File stem, as stored in the config.
+    pub key: String,
+    pub name: String,
+    pub shortcuts: Vec<Shortcut>,
+}
+
+impl ShortcutPack {
+    pub fn parse(key: &str, content: &str) -> Result<Self> {
+        let file: PackFile = toml::from_str(content)?;
+        let mut shortcuts = Vec::with_capacity(file.shortcuts.len());
+        for entry in file.shortcuts {
+            let chords =
+                parse_keys(&entry.keys).map_err(|e| anyhow::anyhow!("{}: {e}", entry.action))?;
+            shortcuts.push(Shortcut {
+                action: entry.action,
+                chords,
+            });
+        }
+        if shortcuts.is_empty() {
+            bail!("shortcut pack \"{key}\" lists no shortcuts");
+        }
+        Ok(Self {
+            key: key.to_string(),
+            name: file.name,
+            shortcuts,
+        })
+    }
+
+    /// Load a pack by file stem, preferring the user's copy over the bundled one.
+    pub fn load(key: &str) -> Result<Self> {
+        let filename = format!("{key}.toml");
+        if let Some(config_dir) = dirs::config_dir() {
+            let user_path = config_dir.join("keydr").join("shortcuts").join(&filename);
+            if let Ok(content) = fs::read_to_string(&user_path) {
+                return Self::parse(key, &content)
+                    .map_err(|e| anyhow::anyhow!("{}: {e}", user_path.display()));
+            }
+        }
+        match ShortcutAssets::get(&filename) {
+            Some(file) => Self::parse(key, std::str::from_utf8(file.data.as_ref())?),
+            None => bail!("no shortcut pack named \"{key}\""),
+        }
+    }
+
+    /// Bundled and user pack keys, sorted and deduplicated.
+    pub fn available() -> Vec<String> {
+        let mut packs: Vec<String> = ShortcutAssets::iter()
+            .filter_map(|f| f.strip_suffix(".toml").map(|n| n.to_string()))
+            .collect();
+        if let Some(config_dir) = dirs::config_dir()
+            && let Ok(entries) = fs::read_dir(config_dir.join("keydr").join("shortcuts"))
+        {
+            packs.extend(entries.flatten().filter_map(|entry| {
+                let name = entry.file_name().into_string().ok()?;
+                name.strip_suffix(".toml").map(|n| n.to_string())
+            }));
+        }
+        packs.sort_unstable();
+        packs.dedup();
+        packs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyEventKind, KeyEventState};
+
+    fn press(code: KeyCode, modifiers: KeyModifiers) -> Chord {
+        Chord::from_key_event(&KeyEvent {
+            code,
+            modifiers,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn key_specs_parse_chords_sequences_and_named_keys() {
+        let chords = parse_keys("Ctrl+Shift+p").unwrap();
+        assert_eq!(chords.len(), 1);
+        assert!(chords[0].ctrl && chords[0].shift);
+        assert_eq!(chords[0].key, ChordKey::Char('p'));
+
+        assert_eq!(parse_keys("Ctrl+X Ctrl+S").unwrap().len(), 2);
+        assert_eq!(parse_keys("ciw").unwrap().len(), 3);
+        let wq = parse_keys(":wq Enter").unwrap();
+        assert_eq!(wq.len(), 4);
+        assert_eq!(wq[3].key, ChordKey::Named(NamedKey::Enter));
+        assert_eq!(parse_keys("Ctrl++").unwrap()[0].key, ChordKey::Char('+'));
+        assert_eq!(parse_keys("+").unwrap()[0].key, ChordKey::Char('+'));
+        assert_eq!(
+            parse_keys("F12").unwrap()[0].key,
+            ChordKey::Named(NamedKey::F(12))
+        );
+        assert!(parse_keys("G").unwrap()[0].shift);
+        assert!(parse_keys("Ctrl+C").is_err());
+        assert!(parse_keys("Hyper+x").is_err());
+        assert!(parse_keys("   ").is_err());
+    }
+
+    #[test]
+    fn key_events_match_parsed_chords() {
+        let [ctrl_shift_t] = parse_keys("Ctrl+Shift+T").unwrap()[..] else {
+            panic!()
+        };
+        assert_eq!(
+            press(
+                KeyCode::Char('T'),
+                KeyModifiers::CONTROL | KeyModifiers::SHIFT
+            ),
+            ctrl_shift_t
+        );
+        assert_eq!(
+            press(
+                KeyCode::Char('t'),
+                KeyModifiers::CONTROL | KeyModifiers::SHIFT
+            ),
+            ctrl_shift_t
+        );
+        assert_ne!(
+            press(KeyCode::Char('t'), KeyModifiers::CONTROL),
+            ctrl_shift_t
+        );
+
+        // Shift reported for punctuation does not matter.
+        let percent = parse_keys("%").unwrap()[0];
+        assert_eq!(press(KeyCode::Char('%'), KeyModifiers::SHIFT), percent);
+        assert_eq!(
+            press(KeyCode::BackTab, KeyModifiers::SHIFT),
+            parse_keys("Shift+Tab").unwrap()[0]
+        );
+        assert_eq!(
+            press(KeyCode::Char('G'), KeyModifiers::SHIFT),
+            parse_keys("G").unwrap()[0]
+        );
+    }
+
+    #[test]
+    fn chords_format_back_into_pack_notation() {
+        for spec in [
+            "Ctrl+Shift+P",
+            "Ctrl+X Ctrl+S",
+            "ciw",
+            ":wq Enter",
+            "G",
+            "Alt+Up",
+        ] {
+            assert_eq!(format_chords(&parse_keys(spec).unwrap()), spec);
+        }
+        assert_eq!(format_chords(&parse_keys("ctrl+b c").unwrap()), "Ctrl+B c");
+    }
+
+    #[test]
+    fn bundled_packs_parse() {
+        let packs = ShortcutPack::available();
+        for key in ["vim", "emacs", "tmux", "vscode"] {
+            assert!(packs.iter().any(|p| p == key), "{key} missing");
+            let content = ShortcutAssets::get(&format!("{key}.toml")).unwrap();
+            let pack =
+                ShortcutPack::parse(key, std::str::from_utf8(content.data.as_ref()).unwrap())
+                    .unwrap_or_else(|e| panic!("{key}: {e}"));
+            assert!(pack.shortcuts.len() >= 10, "{key}");
+        }
+    }
+}
@@ -0,0 +1,229 @@
+use std::time::Instant;
+
+use rand::Rng;
+use rand::rngs::SmallRng;
+
+use crate::shortcut::stats::ShortcutStatsStore;
+use crate::shortcut::{Chord, Shortcut, ShortcutPack};
+
+pub const SHORTCUT_PROMPTS_PER_ROUND: usize = 10;
+
+#[derive(Clone, Debug)]
+pub struct ShortcutAttempt {
+    pub index: usize,
+    pub clean: bool,
+    pub time_ms: f64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChordOutcome {
+    /// Matched the next chord of a multi-chord shortcut.
+    Progress,
+    /// Matched the last chord; the next prompt is up.
+    Completed,
+    /// Not the expected chord; the shortcut starts over and its keys are shown.
+    Wrong,
+}
+
+/// One round of shortcut prompts. The prompt shows an action; the user keeps
+/// pressing until the whole shortcut goes through, and a wrong chord only
+/// costs the attempt its clean mark.
+#[derive(Clone, Debug)]
+pub struct ShortcutSession {
+    pub pack: ShortcutPack,
+    /// Indices into `pack.shortcuts`, in prompt order.
+    pub queue: Vec<usize>,
+    pub position: usize,
+    /// Chords of the current shortcut matched so far.
+    pub progress: usize,
+    pub missed: bool,
+    pub last_wrong: Option<Chord>,
+    pub attempts: Vec<ShortcutAttempt>,
+    prompt_started: Instant,
+}
+
+impl ShortcutSession {
+    /// Draw `prompts` shortcuts, favouring unseen, missed and slow ones.
+    /// Shortcuts repeat only when the pack is smaller than the round.
+    pub fn new(
+        pack: ShortcutPack,
+        stats: &ShortcutStatsStore,
+        prompts: usize,
+        rng: &mut SmallRng,
+    ) -> Self {
+        let mut ranked: Vec<(f64, usize)> = pack
+            .shortcuts
+            .iter()
+            .enumerate()
+            .map(|(i, shortcut)| {
+                let weight = stats.weakness(&pack.key, shortcut) + 0.5;
+                (weight * rng.gen_range(0.5..1.5), i)
+            })
+            .collect();
+        ranked.sort_by(|a, b| b.0.total_cmp(&a.0));
+        let queue = ranked
+            .iter()
+            .map(|&(_, i)| i)
+            .cycle()
+            .take(prompts.max(1))
+            .collect();
+
+        Self {
+            pack,
+            queue,
+            position: 0,
+            progress: 0,
+            missed: false,
+            last_wrong: None,
+            attempts: Vec::new(),
+            prompt_started: Instant::now(),
+        }
+    }
+
+    pub fn current(&self) -> Option<&Shortcut> {
+        self.queue
+            .get(self.position)
+            .map(|&i| &self.pack.shortcuts[i])
+    }
+
+    pub fn expected(&self) -> Option<Chord> {
+        self.current()
+            .and_then(|shortcut| shortcut.chords.get(self.progress).copied())
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.position >= self.queue.len()
+    }
+
+    pub fn press(&mut self, chord: Chord, now: Instant) -> ChordOutcome {
+        let Some(shortcut) = self.current() else {
+            return ChordOutcome::Wrong;
+        };
+        let len = shortcut.chords.len();
+        let first = shortcut.chords[0];
+
+        if Some(chord) != self.expected() {
+            self.missed = true;
+            self.last_wrong = Some(chord);
+            // A wrong chord that starts the shortcut counts as starting over.
+            self.progress = usize::from(chord == first);
+            return ChordOutcome::Wrong;
+        }
+
+        self.progress += 1;
+        if self.progress < len {
+            return ChordOutcome::Progress;
+        }
+
+        self.attempts.push(ShortcutAttempt {
+            index: self.queue[self.position],
+            clean: !self.missed,
+            time_ms: now
+                .saturating_duration_since(self.prompt_started)
+                .as_secs_f64()
+                * 1000.0,
+        });
+        self.position += 1;
+        self.progress = 0;
+        self.missed = false;
+        self.last_wrong = None;
+        self.prompt_started = now;
+        ChordOutcome::Completed
+    }
+
+    pub fn last_attempt(&self) -> Option<(&Shortcut, &ShortcutAttempt)> {
+        self.attempts
+            .last()
+            .map(|attempt| (&self.pack.shortcuts[attempt.index], attempt))
+    }
+
+    pub fn accuracy(&self) -> f64 {
+        if self.attempts.is_empty() {
+            return 0.0;
+        }
+        self.attempts.iter().filter(|a| a.clean).count() as f64 / self.attempts.len() as f64
+    }
+
+    pub fn average_ms(&self) -> f64 {
+        if self.attempts.is_empty() {
+            return 0.0;
+        }
+        self.attempts.iter().map(|a| a.time_ms).sum::<f64>() / self.attempts.len() as f64
+    }
+
+    /// Missed shortcuts first, then the slowest, for the round summary.
+    pub fn needs_work(&self, limit: usize) -> Vec<(&Shortcut, &ShortcutAttempt)> {
+        let mut attempts: Vec<&ShortcutAttempt> = self.attempts.iter().collect();
+        attempts.sort_by(|a, b| a.clean.cmp(&b.clean).then(b.time_ms.total_cmp(&a.time_ms)));
+        attempts
+            .into_iter()
+            .take(limit)
+            .map(|attempt| (&self.pack.shortcuts[attempt.index], attempt))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shortcut::parse_keys;
+    use rand::SeedableRng;
+    use std::time::Duration;
+
+    fn pack() -> ShortcutPack {
+        ShortcutPack::parse(
+            "test",
+            r#"
+name = "Test"
+[[shortcuts]]
+action = "Save"
+keys = "Ctrl+X Ctrl+S"
+[[shortcuts]]
+action = "Undo"
+keys = "u"
+"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn unseen_and_weak_shortcuts_come_first() {
+        let pack = pack();
+        let mut stats = ShortcutStatsStore::default();
+        for _ in 0..5 {
+            stats.record("test", &pack.shortcuts[0], true, 400.0);
+        }
+        let mut rng = SmallRng::seed_from_u64(1);
+        let session = ShortcutSession::new(pack, &stats, 5, &mut rng);
+        assert_eq!(session.queue, vec![1, 0, 1, 0, 1]);
+    }
+
+    #[test]
+    fn wrong_chord_restarts_the_shortcut_and_marks_it_missed() {
+        let mut rng = SmallRng::seed_from_u64(1);
+        let mut session = ShortcutSession::new(pack(), &ShortcutStatsStore::default(), 2, &mut rng);
+        session.queue = vec![0, 1];
+        let start = Instant::now();
+        let ctrl_x = parse_keys("Ctrl+X").unwrap()[0];
+        let ctrl_s = parse_keys("Ctrl+S").unwrap()[0];
+        let u = parse_keys("u").unwrap()[0];
+
+        assert_eq!(session.press(ctrl_x, start), ChordOutcome::Progress);
+        assert_eq!(session.press(u, start), ChordOutcome::Wrong);
+        assert_eq!(session.progress, 0);
+        assert_eq!(session.press(ctrl_x, start), ChordOutcome::Progress);
+        let done = start + Duration::from_millis(800);
+        assert_eq!(session.press(ctrl_s, done), ChordOutcome::Completed);
+        assert_eq!(
+            session.press(u, done + Duration::from_millis(300)),
+            ChordOutcome::Completed
+        );
+
+        assert!(session.is_finished());
+        assert!(!session.attempts[0].clean);
+        assert!(session.attempts[1].clean);
+        assert!((session.attempts[1].time_ms - 300.0).abs() < 1.0);
+        assert!((session.accuracy() - 0.5).abs() < f64::EPSILON);
+        assert_eq!(session.needs_work(1)[0].0.action, "Save");
+    }
+}
@@ -0,0 +1,78 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::shortcut::Shortcut;
+
+const EMA_ALPHA: f64 = 0.3;
+
+/// Timing and accuracy for one shortcut. Times run from the prompt appearing
+/// to the last chord of the shortcut, so they include recalling it.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ShortcutStat {
+    pub attempts: u32,
+    /// Attempts completed without a wrong chord.
+    pub clean: u32,
+    pub filtered_time_ms: f64,
+    pub best_time_ms: Option<f64>,
+}
+
+impl ShortcutStat {
+    pub fn accuracy(&self) -> f64 {
+        if self.attempts == 0 {
+            0.0
+        } else {
+            self.clean as f64 / self.attempts as f64
+        }
+    }
+
+    /// How much the shortcut needs practice: unseen shortcuts rank highest,
+    /// then misses and slow recall.
+    pub fn weakness(&self) -> f64 {
+        if self.attempts == 0 {
+            return 3.0;
+        }
+        2.0 * (1.0 - self.accuracy()) + (self.filtered_time_ms / 2000.0).min(2.0)
+    }
+}
+
+/// Shortcut stats keyed by `"<pack>:<keys>"`, so renaming an action keeps its
+/// history and the same chord in two packs stays separate.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ShortcutStatsStore {
+    pub stats: HashMap<String, ShortcutStat>,
+}
+
+impl ShortcutStatsStore {
+    pub fn stat_key(pack: &str, shortcut: &Shortcut) -> String {
+        format!("{pack}:{}", shortcut.keys())
+    }
+
+    pub fn get(&self, pack: &str, shortcut: &Shortcut) -> Option<&ShortcutStat> {
+        self.stats.get(&Self::stat_key(pack, shortcut))
+    }
+
+    pub fn record(&mut self, pack: &str, shortcut: &Shortcut, clean: bool, time_ms: f64) {
+        let stat = self
+            .stats
+            .entry(Self::stat_key(pack, shortcut))
+            .or_default();
+        stat.attempts += 1;
+        if clean {
+            stat.clean += 1;
+            stat.best_time_ms = Some(stat.best_time_ms.map_or(time_ms, |b| b.min(time_ms)));
+        }
+        stat.filtered_time_ms = if stat.attempts == 1 {
+            time_ms
+        } else {
+            EMA_ALPHA * time_ms + (1.0 - EMA_ALPHA) * stat.filtered_time_ms
+        };
+    }
+
+    pub fn weakness(&self, pack: &str, shortcut: &Shortcut) -> f64 {
+        self.get(pack, shortcut).map_or_else(
+            || ShortcutStat::default().weakness(),
+            ShortcutStat::weakness,
+        )
+    }
+}
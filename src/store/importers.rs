@@ -0,0 +1,463 @@
+//! Importers for typing history exported by other trainers.
+//!
+//! Each importer turns the foreign export into plain `DrillResult` records tagged
+//! with their own `drill_mode` and `ranked = false`, so they show up in history
+//! and charts without ever advancing the skill tree.
+
+use std::collections::HashSet;
+
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, TimeZone, Utc};
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::keyboard::display::BACKSPACE;
+use crate::session::result::{DrillResult, KeyTime};
+use crate::store::merge::{cap_history, drill_identity};
+use crate::store::schema::ExportData;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExternalSource {
+    /// Monkeytype "results" CSV (Account > Export CSV).
+    Monkeytype,
+    /// keybr.com JSON download (Profile > Download data).
+    Keybr,
+}
+
+impl ExternalSource {
+    pub fn drill_mode(self) -> &'static str {
+        match self {
+            Self::Monkeytype => "monkeytype",
+            Self::Keybr => "keybr",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Monkeytype => "Monkeytype",
+            Self::Keybr => "keybr.com",
+        }
+    }
+
+    pub fn parse(self, content: &str) -> Result<ExternalImport> {
+        match self {
+            Self::Monkeytype => parse_monkeytype_csv(content),
+            Self::Keybr => parse_keybr_json(content),
+        }
+    }
+}
+
+/// Parsed foreign history, oldest first.
+#[derive(Clone, Debug)]
+pub struct ExternalImport {
+    pub drills: Vec<DrillResult>,
+    /// Rows that were present but could not be turned into a drill.
+    pub skipped: usize,
+}
+
+/// What merging an `ExternalImport` into the store changed (or would change).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ImportSummary {
+    pub parsed: usize,
+    pub skipped: usize,
+    pub added: usize,
+    pub duplicates: usize,
+    pub first: Option<DateTime<Utc>>,
+    pub last: Option<DateTime<Utc>>,
+    /// Distinct keys whose stats were seeded from per-key data.
+    pub keys_seeded: usize,
+    /// Oldest drills dropped to keep the history within its cap.
+    pub dropped: usize,
+}
+
+/// Append the imported drills to `export`'s history and seed its overall key stats.
+///
+/// Drills whose timestamp and mode already appear in the history are treated as
+/// duplicates, so re-running the same import is a no-op. Ranked key stats are left
+/// alone because imported drills are never ranked.
+pub fn merge_into(export: &mut ExportData, import: &ExternalImport) -> ImportSummary {
+    let mut seen: HashSet<(i64, String)> = export
+        .drill_history
+        .drills
+        .iter()
+        .map(drill_identity)
+        .collect();
+
+    let mut summary = ImportSummary {
+        parsed: import.drills.len(),
+        skipped: import.skipped,
+        ..ImportSummary::default()
+    };
+    let mut seeded_keys: HashSet<char> = HashSet::new();
+    let key_stats = &mut export.key_stats.stats;
+
+    for drill in &import.drills {
+        if !seen.insert(drill_identity(drill)) {
+            summary.duplicates += 1;
+            continue;
+        }
+        for kt in &drill.per_key_times {
+            if kt.correct {
+                key_stats.update_key(kt.key, kt.time_ms);
+            } else {
+                key_stats.update_key_error(kt.key);
+            }
+            seeded_keys.insert(kt.key);
+        }
+        summary.first = Some(
+            summary
+                .first
+                .map_or(drill.timestamp, |t| t.min(drill.timestamp)),
+        );
+        summary.last = Some(
+            summary
+                .last
+                .map_or(drill.timestamp, |t| t.max(drill.timestamp)),
+        );
+        summary.added += 1;
+        export.drill_history.drills.push(drill.clone());
+    }
+
+    export.drill_history.drills.sort_by_key(|d| d.timestamp);
+    summary.dropped = cap_history(&mut export.drill_history.drills);
+    summary.keys_seeded = seeded_keys.len();
+    summary
+}
+
+/// Split one CSV record into fields (RFC 4180 quoting, no embedded newlines).
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+pub fn parse_monkeytype_csv(content: &str) -> Result<ExternalImport> {
+    let mut lines = content
+        .lines()
+        .map(|l| l.trim_end_matches('\r'))
+        .filter(|l| !l.trim().is_empty());
+    let header = lines.next().context("Monkeytype CSV is empty")?;
+    let columns: Vec<String> = split_csv_line(header.trim_start_matches('\u{feff}'));
+    let col = |name: &str| columns.iter().position(|c| c == name);
+
+    let (Some(wpm_col), Some(acc_col), Some(ts_col)) = (col("wpm"), col("acc"), col("timestamp"))
+    else {
+        bail!("not a Monkeytype results CSV (missing wpm, acc or timestamp column)");
+    };
+    let char_stats_col = col("charStats");
+    let correct_col = col("correctChars");
+    let incorrect_col = col("incorrectChars");
+    let duration_col = col("testDuration");
+    let bailed_col = col("bailedOut");
+
+    let mut drills = Vec::new();
+    let mut skipped = 0;
+    for line in lines {
+        let fields = split_csv_line(line);
+        let get = |idx: Option<usize>| idx.and_then(|i| fields.get(i)).map(|s| s.trim());
+        let num = |idx: Option<usize>| get(idx).and_then(|s| s.parse::<f64>().ok());
+
+        let (Some(wpm), Some(accuracy), Some(ts_ms)) =
+            (num(Some(wpm_col)), num(Some(acc_col)), num(Some(ts_col)))
+        else {
+            skipped += 1;
+            continue;
+        };
+        let Some(timestamp) = Utc.timestamp_millis_opt(ts_ms as i64).single() else {
+            skipped += 1;
+            continue;
+        };
+
+        // charStats is "correct;incorrect;extra;missed"; older exports have
+        // separate correctChars/incorrectChars columns instead.
+        let (correct, incorrect, missed) = match get(char_stats_col) {
+            Some(stats) if !stats.is_empty() => {
+                let parts: Vec<usize> = stats
+                    .split(';')
+                    .map(|p| p.trim().parse().unwrap_or(0))
+                    .collect();
+                let part = |i: usize| parts.get(i).copied().unwrap_or(0);
+                (part(0), part(1) + part(2), part(3))
+            }
+            _ => (
+                num(correct_col).unwrap_or(0.0) as usize,
+                num(incorrect_col).unwrap_or(0.0) as usize,
+                0,
+            ),
+        };
+        let elapsed_secs = num(duration_col).filter(|d| *d > 0.0).unwrap_or_else(|| {
+            if wpm > 0.0 {
+                correct as f64 / 5.0 / wpm * 60.0
+            } else {
+                0.0
+            }
+        });
+
+        drills.push(DrillResult {
+            wpm,
+            cpm: wpm * 5.0,
+            accuracy: accuracy.clamp(0.0, 100.0),
+            correct,
+            incorrect,
+            total_chars: correct + incorrect + missed,
+            elapsed_secs,
+            timestamp,
+            per_key_times: Vec::new(),
+            drill_mode: ExternalSource::Monkeytype.drill_mode().to_string(),
+            ranked: false,
+            partial: get(bailed_col) == Some("true"),
+            completion_percent: 100.0,
+        });
+    }
+
+    drills.sort_by_key(|d| d.timestamp);
+    Ok(ExternalImport { drills, skipped })
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct KeybrResult {
+    time_stamp: Value,
+    length: usize,
+    /// Elapsed milliseconds.
+    time: f64,
+    errors: usize,
+    #[serde(default)]
+    histogram: Vec<KeybrKeySample>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct KeybrKeySample {
+    code_point: u32,
+    hit_count: usize,
+    miss_count: usize,
+    /// Average milliseconds per hit.
+    time_to_type: f64,
+}
+
+fn keybr_timestamp(value: &Value) -> Option<DateTime<Utc>> {
+    match value {
+        Value::String(s) => DateTime::parse_from_rfc3339(s)
+            .ok()
+            .map(|t| t.with_timezone(&Utc)),
+        Value::Number(n) => Utc.timestamp_millis_opt(n.as_i64()?).single(),
+        _ => None,
+    }
+}
+
+pub fn parse_keybr_json(content: &str) -> Result<ExternalImport> {
+    let rows: Vec<Value> =
+        serde_json::from_str(content).context("not a keybr.com JSON export (expected an array)")?;
+
+    let mut drills = Vec::new();
+    let mut skipped = 0;
+    for row in rows {
+        let Ok(result) = serde_json::from_value::<KeybrResult>(row) else {
+            skipped += 1;
+            continue;
+        };
+        let Some(timestamp) = keybr_timestamp(&result.time_stamp) else {
+            skipped += 1;
+            continue;
+        };
+        if result.length == 0 || result.time <= 0.0 {
+            skipped += 1;
+            continue;
+        }
+
+        // keybr only keeps per-key aggregates, so expand each histogram entry
+        // into hits at the average time plus misses. The result has no real
+        // order, which `DrillResult::has_keystroke_order` reports.
+        let mut per_key_times = Vec::new();
+        for sample in &result.histogram {
+            let Some(key) = char::from_u32(sample.code_point) else {
+                continue;
+            };
+            if key == BACKSPACE || sample.time_to_type <= 0.0 {
+                continue;
+            }
+            per_key_times.extend((0..sample.hit_count).map(|_| KeyTime {
+                key,
+                time_ms: sample.time_to_type,
+                correct: true,
+            }));
+            per_key_times.extend((0..sample.miss_count).map(|_| KeyTime {
+                key,
+                time_ms: sample.time_to_type,
+                correct: false,
+            }));
+        }
+
+        let elapsed_secs = result.time / 1000.0;
+        let minutes = elapsed_secs / 60.0;
+        let errors = result.errors.min(result.length);
+        let cpm = result.length as f64 / minutes;
+        drills.push(DrillResult {
+            wpm: cpm / 5.0,
+            cpm,
+            accuracy: (result.length - errors) as f64 / result.length as f64 * 100.0,
+            correct: result.length - errors,
+            incorrect: errors,
+            total_chars: result.length,
+            elapsed_secs,
+            timestamp,
+            per_key_times,
+            drill_mode: ExternalSource::Keybr.drill_mode().to_string(),
+            ranked: false,
+            partial: false,
+            completion_percent: 100.0,
+        });
+    }
+
+    drills.sort_by_key(|d| d.timestamp);
+    Ok(ExternalImport { drills, skipped })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::merge::MAX_HISTORY_DRILLS;
+    use crate::store::schema::{DrillHistoryData, EXPORT_VERSION, KeyStatsData, ProfileData};
+
+    const MONKEYTYPE_CSV: &str = "_id,isPb,wpm,acc,rawWpm,consistency,charStats,mode,mode2,quoteLength,restartCount,testDuration,afkDuration,incompleteTestSeconds,punctuation,numbers,language,funbox,difficulty,lazyMode,blindMode,bailedOut,tags,timestamp\n\
+6501,true,82.4,96.5,85.1,78.2,205;6;1;0,time,30,-1,0,30,0,0,false,false,english,none,normal,false,false,false,,1700000030000\n\
+6500,false,75,94.12,80,70,\"180;10;2;1\",words,25,-1,1,29.4,0,0,false,false,english,none,normal,false,false,false,\"a,b\",1700000000000\n\
+broken,false,abc,90,,,,,,,,,,,,,,,,,,,,1700000060000\n";
+
+    const KEYBR_JSON: &str = r#"[
+        {"layout":"en-us","textType":"generated","timeStamp":"2024-03-01T10:00:00.000Z",
+         "length":120,"time":30000,"errors":3,"speed":240,
+         "histogram":[{"codePoint":101,"hitCount":4,"missCount":1,"timeToType":210},
+                      {"codePoint":116,"hitCount":2,"missCount":0,"timeToType":180}]},
+        {"layout":"en-us","textType":"generated","timeStamp":"2024-02-28T09:00:00.000Z",
+         "length":60,"time":20000,"errors":0,"speed":180,"histogram":[]},
+        {"unexpected":true}
+    ]"#;
+
+    fn empty_export() -> ExportData {
+        ExportData {
+            keydr_export_version: EXPORT_VERSION,
+            exported_at: Utc::now(),
+            config: crate::config::Config::default(),
+            profile: ProfileData::default(),
+            key_stats: KeyStatsData::default(),
+            ranked_key_stats: KeyStatsData::default(),
+            drill_history: DrillHistoryData::default(),
+        }
+    }
+
+    #[test]
+    fn monkeytype_rows_become_unranked_drills() {
+        let import = parse_monkeytype_csv(MONKEYTYPE_CSV).unwrap();
+        assert_eq!(import.drills.len(), 2);
+        assert_eq!(import.skipped, 1);
+
+        // Sorted oldest first, quoted fields with commas handled.
+        let first = &import.drills[0];
+        assert_eq!(first.drill_mode, "monkeytype");
+        assert!(!first.ranked);
+        assert_eq!(first.wpm, 75.0);
+        assert_eq!(first.correct, 180);
+        assert_eq!(first.incorrect, 12);
+        assert_eq!(first.total_chars, 193);
+        assert!((first.elapsed_secs - 29.4).abs() < 1e-9);
+        assert_eq!(first.timestamp.timestamp_millis(), 1_700_000_000_000);
+        assert!(import.drills[1].timestamp > first.timestamp);
+    }
+
+    #[test]
+    fn monkeytype_rejects_unrelated_csv() {
+        assert!(parse_monkeytype_csv("name,score\nfoo,1\n").is_err());
+    }
+
+    #[test]
+    fn keybr_results_expand_histogram_into_key_times() {
+        let import = parse_keybr_json(KEYBR_JSON).unwrap();
+        assert_eq!(import.drills.len(), 2);
+        assert_eq!(import.skipped, 1);
+
+        let latest = &import.drills[1];
+        assert_eq!(latest.drill_mode, "keybr");
+        assert!(!latest.ranked);
+        assert!((latest.cpm - 240.0).abs() < 1e-9);
+        assert!((latest.wpm - 48.0).abs() < 1e-9);
+        assert!((latest.accuracy - 97.5).abs() < 1e-9);
+        let e_hits = latest
+            .per_key_times
+            .iter()
+            .filter(|kt| kt.key == 'e' && kt.correct)
+            .count();
+        assert_eq!(e_hits, 4);
+        assert_eq!(latest.per_key_times.len(), 7);
+    }
+
+    #[test]
+    fn merge_seeds_key_stats_and_skips_duplicates() {
+        let import = parse_keybr_json(KEYBR_JSON).unwrap();
+        let mut export = empty_export();
+
+        let summary = merge_into(&mut export, &import);
+        assert_eq!(summary.added, 2);
+        assert_eq!(summary.duplicates, 0);
+        assert_eq!(summary.keys_seeded, 2);
+        let e = export.key_stats.stats.get_stat('e').unwrap();
+        assert_eq!(e.sample_count, 4);
+        assert_eq!(e.error_count, 1);
+        assert!(export.ranked_key_stats.stats.stats.is_empty());
+
+        let again = merge_into(&mut export, &import);
+        assert_eq!(again.added, 0);
+        assert_eq!(again.duplicates, 2);
+        assert_eq!(export.drill_history.drills.len(), 2);
+        assert!(
+            export
+                .drill_history
+                .drills
+                .windows(2)
+                .all(|w| w[0].timestamp <= w[1].timestamp)
+        );
+    }
+    #[test]
+    fn merge_keeps_history_within_its_cap() {
+        let template = parse_keybr_json(KEYBR_JSON).unwrap().drills.remove(0);
+        let at = |secs| Utc.timestamp_opt(secs, 0).unwrap();
+        let mut export = empty_export();
+        export.drill_history.drills = (0..300)
+            .map(|i| DrillResult {
+                timestamp: at(i * 2),
+                ..template.clone()
+            })
+            .collect();
+        let import = ExternalImport {
+            drills: (0..300)
+                .map(|i| DrillResult {
+                    timestamp: at(i * 2 + 1),
+                    ..template.clone()
+                })
+                .collect(),
+            skipped: 0,
+        };
+
+        let summary = merge_into(&mut export, &import);
+        assert_eq!(summary.added, 300);
+        assert_eq!(summary.dropped, 100);
+        let drills = &export.drill_history.drills;
+        assert_eq!(drills.len(), MAX_HISTORY_DRILLS);
+        assert_eq!(drills[0].timestamp, at(100));
+    }
+}
@@ -8,7 +8,7 @@ use serde::{Serialize, de::DeserializeOwned};
 
 use crate::config::Config;
 use crate::store::schema::{
-    DrillHistoryData, EXPORT_VERSION, ExportData, KeyStatsData, ProfileData,
+    DrillHistoryData, EXPORT_VERSION, ExportData, KeyStatsData, ProfileData, ShortcutStatsData,
 };
 
 pub struct JsonStore {
@@ -132,6 +132,14 @@ impl JsonStore {
         self.save("lesson_history.json", data)
     }
 
+    pub fn load_shortcut_stats(&self) -> ShortcutStatsData {
+        self.load("shortcut_stats.json")
+    }
+
+    pub fn save_shortcut_stats(&self, data: &ShortcutStatsData) -> Result<()> {
+        self.save("shortcut_stats.json", data)
+    }
+
     /// Bundle all persisted data + config into an ExportData struct.
     /// N-gram stats are not included — they are always rebuilt from drill history.
     pub fn export_all(&self, config: &Config) -> ExportData {
@@ -0,0 +1,150 @@
+//! Union of two drill histories, used to reconcile exports from different machines.
+
+use std::collections::HashSet;
+
+use chrono::{DateTime, Utc};
+
+use crate::session::result::DrillResult;
+
+/// Most drills kept in history; older drills are dropped first.
+pub const MAX_HISTORY_DRILLS: usize = 500;
+
+/// Identity of a drill across machines: the same drill exported twice has the
+/// same millisecond timestamp and mode.
+pub fn drill_identity(drill: &DrillResult) -> (i64, String) {
+    (drill.timestamp.timestamp_millis(), drill.drill_mode.clone())
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct HistoryMerge {
+    /// Union of both histories, oldest first.
+    pub drills: Vec<DrillResult>,
+    pub added: usize,
+    pub duplicates: usize,
+    /// Oldest drills dropped to stay within [`MAX_HISTORY_DRILLS`].
+    pub dropped: usize,
+    /// Time span of the drills taken from `incoming`.
+    pub first_added: Option<DateTime<Utc>>,
+    pub last_added: Option<DateTime<Utc>>,
+}
+
+/// Drop the oldest drills beyond [`MAX_HISTORY_DRILLS`] from a history sorted
+/// oldest first, returning how many were dropped.
+pub fn cap_history(drills: &mut Vec<DrillResult>) -> usize {
+    let excess = drills.len().saturating_sub(MAX_HISTORY_DRILLS);
+    drills.drain(..excess);
+    excess
+}
+
+/// Append every drill from `incoming` that `local` does not already contain,
+/// keeping the newest [`MAX_HISTORY_DRILLS`] of the union.
+pub fn union_histories(local: &[DrillResult], incoming: &[DrillResult]) -> HistoryMerge {
+    let mut seen: HashSet<(i64, String)> = local.iter().map(drill_identity).collect();
+    let mut merge = HistoryMerge {
+        drills: local.to_vec(),
+        ..HistoryMerge::default()
+    };
+
+    for drill in incoming {
+        if !seen.insert(drill_identity(drill)) {
+            merge.duplicates += 1;
+            continue;
+        }
+        merge.first_added = Some(
+            merge
+                .first_added
+                .map_or(drill.timestamp, |t| t.min(drill.timestamp)),
+        );
+        merge.last_added = Some(
+            merge
+                .last_added
+                .map_or(drill.timestamp, |t| t.max(drill.timestamp)),
+        );
+        merge.added += 1;
+        merge.drills.push(drill.clone());
+    }
+
+    // Stable sort keeps local order for drills sharing a timestamp.
+    merge.drills.sort_by_key(|d| d.timestamp);
+    merge.dropped = cap_history(&mut merge.drills);
+    merge
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn drill(secs: i64, mode: &str) -> DrillResult {
+        DrillResult {
+            wpm: 50.0,
+            cpm: 250.0,
+            accuracy: 100.0,
+            correct: 10,
+            incorrect: 0,
+            total_chars: 10,
+            elapsed_secs: 2.4,
+            timestamp: Utc.timestamp_opt(secs, 0).unwrap(),
+            per_key_times: Vec::new(),
+            drill_mode: mode.to_string(),
+            ranked: true,
+            partial: false,
+            completion_percent: 100.0,
+        }
+    }
+
+    #[test]
+    fn union_interleaves_and_skips_duplicates() {
+        let local = vec![drill(100, "adaptive"), drill(300, "adaptive")];
+        let incoming = vec![
+            drill(100, "adaptive"),
+            drill(200, "code"),
+            drill(300, "passage"),
+            drill(400, "adaptive"),
+        ];
+
+        let merge = union_histories(&local, &incoming);
+        assert_eq!(merge.added, 3);
+        assert_eq!(merge.duplicates, 1);
+        let order: Vec<(i64, &str)> = merge
+            .drills
+            .iter()
+            .map(|d| (d.timestamp.timestamp(), d.drill_mode.as_str()))
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                (100, "adaptive"),
+                (200, "code"),
+                (300, "adaptive"),
+                (300, "passage"),
+                (400, "adaptive"),
+            ]
+        );
+        assert_eq!(merge.first_added.unwrap().timestamp(), 200);
+        assert_eq!(merge.last_added.unwrap().timestamp(), 400);
+    }
+
+    #[test]
+    fn union_with_itself_adds_nothing() {
+        let local = vec![drill(100, "adaptive"), drill(200, "code")];
+        let merge = union_histories(&local, &local);
+        assert_eq!(merge.added, 0);
+        assert_eq!(merge.duplicates, 2);
+        assert_eq!(merge.drills.len(), 2);
+        assert!(merge.first_added.is_none());
+    }
+
+    #[test]
+    fn union_keeps_only_the_newest_drills() {
+        let local: Vec<DrillResult> = (0..400).map(|i| drill(i * 2, "adaptive")).collect();
+        let incoming: Vec<DrillResult> = (0..400).map(|i| drill(i * 2 + 1, "code")).collect();
+
+        let merge = union_histories(&local, &incoming);
+        assert_eq!(merge.added, 400);
+        assert_eq!(merge.dropped, 300);
+        assert_eq!(merge.drills.len(), MAX_HISTORY_DRILLS);
+        assert_eq!(merge.drills[0].timestamp.timestamp(), 300);
+        assert_eq!(merge.drills.last().unwrap().timestamp.timestamp(), 799);
+    }
+}
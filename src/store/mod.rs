@@ -1,2 +1,5 @@
+pub mod importers;
 pub mod json_store;
+pub mod merge;
 pub mod schema;
+pub mod tabular;
@@ -4,8 +4,10 @@ use std::collections::HashMap;
 
 use crate::config::Config;
 use crate::engine::key_stats::KeyStatsStore;
+use crate::engine::review::ReviewSchedule;
 use crate::engine::skill_tree::SkillTreeProgress;
 use crate::session::result::DrillResult;
+use crate::shortcut::stats::ShortcutStatsStore;
 
 pub const SCHEMA_VERSION: u32 = 3;
 
@@ -24,6 +26,9 @@ pub struct ProfileData {
     pub streak_days: u32,
     pub best_streak: u32,
     pub last_practice_date: Option<String>,
+    /// Spaced-repetition review state keyed by dictionary language.
+    #[serde(default)]
+    pub review_schedule_by_language: HashMap<String, ReviewSchedule>,
 }
 
 impl Default for ProfileData {
@@ -37,6 +42,7 @@ impl Default for ProfileData {
             streak_days: 0,
             best_streak: 0,
             last_practice_date: None,
+            review_schedule_by_language: HashMap::new(),
         }
     }
 }
@@ -60,6 +66,12 @@ impl ProfileData {
         // Keep legacy mirror aligned with the current active scope.
         self.skill_tree = progress;
     }
+
+    pub fn review_schedule_mut(&mut self, language_key: &str) -> &mut ReviewSchedule {
+        self.review_schedule_by_language
+            .entry(language_key.to_string())
+            .or_default()
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -93,6 +105,23 @@ impl Default for DrillHistoryData {
     }
 }
 
+/// Shortcut drill stats live in their own file: they share nothing with the
+/// typing stats and are not part of exports.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ShortcutStatsData {
+    pub schema_version: u32,
+    pub stats: ShortcutStatsStore,
+}
+
+impl Default for ShortcutStatsData {
+    fn default() -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            stats: ShortcutStatsStore::default(),
+        }
+    }
+}
+
 pub const EXPORT_VERSION: u32 = 1;
 
 /// Export contract: drill_history is the sole source of truth for n-gram stats.
@@ -0,0 +1,415 @@
+//! Flat CSV/TSV exports for spreadsheets and dataframes.
+//!
+//! Unlike [`ExportData`](crate::store::schema::ExportData), which is a nested
+//! blob meant for round-trip restore, these tables are write-only: one row per
+//! drill, one row per key stat and one row per bigram stat. The overall and
+//! ranked stores share a table and are told apart by the `scope` column.
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use crate::engine::key_stats::{KeyStat, KeyStatsStore};
+use crate::engine::ngram_stats::{BigramStatsStore, NgramStat};
+use crate::keyboard::display;
+use crate::session::result::DrillResult;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TableFormat {
+    Csv,
+    Tsv,
+}
+
+impl TableFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Tsv => "tsv",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Csv => "CSV",
+            Self::Tsv => "TSV",
+        }
+    }
+
+    fn delimiter(self) -> char {
+        match self {
+            Self::Csv => ',',
+            Self::Tsv => '\t',
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            Self::Csv => Self::Tsv,
+            Self::Tsv => Self::Csv,
+        }
+    }
+
+    /// CSV quotes fields per RFC 4180; TSV has no quoting, so tabs, newlines
+    /// and backslashes are written as `\t`, `\n` and `\\`.
+    fn escape(self, field: &str) -> String {
+        match self {
+            Self::Csv => {
+                if field.contains([',', '"', '\n', '\r']) {
+                    format!("\"{}\"", field.replace('"', "\"\""))
+                } else {
+                    field.to_string()
+                }
+            }
+            Self::Tsv => field
+                .replace('\\', "\\\\")
+                .replace('\t', "\\t")
+                .replace('\n', "\\n")
+                .replace('\r', "\\r"),
+        }
+    }
+
+    fn row(self, out: &mut String, fields: &[String]) {
+        let delimiter = self.delimiter().to_string();
+        let escaped: Vec<String> = fields.iter().map(|f| self.escape(f)).collect();
+        out.push_str(&escaped.join(&delimiter));
+        out.push('\n');
+    }
+}
+
+/// Stores the tables are built from. N-gram stats are only held in memory
+/// (they are rebuilt from history), so callers pass the live stores.
+pub struct TableSources<'a> {
+    pub history: &'a [DrillResult],
+    pub key_stats: &'a KeyStatsStore,
+    pub ranked_key_stats: &'a KeyStatsStore,
+    pub bigram_stats: &'a BigramStatsStore,
+    pub ranked_bigram_stats: &'a BigramStatsStore,
+}
+
+const SCOPES: [&str; 2] = ["overall", "ranked"];
+
+fn header(names: &[&str]) -> Vec<String> {
+    names.iter().map(|n| n.to_string()).collect()
+}
+
+/// Readable key name: the character itself, or a name for whitespace and
+/// the control sentinels (Tab, Enter, Backspace) the stats track.
+fn key_label(ch: char) -> String {
+    match display::key_display_name(ch) {
+        "" => ch.to_string(),
+        name => name.to_string(),
+    }
+}
+
+fn codepoint(ch: char) -> String {
+    format!("U+{:04X}", ch as u32)
+}
+
+/// `best_time_ms` starts at `f64::MAX` until a sample arrives; export it empty.
+fn best_time(ms: f64) -> String {
+    if ms == f64::MAX {
+        String::new()
+    } else {
+        ms.to_string()
+    }
+}
+
+pub fn drills_table(history: &[DrillResult], format: TableFormat) -> String {
+    let mut out = String::new();
+    format.row(
+        &mut out,
+        &header(&[
+            "timestamp",
+            "mode",
+            "ranked",
+            "partial",
+            "completion_percent",
+            "wpm",
+            "cpm",
+            "accuracy",
+            "correct",
+            "incorrect",
+            "total_chars",
+            "elapsed_secs",
+        ]),
+    );
+    for drill in history {
+        format.row(
+            &mut out,
+            &[
+                drill.timestamp.to_rfc3339(),
+                drill.drill_mode.clone(),
+                drill.ranked.to_string(),
+                drill.partial.to_string(),
+                drill.completion_percent.to_string(),
+                drill.wpm.to_string(),
+                drill.cpm.to_string(),
+                drill.accuracy.to_string(),
+                drill.correct.to_string(),
+                drill.incorrect.to_string(),
+                drill.total_chars.to_string(),
+                drill.elapsed_secs.to_string(),
+            ],
+        );
+    }
+    out
+}
+
+pub fn key_stats_table(
+    overall: &KeyStatsStore,
+    ranked: &KeyStatsStore,
+    format: TableFormat,
+) -> String {
+    let mut out = String::new();
+    format.row(
+        &mut out,
+        &header(&[
+            "scope",
+            "key",
+            "codepoint",
+            "sample_count",
+            "total_count",
+            "error_count",
+            "error_rate_ema",
+            "filtered_time_ms",
+            "best_time_ms",
+            "confidence",
+        ]),
+    );
+    for (scope, store) in SCOPES.into_iter().zip([overall, ranked]) {
+        let mut keys: Vec<(&char, &KeyStat)> = store.stats.iter().collect();
+        keys.sort_by_key(|(ch, _)| **ch);
+        for (&ch, stat) in keys {
+            format.row(
+                &mut out,
+                &[
+                    scope.to_string(),
+                    key_label(ch),
+                    codepoint(ch),
+                    stat.sample_count.to_string(),
+                    stat.total_count.to_string(),
+                    stat.error_count.to_string(),
+                    stat.error_rate_ema.to_string(),
+                    stat.filtered_time_ms.to_string(),
+                    best_time(stat.best_time_ms),
+                    stat.confidence.to_string(),
+                ],
+            );
+        }
+    }
+    out
+}
+
+pub fn bigram_stats_table(
+    overall: &BigramStatsStore,
+    ranked: &BigramStatsStore,
+    format: TableFormat,
+) -> String {
+    let mut out = String::new();
+    format.row(
+        &mut out,
+        &header(&[
+            "scope",
+            "first",
+            "second",
+            "sample_count",
+            "error_count",
+            "hesitation_count",
+            "error_rate_ema",
+            "filtered_time_ms",
+            "best_time_ms",
+            "error_anomaly_streak",
+            "speed_anomaly_streak",
+            "last_seen_drill_index",
+        ]),
+    );
+    for (scope, store) in SCOPES.into_iter().zip([overall, ranked]) {
+        let mut bigrams: Vec<(&[char; 2], &NgramStat)> = store
+            .stats
+            .iter()
+            .map(|(key, stat)| (&key.0, stat))
+            .collect();
+        bigrams.sort_by_key(|(pair, _)| **pair);
+        for (pair, stat) in bigrams {
+            format.row(
+                &mut out,
+                &[
+                    scope.to_string(),
+                    key_label(pair[0]),
+                    key_label(pair[1]),
+                    stat.sample_count.to_string(),
+                    stat.error_count.to_string(),
+                    stat.hesitation_count.to_string(),
+                    stat.error_rate_ema.to_string(),
+                    stat.filtered_time_ms.to_string(),
+                    best_time(stat.best_time_ms),
+                    stat.error_anomaly_streak.to_string(),
+                    stat.speed_anomaly_streak.to_string(),
+                    stat.last_seen_drill_index.to_string(),
+                ],
+            );
+        }
+    }
+    out
+}
+
+const TABLE_NAMES: [&str; 3] = ["drills", "key_stats", "bigram_stats"];
+
+fn table_path(dir: &Path, prefix: &str, name: &str, format: TableFormat) -> PathBuf {
+    dir.join(format!("{prefix}{name}.{}", format.extension()))
+}
+
+/// Paths `write_tables` writes to for `prefix` and `format`.
+pub fn table_paths(dir: &Path, prefix: &str, format: TableFormat) -> Vec<PathBuf> {
+    TABLE_NAMES
+        .iter()
+        .map(|name| table_path(dir, prefix, name, format))
+        .collect()
+}
+
+/// Write `{prefix}drills`, `{prefix}key_stats` and `{prefix}bigram_stats`
+/// into `dir`, creating it if needed. Returns the written paths.
+pub fn write_tables(
+    sources: &TableSources,
+    dir: &Path,
+    prefix: &str,
+    format: TableFormat,
+) -> Result<Vec<PathBuf>> {
+    fs::create_dir_all(dir)?;
+    let tables = TABLE_NAMES.into_iter().zip([
+        drills_table(sources.history, format),
+        key_stats_table(sources.key_stats, sources.ranked_key_stats, format),
+        bigram_stats_table(sources.bigram_stats, sources.ranked_bigram_stats, format),
+    ]);
+
+    let mut written = Vec::with_capacity(TABLE_NAMES.len());
+    for (name, contents) in tables {
+        let path = table_path(dir, prefix, name, format);
+        let tmp_path = path.with_extension(format!("{}.tmp", format.extension()));
+        let result = (|| -> Result<()> {
+            let mut file = fs::File::create(&tmp_path)?;
+            file.write_all(contents.as_bytes())?;
+            file.sync_all()?;
+            fs::rename(&tmp_path, &path)?;
+            Ok(())
+        })();
+        if let Err(e) = result {
+            let _ = fs::remove_file(&tmp_path);
+            return Err(e);
+        }
+        written.push(path);
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::ngram_stats::BigramKey;
+    use chrono::{TimeZone, Utc};
+    use tempfile::TempDir;
+
+    fn drill(mode: &str) -> DrillResult {
+        DrillResult {
+            wpm: 52.5,
+            cpm: 262.5,
+            accuracy: 97.0,
+            correct: 97,
+            incorrect: 3,
+            total_chars: 100,
+            elapsed_secs: 22.9,
+            timestamp: Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap(),
+            per_key_times: Vec::new(),
+            drill_mode: mode.to_string(),
+            ranked: mode == "adaptive",
+            partial: false,
+            completion_percent: 100.0,
+        }
+    }
+
+    #[test]
+    fn drills_table_has_one_row_per_drill() {
+        let table = drills_table(&[drill("adaptive"), drill("code")], TableFormat::Csv);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("timestamp,mode,ranked,partial,"));
+        assert_eq!(
+            lines[1],
+            "2026-03-01T12:00:00+00:00,adaptive,true,false,100,52.5,262.5,97,97,3,100,22.9"
+        );
+        assert!(lines[2].contains(",code,false,"));
+    }
+
+    #[test]
+    fn key_stats_table_covers_both_scopes_with_readable_keys() {
+        let mut overall = KeyStatsStore::default();
+        overall.update_key('e', 180.0);
+        overall.update_key(',', 300.0);
+        overall.update_key('\t', 400.0);
+        let mut ranked = KeyStatsStore::default();
+        ranked.update_key('e', 200.0);
+
+        let table = key_stats_table(&overall, &ranked, TableFormat::Csv);
+        let rows: Vec<&str> = table.lines().skip(1).collect();
+        assert_eq!(rows.len(), 4);
+        assert!(rows[0].starts_with("overall,Tab,U+0009,"));
+        assert!(rows[1].starts_with("overall,\",\",U+002C,"));
+        assert!(rows[2].starts_with("overall,e,U+0065,1,"));
+        assert!(rows[3].starts_with("ranked,e,U+0065,1,"));
+    }
+
+    #[test]
+    fn unsampled_best_time_is_left_empty() {
+        let mut overall = BigramStatsStore::default();
+        overall
+            .stats
+            .insert(BigramKey(['t', 'h']), NgramStat::default());
+        let table = bigram_stats_table(&overall, &BigramStatsStore::default(), TableFormat::Tsv);
+        let row = table.lines().nth(1).unwrap();
+        let fields: Vec<&str> = row.split('\t').collect();
+        assert_eq!(&fields[..3], &["overall", "t", "h"]);
+        assert_eq!(fields[8], "");
+    }
+
+    #[test]
+    fn fields_are_escaped_per_format() {
+        assert_eq!(TableFormat::Csv.escape("a\"b"), "\"a\"\"b\"");
+        assert_eq!(TableFormat::Csv.escape("plain"), "plain");
+        assert_eq!(TableFormat::Tsv.escape("a\tb\\"), "a\\tb\\\\");
+    }
+
+    #[test]
+    fn write_tables_creates_one_file_per_table() {
+        let dir = TempDir::new().unwrap();
+        let history = vec![drill("adaptive")];
+        let key_stats = KeyStatsStore::default();
+        let bigram_stats = BigramStatsStore::default();
+        let sources = TableSources {
+            history: &history,
+            key_stats: &key_stats,
+            ranked_key_stats: &key_stats,
+            bigram_stats: &bigram_stats,
+            ranked_bigram_stats: &bigram_stats,
+        };
+
+        let out_dir = dir.path().join("tables");
+        let written = write_tables(&sources, &out_dir, "keydr-", TableFormat::Tsv).unwrap();
+        let names: Vec<String> = written
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "keydr-drills.tsv",
+                "keydr-key_stats.tsv",
+                "keydr-bigram_stats.tsv"
+            ]
+        );
+        let drills = fs::read_to_string(&written[0]).unwrap();
+        assert_eq!(drills.lines().count(), 2);
+        assert!(!out_dir.join("keydr-drills.tsv.tmp").exists());
+    }
+}
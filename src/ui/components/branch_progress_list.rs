@@ -4,7 +4,7 @@ use ratatui::style::Style;
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Paragraph, Widget};
 
-use crate::engine::skill_tree::{BranchId, DrillScope, SkillTree, get_branch_definition};
+use crate::engine::skill_tree::{BranchId, DrillScope, SkillTree};
 use crate::i18n::t;
 use crate::ui::theme::Theme;
 
@@ -84,8 +84,8 @@ impl Widget for BranchProgressList<'_> {
                 lines.push(Line::from(spans));
             }
         } else if let Some(branch_id) = drill_branch {
-            let def = get_branch_definition(branch_id);
-            let total = SkillTree::branch_total_keys(branch_id);
+            let def = self.skill_tree.branch_definition(branch_id);
+            let total = self.skill_tree.branch_total_keys_for(branch_id);
             let unlocked = self.skill_tree.branch_unlocked_count(branch_id);
             let mastered = self
                 .skill_tree
@@ -167,8 +167,8 @@ fn render_branch_cell<'a>(
     theme: &'a Theme,
 ) -> Vec<Span<'a>> {
     let colors = &theme.colors;
-    let def = get_branch_definition(branch_id);
-    let total = SkillTree::branch_total_keys(branch_id);
+    let def = skill_tree.branch_definition(branch_id);
+    let total = skill_tree.branch_total_keys_for(branch_id);
     let unlocked = skill_tree.branch_unlocked_count(branch_id);
     let mastered = skill_tree.branch_confident_keys(branch_id, key_stats);
 
@@ -2,7 +2,7 @@ use ratatui::buffer::Buffer;
 use ratatui::layout::{Alignment, Constraint, Direction, Layout, Rect};
 use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, Paragraph, Widget};
+use ratatui::widgets::{Block, Paragraph, Widget, Wrap};
 
 use crate::i18n::t;
 use crate::session::result::DrillResult;
@@ -14,6 +14,7 @@ pub struct Dashboard<'a> {
     pub result: &'a DrillResult,
     pub theme: &'a Theme,
     pub input_lock_remaining_ms: Option<u64>,
+    pub needs_review: &'a [char],
 }
 
 impl<'a> Dashboard<'a> {
@@ -26,8 +27,15 @@ impl<'a> Dashboard<'a> {
             result,
             theme,
             input_lock_remaining_ms,
+            needs_review: &[],
         }
     }
+
+    /// Keys that slipped out of mastery with this drill.
+    pub fn with_needs_review(mut self, keys: &'a [char]) -> Self {
+        self.needs_review = keys;
+        self
+    }
 }
 
 impl Widget for Dashboard<'_> {
@@ -152,6 +160,17 @@ impl Widget for Dashboard<'_> {
         ]);
         Paragraph::new(chars_line).render(layout[4], buf);
 
+        if !self.needs_review.is_empty() {
+            let keys: Vec<String> = self.needs_review.iter().map(char::to_string).collect();
+            let review_text = t!("dashboard.needs_review", keys = keys.join(" "));
+            Paragraph::new(Line::from(Span::styled(
+                review_text.to_string(),
+                Style::default().fg(colors.warning()),
+            )))
+            .wrap(Wrap { trim: false })
+            .render(layout[5], buf);
+        }
+
         let help = if let Some(ms) = self.input_lock_remaining_ms {
             let input_blocked_label = t!("dashboard.input_blocked");
             let input_blocked_ms = t!("dashboard.input_blocked_ms", ms = ms);
@@ -14,6 +14,10 @@ pub struct KeyboardDiagram<'a> {
     pub selected_key: Option<char>,
     pub next_key: Option<char>,
     pub unlocked_keys: &'a [char],
+    /// Mastered keys that have slipped since, drawn in the warning color.
+    pub regressed_keys: &'a [char],
+    /// Keys picked alongside `selected_key`, drawn the same way.
+    pub marked_keys: &'a [char],
     pub depressed_keys: &'a HashSet<char>,
     pub theme: &'a Theme,
     pub compact: bool,
@@ -59,6 +63,8 @@ impl<'a> KeyboardDiagram<'a> {
             selected_key: None,
             next_key,
             unlocked_keys,
+            regressed_keys: &[],
+            marked_keys: &[],
             depressed_keys,
             theme,
             compact: false,
@@ -73,11 +79,21 @@ impl<'a> KeyboardDiagram<'a> {
         self
     }
 
+    pub fn regressed_keys(mut self, keys: &'a [char]) -> Self {
+        self.regressed_keys = keys;
+        self
+    }
+
     pub fn selected_key(mut self, key: Option<char>) -> Self {
         self.selected_key = key;
         self
     }
 
+    pub fn marked_keys(mut self, keys: &'a [char]) -> Self {
+        self.marked_keys = keys;
+        self
+    }
+
     pub fn compact(mut self, compact: bool) -> Self {
         self.compact = compact;
         self
@@ -88,9 +104,13 @@ impl<'a> KeyboardDiagram<'a> {
         self
     }
 
-    /// Check if a key (by display or base char) matches the selected key.
+    /// Check if a key (by display or base char) matches the selected or a
+    /// marked key.
     fn is_key_selected(&self, display_char: char, base_char: char) -> bool {
-        self.selected_key == Some(display_char) || self.selected_key == Some(base_char)
+        self.selected_key == Some(display_char)
+            || self.selected_key == Some(base_char)
+            || self.marked_keys.contains(&display_char)
+            || self.marked_keys.contains(&base_char)
     }
 
     /// Check if a sentinel/modifier key matches the selected key.
@@ -384,11 +404,12 @@ fn modifier_key_style(
     }
 }
 
-fn key_style(
+pub(crate) fn key_style(
     is_depressed: bool,
     is_next: bool,
     is_selected: bool,
     is_unlocked: bool,
+    is_regressed: bool,
     colors: &crate::ui::theme::ThemeColors,
 ) -> Style {
     if is_depressed {
@@ -403,6 +424,8 @@ fn key_style(
     } else if is_selected {
         let bg = colors.accent_dim();
         Style::default().fg(readable_fg(bg, colors.fg())).bg(bg)
+    } else if is_regressed {
+        Style::default().fg(colors.warning()).bg(colors.bg())
     } else if is_unlocked {
         Style::default().fg(colors.fg()).bg(colors.bg())
     } else {
@@ -525,8 +548,17 @@ impl KeyboardDiagram<'_> {
                 let is_next =
                     self.next_key == Some(display_char) || self.next_key == Some(base_char);
                 let is_sel = self.is_key_selected(display_char, base_char);
-
-                let style = key_style(is_depressed, is_next, is_sel, is_unlocked, colors);
+                let is_regressed = self.regressed_keys.contains(&display_char)
+                    || self.regressed_keys.contains(&base_char);
+
+                let style = key_style(
+                    is_depressed,
+                    is_next,
+                    is_sel,
+                    is_unlocked,
+                    is_regressed,
+                    colors,
+                );
 
                 let display = format!("[{display_char}]");
                 buf.set_string(x, y, &display, style);
@@ -648,8 +680,17 @@ impl KeyboardDiagram<'_> {
                 let is_next =
                     self.next_key == Some(display_char) || self.next_key == Some(base_char);
                 let is_sel = self.is_key_selected(display_char, base_char);
-
-                let style = key_style(is_depressed, is_next, is_sel, is_unlocked, colors);
+                let is_regressed = self.regressed_keys.contains(&display_char)
+                    || self.regressed_keys.contains(&base_char);
+
+                let style = key_style(
+                    is_depressed,
+                    is_next,
+                    is_sel,
+                    is_unlocked,
+                    is_regressed,
+                    colors,
+                );
 
                 let display = format!("[ {display_char} ]");
                 buf.set_string(x, y, &display, style);
@@ -746,8 +787,17 @@ impl KeyboardDiagram<'_> {
                 let is_next =
                     self.next_key == Some(display_char) || self.next_key == Some(base_char);
                 let is_sel = self.is_key_selected(display_char, base_char);
-
-                let style = key_style(is_depressed, is_next, is_sel, is_unlocked, colors);
+                let is_regressed = self.regressed_keys.contains(&display_char)
+                    || self.regressed_keys.contains(&base_char);
+
+                let style = key_style(
+                    is_depressed,
+                    is_next,
+                    is_sel,
+                    is_unlocked,
+                    is_regressed,
+                    colors,
+                );
 
                 let display = format!("[ {display_char} ]");
                 buf.set_string(x, y, &display, style);
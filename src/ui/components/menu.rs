@@ -11,6 +11,15 @@ const MENU_ITEMS: &[(&str, &str, &str)] = &[
     ("1", "menu.adaptive_drill", "menu.adaptive_drill_desc"),
     ("2", "menu.code_drill", "menu.code_drill_desc"),
     ("3", "menu.passage_drill", "menu.passage_drill_desc"),
+    ("4", "menu.data_entry_drill", "menu.data_entry_drill_desc"),
+    ("5", "menu.numpad_drill", "menu.numpad_drill_desc"),
+    ("6", "menu.shortcut_drill", "menu.shortcut_drill_desc"),
+    (
+        "7",
+        "menu.problem_words_drill",
+        "menu.problem_words_drill_desc",
+    ),
+    ("8", "menu.technique_drill", "menu.technique_drill_desc"),
     ("t", "menu.skill_tree", "menu.skill_tree_desc"),
     ("b", "menu.keyboard", "menu.keyboard_desc"),
     ("s", "menu.statistics", "menu.statistics_desc"),
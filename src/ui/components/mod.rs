@@ -4,6 +4,8 @@ pub mod chart;
 pub mod dashboard;
 pub mod keyboard_diagram;
 pub mod menu;
+pub mod numpad_diagram;
+pub mod race_standings;
 pub mod skill_tree;
 pub mod stats_dashboard;
 pub mod stats_sidebar;
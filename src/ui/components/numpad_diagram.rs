@@ -0,0 +1,75 @@
+use std::collections::HashSet;
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::widgets::{Block, Widget};
+
+use crate::keyboard::display::numpad_sentinel;
+use crate::keyboard::numpad::{NUMPAD_COLS, NUMPAD_KEYS, NUMPAD_ROWS, numpad_finger};
+use crate::ui::components::keyboard_diagram::key_style;
+use crate::ui::theme::Theme;
+
+const KEY_WIDTH: u16 = 5;
+
+/// Numeric keypad diagram for numpad drills. The title names the finger for
+/// the next key; keys light up from keypad presses only.
+pub struct NumpadDiagram<'a> {
+    pub next_key: Option<char>,
+    pub depressed_keys: &'a HashSet<char>,
+    pub theme: &'a Theme,
+}
+
+impl Widget for NumpadDiagram<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let colors = &self.theme.colors;
+
+        let title = match self.next_key.and_then(numpad_finger) {
+            Some(finger) => format!(" Numpad · {} ", finger.localized_description()),
+            None => " Numpad ".to_string(),
+        };
+        let block = Block::bordered()
+            .title(title)
+            .border_style(Style::default().fg(colors.border()))
+            .style(Style::default().bg(colors.bg()));
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let pad_width = NUMPAD_COLS * KEY_WIDTH;
+        let start_x = inner.x + inner.width.saturating_sub(pad_width) / 2;
+        let start_y = inner.y + inner.height.saturating_sub(NUMPAD_ROWS) / 2;
+        for key in NUMPAD_KEYS {
+            let x = start_x + key.col * KEY_WIDTH;
+            let width = if key.wide { KEY_WIDTH * 2 } else { KEY_WIDTH };
+            if x + width > inner.x + inner.width {
+                continue;
+            }
+            let style = match key.ch {
+                Some(ch) => {
+                    let is_depressed = numpad_sentinel(ch)
+                        .is_some_and(|sentinel| self.depressed_keys.contains(&sentinel));
+                    key_style(
+                        is_depressed,
+                        self.next_key == Some(ch),
+                        false,
+                        true,
+                        false,
+                        colors,
+                    )
+                }
+                None => Style::default().fg(colors.text_pending()).bg(colors.bg()),
+            };
+            let inner_width = (width - 2) as usize;
+            let rows = if key.tall { 2 } else { 1 };
+            for dr in 0..rows {
+                let y = start_y + key.row + dr;
+                if y >= inner.y + inner.height {
+                    break;
+                }
+                let label = if dr == 0 { key.label } else { "" };
+                let text = format!("[{label:^inner_width$}]");
+                buf.set_string(x, y, &text, style);
+            }
+        }
+    }
+}
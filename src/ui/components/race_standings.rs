@@ -0,0 +1,167 @@
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph, Widget};
+
+use crate::i18n::t;
+use crate::race::{RacePhase, RaceSession, RacerStatus, placings};
+use crate::ui::theme::Theme;
+
+const NAME_WIDTH: usize = 14;
+
+fn truncated_name(name: &str) -> String {
+    let name: String = name.chars().take(NAME_WIDTH).collect();
+    format!("{name:<NAME_WIDTH$}")
+}
+
+fn progress_percent(racer: &RacerStatus, text_len: usize) -> f64 {
+    if racer.finish.is_some() {
+        return 100.0;
+    }
+    if text_len == 0 {
+        return 0.0;
+    }
+    (racer.cursor as f64 / text_len as f64 * 100.0).clamp(0.0, 100.0)
+}
+
+/// One progress bar per opponent, shown under the typing area during a race.
+pub struct RaceProgressBars<'a> {
+    pub session: &'a RaceSession,
+    pub theme: &'a Theme,
+}
+
+impl Widget for RaceProgressBars<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let colors = &self.theme.colors;
+        let text_len = self.session.text_len();
+        // name + bar + percent column
+        let bar_width = (area.width as usize).saturating_sub(NAME_WIDTH + 10).max(4);
+
+        let lines: Vec<Line> = self
+            .session
+            .opponents()
+            .take(area.height as usize)
+            .map(|racer| {
+                let pct = progress_percent(racer, text_len);
+                let filled = ((pct / 100.0) * bar_width as f64).round() as usize;
+                let name_style = if racer.connected {
+                    Style::default().fg(colors.fg())
+                } else {
+                    Style::default().fg(colors.text_pending())
+                };
+                let bar_color = if racer.finish.is_some() {
+                    colors.success()
+                } else {
+                    colors.bar_filled()
+                };
+                Line::from(vec![
+                    Span::styled(format!(" {} ", truncated_name(&racer.name)), name_style),
+                    Span::styled("\u{2588}".repeat(filled), Style::default().fg(bar_color)),
+                    Span::styled(
+                        "\u{2591}".repeat(bar_width.saturating_sub(filled)),
+                        Style::default().fg(colors.bar_empty()),
+                    ),
+                    Span::styled(
+                        format!(" {pct:>3.0}%"),
+                        Style::default().fg(colors.text_pending()),
+                    ),
+                ])
+            })
+            .collect();
+
+        Paragraph::new(lines).render(area, buf);
+    }
+}
+
+/// Placing table shown after the local racer finishes. Updates live as the
+/// remaining racers cross the line.
+pub struct RaceStandings<'a> {
+    pub session: &'a RaceSession,
+    pub theme: &'a Theme,
+}
+
+impl Widget for RaceStandings<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let colors = &self.theme.colors;
+
+        let title = t!("race.standings_title");
+        let block = Block::bordered()
+            .title(title.to_string())
+            .border_style(Style::default().fg(colors.accent()))
+            .style(Style::default().bg(colors.bg()));
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let header_style = Style::default()
+            .fg(colors.accent())
+            .add_modifier(Modifier::BOLD);
+        let mut lines = vec![
+            Line::from(Span::styled(
+                format!(
+                    "  {:<4} {} {:>8} {:>7} {:>8}",
+                    "#",
+                    truncated_name(&t!("race.col_racer")),
+                    t!("race.col_time"),
+                    t!("common.wpm"),
+                    t!("race.col_accuracy"),
+                ),
+                header_style,
+            )),
+            Line::from(""),
+        ];
+
+        let text_len = self.session.text_len();
+        for (place, racer) in placings(&self.session.racers).into_iter().enumerate() {
+            let is_local = racer.id == self.session.local_id;
+            let (time, wpm, acc) = match &racer.finish {
+                Some(finish) => (
+                    format!("{:.1}s", finish.elapsed_secs),
+                    format!("{:.0}", finish.wpm),
+                    format!("{:.1}%", finish.accuracy),
+                ),
+                None if !racer.connected => (t!("race.left").to_string(), "-".into(), "-".into()),
+                None => (
+                    format!("{:.0}%", progress_percent(racer, text_len)),
+                    "-".into(),
+                    "-".into(),
+                ),
+            };
+            let style = if is_local {
+                Style::default()
+                    .fg(colors.focused_key())
+                    .add_modifier(Modifier::BOLD)
+            } else if racer.finish.is_some() {
+                Style::default().fg(colors.fg())
+            } else {
+                Style::default().fg(colors.text_pending())
+            };
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "  {:<4} {} {:>8} {:>7} {:>8}",
+                    place + 1,
+                    truncated_name(&racer.name),
+                    time,
+                    wpm,
+                    acc
+                ),
+                style,
+            )));
+        }
+
+        lines.push(Line::from(""));
+        let status = if self.session.host_lost {
+            t!("race.host_lost")
+        } else if self.session.phase() == RacePhase::Finished {
+            t!("race.all_finished")
+        } else {
+            t!("race.waiting_for_finishers")
+        };
+        lines.push(Line::from(Span::styled(
+            format!("  {status}"),
+            Style::default().fg(colors.text_pending()),
+        )));
+
+        Paragraph::new(lines).render(inner, buf);
+    }
+}
@@ -6,9 +6,9 @@ use ratatui::widgets::{Block, Paragraph, Widget, Wrap};
 
 use crate::i18n::t;
 use crate::engine::key_stats::KeyStatsStore;
-use crate::engine::skill_tree::{
-    BranchId, BranchStatus, DrillScope, SkillTree as SkillTreeEngine, get_branch_definition,
-};
+#[cfg(test)]
+use crate::engine::skill_tree::get_branch_definition;
+use crate::engine::skill_tree::{BranchId, BranchStatus, DrillScope, SkillTree as SkillTreeEngine};
 use crate::ui::hint;
 use crate::ui::layout::{pack_hint_lines, wrapped_line_count};
 use crate::ui::theme::Theme;
@@ -43,18 +43,13 @@ fn locked_branch_notice(skill_tree: &SkillTreeEngine) -> String {
     t!("skill_tree.locked_notice", count = skill_tree.primary_letters().len()).to_string()
 }
 
-/// Get the list of selectable branch IDs (Lowercase first, then other branches).
-pub fn selectable_branches() -> Vec<BranchId> {
-    vec![
-        BranchId::Lowercase,
-        BranchId::Capitals,
-        BranchId::Numbers,
-        BranchId::ProsePunctuation,
-        BranchId::Whitespace,
-        BranchId::CodeSymbols,
-    ]
+/// Get the list of selectable branch IDs (Lowercase first, then other
+/// branches, then custom ones).
+pub fn selectable_branches(skill_tree: &SkillTreeEngine) -> Vec<BranchId> {
+    skill_tree.branch_ids()
 }
 
+#[cfg(test)]
 pub fn detail_line_count(branch_id: BranchId) -> usize {
     let def = get_branch_definition(branch_id);
     // 1 line branch header + for each level: 1 line level header + 1 line per key
@@ -70,7 +65,10 @@ pub fn detail_line_count_for_tree(skill_tree: &SkillTreeEngine, branch_id: Branc
         // 1 branch header + 1 level header + one line per primary letter.
         1 + 1 + skill_tree.primary_letters().len()
     } else {
-        detail_line_count(branch_id)
+        let def = skill_tree.branch_definition(branch_id);
+        1 + (0..def.levels.len())
+            .map(|i| 1 + skill_tree.level_keys(branch_id, i).len())
+            .sum::<usize>()
     }
 }
 
@@ -83,7 +81,7 @@ pub fn detail_line_count_with_level_spacing_for_tree(
     if !level_spacing {
         return base;
     }
-    let def = get_branch_definition(branch_id);
+    let def = skill_tree.branch_definition(branch_id);
     base + def.levels.len().saturating_sub(1)
 }
 
@@ -100,7 +98,7 @@ pub fn use_expanded_level_spacing_for_tree(
     detail_area_height: u16,
     branch_id: BranchId,
 ) -> bool {
-    let def = get_branch_definition(branch_id);
+    let def = skill_tree.branch_definition(branch_id);
     let base = detail_line_count_for_tree(skill_tree, branch_id);
     let extra = def.levels.len().saturating_sub(1);
     (detail_area_height as usize) >= base + extra
@@ -137,12 +135,13 @@ impl Widget for SkillTreeWidget<'_> {
         block.render(area, buf);
 
         // Layout: main split (branch list + detail) and footer (adaptive height)
-        let branches = selectable_branches();
+        let branches = selectable_branches(self.skill_tree);
         let h_navigate = hint::hint(hint::K_UD_JK, t!("skill_tree.hint_navigate").as_ref());
         let h_scroll = hint::hint(hint::K_SCROLL_KEYS, t!("skill_tree.hint_scroll").as_ref());
         let h_back = hint::hint(hint::K_Q_ESC, t!("skill_tree.hint_back").as_ref());
         let h_unlock = hint::hint(hint::K_ENTER, t!("skill_tree.hint_unlock").as_ref());
         let h_start_drill = hint::hint(hint::K_ENTER, t!("skill_tree.hint_start_drill").as_ref());
+        let h_placement = hint::hint(hint::K_P, t!("skill_tree.hint_placement").as_ref());
         let (footer_hints, footer_notice): (Vec<&str>, Option<String>) =
             if self.selected < branches.len() {
                 let bp = self.skill_tree.branch_progress(branches[self.selected]);
@@ -151,6 +150,7 @@ impl Widget for SkillTreeWidget<'_> {
                         vec![
                             h_navigate.as_str(),
                             h_scroll.as_str(),
+                            h_placement.as_str(),
                             h_back.as_str(),
                         ],
                         Some(locked_branch_notice(self.skill_tree)),
@@ -161,6 +161,7 @@ impl Widget for SkillTreeWidget<'_> {
                             h_unlock.as_str(),
                             h_navigate.as_str(),
                             h_scroll.as_str(),
+                            h_placement.as_str(),
                             h_back.as_str(),
                         ],
                         None,
@@ -171,6 +172,7 @@ impl Widget for SkillTreeWidget<'_> {
                             h_start_drill.as_str(),
                             h_navigate.as_str(),
                             h_scroll.as_str(),
+                            h_placement.as_str(),
                             h_back.as_str(),
                         ],
                         None,
@@ -180,6 +182,7 @@ impl Widget for SkillTreeWidget<'_> {
                         vec![
                             h_navigate.as_str(),
                             h_scroll.as_str(),
+                            h_placement.as_str(),
                             h_back.as_str(),
                         ],
                         None,
@@ -190,6 +193,7 @@ impl Widget for SkillTreeWidget<'_> {
                     vec![
                         h_navigate.as_str(),
                         h_scroll.as_str(),
+                        h_placement.as_str(),
                         h_back.as_str(),
                     ],
                     None,
@@ -310,7 +314,7 @@ impl SkillTreeWidget<'_> {
             }
 
             let bp = self.skill_tree.branch_progress(branch_id);
-            let def = get_branch_definition(branch_id);
+            let def = self.skill_tree.branch_definition(branch_id);
             let total_keys = self.skill_tree.branch_total_keys_for(branch_id);
             let confident_keys = self
                 .skill_tree
@@ -419,7 +423,7 @@ impl SkillTreeWidget<'_> {
 
         let branch_id = branches[self.selected];
         let bp = self.skill_tree.branch_progress(branch_id);
-        let def = get_branch_definition(branch_id);
+        let def = self.skill_tree.branch_definition(branch_id);
         let expanded_level_spacing = allow_expanded_level_spacing
             && use_expanded_level_spacing_for_tree(self.skill_tree, area.height, branch_id);
 
@@ -441,6 +445,16 @@ impl SkillTreeWidget<'_> {
                 _ => t!("skill_tree.level_zero", total = def.levels.len()).to_string(),
             }
         };
+        // Effective mastery targets for this branch
+        let mastery = self.skill_tree.mastery();
+        let target_wpm =
+            self.key_stats.target_cpm / 5.0 * mastery.speed_factor(branch_id.to_key());
+        let mut target_text = t!("skill_tree.target_wpm", wpm = format!("{target_wpm:.0}")).to_string();
+        if let Some(max_error_rate) = mastery.max_error_rate {
+            let accuracy = format!("{:.0}", (1.0 - max_error_rate) * 100.0);
+            target_text.push_str(", ");
+            target_text.push_str(&t!("skill_tree.target_accuracy", accuracy = accuracy));
+        }
         lines.push(Line::from(vec![
             Span::styled(
                 format!("  {}", def.display_name()),
@@ -452,6 +466,10 @@ impl SkillTreeWidget<'_> {
                 format!("  {level_text}"),
                 Style::default().fg(colors.text_pending()),
             ),
+            Span::styled(
+                format!("  \u{00b7} {target_text}"),
+                Style::default().fg(colors.text_pending()),
+            ),
         ]));
 
         // Per-level key breakdown with per-key mastery bars
@@ -489,12 +507,16 @@ impl SkillTreeWidget<'_> {
             let level_keys: Vec<char> = if branch_id == BranchId::Lowercase {
                 self.skill_tree.primary_letters().to_vec()
             } else {
-                level.keys.to_vec()
+                self.skill_tree.level_keys(branch_id, level_idx).to_vec()
             };
             for &key in &level_keys {
                 let is_focused = focused == Some(key);
-                let confidence = self.key_stats.get_confidence(key).min(1.0);
-                let is_confident = confidence >= 1.0;
+                let confidence = self
+                    .skill_tree
+                    .key_progress(branch_id, key, self.key_stats)
+                    .min(1.0);
+                let is_confident = self.skill_tree.key_mastered(branch_id, key, self.key_stats);
+                let is_regressed = self.skill_tree.is_regressed(key);
 
                 // For Lowercase, check if this specific key is unlocked
                 let is_locked = if branch_id == BranchId::Lowercase {
@@ -540,9 +562,16 @@ impl SkillTreeWidget<'_> {
 
                     let bar_color = if is_confident {
                         colors.text_correct()
+                    } else if is_regressed {
+                        colors.warning()
                     } else {
                         colors.accent()
                     };
+                    let review_label = if is_regressed {
+                        t!("skill_tree.needs_review").to_string()
+                    } else {
+                        String::new()
+                    };
 
                     lines.push(Line::from(vec![
                         Span::styled(format!("    {display} "), key_style),
@@ -557,6 +586,7 @@ impl SkillTreeWidget<'_> {
                                 .fg(colors.focused_key())
                                 .add_modifier(Modifier::BOLD),
                         ),
+                        Span::styled(review_label, Style::default().fg(colors.warning())),
                     ]));
                 }
             }
@@ -7,7 +7,8 @@ use ratatui::widgets::{Block, Clear, Paragraph, Widget};
 use std::collections::{BTreeSet, HashMap};
 
 use crate::engine::key_stats::KeyStatsStore;
-use crate::engine::ngram_stats::{AnomalyType, FocusSelection};
+use crate::engine::ngram_stats::{AnomalyType, FocusSelection, NgramKey};
+use crate::engine::word_stats::ProblemWord;
 use crate::keyboard::display::{self, BACKSPACE, ENTER, MODIFIER_SENTINELS, SPACE, TAB};
 use crate::keyboard::model::KeyboardModel;
 use crate::session::result::DrillResult;
@@ -21,8 +22,8 @@ use crate::ui::theme::Theme;
 // N-grams tab view models
 // ---------------------------------------------------------------------------
 
-pub struct AnomalyBigramRow {
-    pub bigram: String,
+pub struct AnomalyRow {
+    pub ngram: String,
     pub anomaly_pct: f64,
     pub sample_count: usize,
     pub error_count: usize,
@@ -34,13 +35,19 @@ pub struct AnomalyBigramRow {
 
 pub struct NgramTabData {
     pub focus: FocusSelection,
-    pub error_anomalies: Vec<AnomalyBigramRow>,
-    pub speed_anomalies: Vec<AnomalyBigramRow>,
+    pub error_anomalies: Vec<AnomalyRow>,
+    pub speed_anomalies: Vec<AnomalyRow>,
     pub total_bigrams: usize,
+    pub trigram_error_anomalies: Vec<AnomalyRow>,
+    pub trigram_speed_anomalies: Vec<AnomalyRow>,
+    pub total_trigrams: usize,
     pub hesitation_threshold_ms: f64,
     pub scope_label: String,
 }
 
+/// Height of the anomaly lists area from which trigrams get their own section.
+const TRIGRAM_SECTION_MIN_HEIGHT: u16 = 16;
+
 pub struct StatsDashboard<'a> {
     pub history: &'a [DrillResult],
     pub key_stats: &'a KeyStatsStore,
@@ -55,6 +62,7 @@ pub struct StatsDashboard<'a> {
     pub history_confirm_delete: bool,
     pub keyboard_model: &'a KeyboardModel,
     pub ngram_data: Option<&'a NgramTabData>,
+    pub problem_words: &'a [ProblemWord],
 }
 
 impl<'a> StatsDashboard<'a> {
@@ -87,8 +95,14 @@ impl<'a> StatsDashboard<'a> {
             history_confirm_delete,
             keyboard_model,
             ngram_data,
+            problem_words: &[],
         }
     }
+
+    pub fn with_problem_words(mut self, problem_words: &'a [ProblemWord]) -> Self {
+        self.problem_words = problem_words;
+        self
+    }
 }
 
 impl Widget for StatsDashboard<'_> {
@@ -237,10 +251,15 @@ impl StatsDashboard<'_> {
         self.render_keyboard_heatmap(layout[0], buf);
         let lists = Layout::default()
             .direction(Direction::Horizontal)
-            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .constraints([
+                Constraint::Percentage(33),
+                Constraint::Percentage(33),
+                Constraint::Percentage(34),
+            ])
             .split(layout[1]);
         self.render_worst_accuracy_keys(lists[0], buf);
         self.render_best_accuracy_keys(lists[1], buf);
+        self.render_problem_words(lists[2], buf);
     }
 
     fn render_timing_tab(&self, area: Rect, buf: &mut Buffer) {
@@ -1264,6 +1283,52 @@ impl StatsDashboard<'_> {
         }
     }
 
+    fn render_problem_words(&self, area: Rect, buf: &mut Buffer) {
+        let colors = &self.theme.colors;
+
+        let title = t!("stats.problem_words_title");
+        let block = Block::bordered()
+            .title(Line::from(Span::styled(
+                title.to_string(),
+                Style::default()
+                    .fg(colors.accent())
+                    .add_modifier(Modifier::BOLD),
+            )))
+            .border_style(Style::default().fg(colors.accent()));
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        if self.problem_words.is_empty() {
+            let no_data = t!("stats.problem_words_empty");
+            buf.set_string(
+                inner.x,
+                inner.y,
+                no_data.as_ref(),
+                Style::default().fg(colors.text_pending()),
+            );
+            return;
+        }
+
+        let word_width = inner.width.saturating_sub(14) as usize;
+        for (i, word) in self
+            .problem_words
+            .iter()
+            .take(inner.height as usize)
+            .enumerate()
+        {
+            let y = inner.y + i as u16;
+            let name: String = word.word.chars().take(word_width).collect();
+            let err_pct = word.error_rate * 100.0;
+            let label = format!(" {name:<word_width$} {:>4.0} {err_pct:>3.0}%", word.wpm);
+            let color = if word.error_rate >= 0.25 {
+                colors.error()
+            } else {
+                colors.warning()
+            };
+            buf.set_string(inner.x, y, &label, Style::default().fg(color));
+        }
+    }
+
     fn render_activity_stats(&self, area: Rect, buf: &mut Buffer) {
         let colors = &self.theme.colors;
         let streaks_title = t!("stats.streaks_title");
@@ -1361,31 +1426,70 @@ impl StatsDashboard<'_> {
 
         self.render_ngram_focus(data, layout[0], buf);
 
-        let wide = layout[1].width >= 60;
+        // Trigrams get their own section below the bigrams once there is room
+        if layout[1].height >= TRIGRAM_SECTION_MIN_HEIGHT {
+            let sections = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Percentage(60), Constraint::Min(0)])
+                .split(layout[1]);
+            self.render_anomaly_lists(
+                &data.error_anomalies,
+                &data.speed_anomalies,
+                false,
+                sections[0],
+                buf,
+            );
+            self.render_anomaly_lists(
+                &data.trigram_error_anomalies,
+                &data.trigram_speed_anomalies,
+                true,
+                sections[1],
+                buf,
+            );
+        } else {
+            self.render_anomaly_lists(
+                &data.error_anomalies,
+                &data.speed_anomalies,
+                false,
+                layout[1],
+                buf,
+            );
+        }
+        self.render_ngram_summary(data, layout[2], buf);
+    }
+
+    fn render_anomaly_lists(
+        &self,
+        error_rows: &[AnomalyRow],
+        speed_rows: &[AnomalyRow],
+        trigram: bool,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        let wide = area.width >= 60;
         if wide {
             let lists = Layout::default()
                 .direction(Direction::Horizontal)
                 .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-                .split(layout[1]);
-            self.render_error_anomalies(data, lists[0], buf);
-            self.render_speed_anomalies(data, lists[1], buf);
+                .split(area);
+            self.render_anomaly_panel(error_rows, false, trigram, lists[0], buf);
+            self.render_anomaly_panel(speed_rows, true, trigram, lists[1], buf);
         } else {
             // Stacked vertically for narrow terminals
-            let available = layout[1].height;
+            let available = area.height;
             if available < 10 {
                 // Only show error anomalies if very little space
-                self.render_error_anomalies(data, layout[1], buf);
+                self.render_anomaly_panel(error_rows, false, trigram, area, buf);
             } else {
                 let half = available / 2;
                 let lists = Layout::default()
                     .direction(Direction::Vertical)
                     .constraints([Constraint::Length(half), Constraint::Min(0)])
-                    .split(layout[1]);
-                self.render_error_anomalies(data, lists[0], buf);
-                self.render_speed_anomalies(data, lists[1], buf);
+                    .split(area);
+                self.render_anomaly_panel(error_rows, false, trigram, lists[0], buf);
+                self.render_anomaly_panel(speed_rows, true, trigram, lists[1], buf);
             }
         }
-        self.render_ngram_summary(data, layout[2], buf);
     }
 
     fn render_ngram_focus(&self, data: &NgramTabData, area: Rect, buf: &mut Buffer) {
@@ -1487,20 +1591,77 @@ impl StatsDashboard<'_> {
             }
         }
 
+        if let Some((key, anomaly_pct, anomaly_type)) = &data.focus.trigram_focus {
+            let trigram_label = format!("\"{}\"", key.label());
+            let value = Span::styled(
+                t!("stats.focus_trigram_value", label = &trigram_label).to_string(),
+                Style::default()
+                    .fg(colors.focused_key())
+                    .add_modifier(Modifier::BOLD),
+            );
+            if data.focus.char_focus.is_none() && data.focus.bigram_focus.is_none() {
+                let type_label = match anomaly_type {
+                    AnomalyType::Error => t!("stats.anomaly_error").to_string(),
+                    AnomalyType::Speed => t!("stats.anomaly_speed").to_string(),
+                };
+                lines = vec![Line::from(vec![
+                    Span::styled(
+                        t!("stats.focus_char_label").to_string(),
+                        Style::default().fg(colors.fg()),
+                    ),
+                    value,
+                    Span::styled(
+                        t!(
+                            "stats.focus_detail_bigram_only",
+                            r#type = &type_label,
+                            pct = format!("{anomaly_pct:.0}")
+                        )
+                        .to_string(),
+                        Style::default().fg(colors.text_pending()),
+                    ),
+                ])];
+            } else if let Some(first) = lines.first_mut() {
+                first.spans.push(Span::styled(
+                    t!("stats.focus_plus").to_string(),
+                    Style::default().fg(colors.fg()),
+                ));
+                first.spans.push(value);
+            }
+        }
+
         Paragraph::new(lines).render(inner, buf);
     }
 
     fn render_anomaly_panel(
         &self,
-        title: &str,
-        empty_msg: &str,
-        rows: &[AnomalyBigramRow],
+        rows: &[AnomalyRow],
         is_speed: bool,
+        trigram: bool,
         area: Rect,
         buf: &mut Buffer,
     ) {
         let colors = &self.theme.colors;
 
+        let count = rows.len();
+        let (title, empty_msg) = match (trigram, is_speed) {
+            (false, false) => (
+                t!("stats.error_anomalies_title", count = count),
+                t!("stats.no_error_anomalies"),
+            ),
+            (false, true) => (
+                t!("stats.speed_anomalies_title", count = count),
+                t!("stats.no_speed_anomalies"),
+            ),
+            (true, false) => (
+                t!("stats.trigram_error_anomalies_title", count = count),
+                t!("stats.no_error_anomalies"),
+            ),
+            (true, true) => (
+                t!("stats.trigram_speed_anomalies_title", count = count),
+                t!("stats.no_speed_anomalies"),
+            ),
+        };
+
         let block = Block::bordered()
             .title(Line::from(Span::styled(
                 title.to_string(),
@@ -1520,7 +1681,7 @@ impl StatsDashboard<'_> {
             buf.set_string(
                 inner.x,
                 inner.y,
-                empty_msg,
+                empty_msg.as_ref(),
                 Style::default().fg(colors.text_pending()),
             );
             return;
@@ -1530,17 +1691,17 @@ impl StatsDashboard<'_> {
 
         // Error table: Bigram  Anom%  Rate  Errors  Smp  Strk
         // Speed table: Bigram  Anom%  Speed  Smp  Strk
-        let header = if narrow {
-            if is_speed {
-                t!("stats.ngram_header_speed_narrow").to_string()
-            } else {
-                t!("stats.ngram_header_error_narrow").to_string()
-            }
-        } else if is_speed {
-            t!("stats.ngram_header_speed").to_string()
-        } else {
-            t!("stats.ngram_header_error").to_string()
-        };
+        let header = match (trigram, narrow, is_speed) {
+            (false, true, true) => t!("stats.ngram_header_speed_narrow"),
+            (false, true, false) => t!("stats.ngram_header_error_narrow"),
+            (false, false, true) => t!("stats.ngram_header_speed"),
+            (false, false, false) => t!("stats.ngram_header_error"),
+            (true, true, true) => t!("stats.trigram_header_speed_narrow"),
+            (true, true, false) => t!("stats.trigram_header_error_narrow"),
+            (true, false, true) => t!("stats.trigram_header_speed"),
+            (true, false, false) => t!("stats.trigram_header_error"),
+        }
+        .to_string();
         buf.set_string(
             inner.x,
             inner.y,
@@ -1561,12 +1722,12 @@ impl StatsDashboard<'_> {
                 if is_speed {
                     format!(
                         " {:>4} {:>3.0}ms {:>3.0}ms {:>4.0}%",
-                        row.bigram, row.speed_ms, row.expected_baseline, row.anomaly_pct,
+                        row.ngram, row.speed_ms, row.expected_baseline, row.anomaly_pct,
                     )
                 } else {
                     format!(
                         " {:>4} {:>3} {:>3} {:>3.0}% {:>2.0}% {:>4.0}%",
-                        row.bigram,
+                        row.ngram,
                         row.error_count,
                         row.sample_count,
                         row.error_rate_ema * 100.0,
@@ -1577,7 +1738,7 @@ impl StatsDashboard<'_> {
             } else if is_speed {
                 format!(
                     " {:>6}  {:>4.0}ms  {:>4.0}ms  {:>5}  {:>4.0}%",
-                    row.bigram,
+                    row.ngram,
                     row.speed_ms,
                     row.expected_baseline,
                     row.sample_count,
@@ -1586,7 +1747,7 @@ impl StatsDashboard<'_> {
             } else {
                 format!(
                     " {:>6}  {:>5}  {:>5}  {:>4.0}%  {:>4.0}%  {:>5.0}%",
-                    row.bigram,
+                    row.ngram,
                     row.error_count,
                     row.sample_count,
                     row.error_rate_ema * 100.0,
@@ -1605,32 +1766,6 @@ impl StatsDashboard<'_> {
         }
     }
 
-    fn render_error_anomalies(&self, data: &NgramTabData, area: Rect, buf: &mut Buffer) {
-        let title = t!("stats.error_anomalies_title", count = data.error_anomalies.len());
-        let empty_msg = t!("stats.no_error_anomalies");
-        self.render_anomaly_panel(
-            title.as_ref(),
-            empty_msg.as_ref(),
-            &data.error_anomalies,
-            false,
-            area,
-            buf,
-        );
-    }
-
-    fn render_speed_anomalies(&self, data: &NgramTabData, area: Rect, buf: &mut Buffer) {
-        let title = t!("stats.speed_anomalies_title", count = data.speed_anomalies.len());
-        let empty_msg = t!("stats.no_speed_anomalies");
-        self.render_anomaly_panel(
-            title.as_ref(),
-            empty_msg.as_ref(),
-            &data.speed_anomalies,
-            true,
-            area,
-            buf,
-        );
-    }
-
     fn render_ngram_summary(&self, data: &NgramTabData, area: Rect, buf: &mut Buffer) {
         let colors = &self.theme.colors;
         let w = area.width as usize;
@@ -1638,9 +1773,10 @@ impl StatsDashboard<'_> {
         // Build segments from most to least important, progressively drop from the right
         let scope = t!("stats.scope_label_prefix", ).to_string() + &data.scope_label;
         let bigrams = t!("stats.bi_label", count = data.total_bigrams).to_string();
+        let trigrams = t!("stats.tri_label", count = data.total_trigrams).to_string();
         let hesitation = t!("stats.hes_label", ms = format!("{:.0}", data.hesitation_threshold_ms)).to_string();
 
-        let segments: &[&str] = &[&scope, &bigrams, &hesitation];
+        let segments: &[&str] = &[&scope, &bigrams, &trigrams, &hesitation];
         let mut line = String::new();
         for seg in segments {
             if line.len() + seg.len() <= w {
@@ -1959,7 +2095,7 @@ fn render_text_bar(
     }
 }
 
-fn format_duration(secs: f64) -> String {
+pub fn format_duration(secs: f64) -> String {
     let total = secs as u64;
     let hours = total / 3600;
     let mins = (total % 3600) / 60;
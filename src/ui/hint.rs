@@ -30,7 +30,7 @@ pub const K_TAB: &str = "Tab";
 pub const K_BACKSPACE: &str = "Backspace";
 
 // ── Menu ──────────────────────────────────────────────────────────────────────
-pub const K_1_3: &str = "1-3";
+pub const K_1_8: &str = "1-8";
 pub const K_T: &str = "t";
 pub const K_B: &str = "b";
 pub const K_S: &str = "s";
@@ -54,14 +54,27 @@ pub const K_ARROW_LR: &str = "←→";
 // ── Select screens ────────────────────────────────────────────────────────────
 pub const K_UP_DOWN_PGUP_PGDN: &str = "Up/Down/PgUp/PgDn";
 
+// ── Vocabulary editor ─────────────────────────────────────────────────────────
+pub const K_A: &str = "a";
+pub const K_D_DEL: &str = "d/Del";
+pub const K_PLUS_MINUS: &str = "+/-";
+
 // ── Intro screens ─────────────────────────────────────────────────────────────
 pub const K_UP_DOWN: &str = "Up/Down";
 pub const K_LEFT_RIGHT: &str = "Left/Right";
 pub const K_TYPE_BACKSPACE: &str = "Type/Backspace";
 
+// ── Keyboard explorer ─────────────────────────────────────────────────────────
+pub const K_CTRL_A: &str = "Ctrl+A";
+pub const K_CTRL_D: &str = "Ctrl+D";
+pub const K_CTRL_S: &str = "Ctrl+S";
+pub const K_CTRL_P: &str = "Ctrl+P";
+
 // ── Skill tree ────────────────────────────────────────────────────────────────
 pub const K_UD_JK: &str = "↑↓/jk";
 pub const K_SCROLL_KEYS: &str = "PgUp/PgDn or Ctrl+U/Ctrl+D";
+/// Placement test, offered on the skill tree and the first-launch intro.
+pub const K_P: &str = "p";
 
 /// Assembles a single hint entry: `"[key] label"`.
 ///